@@ -2,7 +2,9 @@ use napi::Result;
 use napi_derive::napi;
 
 use crate::error_handle::err_handle::*;
+use crate::service_database::database_auto_shutter::service::auto_shutter_retention_service::*;
 use crate::service_database::database_auto_shutter::service::auto_shutter_service::*;
+use crate::service_database::shutter_chunk::chunk_store::{ShutterChunkStats, ShutterDictInfo};
 use crate::{generate_napi_i32_methods, generate_napi_methods, generate_napi_u32_methods};
 use paste::paste;
 
@@ -13,7 +15,19 @@ paste! {
             order_flag: String, // "DESC" or "ASC"
             auto_count: u32,
             model_id: String) -> Vec<AutoShutterListItem> => get_all_model_auto_shutter_entity_list_cache,
-         get_auto_shutter_cache_detail_api(id: i32, model_id: String) -> FullCacheData => get_model_auto_shutter_entity_by_id_cache
+         get_auto_shutter_cache_detail_api(id: i32, model_id: String) -> FullCacheData => get_model_auto_shutter_entity_by_id_cache,
+        compact_auto_shutter_storage_api(model_id: String) -> u32 => compact_model_auto_shutter_storage,
+        train_auto_shutter_dictionary_api(
+            model_id: String,
+            max_size: Option<u32>) -> ShutterDictInfo => train_model_auto_shutter_dictionary,
+        get_auto_shutter_chunk_stats_api(model_id: String) -> ShutterChunkStats => get_model_auto_shutter_chunk_stats,
+        pin_auto_shutter_api(id: i32, model_id: String, pinned: bool) -> bool => pin_model_auto_shutter_entity,
+        delete_auto_shutters_api(ids: Vec<i32>, model_id: String) -> u32 => delete_model_auto_shutter_entities,
+        enforce_auto_shutter_retention_api(model_id: String) -> AutoShutterRetentionResult => enforce_auto_shutter_retention,
+        start_auto_shutter_retention_api(
+            model_id: String,
+            interval_seconds: u32) -> bool => start_auto_shutter_retention_task,
+        stop_auto_shutter_retention_api(model_id: String) -> bool => stop_auto_shutter_retention_task,
     }
 }
 
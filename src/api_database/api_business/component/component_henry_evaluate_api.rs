@@ -0,0 +1,10 @@
+use crate::error_handle::err_handle::*;
+use crate::service_database::database_business::service::component::component_henry_evaluate_service::*;
+use napi::Result;
+use napi_derive::napi;
+
+#[napi(namespace = "henry", js_name = "evaluate")]
+/// 计算溶质在（混合）溶剂中的亨利常数 H(T)，标记超出适用范围的温度，给出分压时计算溶解度
+pub async fn evaluate_henry_constant_api(query: HenryEvaluateQuery) -> Result<HenryEvaluateResult> {
+    evaluate_henry_constant(query).await.map_err(handle_db_err)
+}
@@ -0,0 +1,14 @@
+use crate::error_handle::err_handle::*;
+use crate::service_database::database_business::service::component::component_oil_assay_service::*;
+use napi::Result;
+use napi_derive::napi;
+
+#[napi(namespace = "petroleumAssay")]
+/// 由蒸馏曲线与总体比重切割虚拟组分并估算物性，给出组分通道时写入石油组分表
+pub async fn characterize_petroleum_assay_api(
+    query: PetroleumAssayQuery,
+) -> Result<PetroleumAssayResult> {
+    characterize_petroleum_assay(query)
+        .await
+        .map_err(handle_db_err)
+}
@@ -2,4 +2,6 @@ pub mod component_all_detail_api;
 pub mod component_api;
 pub mod component_henry_api;
 pub mod component_henry_detail_api;
+pub mod component_henry_evaluate_api;
 pub mod component_oil_api;
+pub mod component_oil_assay_api;
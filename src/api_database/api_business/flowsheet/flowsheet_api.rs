@@ -0,0 +1,101 @@
+use crate::error_handle::err_handle::*;
+use napi::Result;
+use napi_derive::napi;
+use paste::paste;
+
+use crate::generate_napi_methods;
+use crate::service_database::database_business::service::flowsheet::flowsheet_service::*;
+use crate::service_database::database_business::service::flowsheet::flowsheet_topology_service::*;
+
+paste! {
+    generate_napi_methods! {
+        "flowsheet",
+
+        get_flowsheet_api(model_id: String) -> FlowsheetGraph => get_flowsheet,
+
+        save_flowsheet_api(model_id: String, graph: FlowsheetGraph) -> u32 => save_flowsheet_graph,
+
+        replace_flowsheet_api(model_id: String, graph: FlowsheetGraph) -> u32 => replace_flowsheet_graph,
+
+        delete_flowsheet_api(model_id: String) -> u32 => delete_flowsheet_graph,
+
+        analyze_flowsheet_topology_api(model_id: String) -> FlowsheetTopologyReport => analyze_flowsheet_topology,
+
+        save_flowsheet_material_api(datas: Vec<FlowsheetMaterial>) -> u32 => MaterialService::batch_save,
+        get_flowsheet_material_by_model_id_api(model_id: String) -> Vec<FlowsheetMaterial> => MaterialService::find_by_model_id,
+        get_flowsheet_material_by_ids_api(model_id: String, ids: Vec<String>) -> Vec<FlowsheetMaterial> => MaterialService::find_by_ids,
+        delete_flowsheet_material_by_ids_api(model_id: String, ids: Vec<String>) -> u32 => MaterialService::delete_by_ids,
+        delete_flowsheet_material_by_model_id_api(model_id: String) -> u32 => MaterialService::delete_by_model_id,
+
+        save_flowsheet_energy_api(datas: Vec<FlowsheetEnergy>) -> u32 => EnergyService::batch_save,
+        get_flowsheet_energy_by_model_id_api(model_id: String) -> Vec<FlowsheetEnergy> => EnergyService::find_by_model_id,
+        get_flowsheet_energy_by_ids_api(model_id: String, ids: Vec<String>) -> Vec<FlowsheetEnergy> => EnergyService::find_by_ids,
+        delete_flowsheet_energy_by_ids_api(model_id: String, ids: Vec<String>) -> u32 => EnergyService::delete_by_ids,
+        delete_flowsheet_energy_by_model_id_api(model_id: String) -> u32 => EnergyService::delete_by_model_id,
+
+        save_flowsheet_logic_api(datas: Vec<FlowsheetLogic>) -> u32 => LogicService::batch_save,
+        get_flowsheet_logic_by_model_id_api(model_id: String) -> Vec<FlowsheetLogic> => LogicService::find_by_model_id,
+        get_flowsheet_logic_by_ids_api(model_id: String, ids: Vec<String>) -> Vec<FlowsheetLogic> => LogicService::find_by_ids,
+        delete_flowsheet_logic_by_ids_api(model_id: String, ids: Vec<String>) -> u32 => LogicService::delete_by_ids,
+        delete_flowsheet_logic_by_model_id_api(model_id: String) -> u32 => LogicService::delete_by_model_id,
+
+        save_flowsheet_sensor_api(datas: Vec<FlowsheetSensor>) -> u32 => SensorService::batch_save,
+        get_flowsheet_sensor_by_model_id_api(model_id: String) -> Vec<FlowsheetSensor> => SensorService::find_by_model_id,
+        get_flowsheet_sensor_by_ids_api(model_id: String, ids: Vec<String>) -> Vec<FlowsheetSensor> => SensorService::find_by_ids,
+        delete_flowsheet_sensor_by_ids_api(model_id: String, ids: Vec<String>) -> u32 => SensorService::delete_by_ids,
+        delete_flowsheet_sensor_by_model_id_api(model_id: String) -> u32 => SensorService::delete_by_model_id,
+
+        save_flowsheet_aiuo_api(datas: Vec<FlowsheetAiuo>) -> u32 => AiuoService::batch_save,
+        get_flowsheet_aiuo_by_model_id_api(model_id: String) -> Vec<FlowsheetAiuo> => AiuoService::find_by_model_id,
+        get_flowsheet_aiuo_by_ids_api(model_id: String, ids: Vec<String>) -> Vec<FlowsheetAiuo> => AiuoService::find_by_ids,
+        delete_flowsheet_aiuo_by_ids_api(model_id: String, ids: Vec<String>) -> u32 => AiuoService::delete_by_ids,
+        delete_flowsheet_aiuo_by_model_id_api(model_id: String) -> u32 => AiuoService::delete_by_model_id,
+
+        save_flowsheet_module_api(datas: Vec<FlowsheetModule>) -> u32 => ModuleService::batch_save,
+        get_flowsheet_module_by_model_id_api(model_id: String) -> Vec<FlowsheetModule> => ModuleService::find_by_model_id,
+        get_flowsheet_module_by_ids_api(model_id: String, ids: Vec<String>) -> Vec<FlowsheetModule> => ModuleService::find_by_ids,
+        delete_flowsheet_module_by_ids_api(model_id: String, ids: Vec<String>) -> u32 => ModuleService::delete_by_ids,
+        delete_flowsheet_module_by_model_id_api(model_id: String) -> u32 => ModuleService::delete_by_model_id,
+
+        save_flowsheet_script_uo_api(datas: Vec<FlowsheetScriptUo>) -> u32 => ScriptUoService::batch_save,
+        get_flowsheet_script_uo_by_model_id_api(model_id: String) -> Vec<FlowsheetScriptUo> => ScriptUoService::find_by_model_id,
+        get_flowsheet_script_uo_by_ids_api(model_id: String, ids: Vec<String>) -> Vec<FlowsheetScriptUo> => ScriptUoService::find_by_ids,
+        delete_flowsheet_script_uo_by_ids_api(model_id: String, ids: Vec<String>) -> u32 => ScriptUoService::delete_by_ids,
+        delete_flowsheet_script_uo_by_model_id_api(model_id: String) -> u32 => ScriptUoService::delete_by_model_id,
+
+        save_flowsheet_connection_api(datas: Vec<FlowsheetConnection>) -> u32 => ConnectionService::batch_save,
+        get_flowsheet_connection_by_model_id_api(model_id: String) -> Vec<FlowsheetConnection> => ConnectionService::find_by_model_id,
+        get_flowsheet_connection_by_ids_api(model_id: String, ids: Vec<String>) -> Vec<FlowsheetConnection> => ConnectionService::find_by_ids,
+        delete_flowsheet_connection_by_ids_api(model_id: String, ids: Vec<String>) -> u32 => ConnectionService::delete_by_ids,
+        delete_flowsheet_connection_by_model_id_api(model_id: String) -> u32 => ConnectionService::delete_by_model_id,
+
+        get_flowsheet_connection_by_unit_id_api(model_id: String, unit_id: String) -> Vec<FlowsheetConnection> => ConnectionService::find_by_unit_id,
+
+        save_flowsheet_node_api(datas: Vec<FlowsheetNode>) -> u32 => NodeService::batch_save,
+        get_flowsheet_node_by_model_id_api(model_id: String) -> Vec<FlowsheetNode> => NodeService::find_by_model_id,
+        get_flowsheet_node_by_ids_api(model_id: String, ids: Vec<String>) -> Vec<FlowsheetNode> => NodeService::find_by_ids,
+        delete_flowsheet_node_by_ids_api(model_id: String, ids: Vec<String>) -> u32 => NodeService::delete_by_ids,
+        delete_flowsheet_node_by_model_id_api(model_id: String) -> u32 => NodeService::delete_by_model_id,
+
+        get_flowsheet_node_by_page_id_api(model_id: String, graphic_page_id: String) -> Vec<FlowsheetNode> => NodeService::find_by_page_id,
+        delete_flowsheet_node_by_page_id_api(model_id: String, graphic_page_id: String) -> u32 => NodeService::delete_by_page_id,
+
+        save_flowsheet_mark_node_api(datas: Vec<FlowsheetMarkNode>) -> u32 => MarkNodeService::batch_save,
+        get_flowsheet_mark_node_by_model_id_api(model_id: String) -> Vec<FlowsheetMarkNode> => MarkNodeService::find_by_model_id,
+        get_flowsheet_mark_node_by_ids_api(model_id: String, ids: Vec<String>) -> Vec<FlowsheetMarkNode> => MarkNodeService::find_by_ids,
+        delete_flowsheet_mark_node_by_ids_api(model_id: String, ids: Vec<String>) -> u32 => MarkNodeService::delete_by_ids,
+        delete_flowsheet_mark_node_by_model_id_api(model_id: String) -> u32 => MarkNodeService::delete_by_model_id,
+
+        get_flowsheet_mark_node_by_page_id_api(model_id: String, graphic_page_id: String) -> Vec<FlowsheetMarkNode> => MarkNodeService::find_by_page_id,
+        delete_flowsheet_mark_node_by_page_id_api(model_id: String, graphic_page_id: String) -> u32 => MarkNodeService::delete_by_page_id,
+
+        save_flowsheet_edge_api(datas: Vec<FlowsheetEdge>) -> u32 => EdgeService::batch_save,
+        get_flowsheet_edge_by_model_id_api(model_id: String) -> Vec<FlowsheetEdge> => EdgeService::find_by_model_id,
+        get_flowsheet_edge_by_ids_api(model_id: String, ids: Vec<String>) -> Vec<FlowsheetEdge> => EdgeService::find_by_ids,
+        delete_flowsheet_edge_by_ids_api(model_id: String, ids: Vec<String>) -> u32 => EdgeService::delete_by_ids,
+        delete_flowsheet_edge_by_model_id_api(model_id: String) -> u32 => EdgeService::delete_by_model_id,
+
+        get_flowsheet_edge_by_page_id_api(model_id: String, graphic_page_id: String) -> Vec<FlowsheetEdge> => EdgeService::find_by_page_id,
+        delete_flowsheet_edge_by_page_id_api(model_id: String, graphic_page_id: String) -> u32 => EdgeService::delete_by_page_id,
+    }
+}
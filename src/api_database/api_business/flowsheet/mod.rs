@@ -0,0 +1 @@
+pub mod flowsheet_api;
@@ -1,4 +1,6 @@
 pub mod model_fluid_package_api;
 pub mod model_fluid_package_binary_api;
+pub mod model_fluid_package_thermo_api;
 pub mod model_reaction_detail_api;
 pub mod model_reaction_package_api;
+pub mod model_reaction_validate_api;
@@ -1,14 +1,34 @@
 use napi_derive::napi;
 use crate::service_database::database_business::service::fluid_package::model_fluid_package_binary_service::*;
+use crate::service_database::database_business::service::fluid_package::model_fluid_package_binary_estimate_service::*;
+use crate::service_database::database_business::service::fluid_package::model_fluid_package_binary_regression_service::*;
 
 use crate::error_handle::err_handle::*;
 use futures::try_join;
 use napi::Result;
 use serde::{Deserialize, Serialize};
+/// 保存用户录入或修改的二元参数，未指定来源的新记录标记为 USER
 #[napi(namespace = "modelFluidPackageBinary")]
 pub async fn update_binary_interaction_parameters(
     func_code: String,
     data_json: String,
+) -> Result<bool> {
+    save_binary_parameters(func_code, data_json, BinaryParameterSource::User).await
+}
+
+/// 保存从物性库复制到流体包的二元参数，未指定来源的新记录标记为 DATABANK
+#[napi(namespace = "modelFluidPackageBinary")]
+pub async fn copy_databank_binary_interaction_parameters(
+    func_code: String,
+    data_json: String,
+) -> Result<bool> {
+    save_binary_parameters(func_code, data_json, BinaryParameterSource::Databank).await
+}
+
+async fn save_binary_parameters(
+    func_code: String,
+    data_json: String,
+    insert_source: BinaryParameterSource,
 ) -> Result<bool> {
     // 1. 解析前端传来的 JSON 字符串
     let datas: Vec<BinaryParameterDto> = serde_json::from_str(&data_json)
@@ -17,22 +37,28 @@ pub async fn update_binary_interaction_parameters(
     // 2. 根据 func_code 分发到不同的 Service
     // 这里的字符串匹配逻辑与 TS 端保持一致
     match func_code.to_uppercase().as_str() {
-        "PR" => PrService::batch_save(datas).await.map_err(handle_db_err)?,
-        "RK" => RkService::batch_save(datas).await.map_err(handle_db_err)?,
-        "SRK" => SrkService::batch_save(datas).await.map_err(handle_db_err)?,
-        "NRTL" => NrtlService::batch_save(datas)
+        "PR" => PrService::batch_save_as(datas, insert_source)
+            .await
+            .map_err(handle_db_err)?,
+        "RK" => RkService::batch_save_as(datas, insert_source)
+            .await
+            .map_err(handle_db_err)?,
+        "SRK" => SrkService::batch_save_as(datas, insert_source)
             .await
             .map_err(handle_db_err)?,
-        "NRTL-RK" => NrtlRkService::batch_save(datas)
+        "NRTL" => NrtlService::batch_save_as(datas, insert_source)
             .await
             .map_err(handle_db_err)?,
-        "WILSON" => WilsonService::batch_save(datas)
+        "NRTL-RK" => NrtlRkService::batch_save_as(datas, insert_source)
             .await
             .map_err(handle_db_err)?,
-        "UNIQUAC" => UniquacService::batch_save(datas)
+        "WILSON" => WilsonService::batch_save_as(datas, insert_source)
             .await
             .map_err(handle_db_err)?,
-        "PSRK" => PsrkService::batch_save(datas)
+        "UNIQUAC" => UniquacService::batch_save_as(datas, insert_source)
+            .await
+            .map_err(handle_db_err)?,
+        "PSRK" => PsrkService::batch_save_as(datas, insert_source)
             .await
             .map_err(handle_db_err)?,
         _ => {
@@ -393,6 +419,7 @@ pub async fn get_all_binary_message_by_package_id_is_default(
                             "minT": to_f64(item.min_t), "maxT": to_f64(item.max_t),
                             "componentI": item.compound_i, "componentJ": item.compound_j,
                               "fluidPackageId": item.fluid_package_id,
+                              "source": BinaryParameterSource::parse(&item.source),
                         })
                     }).collect::<Vec<_>>())
             }
@@ -407,6 +434,7 @@ pub async fn get_all_binary_message_by_package_id_is_default(
                             "minT": to_f64(item.min_t), "maxT": to_f64(item.max_t),
                             "componentI": item.compound_i, "componentJ": item.compound_j,
                              "fluidPackageId": item.fluid_package_id,
+                             "source": BinaryParameterSource::parse(&item.source),
                         })
                     }).collect::<Vec<_>>())
             }
@@ -421,6 +449,7 @@ pub async fn get_all_binary_message_by_package_id_is_default(
                             "minT": to_f64(item.min_t), "maxT": to_f64(item.max_t),
                             "componentI": item.compound_i, "componentJ": item.compound_j,
                              "fluidPackageId": item.fluid_package_id,
+                             "source": BinaryParameterSource::parse(&item.source),
                         })
                     }).collect::<Vec<_>>())
             }
@@ -441,6 +470,7 @@ pub async fn get_all_binary_message_by_package_id_is_default(
                                 "minT": to_f64(item.min_t), "maxT": to_f64(item.max_t),
                                 "componentI": item.compound_i, "componentJ": item.compound_j,
                                  "fluidPackageId": item.fluid_package_id,
+                                 "source": BinaryParameterSource::parse(&item.source),
                             })
                         })
                         .collect::<Vec<_>>()
@@ -464,6 +494,7 @@ pub async fn get_all_binary_message_by_package_id_is_default(
                                 "minT": to_f64(item.min_t), "maxT": to_f64(item.max_t),
                                 "componentI": item.compound_i, "componentJ": item.compound_j,
                                  "fluidPackageId": item.fluid_package_id,
+                                 "source": BinaryParameterSource::parse(&item.source),
                             })
                         })
                         .collect::<Vec<_>>()
@@ -486,6 +517,7 @@ pub async fn get_all_binary_message_by_package_id_is_default(
                                 "minT": to_f64(item.min_t), "maxT": to_f64(item.max_t),
                                 "componentI": item.compound_i, "componentJ": item.compound_j,
                                  "fluidPackageId": item.fluid_package_id,
+                                 "source": BinaryParameterSource::parse(&item.source),
                             })
                         })
                         .collect::<Vec<_>>()
@@ -507,6 +539,7 @@ pub async fn get_all_binary_message_by_package_id_is_default(
                                 "minT": to_f64(item.min_t), "maxT": to_f64(item.max_t),
                                 "componentI": item.compound_i, "componentJ": item.compound_j,
                                  "fluidPackageId": item.fluid_package_id,
+                                 "source": BinaryParameterSource::parse(&item.source),
                             })
                         })
                         .collect::<Vec<_>>()
@@ -525,6 +558,7 @@ pub async fn get_all_binary_message_by_package_id_is_default(
                                 "VIJ": to_f64(item.vij), "VJI": to_f64(item.vji),
                                 "componentI": item.compound_i, "componentJ": item.compound_j,
                                  "fluidPackageId": item.fluid_package_id,
+                                 "source": BinaryParameterSource::parse(&item.source),
                             })
                         })
                         .collect::<Vec<_>>()
@@ -578,6 +612,7 @@ pub async fn get_all_binary_message_by_package_ids(
                             "minT": to_f64(item.min_t), "maxT": to_f64(item.max_t),
                             "componentI": item.compound_i, "componentJ": item.compound_j,
                               "fluidPackageId": item.fluid_package_id,
+                              "source": BinaryParameterSource::parse(&item.source),
                         })
                     }).collect::<Vec<_>>())
             }
@@ -591,6 +626,7 @@ pub async fn get_all_binary_message_by_package_ids(
                             "minT": to_f64(item.min_t), "maxT": to_f64(item.max_t),
                             "componentI": item.compound_i, "componentJ": item.compound_j,
                              "fluidPackageId": item.fluid_package_id,
+                             "source": BinaryParameterSource::parse(&item.source),
                         })
                     }).collect::<Vec<_>>())
             }
@@ -604,6 +640,7 @@ pub async fn get_all_binary_message_by_package_ids(
                             "minT": to_f64(item.min_t), "maxT": to_f64(item.max_t),
                             "componentI": item.compound_i, "componentJ": item.compound_j,
                              "fluidPackageId": item.fluid_package_id,
+                             "source": BinaryParameterSource::parse(&item.source),
                         })
                     }).collect::<Vec<_>>())
             }
@@ -624,6 +661,7 @@ pub async fn get_all_binary_message_by_package_ids(
                                 "minT": to_f64(item.min_t), "maxT": to_f64(item.max_t),
                                 "componentI": item.compound_i, "componentJ": item.compound_j,
                                  "fluidPackageId": item.fluid_package_id,
+                                 "source": BinaryParameterSource::parse(&item.source),
                             })
                         })
                         .collect::<Vec<_>>()
@@ -647,6 +685,7 @@ pub async fn get_all_binary_message_by_package_ids(
                                 "minT": to_f64(item.min_t), "maxT": to_f64(item.max_t),
                                 "componentI": item.compound_i, "componentJ": item.compound_j,
                                  "fluidPackageId": item.fluid_package_id,
+                                 "source": BinaryParameterSource::parse(&item.source),
                             })
                         })
                         .collect::<Vec<_>>()
@@ -669,6 +708,7 @@ pub async fn get_all_binary_message_by_package_ids(
                                 "minT": to_f64(item.min_t), "maxT": to_f64(item.max_t),
                                 "componentI": item.compound_i, "componentJ": item.compound_j,
                                  "fluidPackageId": item.fluid_package_id,
+                                 "source": BinaryParameterSource::parse(&item.source),
                             })
                         })
                         .collect::<Vec<_>>()
@@ -690,6 +730,7 @@ pub async fn get_all_binary_message_by_package_ids(
                                 "minT": to_f64(item.min_t), "maxT": to_f64(item.max_t),
                                 "componentI": item.compound_i, "componentJ": item.compound_j,
                                  "fluidPackageId": item.fluid_package_id,
+                                 "source": BinaryParameterSource::parse(&item.source),
                             })
                         })
                         .collect::<Vec<_>>()
@@ -708,6 +749,7 @@ pub async fn get_all_binary_message_by_package_ids(
                                 "VIJ": to_f64(item.vij), "VJI": to_f64(item.vji),
                                 "componentI": item.compound_i, "componentJ": item.compound_j,
                                  "fluidPackageId": item.fluid_package_id,
+                                 "source": BinaryParameterSource::parse(&item.source),
                             })
                         })
                         .collect::<Vec<_>>()
@@ -754,6 +796,7 @@ pub async fn get_all_binary_message_by_package_ids_default(
                             "minT": to_f64(item.min_t), "maxT": to_f64(item.max_t),
                             "componentI": item.compound_i, "componentJ": item.compound_j,
                               "fluidPackageId": item.fluid_package_id,
+                              "source": BinaryParameterSource::parse(&item.source),
                         })
                     }).collect::<Vec<_>>())
             }
@@ -770,6 +813,7 @@ pub async fn get_all_binary_message_by_package_ids_default(
                             "minT": to_f64(item.min_t), "maxT": to_f64(item.max_t),
                             "componentI": item.compound_i, "componentJ": item.compound_j,
                              "fluidPackageId": item.fluid_package_id,
+                             "source": BinaryParameterSource::parse(&item.source),
                         })
                     }).collect::<Vec<_>>())
             }
@@ -786,6 +830,7 @@ pub async fn get_all_binary_message_by_package_ids_default(
                             "minT": to_f64(item.min_t), "maxT": to_f64(item.max_t),
                             "componentI": item.compound_i, "componentJ": item.compound_j,
                              "fluidPackageId": item.fluid_package_id,
+                             "source": BinaryParameterSource::parse(&item.source),
                         })
                     }).collect::<Vec<_>>())
             }
@@ -808,6 +853,7 @@ pub async fn get_all_binary_message_by_package_ids_default(
                                 "minT": to_f64(item.min_t), "maxT": to_f64(item.max_t),
                                 "componentI": item.compound_i, "componentJ": item.compound_j,
                                  "fluidPackageId": item.fluid_package_id,
+                                 "source": BinaryParameterSource::parse(&item.source),
                             })
                         })
                         .collect::<Vec<_>>()
@@ -833,6 +879,7 @@ pub async fn get_all_binary_message_by_package_ids_default(
                                 "minT": to_f64(item.min_t), "maxT": to_f64(item.max_t),
                                 "componentI": item.compound_i, "componentJ": item.compound_j,
                                  "fluidPackageId": item.fluid_package_id,
+                                 "source": BinaryParameterSource::parse(&item.source),
                             })
                         })
                         .collect::<Vec<_>>()
@@ -857,6 +904,7 @@ pub async fn get_all_binary_message_by_package_ids_default(
                                 "minT": to_f64(item.min_t), "maxT": to_f64(item.max_t),
                                 "componentI": item.compound_i, "componentJ": item.compound_j,
                                  "fluidPackageId": item.fluid_package_id,
+                                 "source": BinaryParameterSource::parse(&item.source),
                             })
                         })
                         .collect::<Vec<_>>()
@@ -880,6 +928,7 @@ pub async fn get_all_binary_message_by_package_ids_default(
                                 "minT": to_f64(item.min_t), "maxT": to_f64(item.max_t),
                                 "componentI": item.compound_i, "componentJ": item.compound_j,
                                  "fluidPackageId": item.fluid_package_id,
+                                 "source": BinaryParameterSource::parse(&item.source),
                             })
                         })
                         .collect::<Vec<_>>()
@@ -900,6 +949,7 @@ pub async fn get_all_binary_message_by_package_ids_default(
                                 "VIJ": to_f64(item.vij), "VJI": to_f64(item.vji),
                                 "componentI": item.compound_i, "componentJ": item.compound_j,
                                  "fluidPackageId": item.fluid_package_id,
+                                 "source": BinaryParameterSource::parse(&item.source),
                             })
                         })
                         .collect::<Vec<_>>()
@@ -948,3 +998,24 @@ pub async fn delete_binary_interaction_by_cas(
 
     Ok(())
 }
+
+#[napi(namespace = "modelFluidPackageBinary")]
+/// 检测流体包缺失的二元参数组分对，估算后以 source = ESTIMATED 写入并返回已填充的组分对
+pub async fn estimate_missing_binary_parameters_api(
+    package_id: String,
+    options: Option<BinaryEstimateOptions>,
+) -> Result<BinaryEstimateReport> {
+    estimate_missing_binary_parameters(package_id, options)
+        .await
+        .map_err(handle_db_err)
+}
+
+#[napi(namespace = "modelFluidPackageBinary")]
+/// 由实验 VLE / LLE 数据回归活度系数模型二元参数，返回拟合统计与残差数据，可直接写入流体包
+pub async fn regress_binary_parameters_api(
+    query: BinaryRegressionQuery,
+) -> Result<BinaryRegressionResult> {
+    regress_binary_parameters(query)
+        .await
+        .map_err(handle_db_err)
+}
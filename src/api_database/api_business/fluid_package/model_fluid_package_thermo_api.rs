@@ -0,0 +1,29 @@
+use crate::error_handle::err_handle::handle_db_err;
+use crate::service_database::database_business::service::fluid_package::model_fluid_package_thermo_service::*;
+use napi::Result;
+use crate::service_database::database_physical_property::service::physical_enum::BinaryFuncCode;
+use napi_derive::napi;
+
+#[napi(namespace = "physicalCalc")]
+/// 按流体包所选二元方法（NRTL / Wilson / UNIQUAC）计算活度系数与超额吉布斯自由能
+pub async fn calc_activity_coefficients_api(
+    query: ActivityCoefficientQuery,
+) -> Result<ActivityCoefficientResult> {
+    calc_activity_coefficients(query)
+        .await
+        .map_err(handle_db_err)
+}
+
+#[napi(namespace = "physicalCalc", js_name = "flashPT")]
+/// 立方型状态方程（PR / SRK / RK）等温等压闪蒸；composition 为 CAS 号 → 摩尔分数，温度 K，压力 Pa
+pub async fn flash_pt_api(
+    package_id: String,
+    composition: std::collections::HashMap<String, f64>,
+    temperature: f64,
+    pressure: f64,
+    func_code: Option<BinaryFuncCode>,
+) -> Result<FlashPtResult> {
+    flash_pt_by_package(package_id, composition, temperature, pressure, func_code)
+        .await
+        .map_err(handle_db_err)
+}
@@ -0,0 +1,22 @@
+use crate::error_handle::err_handle::*;
+use crate::service_database::database_business::service::fluid_package::model_reaction_validate_service::*;
+use napi::Result;
+use napi_derive::napi;
+
+#[napi(namespace = "modelReactionValidate")]
+/// 校验反应包下全部反应：方程式、元素守恒、标准反应热与动力学/平衡参数
+pub async fn validate_reaction_package_api(
+    reaction_package_id: String,
+) -> Result<Vec<ReactionValidationResult>> {
+    validate_reaction_package(reaction_package_id)
+        .await
+        .map_err(handle_db_err)
+}
+
+#[napi(namespace = "modelReactionValidate")]
+/// 保存前校验单条反应
+pub async fn check_reaction_equation_api(
+    query: ReactionCheckQuery,
+) -> Result<ReactionValidationResult> {
+    check_reaction_equation(query).await.map_err(handle_db_err)
+}
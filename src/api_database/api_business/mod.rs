@@ -1,4 +1,5 @@
 pub mod component;
+pub mod flowsheet;
 pub mod fluid_package;
 pub mod model_config;
 pub mod util_handle;
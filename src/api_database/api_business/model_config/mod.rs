@@ -1,4 +1,5 @@
 pub mod model_api;
+pub mod model_archive_api;
 pub mod model_config_api;
 pub mod model_file_comparison_api;
 pub mod model_graphic_page_api;
@@ -3,6 +3,7 @@ use napi_derive::napi;
 
 use crate::error_handle::err_handle::*;
 // 2. 引入 Service 层 (包含 ModelDTO, insert_model 等)
+use crate::service_database::database_business::service::model_config::model_bundle_service;
 use crate::service_database::database_business::service::model_config::model_service as service;
 // 3. 引入 DTO 类型用于参数接收
 use crate::service_database::database_business::service::model_config::model_service::{
@@ -76,3 +77,12 @@ pub async fn select_model_by_model_name_api(name: String) -> Result<String> {
         .map_err(handle_db_err)?;
     Ok(result)
 }
+
+/// 模型另存为：深拷贝模型及全部关联数据，返回新模型 ID
+#[napi(namespace = "modelHandle")]
+pub async fn clone_model_api(source_model_id: String, new_name: String) -> Result<String> {
+    let result = model_bundle_service::clone_model(source_model_id, new_name)
+        .await
+        .map_err(handle_db_err)?;
+    Ok(result)
+}
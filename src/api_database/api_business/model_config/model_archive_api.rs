@@ -0,0 +1,16 @@
+use napi::Result;
+use napi_derive::napi;
+
+use crate::error_handle::err_handle::*;
+use crate::generate_napi_methods;
+use crate::service_database::model_archive_service::*;
+use paste::paste;
+
+paste! {
+    generate_napi_methods! {
+        "modelArchive",
+        export_model_archive_api(model_id: String, path: String) -> ModelArchiveManifest => export_model_archive,
+        read_model_archive_manifest_api(path: String) -> ModelArchiveManifest => read_model_archive_manifest,
+        import_model_archive_api(path: String, new_name: Option<String>) -> ModelArchiveImportResult => import_model_archive,
+    }
+}
@@ -105,3 +105,27 @@ pub async fn get_model_unit_set_items_by_model_id_api(
         .await
         .map_err(handle_db_err)
 }
+
+/// 将 SI 参数对象换算为模型当前单位集下的数值（categories: 参数名 → 单位类别编码）
+#[napi(namespace = "modelUnit")]
+pub async fn convert_model_params_from_si_api(
+    model_id: String,
+    params: serde_json::Value,
+    categories: std::collections::HashMap<String, String>,
+) -> Result<serde_json::Value> {
+    convert_model_params_from_si(model_id, params, categories)
+        .await
+        .map_err(handle_db_err)
+}
+
+/// 将模型当前单位集下的参数对象换算为 SI 数值
+#[napi(namespace = "modelUnit")]
+pub async fn convert_model_params_to_si_api(
+    model_id: String,
+    params: serde_json::Value,
+    categories: std::collections::HashMap<String, String>,
+) -> Result<serde_json::Value> {
+    convert_model_params_to_si(model_id, params, categories)
+        .await
+        .map_err(handle_db_err)
+}
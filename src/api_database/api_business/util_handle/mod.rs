@@ -1,3 +1,4 @@
+pub mod model_dcs_tag_api;
 pub mod model_global_script_api;
 pub mod model_initialize_data_in_cold_state_api;
 pub mod model_status_handle_api;
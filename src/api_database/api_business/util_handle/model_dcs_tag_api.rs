@@ -0,0 +1,31 @@
+use crate::error_handle::err_handle::*;
+use napi::Result;
+use napi_derive::napi;
+use paste::paste;
+
+use crate::generate_napi_methods;
+use crate::service_database::database_business::service::util_handle::model_dcs_tag_service::*;
+
+paste! {
+    generate_napi_methods! {
+        "modelDcsTag",
+
+        get_dcs_tags_by_model_id_api(model_id: String) -> Vec<ModelDcsTagDTO> => get_dcs_tags_by_model_id,
+
+        get_dcs_tags_by_graphic_id_api(model_id: String, graphic_id: String) -> Vec<ModelDcsTagDTO> => get_dcs_tags_by_graphic_id,
+
+        get_dcs_tag_by_name_api(model_id: String, tag_name: String) -> Option<ModelDcsTagDTO> => get_dcs_tag_by_name,
+
+        save_dcs_tags_api(model_id: String, tags: Vec<ModelDcsTagDTO>) -> u32 => save_dcs_tags,
+
+        delete_dcs_tags_by_ids_api(model_id: String, ids: Vec<i32>) -> bool => delete_dcs_tags_by_ids,
+
+        preview_dcs_tag_import_api(content: String, options: Option<DcsTagImportOptions>) -> DcsTagImportPreview => preview_dcs_tag_import,
+
+        import_dcs_tags_api(model_id: String, content: String, options: Option<DcsTagImportOptions>) -> DcsTagImportResult => import_dcs_tags,
+
+        import_dcs_tags_from_file_api(model_id: String, path: String, options: Option<DcsTagImportOptions>) -> DcsTagImportResult => import_dcs_tags_from_file,
+
+        sync_dcs_tags_to_point_cache_api(model_id: String) -> u32 => sync_dcs_tags_to_point_cache,
+    }
+}
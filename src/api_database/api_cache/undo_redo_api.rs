@@ -100,6 +100,71 @@ pub async fn update_undo_new_data(id: i32, model_id: String, new_data: String) -
     Ok(())
 }
 
+#[napi(namespace = "undoRedoHandle")]
+/// 开始一个操作组，组内的记录合并为一个撤销步骤
+pub async fn begin_undo_group(model_id: String) -> Result<()> {
+    begin_undo_group_service(model_id)
+        .await
+        .map_err(handle_db_err)
+}
+
+#[napi(namespace = "undoRedoHandle")]
+/// 结束操作组，最外层结束时返回步骤 id
+pub async fn end_undo_group(model_id: String) -> Result<Option<i32>> {
+    end_undo_group_service(model_id)
+        .await
+        .map_err(handle_db_err)
+}
+
+#[napi(namespace = "undoRedoHandle")]
+/// 获取最近一个可撤销步骤的全部记录，无可撤销步骤时返回空数组
+pub async fn get_the_last_undo_step(model_id: String) -> Result<Vec<UndoRedoData>> {
+    get_latest_undo_step(model_id).await.map_err(handle_db_err)
+}
+
+#[napi(namespace = "undoRedoHandle")]
+/// 获取最近一个可重做步骤的全部记录，无可重做步骤时返回空数组
+pub async fn get_the_last_redo_step(model_id: String) -> Result<Vec<UndoRedoData>> {
+    get_latest_redo_step(model_id).await.map_err(handle_db_err)
+}
+
+#[napi(namespace = "undoRedoHandle")]
+/// 更新整个步骤的状态
+pub async fn update_undo_step_status(step_id: i32, model_id: String, status: i32) -> Result<u32> {
+    let count = update_step_status_service(step_id, model_id, status)
+        .await
+        .map_err(handle_db_err)?;
+    Ok(count as u32)
+}
+
+#[napi(namespace = "undoRedoHandle")]
+/// 撤销：在服务端执行最近一个步骤的逆操作，返回该步骤的记录（无可撤销步骤时为空数组）
+pub async fn apply_undo(model_id: String) -> Result<Vec<UndoRedoData>> {
+    apply_undo_service(model_id).await.map_err(handle_db_err)
+}
+
+#[napi(namespace = "undoRedoHandle")]
+/// 重做：在服务端重新执行最近一个已撤销的步骤，返回该步骤的记录（无可重做步骤时为空数组）
+pub async fn apply_redo(model_id: String) -> Result<Vec<UndoRedoData>> {
+    apply_redo_service(model_id).await.map_err(handle_db_err)
+}
+
+#[napi(namespace = "undoRedoHandle")]
+/// 设置模型的撤销历史配置（步数上限、是否持久化）
+pub async fn set_undo_history_config(model_id: String, config: UndoHistoryConfig) -> Result<()> {
+    set_undo_history_config_service(model_id, config)
+        .await
+        .map_err(handle_db_err)
+}
+
+#[napi(namespace = "undoRedoHandle")]
+/// 获取模型的撤销历史配置
+pub async fn get_undo_history_config(model_id: String) -> Result<UndoHistoryConfig> {
+    get_undo_history_config_service(model_id)
+        .await
+        .map_err(handle_db_err)
+}
+
 #[napi(namespace = "undoRedoHandle")]
 pub async fn test() -> Result<()> {
     bench_insert()
@@ -1,10 +1,15 @@
 use crate::error_handle::err_handle::handle_db_err;
+use crate::service_database::database_cache::service::trend_archive_service::*;
+use crate::service_database::database_cache::service::trend_service::*;
+use crate::service_database::database_cache::service::trend_subscription_service::*;
 use crate::service_database::database_cache::service::variable_curve_service::*;
+use crate::service_database::trend_export_service::*;
+use napi::bindgen_prelude::Function;
 use napi::*;
 use napi_derive::napi;
 
 #[napi(namespace = "variableCurveHandle")]
-/// 插入新的变量曲线信息
+/// 插入新的变量曲线信息，返回写入记录的 ID
 pub async fn add_variable_curve_log(
     datasets: Vec<DatasetItem>,
     model_id: String,
@@ -12,10 +17,26 @@ pub async fn add_variable_curve_log(
     sim_time: f64,
 ) -> Result<i32> {
     // 调用服务层方法并处理错误
-    let result = insert_model_variable_curve_entity_cache(datasets, model_id, config_id, sim_time)
-        .await
-        .map_err(handle_db_err)?;
-    Ok(result.last_insert_id)
+    let (record_id, _) =
+        insert_model_variable_curve_entity_cache(datasets, model_id, config_id, sim_time)
+            .await
+            .map_err(handle_db_err)?;
+    Ok(record_id)
+}
+
+#[napi(namespace = "variableCurveHandle")]
+/// 插入新的变量曲线信息，返回入库的点数
+pub async fn add_variable_curve_samples(
+    datasets: Vec<DatasetItem>,
+    model_id: String,
+    config_id: String,
+    sim_time: f64,
+) -> Result<u32> {
+    let (_, appended) =
+        insert_model_variable_curve_entity_cache(datasets, model_id, config_id, sim_time)
+            .await
+            .map_err(handle_db_err)?;
+    Ok(appended)
 }
 
 // 注意：由于 NAPI 只能返回 JS 支持的类型 (如 Vec<JsObject>),
@@ -32,15 +53,147 @@ pub async fn list_variable_curves_by_time(
 }
 
 #[napi(namespace = "variableCurveHandle")]
-/// 根据 modelId, configId 和时间间隔 filterCount 获取过滤后的曲线数据
+/// 根据 modelId, configId 和时间间隔 filterCount 获取过滤后的曲线数据，
+/// 可按仿真时间窗口查询，maxPoints 大于 0 时每个变量先在服务端降采样到该点数
 pub async fn list_variable_curves_by_filter_count(
     model_id: String,
     config_id: String,
     filter_count: i32,
+    start_time: Option<f64>,
+    end_time: Option<f64>,
+    max_points: Option<u32>,
 ) -> Result<Vec<CurveModel>> {
-    let result =
-        get_model_variable_curve_entity_cache_by_filter_count(model_id, config_id, filter_count)
-            .await
-            .map_err(handle_db_err)?;
+    let result = get_model_variable_curve_entity_cache_by_filter_count(
+        model_id,
+        config_id,
+        filter_count,
+        start_time,
+        end_time,
+        max_points,
+    )
+    .await
+    .map_err(handle_db_err)?;
+    Ok(result)
+}
+
+#[napi(namespace = "variableCurveHandle")]
+/// 按仿真时间窗口查询变量趋势，maxPoints 大于 0 时在服务端降采样到该点数
+pub async fn query_variable_trend(
+    model_id: String,
+    config_id: String,
+    variable_ids: Vec<String>,
+    start_time: Option<f64>,
+    end_time: Option<f64>,
+    max_points: Option<u32>,
+    mode: Option<TrendDownsampleMode>,
+) -> Result<Vec<TrendSeries>> {
+    let result = query_trend_series(
+        model_id,
+        config_id,
+        variable_ids,
+        start_time,
+        end_time,
+        max_points,
+        mode,
+    )
+    .await
+    .map_err(handle_db_err)?;
+    Ok(result)
+}
+
+#[napi(namespace = "variableCurveHandle")]
+/// 列出趋势配置下的变量及其数据范围
+pub async fn list_variable_trend_variables(
+    model_id: String,
+    config_id: String,
+) -> Result<Vec<TrendVariable>> {
+    let result = get_trend_variables(model_id, config_id)
+        .await
+        .map_err(handle_db_err)?;
+    Ok(result)
+}
+
+#[napi(namespace = "variableCurveHandle")]
+/// 把模型缓冲中的趋势采样点写成数据块，返回写入的数据块数
+pub async fn flush_variable_trend(model_id: String) -> Result<u32> {
+    let result = flush_trend_buffer(model_id).await.map_err(handle_db_err)?;
+    Ok(result)
+}
+
+#[napi(namespace = "variableCurveHandle")]
+/// 清空趋势配置下的全部数据，返回删除的数据块数
+pub async fn clear_variable_trend(model_id: String, config_id: String) -> Result<u32> {
+    let result = clear_trend_data(model_id, config_id)
+        .await
+        .map_err(handle_db_err)?;
+    Ok(result)
+}
+
+#[napi(namespace = "variableCurveHandle")]
+/// 订阅趋势配置的实时采样，写入曲线数据时按批推送给 callback，返回订阅 ID
+pub fn subscribe_variable_trend(
+    config_id: String,
+    callback: Function<Vec<TrendTick>, ()>,
+) -> Result<u32> {
+    subscribe_trend(config_id, callback)
+}
+
+#[napi(namespace = "variableCurveHandle")]
+/// 取消趋势订阅，订阅不存在时返回 false
+pub fn unsubscribe_variable_trend(subscription_id: u32) -> bool {
+    unsubscribe_trend(subscription_id)
+}
+
+#[napi(namespace = "variableCurveHandle")]
+/// 为模型打开磁盘趋势归档，缓存中的趋势数据超过 spillThresholdBytes 后自动搬到归档文件
+pub async fn open_variable_trend_archive(
+    model_id: String,
+    file_path: String,
+    spill_threshold_bytes: Option<i64>,
+) -> Result<TrendArchiveStatus> {
+    let result = open_trend_archive(model_id, file_path, spill_threshold_bytes)
+        .await
+        .map_err(handle_db_err)?;
+    Ok(result)
+}
+
+#[napi(namespace = "variableCurveHandle")]
+/// 查询模型的趋势归档状态，未打开归档时返回 null
+pub async fn get_variable_trend_archive_status(
+    model_id: String,
+) -> Result<Option<TrendArchiveStatus>> {
+    let result = get_trend_archive_status(model_id)
+        .await
+        .map_err(handle_db_err)?;
+    Ok(result)
+}
+
+#[napi(namespace = "variableCurveHandle")]
+/// 把模型的全部趋势数据立即写入归档，返回搬迁的数据块数
+pub async fn spill_variable_trend_archive(model_id: String) -> Result<u32> {
+    let result = spill_trend_archive(model_id).await.map_err(handle_db_err)?;
+    Ok(result)
+}
+
+#[napi(namespace = "variableCurveHandle")]
+/// 趋势数据全部落盘后关闭模型的归档，返回搬迁的数据块数
+pub async fn close_variable_trend_archive(model_id: String) -> Result<u32> {
+    let result = close_trend_archive(model_id).await.map_err(handle_db_err)?;
+    Ok(result)
+}
+
+#[napi(namespace = "variableCurveHandle")]
+/// 导出趋势配置在仿真时间窗口内的原始数据为 CSV 或 Parquet，每个变量一列
+pub async fn export_variable_trend(
+    model_id: String,
+    config_id: String,
+    start_time: Option<f64>,
+    end_time: Option<f64>,
+    format: TrendExportFormat,
+    file_path: String,
+) -> Result<TrendExportResult> {
+    let result = export_trend(model_id, config_id, start_time, end_time, format, file_path)
+        .await
+        .map_err(handle_db_err)?;
     Ok(result)
 }
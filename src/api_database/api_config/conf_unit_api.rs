@@ -7,6 +7,7 @@ use napi_derive::napi;
 
 // 引入服务层函数和结构体
 // 注意：PointData 必须是 pub 并且实现 Deserialize
+use crate::service_database::database_config::service::conf_unit_conversion_service::*;
 use crate::service_database::database_config::service::conf_unit_service::*;
 
 /// 将 sea_orm::DbErr 转换为 napi::Error，以便在 JS 中抛出异常
@@ -154,3 +155,63 @@ pub async fn updata_and_insert_conf_unit_item_category_api(
         .map_err(handle_db_err)?;
     Ok(result)
 }
+
+// 单位换算系数
+#[napi(namespace = "confUnit")]
+pub async fn get_all_unit_conversion_api() -> Result<Vec<ConfUnitConversionDTO>> {
+    select_conf_unit_conversion_all()
+        .await
+        .map_err(handle_db_err)
+}
+
+#[napi(namespace = "confUnit")]
+pub async fn get_unit_conversion_by_category_api(
+    category_code: String,
+) -> Result<Vec<ConfUnitConversionDTO>> {
+    select_conf_unit_conversion_by_category(category_code)
+        .await
+        .map_err(handle_db_err)
+}
+
+#[napi(namespace = "confUnit")]
+/// 批量更新或插入单位换算系数（按 category_code + unit 匹配）
+pub async fn updata_and_insert_conf_unit_conversion_api(
+    data: Vec<ConfUnitConversionDTO>,
+) -> Result<i32> {
+    upsert_and_insert_conf_unit_conversion(data)
+        .await
+        .map_err(handle_db_err)
+}
+
+#[napi(namespace = "confUnit")]
+pub async fn delete_conf_unit_conversion_api(category_code: String, unit: String) -> Result<i64> {
+    let result = delete_conf_unit_conversion(category_code, unit)
+        .await
+        .map_err(handle_db_err)?;
+    Ok(result as i64)
+}
+
+#[napi(namespace = "confUnit")]
+/// 单位换算，量纲不一致或单位所属类别不明确时抛出异常
+pub async fn convert_api(
+    value: f64,
+    from_unit: String,
+    to_unit: String,
+    category_code: Option<String>,
+) -> Result<f64> {
+    convert_unit(value, from_unit, to_unit, category_code)
+        .await
+        .map_err(handle_db_err)
+}
+
+#[napi(namespace = "confUnit")]
+pub async fn convert_batch_api(
+    values: Vec<f64>,
+    from_unit: String,
+    to_unit: String,
+    category_code: Option<String>,
+) -> Result<Vec<f64>> {
+    convert_unit_batch(values, from_unit, to_unit, category_code)
+        .await
+        .map_err(handle_db_err)
+}
@@ -102,3 +102,13 @@ pub async fn init_physical_temperature_equation_data_fast_api(
         .map_err(handle_db_err)?;
     Ok(())
 }
+
+#[napi(namespace = "physicalComponent")]
+/// 按温度关联方程计算组分性质曲线（可指定温度单位、结果单位与超范围处理方式）
+pub async fn evaluate_physical_temperature_equation_api(
+    query: TemperatureEquationEvalQuery,
+) -> Result<TemperatureEquationCurve> {
+    evaluate_physical_temperature_equation(query)
+        .await
+        .map_err(handle_db_err)
+}
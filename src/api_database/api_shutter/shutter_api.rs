@@ -3,7 +3,11 @@ use napi_derive::napi;
 // 导入您之前定义的 DTOs 和数据库操作函数
 /// 将 sea_orm::DbErr 转换为 napi::Error，以便在 JS 中抛出异常
 use crate::error_handle::err_handle::handle_db_err;
+use crate::service_database::database_shutter::service::shutter_diff_service::*;
+use crate::service_database::database_shutter::service::shutter_integrity_service::*;
 use crate::service_database::database_shutter::service::shutter_service::*;
+use crate::service_database::database_shutter::service::shutter_transfer_service::*;
+use crate::service_database::shutter_chunk::chunk_store::{ShutterChunkStats, ShutterDictInfo};
 use crate::{generate_napi_i32_methods, generate_napi_methods, generate_napi_u32_methods};
 use paste::paste;
 
@@ -44,6 +48,47 @@ paste! {
     }
 }
 
+paste! {
+    generate_napi_methods! {
+        "shutterHandle",
+        diff_shutters_api(id_a: String, id_b: String, model_id: String) -> ShutterDiffResult => diff_shutters,
+        diff_auto_shutters_api(id_a: i32, id_b: i32, model_id: String) -> ShutterDiffResult => diff_auto_shutters,
+        restore_shutter_partial_api(
+            shutter_id: String,
+            model_id: String,
+            graphic_ids: Vec<String>,
+            state_code: String) -> ShutterRestoreResult => restore_shutter_partial,
+        restore_auto_shutter_partial_api(
+            id: i32,
+            model_id: String,
+            graphic_ids: Vec<String>,
+            state_code: String) -> ShutterRestoreResult => restore_auto_shutter_partial,
+        compact_shutter_storage_api(model_id: String) -> u32 => compact_model_shutter_storage,
+        train_shutter_dictionary_api(
+            model_id: String,
+            max_size: Option<u32>) -> ShutterDictInfo => train_model_shutter_dictionary,
+        get_shutter_chunk_stats_api(model_id: String) -> ShutterChunkStats => get_model_shutter_chunk_stats,
+    }
+}
+
+paste! {
+    generate_napi_methods! {
+        "shutterHandle",
+        verify_shutters_api(model_id: String) -> ShutterVerifyReport => verify_shutters,
+        recover_shutter_api(
+            shutter_id: String,
+            model_id: String,
+            repair: bool) -> ShutterRecoveryResult => recover_shutter,
+        recover_auto_shutter_api(id: i32, model_id: String) -> ShutterRecoveryResult => recover_auto_shutter,
+        promote_auto_shutter_api(
+            auto_id: i32,
+            index_num: i32,
+            name: String,
+            model_id: String) -> String => promote_auto_shutter,
+        demote_shutter_api(id: String, model_id: String) -> i32 => demote_shutter,
+    }
+}
+
 // #[napi(namespace = "shutterHandle")]
 // pub async fn delete_shutter_entity(id: String, model_id: String) -> Result<u32> {
 //     delete_model_shutter_entity(id, model_id)
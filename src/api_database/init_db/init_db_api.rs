@@ -1,7 +1,9 @@
 use napi::Result;
 use napi_derive::napi;
 
+use crate::error_handle::err_handle::handle_db_err;
 use crate::service_database::database_cache::db_cache_connection::{DbStats, get_cache_db_stats};
+use crate::service_database::database_cache::service::cache_governance_service::*;
 
 #[napi(namespace = "initDB")]
 /// 初始化缓存数据库
@@ -78,6 +80,12 @@ pub async fn init_business_db(url: String) -> Result<()> {
 /// 关闭数据库使用
 #[napi(namespace = "initDB")]
 pub async fn close_all_dbs() -> Result<()> {
+    // 停止缓存容量治理任务
+    let _ = stop_cache_governance_task().await;
+    // 释放趋势订阅回调
+    crate::service_database::database_cache::service::trend_subscription_service::unsubscribe_all_trends();
+    // 趋势数据落盘并关闭归档
+    crate::service_database::database_cache::service::trend_archive_service::close_all_trend_archives().await;
     // 关闭业务数据库 business
     crate::service_database::database_business::db_business_connection::close_business_db().await;
     // 关闭auto_shutter数据库
@@ -111,3 +119,41 @@ pub async fn move_db_by_url(target_path: String) -> Result<()> {
 pub async fn get_cache_db_stats_api() -> Result<DbStats> {
     get_cache_db_stats().await
 }
+
+#[napi(namespace = "initDB")]
+/// 设置一类缓存数据（撤销日志 / 曲线）的行数与字节数上限并立即淘汰超出部分，两项都为空时取消上限
+pub async fn set_cache_limit_api(limit: CacheTableLimit) -> Result<CacheEvictionResult> {
+    set_cache_limit(limit).await.map_err(handle_db_err)
+}
+
+#[napi(namespace = "initDB")]
+/// 查询当前设置的缓存上限
+pub fn get_cache_limits_api() -> Vec<CacheTableLimit> {
+    get_cache_limits()
+}
+
+#[napi(namespace = "initDB")]
+/// 按当前上限淘汰超出的缓存数据，从最旧的数据开始
+pub async fn enforce_cache_limits_api() -> Result<CacheEvictionResult> {
+    enforce_cache_limits().await.map_err(handle_db_err)
+}
+
+#[napi(namespace = "initDB")]
+/// 启动后台缓存容量治理任务，每 intervalSeconds 秒执行一次，返回是否替换了已有任务
+pub async fn start_cache_governance_api(interval_seconds: u32) -> Result<bool> {
+    start_cache_governance_task(interval_seconds)
+        .await
+        .map_err(handle_db_err)
+}
+
+#[napi(namespace = "initDB")]
+/// 停止后台缓存容量治理任务
+pub async fn stop_cache_governance_api() -> Result<bool> {
+    stop_cache_governance_task().await.map_err(handle_db_err)
+}
+
+#[napi(namespace = "initDB")]
+/// 整理缓存内存库（VACUUM），回收淘汰数据后留下的空闲页
+pub async fn shrink_cache_db_api() -> Result<CacheShrinkResult> {
+    shrink_cache_db().await.map_err(handle_db_err)
+}
@@ -0,0 +1,310 @@
+// ======================================
+// 液相活度系数模型：NRTL / Wilson / UNIQUAC
+// 二元参数按有序组分对 (i, j) 存放，缺失的组分对按理想（无相互作用）处理
+// ======================================
+
+/// 理想气体常数 J/(mol·K)
+pub const GAS_CONSTANT: f64 = 8.314_462_618;
+
+/// UNIQUAC 配位数
+const UNIQUAC_Z: f64 = 10.0;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ActivityModel {
+    Nrtl,
+    Wilson,
+    Uniquac,
+}
+
+/// 有序组分对 (i, j) 的温度相关系数
+///
+/// - NRTL: τij = a + b/T + e·ln T + f·T，αij = c + d·(T - 273.15)
+/// - Wilson: ln Λij = a + b/T + c·ln T + d·T + e/T²
+/// - UNIQUAC: τij = exp(a + b/T + c·ln T + d·T + e/T²)
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct PairCoefficients {
+    pub a: f64,
+    pub b: f64,
+    pub c: f64,
+    pub d: f64,
+    pub e: f64,
+    pub f: f64,
+}
+
+impl PairCoefficients {
+    fn exponent(&self, t: f64) -> f64 {
+        self.a + self.b / t + self.c * t.ln() + self.d * t + self.e / (t * t)
+    }
+}
+
+/// UNIQUAC 纯组分结构参数
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct UniquacStructure {
+    /// 体积参数 r
+    pub r: f64,
+    /// 表面积参数 q
+    pub q: f64,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ActivityResult {
+    pub ln_gamma: Vec<f64>,
+    pub gamma: Vec<f64>,
+    /// 超额吉布斯自由能 Gᴱ/RT
+    pub ge_rt: f64,
+}
+
+/// 二元参数矩阵，pairs[i][j] 为有序对 (i, j) 的系数
+#[derive(Clone, Debug, PartialEq)]
+pub struct ActivityInput<'a> {
+    pub temperature: f64,
+    pub x: &'a [f64],
+    pub pairs: &'a [Vec<Option<PairCoefficients>>],
+    /// 仅 UNIQUAC 需要
+    pub structure: &'a [UniquacStructure],
+}
+
+/// 计算活度系数与超额吉布斯自由能；组成会先归一化
+pub fn activity_coefficients(
+    model: ActivityModel,
+    input: &ActivityInput,
+) -> Result<ActivityResult, String> {
+    let n = input.x.len();
+    let t = input.temperature;
+    if n == 0 {
+        return Err("组分列表为空".to_string());
+    }
+    if !(t.is_finite() && t > 0.0) {
+        return Err(format!("温度必须为正数（K）: {}", t));
+    }
+    if input.pairs.len() != n || input.pairs.iter().any(|row| row.len() != n) {
+        return Err("二元参数矩阵维数与组分数不一致".to_string());
+    }
+    if input.x.iter().any(|&v| !(v.is_finite() && v >= 0.0)) {
+        return Err("摩尔分数必须为非负数".to_string());
+    }
+    let total: f64 = input.x.iter().sum();
+    if total <= 0.0 {
+        return Err("摩尔分数之和必须大于 0".to_string());
+    }
+    let x: Vec<f64> = input.x.iter().map(|v| v / total).collect();
+
+    let ln_gamma = match model {
+        ActivityModel::Nrtl => nrtl(&x, t, input.pairs),
+        ActivityModel::Wilson => wilson(&x, t, input.pairs),
+        ActivityModel::Uniquac => {
+            if input.structure.len() != n {
+                return Err("UNIQUAC 需要每个组分的 r、q 参数".to_string());
+            }
+            uniquac(&x, t, input.pairs, input.structure)
+        }
+    };
+    if ln_gamma.iter().any(|v| !v.is_finite()) {
+        return Err("活度系数计算结果无效，请检查二元参数".to_string());
+    }
+    let ge_rt = x.iter().zip(&ln_gamma).map(|(xi, lg)| xi * lg).sum();
+    Ok(ActivityResult {
+        gamma: ln_gamma.iter().map(|v| v.exp()).collect(),
+        ln_gamma,
+        ge_rt,
+    })
+}
+
+fn matrix(n: usize, f: impl Fn(usize, usize) -> f64) -> Vec<Vec<f64>> {
+    (0..n).map(|i| (0..n).map(|j| f(i, j)).collect()).collect()
+}
+
+fn nrtl(x: &[f64], t: f64, pairs: &[Vec<Option<PairCoefficients>>]) -> Vec<f64> {
+    let n = x.len();
+    let tau = matrix(n, |i, j| match pairs[i][j] {
+        Some(p) if i != j => p.a + p.b / t + p.e * t.ln() + p.f * t,
+        _ => 0.0,
+    });
+    let g = matrix(n, |i, j| match pairs[i][j] {
+        Some(p) if i != j => (-(p.c + p.d * (t - 273.15)) * tau[i][j]).exp(),
+        _ => 1.0,
+    });
+    // 按列汇总：Σk xk Gkj 与 Σk xk τkj Gkj
+    let sum_g: Vec<f64> = (0..n)
+        .map(|j| (0..n).map(|k| x[k] * g[k][j]).sum())
+        .collect();
+    let sum_tg: Vec<f64> = (0..n)
+        .map(|j| (0..n).map(|k| x[k] * tau[k][j] * g[k][j]).sum())
+        .collect();
+    (0..n)
+        .map(|i| {
+            let first = sum_tg[i] / sum_g[i];
+            let second: f64 = (0..n)
+                .map(|j| x[j] * g[i][j] / sum_g[j] * (tau[i][j] - sum_tg[j] / sum_g[j]))
+                .sum();
+            first + second
+        })
+        .collect()
+}
+
+fn wilson(x: &[f64], t: f64, pairs: &[Vec<Option<PairCoefficients>>]) -> Vec<f64> {
+    let n = x.len();
+    let lambda = matrix(n, |i, j| match pairs[i][j] {
+        Some(p) if i != j => p.exponent(t).exp(),
+        _ => 1.0,
+    });
+    let sum_row: Vec<f64> = (0..n)
+        .map(|i| (0..n).map(|j| x[j] * lambda[i][j]).sum())
+        .collect();
+    (0..n)
+        .map(|i| {
+            let tail: f64 = (0..n).map(|k| x[k] * lambda[k][i] / sum_row[k]).sum();
+            1.0 - sum_row[i].ln() - tail
+        })
+        .collect()
+}
+
+fn uniquac(
+    x: &[f64],
+    t: f64,
+    pairs: &[Vec<Option<PairCoefficients>>],
+    structure: &[UniquacStructure],
+) -> Vec<f64> {
+    let n = x.len();
+    let tau = matrix(n, |i, j| match pairs[i][j] {
+        Some(p) if i != j => p.exponent(t).exp(),
+        _ => 1.0,
+    });
+    let sum_rx: f64 = (0..n).map(|i| structure[i].r * x[i]).sum();
+    let sum_qx: f64 = (0..n).map(|i| structure[i].q * x[i]).sum();
+    let theta: Vec<f64> = (0..n).map(|i| structure[i].q * x[i] / sum_qx).collect();
+    let l: Vec<f64> = structure
+        .iter()
+        .map(|s| UNIQUAC_Z / 2.0 * (s.r - s.q) - (s.r - 1.0))
+        .collect();
+    let sum_xl: f64 = (0..n).map(|j| x[j] * l[j]).sum();
+    let sum_theta_tau: Vec<f64> = (0..n)
+        .map(|j| (0..n).map(|k| theta[k] * tau[k][j]).sum())
+        .collect();
+    (0..n)
+        .map(|i| {
+            let UniquacStructure { r, q } = structure[i];
+            // φi/xi 与 θi/φi 直接由 r、q 计算，避免 xi = 0 时除零
+            let phi_over_x = r / sum_rx;
+            let theta_over_phi = (q / sum_qx) / phi_over_x;
+            let combinatorial = phi_over_x.ln() + UNIQUAC_Z / 2.0 * q * theta_over_phi.ln() + l[i]
+                - phi_over_x * sum_xl;
+            let residual_tail: f64 = (0..n)
+                .map(|j| theta[j] * tau[i][j] / sum_theta_tau[j])
+                .sum();
+            let residual = q * (1.0 - sum_theta_tau[i].ln() - residual_tail);
+            combinatorial + residual
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn binary(p12: PairCoefficients, p21: PairCoefficients) -> Vec<Vec<Option<PairCoefficients>>> {
+        vec![vec![None, Some(p12)], vec![Some(p21), None]]
+    }
+
+    #[test]
+    fn test_infinite_dilution_and_gibbs_duhem() {
+        let t = 350.0;
+        let close = |a: f64, b: f64| (a - b).abs() < 1e-9;
+
+        // NRTL：ln γ1∞ = τ21 + τ12·exp(-α·τ12)
+        let p12 = PairCoefficients {
+            a: 0.3,
+            b: 120.0,
+            c: 0.3,
+            ..Default::default()
+        };
+        let p21 = PairCoefficients {
+            a: -0.1,
+            b: 250.0,
+            c: 0.3,
+            ..Default::default()
+        };
+        let pairs = binary(p12, p21);
+        let input = |x: &'static [f64]| ActivityInput {
+            temperature: t,
+            x,
+            pairs: &pairs,
+            structure: &[],
+        };
+        let r = activity_coefficients(ActivityModel::Nrtl, &input(&[0.0, 1.0])).unwrap();
+        let (tau12, tau21) = (0.3 + 120.0 / t, -0.1 + 250.0 / t);
+        assert!(close(r.ln_gamma[0], tau21 + tau12 * (-0.3 * tau12).exp()));
+        assert!(close(r.ln_gamma[1], 0.0));
+
+        // Wilson：ln γ1∞ = 1 - ln Λ12 - Λ21
+        let w12 = PairCoefficients {
+            b: -200.0,
+            ..Default::default()
+        };
+        let w21 = PairCoefficients {
+            b: -50.0,
+            ..Default::default()
+        };
+        let wp = binary(w12, w21);
+        let w = activity_coefficients(
+            ActivityModel::Wilson,
+            &ActivityInput {
+                temperature: t,
+                x: &[0.0, 1.0],
+                pairs: &wp,
+                structure: &[],
+            },
+        )
+        .unwrap();
+        let (l12, l21) = ((-200.0 / t).exp(), (-50.0 / t).exp());
+        assert!(close(w.ln_gamma[0], 1.0 - l12.ln() - l21));
+
+        // UNIQUAC：Gibbs-Duhem 数值校验 x1·dlnγ1 + x2·dlnγ2 = 0
+        let structure = [
+            UniquacStructure { r: 2.57, q: 2.34 },
+            UniquacStructure { r: 0.92, q: 1.4 },
+        ];
+        let up = binary(
+            PairCoefficients {
+                b: -80.0,
+                ..Default::default()
+            },
+            PairCoefficients {
+                b: -300.0,
+                ..Default::default()
+            },
+        );
+        let at = |x1: f64| {
+            let x = [x1, 1.0 - x1];
+            activity_coefficients(
+                ActivityModel::Uniquac,
+                &ActivityInput {
+                    temperature: t,
+                    x: &x,
+                    pairs: &up,
+                    structure: &structure,
+                },
+            )
+            .unwrap()
+        };
+        let (h, x1) = (1e-6, 0.4);
+        let (lo, hi) = (at(x1 - h), at(x1 + h));
+        let gd =
+            x1 * (hi.ln_gamma[0] - lo.ln_gamma[0]) + (1.0 - x1) * (hi.ln_gamma[1] - lo.ln_gamma[1]);
+        assert!(gd.abs() < 1e-8, "{}", gd);
+
+        // 无二元参数时为理想溶液
+        let none = vec![vec![None; 2]; 2];
+        let ideal = activity_coefficients(
+            ActivityModel::Nrtl,
+            &ActivityInput {
+                temperature: t,
+                x: &[0.5, 0.5],
+                pairs: &none,
+                structure: &[],
+            },
+        )
+        .unwrap();
+        assert!(ideal.gamma.iter().all(|g| close(*g, 1.0)) && close(ideal.ge_rt, 0.0));
+    }
+}
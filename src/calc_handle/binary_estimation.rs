@@ -0,0 +1,146 @@
+// ======================================
+// 缺失二元参数估算
+// - 活度系数模型：以 UNIFAC 预测的 ln γ 为目标回归 bij / bji（τ 或 ln Λ 取 b/T 形式）
+// - 立方型状态方程：Chueh-Prausnitz 临界体积关联式估算 kij
+// ======================================
+
+use crate::calc_handle::activity_model::{
+    ActivityInput, ActivityModel, PairCoefficients, activity_coefficients,
+};
+use crate::calc_handle::least_squares::levenberg_marquardt;
+use crate::calc_handle::unifac::Unifac;
+
+/// NRTL 非随机参数默认值
+pub const DEFAULT_NRTL_ALPHA: f64 = 0.3;
+
+/// 每个温度下的回归组成点
+const COMPOSITION_POINTS: usize = 11;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct EstimatedPair {
+    /// 组分 1 → 2 的系数
+    pub forward: PairCoefficients,
+    /// 组分 2 → 1 的系数
+    pub backward: PairCoefficients,
+    /// ln γ 拟合残差的均方根
+    pub rms: f64,
+}
+
+/// 以 b 为回归变量组装二元系数
+fn pair_from_b(model: ActivityModel, b: f64, alpha: f64) -> PairCoefficients {
+    PairCoefficients {
+        b,
+        c: if model == ActivityModel::Nrtl {
+            alpha
+        } else {
+            0.0
+        },
+        ..Default::default()
+    }
+}
+
+/// 由二元 UNIFAC 模型回归活度系数模型参数
+pub fn estimate_activity_pair(
+    model: ActivityModel,
+    unifac: &Unifac,
+    temperatures: &[f64],
+    nrtl_alpha: f64,
+) -> Result<EstimatedPair, String> {
+    if unifac.structure().len() != 2 {
+        return Err("二元参数估算只接受两个组分".to_string());
+    }
+    if temperatures.is_empty() || temperatures.iter().any(|t| !(t.is_finite() && *t > 0.0)) {
+        return Err("估算温度必须为正数（K）".to_string());
+    }
+    let mut targets = Vec::new();
+    for &t in temperatures {
+        for k in 0..COMPOSITION_POINTS {
+            let x1 = k as f64 / (COMPOSITION_POINTS - 1) as f64;
+            let x = [x1, 1.0 - x1];
+            targets.push((t, x, unifac.ln_gamma(&x, t)?));
+        }
+    }
+
+    let structure = unifac.structure();
+    let residuals = |p: &[f64]| -> Option<Vec<f64>> {
+        let pairs = vec![
+            vec![None, Some(pair_from_b(model, p[0], nrtl_alpha))],
+            vec![Some(pair_from_b(model, p[1], nrtl_alpha)), None],
+        ];
+        let mut r = Vec::with_capacity(targets.len() * 2);
+        for (t, x, target) in targets.iter() {
+            let result = activity_coefficients(
+                model,
+                &ActivityInput {
+                    temperature: *t,
+                    x,
+                    pairs: &pairs,
+                    structure,
+                },
+            )
+            .ok()?;
+            r.push(result.ln_gamma[0] - target[0]);
+            r.push(result.ln_gamma[1] - target[1]);
+        }
+        Some(r)
+    };
+    let fit = levenberg_marquardt(residuals, &[0.0, 0.0], 1e-10)?;
+    Ok(EstimatedPair {
+        forward: pair_from_b(model, fit.params[0], nrtl_alpha),
+        backward: pair_from_b(model, fit.params[1], nrtl_alpha),
+        rms: (fit.sum_squares / fit.residuals.len() as f64).sqrt(),
+    })
+}
+
+/// Chueh-Prausnitz：kij = 1 - 8·√(Vci·Vcj) / (Vci^⅓ + Vcj^⅓)³
+pub fn chueh_prausnitz_kij(vc_i: f64, vc_j: f64) -> Option<f64> {
+    if !(vc_i > 0.0 && vc_j > 0.0) {
+        return None;
+    }
+    let sum = vc_i.cbrt() + vc_j.cbrt();
+    Some(1.0 - 8.0 * (vc_i * vc_j).sqrt() / sum.powi(3))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_estimate_from_unifac() {
+        let ethanol = vec![(1, 1.0), (2, 1.0), (14, 1.0)];
+        let water = vec![(16, 1.0)];
+        let unifac = Unifac::new(&[ethanol, water]).unwrap();
+        let temperatures = [330.0, 350.0, 370.0];
+
+        for model in [
+            ActivityModel::Nrtl,
+            ActivityModel::Wilson,
+            ActivityModel::Uniquac,
+        ] {
+            let est =
+                estimate_activity_pair(model, &unifac, &temperatures, DEFAULT_NRTL_ALPHA).unwrap();
+            assert!(est.rms < 0.05, "{:?}: {}", model, est.rms);
+            // 回归参数在中间组成下重现 UNIFAC
+            let pairs = vec![
+                vec![None, Some(est.forward)],
+                vec![Some(est.backward), None],
+            ];
+            let fitted = activity_coefficients(
+                model,
+                &ActivityInput {
+                    temperature: 350.0,
+                    x: &[0.4, 0.6],
+                    pairs: &pairs,
+                    structure: unifac.structure(),
+                },
+            )
+            .unwrap();
+            let target = unifac.ln_gamma(&[0.4, 0.6], 350.0).unwrap();
+            assert!((fitted.ln_gamma[0] - target[0]).abs() < 0.05);
+        }
+
+        assert!(chueh_prausnitz_kij(1e-4, 1e-4).unwrap().abs() < 1e-12);
+        assert!(chueh_prausnitz_kij(9.9e-5, 2.0e-4).unwrap() > 0.0);
+        assert!(chueh_prausnitz_kij(0.0, 1.0).is_none());
+    }
+}
@@ -0,0 +1,316 @@
+// ======================================
+// 由实验数据回归活度系数模型二元参数
+// - VLE：改进 Raoult 定律 yi·P = xi·γi·Psat_i，目标为泡点压力相对偏差与汽相组成偏差
+// - LLE：两液相中各组分活度相等 ln(xᴵ·γᴵ) = ln(xᴵᴵ·γᴵᴵ)
+// ======================================
+
+use crate::calc_handle::activity_model::{
+    ActivityInput, ActivityModel, PairCoefficients, UniquacStructure, activity_coefficients,
+};
+use crate::calc_handle::least_squares::levenberg_marquardt;
+
+/// 可回归的参数：*ij 属于 1 → 2，*ji 属于 2 → 1；NRTL 的 cij 为对称的 α
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FitParameter {
+    Aij,
+    Aji,
+    Bij,
+    Bji,
+    Cij,
+}
+
+/// 二元 VLE 数据点（温度 K，压力与饱和蒸气压同一单位）
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct VlePoint {
+    pub t: f64,
+    pub p: f64,
+    pub x1: f64,
+    pub y1: Option<f64>,
+    pub psat1: f64,
+    pub psat2: f64,
+}
+
+/// 二元 LLE 数据点：两液相中组分 1 的摩尔分数
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LlePoint {
+    pub t: f64,
+    pub x1_a: f64,
+    pub x1_b: f64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct VleFit {
+    pub p_calc: f64,
+    pub y1_calc: f64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LleFit {
+    /// 组分 1、2 的 ln 活度之差
+    pub residual1: f64,
+    pub residual2: f64,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct RegressionResult {
+    pub forward: PairCoefficients,
+    pub backward: PairCoefficients,
+    pub vle: Vec<VleFit>,
+    pub lle: Vec<LleFit>,
+    /// 泡点压力平均绝对相对偏差 %
+    pub aad_pressure: Option<f64>,
+    /// 汽相组成平均绝对偏差
+    pub aad_y: Option<f64>,
+    /// 全部残差的均方根
+    pub rmsd: f64,
+    pub iterations: usize,
+    pub converged: bool,
+}
+
+pub struct RegressionProblem<'a> {
+    pub model: ActivityModel,
+    /// 回归初值，未回归的系数保持不变
+    pub forward: PairCoefficients,
+    pub backward: PairCoefficients,
+    pub fit: &'a [FitParameter],
+    pub structure: &'a [UniquacStructure],
+    pub vle: &'a [VlePoint],
+    pub lle: &'a [LlePoint],
+}
+
+impl RegressionProblem<'_> {
+    fn apply(&self, params: &[f64]) -> (PairCoefficients, PairCoefficients) {
+        let (mut f, mut b) = (self.forward, self.backward);
+        for (key, &v) in self.fit.iter().zip(params) {
+            match key {
+                FitParameter::Aij => f.a = v,
+                FitParameter::Aji => b.a = v,
+                FitParameter::Bij => f.b = v,
+                FitParameter::Bji => b.b = v,
+                FitParameter::Cij => {
+                    f.c = v;
+                    if self.model == ActivityModel::Nrtl {
+                        b.c = v;
+                    }
+                }
+            }
+        }
+        (f, b)
+    }
+
+    fn initial(&self) -> Vec<f64> {
+        self.fit
+            .iter()
+            .map(|key| match key {
+                FitParameter::Aij => self.forward.a,
+                FitParameter::Aji => self.backward.a,
+                FitParameter::Bij => self.forward.b,
+                FitParameter::Bji => self.backward.b,
+                FitParameter::Cij => self.forward.c,
+            })
+            .collect()
+    }
+
+    fn ln_gamma(
+        &self,
+        pair: (PairCoefficients, PairCoefficients),
+        t: f64,
+        x1: f64,
+    ) -> Option<Vec<f64>> {
+        let pairs = vec![vec![None, Some(pair.0)], vec![Some(pair.1), None]];
+        activity_coefficients(
+            self.model,
+            &ActivityInput {
+                temperature: t,
+                x: &[x1, 1.0 - x1],
+                pairs: &pairs,
+                structure: self.structure,
+            },
+        )
+        .ok()
+        .map(|r| r.ln_gamma)
+    }
+
+    fn evaluate(&self, params: &[f64]) -> Option<(Vec<VleFit>, Vec<LleFit>)> {
+        let pair = self.apply(params);
+        let mut vle = Vec::with_capacity(self.vle.len());
+        for pt in self.vle {
+            let lg = self.ln_gamma(pair, pt.t, pt.x1)?;
+            let p1 = pt.x1 * lg[0].exp() * pt.psat1;
+            let p2 = (1.0 - pt.x1) * lg[1].exp() * pt.psat2;
+            let p_calc = p1 + p2;
+            vle.push(VleFit {
+                p_calc,
+                y1_calc: p1 / p_calc,
+            });
+        }
+        let mut lle = Vec::with_capacity(self.lle.len());
+        for pt in self.lle {
+            let a = self.ln_gamma(pair, pt.t, pt.x1_a)?;
+            let b = self.ln_gamma(pair, pt.t, pt.x1_b)?;
+            lle.push(LleFit {
+                residual1: pt.x1_a.ln() + a[0] - pt.x1_b.ln() - b[0],
+                residual2: (1.0 - pt.x1_a).ln() + a[1] - (1.0 - pt.x1_b).ln() - b[1],
+            });
+        }
+        Some((vle, lle))
+    }
+
+    fn residuals(&self, params: &[f64]) -> Option<Vec<f64>> {
+        let (vle, lle) = self.evaluate(params)?;
+        let mut r = Vec::new();
+        for (pt, fit) in self.vle.iter().zip(&vle) {
+            r.push((fit.p_calc - pt.p) / pt.p);
+            if let Some(y1) = pt.y1 {
+                r.push(fit.y1_calc - y1);
+            }
+        }
+        for fit in &lle {
+            r.push(fit.residual1);
+            r.push(fit.residual2);
+        }
+        Some(r)
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        if self.fit.is_empty() {
+            return Err("没有需要回归的参数".to_string());
+        }
+        if self.vle.is_empty() && self.lle.is_empty() {
+            return Err("没有实验数据点".to_string());
+        }
+        let fraction = |v: f64| (0.0..=1.0).contains(&v);
+        for (i, pt) in self.vle.iter().enumerate() {
+            let positive = pt.t > 0.0 && pt.p > 0.0 && pt.psat1 > 0.0 && pt.psat2 > 0.0;
+            if !positive || !fraction(pt.x1) || pt.y1.is_some_and(|y| !fraction(y)) {
+                return Err(format!("第 {} 个 VLE 数据点无效", i + 1));
+            }
+        }
+        let open = |v: f64| v > 0.0 && v < 1.0;
+        for (i, pt) in self.lle.iter().enumerate() {
+            if !(pt.t > 0.0 && open(pt.x1_a) && open(pt.x1_b)) {
+                return Err(format!("第 {} 个 LLE 数据点无效", i + 1));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// 最小二乘回归二元参数
+pub fn regress_binary(problem: &RegressionProblem) -> Result<RegressionResult, String> {
+    problem.validate()?;
+    let fit = levenberg_marquardt(|p| problem.residuals(p), &problem.initial(), 1e-10)?;
+    let (vle, lle) = problem
+        .evaluate(&fit.params)
+        .ok_or("回归结果无法计算活度系数")?;
+    let (forward, backward) = problem.apply(&fit.params);
+
+    let aad_pressure = (!vle.is_empty()).then(|| {
+        let sum: f64 = problem
+            .vle
+            .iter()
+            .zip(&vle)
+            .map(|(pt, f)| ((f.p_calc - pt.p) / pt.p).abs())
+            .sum();
+        100.0 * sum / vle.len() as f64
+    });
+    let dy: Vec<f64> = problem
+        .vle
+        .iter()
+        .zip(&vle)
+        .filter_map(|(pt, f)| pt.y1.map(|y| (f.y1_calc - y).abs()))
+        .collect();
+    let aad_y = (!dy.is_empty()).then(|| dy.iter().sum::<f64>() / dy.len() as f64);
+
+    Ok(RegressionResult {
+        forward,
+        backward,
+        vle,
+        lle,
+        aad_pressure,
+        aad_y,
+        rmsd: (fit.sum_squares / fit.residuals.len().max(1) as f64).sqrt(),
+        iterations: fit.iterations,
+        converged: fit.converged,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_recover_nrtl_parameters() {
+        // 以已知 NRTL 参数生成 VLE 数据，再回归 bij / bji
+        let truth = RegressionProblem {
+            model: ActivityModel::Nrtl,
+            forward: PairCoefficients {
+                b: 150.0,
+                c: 0.3,
+                ..Default::default()
+            },
+            backward: PairCoefficients {
+                b: 420.0,
+                c: 0.3,
+                ..Default::default()
+            },
+            fit: &[],
+            structure: &[],
+            vle: &[],
+            lle: &[],
+        };
+        let vle: Vec<VlePoint> = (1..10)
+            .map(|k| {
+                let x1 = k as f64 / 10.0;
+                let t = 340.0 + k as f64;
+                let (psat1, psat2) = (1.1e5, 0.6e5);
+                let lg = truth
+                    .ln_gamma((truth.forward, truth.backward), t, x1)
+                    .unwrap();
+                let p1 = x1 * lg[0].exp() * psat1;
+                let p = p1 + (1.0 - x1) * lg[1].exp() * psat2;
+                VlePoint {
+                    t,
+                    p,
+                    x1,
+                    y1: Some(p1 / p),
+                    psat1,
+                    psat2,
+                }
+            })
+            .collect();
+
+        let problem = RegressionProblem {
+            forward: PairCoefficients {
+                c: 0.3,
+                ..Default::default()
+            },
+            backward: PairCoefficients {
+                c: 0.3,
+                ..Default::default()
+            },
+            fit: &[FitParameter::Bij, FitParameter::Bji],
+            vle: &vle,
+            ..truth
+        };
+        let result = regress_binary(&problem).unwrap();
+        assert!(result.converged);
+        assert!(
+            (result.forward.b - 150.0).abs() < 1e-3,
+            "{:?}",
+            result.forward
+        );
+        assert!(
+            (result.backward.b - 420.0).abs() < 1e-3,
+            "{:?}",
+            result.backward
+        );
+        assert!(result.aad_pressure.unwrap() < 1e-6 && result.aad_y.unwrap() < 1e-8);
+
+        let empty = RegressionProblem {
+            vle: &[],
+            ..problem
+        };
+        assert!(regress_binary(&empty).is_err());
+    }
+}
@@ -0,0 +1,396 @@
+use crate::calc_handle::activity_model::GAS_CONSTANT;
+
+// ======================================
+// 立方型状态方程（PR / SRK / RK）：压缩因子、逸度系数与等温等压两相闪蒸
+// 温度 K，压力 Pa
+// ======================================
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CubicEos {
+    Pr,
+    Srk,
+    Rk,
+}
+
+/// 纯组分临界参数
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CriticalConstants {
+    /// 临界温度 K
+    pub tc: f64,
+    /// 临界压力 Pa
+    pub pc: f64,
+    /// 偏心因子
+    pub omega: f64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PhaseRoot {
+    Liquid,
+    Vapor,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FlashPhase {
+    Liquid,
+    Vapor,
+    TwoPhase,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct FlashResult {
+    pub phase: FlashPhase,
+    /// 汽化率（摩尔）
+    pub vapor_fraction: f64,
+    pub x: Vec<f64>,
+    pub y: Vec<f64>,
+    pub k: Vec<f64>,
+    pub z_liquid: f64,
+    pub z_vapor: f64,
+    pub phi_liquid: Vec<f64>,
+    pub phi_vapor: Vec<f64>,
+    pub iterations: u32,
+    /// K 值迭代是否收敛；未收敛时单相判断同样基于最后一次迭代
+    pub converged: bool,
+}
+
+const MAX_ITERATIONS: u32 = 500;
+/// 相邻两次迭代 ln K 变化量的平方和
+const TOLERANCE: f64 = 1e-12;
+
+impl CubicEos {
+    /// (Ωa, Ωb, δ1, δ2)，其中立方方程分母为 (V + δ1·b)(V + δ2·b)
+    fn constants(self) -> (f64, f64, f64, f64) {
+        match self {
+            CubicEos::Pr => (
+                0.457_235_529,
+                0.077_796_074,
+                1.0 + std::f64::consts::SQRT_2,
+                1.0 - std::f64::consts::SQRT_2,
+            ),
+            CubicEos::Srk | CubicEos::Rk => (0.427_480_234, 0.086_640_350, 1.0, 0.0),
+        }
+    }
+
+    fn alpha(self, c: &CriticalConstants, t: f64) -> f64 {
+        let tr = t / c.tc;
+        let m = match self {
+            CubicEos::Pr => 0.37464 + 1.54226 * c.omega - 0.26992 * c.omega * c.omega,
+            CubicEos::Srk => 0.480 + 1.574 * c.omega - 0.176 * c.omega * c.omega,
+            CubicEos::Rk => return 1.0 / tr.sqrt(),
+        };
+        let s = 1.0 + m * (1.0 - tr.sqrt());
+        s * s
+    }
+}
+
+/// 给定温度下的混合物状态方程参数
+#[derive(Clone, Debug)]
+pub struct EosMixture {
+    eos: CubicEos,
+    t: f64,
+    a: Vec<f64>,
+    b: Vec<f64>,
+    kij: Vec<Vec<f64>>,
+}
+
+impl EosMixture {
+    /// kij 为对称二元交互参数矩阵（已按温度求值）
+    pub fn new(
+        eos: CubicEos,
+        t: f64,
+        constants: &[CriticalConstants],
+        kij: Vec<Vec<f64>>,
+    ) -> Result<Self, String> {
+        if !(t.is_finite() && t > 0.0) {
+            return Err(format!("温度必须为正数（K）: {}", t));
+        }
+        if kij.len() != constants.len() || kij.iter().any(|row| row.len() != constants.len()) {
+            return Err("二元交互参数矩阵维数与组分数不一致".to_string());
+        }
+        if let Some(i) = constants
+            .iter()
+            .position(|c| !(c.tc > 0.0 && c.pc > 0.0 && c.omega.is_finite()))
+        {
+            return Err(format!("第 {} 个组分的临界参数无效", i + 1));
+        }
+        let (omega_a, omega_b, _, _) = eos.constants();
+        let a = constants
+            .iter()
+            .map(|c| omega_a * (GAS_CONSTANT * c.tc).powi(2) / c.pc * eos.alpha(c, t))
+            .collect();
+        let b = constants
+            .iter()
+            .map(|c| omega_b * GAS_CONSTANT * c.tc / c.pc)
+            .collect();
+        Ok(Self { eos, t, a, b, kij })
+    }
+
+    fn a_ij(&self, i: usize, j: usize) -> f64 {
+        (self.a[i] * self.a[j]).sqrt() * (1.0 - self.kij[i][j])
+    }
+
+    /// 压缩因子与逸度系数；没有大于 B 的实根时报错
+    pub fn fugacity(&self, x: &[f64], p: f64, root: PhaseRoot) -> Result<(f64, Vec<f64>), String> {
+        let n = x.len();
+        let rt = GAS_CONSTANT * self.t;
+        let sum_a: Vec<f64> = (0..n)
+            .map(|i| (0..n).map(|j| x[j] * self.a_ij(i, j)).sum())
+            .collect();
+        let a_mix: f64 = (0..n).map(|i| x[i] * sum_a[i]).sum();
+        let b_mix: f64 = (0..n).map(|i| x[i] * self.b[i]).sum();
+        let big_a = a_mix * p / (rt * rt);
+        let big_b = b_mix * p / rt;
+
+        let (_, _, d1, d2) = self.eos.constants();
+        let (u, w) = (d1 + d2, d1 * d2);
+        let roots = cubic_roots(
+            -(1.0 + big_b - u * big_b),
+            big_a + w * big_b * big_b - u * big_b - u * big_b * big_b,
+            -(big_a * big_b + w * big_b * big_b + w * big_b * big_b * big_b),
+        );
+        let valid = roots.iter().copied().filter(|&z| z > big_b);
+        let z = match root {
+            PhaseRoot::Liquid => valid.min_by(f64::total_cmp),
+            PhaseRoot::Vapor => valid.max_by(f64::total_cmp),
+        }
+        .ok_or_else(|| {
+            format!(
+                "状态方程在 T = {} K、p = {} Pa 下没有有效的压缩因子根",
+                self.t, p
+            )
+        })?;
+
+        let log_term = ((z + d1 * big_b) / (z + d2 * big_b)).ln();
+        let phi = (0..n)
+            .map(|i| {
+                let bi_b = self.b[i] / b_mix;
+                (bi_b * (z - 1.0)
+                    - (z - big_b).ln()
+                    - big_a / (big_b * (d1 - d2)) * (2.0 * sum_a[i] / a_mix - bi_b) * log_term)
+                    .exp()
+            })
+            .collect();
+        Ok((z, phi))
+    }
+}
+
+/// 求 z³ + c2·z² + c1·z + c0 = 0 的实根
+fn cubic_roots(c2: f64, c1: f64, c0: f64) -> Vec<f64> {
+    let q = (3.0 * c1 - c2 * c2) / 9.0;
+    let r = (9.0 * c2 * c1 - 27.0 * c0 - 2.0 * c2.powi(3)) / 54.0;
+    let disc = q.powi(3) + r * r;
+    let shift = c2 / 3.0;
+    if disc > 0.0 {
+        let s = (r + disc.sqrt()).cbrt();
+        let t = (r - disc.sqrt()).cbrt();
+        vec![s + t - shift]
+    } else {
+        let theta = (r / (-q.powi(3)).sqrt()).clamp(-1.0, 1.0).acos();
+        let m = 2.0 * (-q).sqrt();
+        (0..3)
+            .map(|k| m * ((theta + 2.0 * std::f64::consts::PI * k as f64) / 3.0).cos() - shift)
+            .collect()
+    }
+}
+
+/// Rachford–Rice 方程 Σ zi(Ki-1)/(1+β(Ki-1)) = 0，在有效区间内二分求根（允许 β 超出 [0, 1]）
+fn rachford_rice(z: &[f64], k: &[f64]) -> f64 {
+    let f = |beta: f64| -> f64 {
+        z.iter()
+            .zip(k)
+            .map(|(zi, ki)| zi * (ki - 1.0) / (1.0 + beta * (ki - 1.0)))
+            .sum()
+    };
+    let k_max = k.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    let k_min = k.iter().copied().fold(f64::INFINITY, f64::min);
+    if k_max <= 1.0 {
+        return 0.0;
+    }
+    if k_min >= 1.0 {
+        return 1.0;
+    }
+    let eps = 1e-12;
+    let (mut lo, mut hi) = (1.0 / (1.0 - k_max) + eps, 1.0 / (1.0 - k_min) - eps);
+    for _ in 0..200 {
+        let mid = 0.5 * (lo + hi);
+        if f(mid) > 0.0 {
+            lo = mid;
+        } else {
+            hi = mid;
+        }
+        if hi - lo < 1e-14 {
+            break;
+        }
+    }
+    0.5 * (lo + hi)
+}
+
+/// Wilson 经验式估算初始平衡常数
+fn wilson_k(constants: &[CriticalConstants], t: f64, p: f64) -> Vec<f64> {
+    constants
+        .iter()
+        .map(|c| c.pc / p * (5.373 * (1.0 + c.omega) * (1.0 - c.tc / t)).exp())
+        .collect()
+}
+
+/// 等温等压闪蒸：Rachford–Rice + 逐次代入
+pub fn flash_pt(
+    eos: CubicEos,
+    constants: &[CriticalConstants],
+    kij: Vec<Vec<f64>>,
+    z: &[f64],
+    t: f64,
+    p: f64,
+) -> Result<FlashResult, String> {
+    let n = z.len();
+    if n == 0 || constants.len() != n {
+        return Err("组分数与临界参数个数不一致".to_string());
+    }
+    if !(p.is_finite() && p > 0.0) {
+        return Err(format!("压力必须为正数（Pa）: {}", p));
+    }
+    if z.iter().any(|&v| !(v.is_finite() && v >= 0.0)) {
+        return Err("摩尔分数必须为非负数".to_string());
+    }
+    let total: f64 = z.iter().sum();
+    if total <= 0.0 {
+        return Err("摩尔分数之和必须大于 0".to_string());
+    }
+    let z: Vec<f64> = z.iter().map(|v| v / total).collect();
+    let mix = EosMixture::new(eos, t, constants, kij)?;
+
+    let mut k = wilson_k(constants, t, p);
+    let mut beta = 0.5;
+    let mut iterations = 0;
+    let mut converged = false;
+    let (mut x, mut y) = (z.clone(), z.clone());
+    let (mut z_l, mut z_v) = (0.0, 0.0);
+    let (mut phi_l, mut phi_v) = (vec![1.0; n], vec![1.0; n]);
+    while iterations < MAX_ITERATIONS {
+        iterations += 1;
+        beta = rachford_rice(&z, &k);
+        // 负闪蒸：β 可暂时越界，用于判断单相
+        for i in 0..n {
+            let d = 1.0 + beta * (k[i] - 1.0);
+            x[i] = z[i] / d;
+            y[i] = k[i] * x[i];
+        }
+        let (sx, sy): (f64, f64) = (x.iter().sum(), y.iter().sum());
+        x.iter_mut().for_each(|v| *v /= sx);
+        y.iter_mut().for_each(|v| *v /= sy);
+
+        (z_l, phi_l) = mix.fugacity(&x, p, PhaseRoot::Liquid)?;
+        (z_v, phi_v) = mix.fugacity(&y, p, PhaseRoot::Vapor)?;
+        let mut change = 0.0;
+        for i in 0..n {
+            let k_new = phi_l[i] / phi_v[i];
+            change += (k_new.ln() - k[i].ln()).powi(2);
+            k[i] = k_new;
+        }
+        if !change.is_finite() {
+            return Err("闪蒸迭代发散，请检查组分参数".to_string());
+        }
+        if change < TOLERANCE {
+            converged = true;
+            break;
+        }
+    }
+
+    let trivial = k.iter().all(|ki| (ki.ln()).abs() < 1e-4);
+    let phase = if trivial {
+        // 平衡常数退化为 1 时按拟临界温度判断相态
+        let tpc: f64 = z.iter().zip(constants).map(|(zi, c)| zi * c.tc).sum();
+        if t > tpc {
+            FlashPhase::Vapor
+        } else {
+            FlashPhase::Liquid
+        }
+    } else if beta <= 0.0 {
+        FlashPhase::Liquid
+    } else if beta >= 1.0 {
+        FlashPhase::Vapor
+    } else {
+        FlashPhase::TwoPhase
+    };
+    match phase {
+        FlashPhase::Liquid => {
+            beta = 0.0;
+            x = z.clone();
+            (z_l, phi_l) = mix.fugacity(&x, p, PhaseRoot::Liquid)?;
+        }
+        FlashPhase::Vapor => {
+            beta = 1.0;
+            y = z.clone();
+            (z_v, phi_v) = mix.fugacity(&y, p, PhaseRoot::Vapor)?;
+        }
+        FlashPhase::TwoPhase => {}
+    }
+    Ok(FlashResult {
+        phase,
+        vapor_fraction: beta,
+        x,
+        y,
+        k,
+        z_liquid: z_l,
+        z_vapor: z_v,
+        phi_liquid: phi_l,
+        phi_vapor: phi_v,
+        iterations,
+        converged,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const METHANE: CriticalConstants = CriticalConstants {
+        tc: 190.56,
+        pc: 4.599e6,
+        omega: 0.0115,
+    };
+    const PROPANE: CriticalConstants = CriticalConstants {
+        tc: 369.83,
+        pc: 4.248e6,
+        omega: 0.1523,
+    };
+    const N_PENTANE: CriticalConstants = CriticalConstants {
+        tc: 469.7,
+        pc: 3.37e6,
+        omega: 0.251,
+    };
+
+    #[test]
+    fn test_pure_and_two_phase_flash() {
+        let zero = vec![vec![0.0; 3]; 3];
+        let comps = [METHANE, PROPANE, N_PENTANE];
+
+        // 常温常压下甲烷接近理想气体
+        let mix = EosMixture::new(CubicEos::Pr, 300.0, &[METHANE], vec![vec![0.0]]).unwrap();
+        let (z, phi) = mix.fugacity(&[1.0], 101_325.0, PhaseRoot::Vapor).unwrap();
+        assert!((z - 1.0).abs() < 0.01 && (phi[0] - 1.0).abs() < 0.01);
+
+        // 两相区：物料平衡与逸度相等
+        let feed = [0.4, 0.3, 0.3];
+        for eos in [CubicEos::Pr, CubicEos::Srk, CubicEos::Rk] {
+            let r = flash_pt(eos, &comps, zero.clone(), &feed, 300.0, 2.0e6).unwrap();
+            assert_eq!(r.phase, FlashPhase::TwoPhase, "{:?}", eos);
+            assert!(r.converged && r.iterations < MAX_ITERATIONS);
+            for (i, zi) in feed.iter().enumerate() {
+                let balance = r.vapor_fraction * r.y[i] + (1.0 - r.vapor_fraction) * r.x[i];
+                assert!((balance - zi).abs() < 1e-8);
+                let f_l = r.x[i] * r.phi_liquid[i];
+                let f_v = r.y[i] * r.phi_vapor[i];
+                assert!((f_l - f_v).abs() < 1e-6 * f_v, "{:?} {} {}", eos, f_l, f_v);
+            }
+            assert!(r.z_vapor > r.z_liquid);
+        }
+
+        // 高温低压为气相，低温高压为液相
+        let vapor = flash_pt(CubicEos::Pr, &comps, zero.clone(), &feed, 500.0, 1.0e5).unwrap();
+        assert_eq!(vapor.phase, FlashPhase::Vapor);
+        let liquid =
+            flash_pt(CubicEos::Pr, &comps, zero, &[0.01, 0.3, 0.69], 250.0, 5.0e6).unwrap();
+        assert_eq!(liquid.phase, FlashPhase::Liquid);
+    }
+}
@@ -0,0 +1,355 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+
+// ======================================
+// 流程图拓扑分析：连通性、回路（强连通分量）、断裂物流与计算顺序
+// 顶点为单元或物流，边为物料/能量流动方向
+// ======================================
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VertexKind {
+    /// 设备单元（模块、逻辑、传感器等）
+    Unit,
+    /// 物流 / 能流
+    Stream,
+}
+
+/// 过程网络（有向图）
+#[derive(Clone, Debug, Default)]
+pub struct ProcessNetwork {
+    ids: Vec<String>,
+    kinds: Vec<VertexKind>,
+    index: HashMap<String, usize>,
+    succ: Vec<Vec<usize>>,
+    pred: Vec<Vec<usize>>,
+}
+
+/// 拓扑分析结果（均为顶点 ID，结果按 ID 排序保证稳定）
+#[derive(Clone, Debug, Default)]
+pub struct TopologyAnalysis {
+    /// 没有任何连接的单元
+    pub disconnected_units: Vec<String>,
+    /// 没有上游（缺少进料端）的物流
+    pub streams_without_source: Vec<String>,
+    /// 没有下游（缺少产品端）的物流
+    pub streams_without_destination: Vec<String>,
+    /// 回路：每个强连通分量中的顶点
+    pub recycle_loops: Vec<Vec<String>>,
+    /// 建议的断裂顶点（优先选物流，回路中不含物流时才选单元）
+    pub tear_streams: Vec<String>,
+    /// 建议的计算顺序（回路内断裂顶点排在最前）
+    pub calculation_order: Vec<String>,
+}
+
+impl ProcessNetwork {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.ids.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ids.is_empty()
+    }
+
+    /// 添加顶点，已存在时更新类型并返回原索引
+    pub fn add_vertex(&mut self, id: &str, kind: VertexKind) -> usize {
+        if let Some(&i) = self.index.get(id) {
+            self.kinds[i] = kind;
+            return i;
+        }
+        let i = self.ids.len();
+        self.ids.push(id.to_string());
+        self.kinds.push(kind);
+        self.index.insert(id.to_string(), i);
+        self.succ.push(vec![]);
+        self.pred.push(vec![]);
+        i
+    }
+
+    /// 添加有向边，未登记的顶点按单元处理；重复边只记录一次
+    pub fn add_edge(&mut self, from: &str, to: &str) {
+        if from.is_empty() || to.is_empty() {
+            return;
+        }
+        let a = match self.index.get(from) {
+            Some(&i) => i,
+            None => self.add_vertex(from, VertexKind::Unit),
+        };
+        let b = match self.index.get(to) {
+            Some(&i) => i,
+            None => self.add_vertex(to, VertexKind::Unit),
+        };
+        if !self.succ[a].contains(&b) {
+            self.succ[a].push(b);
+            self.pred[b].push(a);
+        }
+    }
+
+    pub fn edge_count(&self) -> usize {
+        self.succ.iter().map(Vec::len).sum()
+    }
+
+    /// Tarjan 强连通分量（迭代实现，避免大模型递归过深）
+    ///
+    /// 只考虑 active 为 true 的顶点；返回顺序为逆拓扑序
+    fn tarjan(&self, active: &[bool]) -> Vec<Vec<usize>> {
+        let n = self.ids.len();
+        let mut index = vec![usize::MAX; n];
+        let mut low = vec![0; n];
+        let mut on_stack = vec![false; n];
+        let mut stack = Vec::new();
+        let mut sccs = Vec::new();
+        let mut counter = 0;
+
+        for root in 0..n {
+            if !active[root] || index[root] != usize::MAX {
+                continue;
+            }
+            // (顶点, 下一个待访问的后继下标)
+            let mut call = vec![(root, 0usize)];
+            index[root] = counter;
+            low[root] = counter;
+            counter += 1;
+            stack.push(root);
+            on_stack[root] = true;
+
+            while let Some(&mut (v, ref mut next)) = call.last_mut() {
+                if let Some(&w) = self.succ[v].get(*next) {
+                    *next += 1;
+                    if !active[w] {
+                        continue;
+                    }
+                    if index[w] == usize::MAX {
+                        index[w] = counter;
+                        low[w] = counter;
+                        counter += 1;
+                        stack.push(w);
+                        on_stack[w] = true;
+                        call.push((w, 0));
+                    } else if on_stack[w] {
+                        low[v] = low[v].min(index[w]);
+                    }
+                    continue;
+                }
+                call.pop();
+                if let Some(&(parent, _)) = call.last() {
+                    low[parent] = low[parent].min(low[v]);
+                }
+                if low[v] == index[v] {
+                    let mut scc = Vec::new();
+                    while let Some(w) = stack.pop() {
+                        on_stack[w] = false;
+                        scc.push(w);
+                        if w == v {
+                            break;
+                        }
+                    }
+                    sccs.push(scc);
+                }
+            }
+        }
+        sccs
+    }
+
+    /// 分量是否构成回路（多于一个顶点，或存在自环）
+    fn is_cycle(&self, scc: &[usize]) -> bool {
+        scc.len() > 1 || self.succ[scc[0]].contains(&scc[0])
+    }
+
+    /// 在一个回路内选择断裂顶点，直到剩余部分无环
+    fn select_tears(&self, scc: &[usize]) -> Vec<usize> {
+        let mut active = vec![false; self.ids.len()];
+        for &v in scc {
+            active[v] = true;
+        }
+        let mut tears = Vec::new();
+        loop {
+            let cycles: Vec<Vec<usize>> = self
+                .tarjan(&active)
+                .into_iter()
+                .filter(|c| self.is_cycle(c))
+                .collect();
+            if cycles.is_empty() {
+                break;
+            }
+            for cycle in cycles {
+                let members: HashSet<usize> = cycle.iter().copied().collect();
+                let degree = |v: usize| {
+                    let i = self.pred[v].iter().filter(|p| members.contains(p)).count();
+                    let o = self.succ[v].iter().filter(|s| members.contains(s)).count();
+                    i * o
+                };
+                // 优先物流；同类中选回路内进出度乘积最大者，再按 ID 保证结果稳定
+                let best = cycle
+                    .iter()
+                    .copied()
+                    .max_by(|&a, &b| {
+                        let ka = self.kinds[a] == VertexKind::Stream;
+                        let kb = self.kinds[b] == VertexKind::Stream;
+                        ka.cmp(&kb)
+                            .then(degree(a).cmp(&degree(b)))
+                            .then(self.ids[b].cmp(&self.ids[a]))
+                    })
+                    .unwrap();
+                active[best] = false;
+                tears.push(best);
+            }
+        }
+        tears
+    }
+
+    /// 去掉断裂顶点的入边后对回路内部做拓扑排序，断裂顶点排在最前
+    fn order_within(&self, scc: &[usize], tears: &[usize]) -> Vec<usize> {
+        let members: HashSet<usize> = scc.iter().copied().collect();
+        let torn: HashSet<usize> = tears.iter().copied().collect();
+        let mut in_deg: HashMap<usize, usize> = scc.iter().map(|&v| (v, 0)).collect();
+        for &v in scc {
+            for &w in &self.succ[v] {
+                if members.contains(&w) && !torn.contains(&w) {
+                    *in_deg.get_mut(&w).unwrap() += 1;
+                }
+            }
+        }
+        let mut ready: Vec<usize> = scc.iter().copied().filter(|v| in_deg[v] == 0).collect();
+        ready.sort_by(|&a, &b| {
+            torn.contains(&b)
+                .cmp(&torn.contains(&a))
+                .then(self.ids[a].cmp(&self.ids[b]))
+        });
+        let mut queue: VecDeque<usize> = ready.into();
+        let mut order = Vec::with_capacity(scc.len());
+        while let Some(v) = queue.pop_front() {
+            order.push(v);
+            let mut next: Vec<usize> = Vec::new();
+            for &w in &self.succ[v] {
+                if members.contains(&w) && !torn.contains(&w) {
+                    let d = in_deg.get_mut(&w).unwrap();
+                    *d -= 1;
+                    if *d == 0 {
+                        next.push(w);
+                    }
+                }
+            }
+            next.sort_by(|&a, &b| self.ids[a].cmp(&self.ids[b]));
+            queue.extend(next);
+        }
+        order
+    }
+
+    pub fn analyze(&self) -> TopologyAnalysis {
+        let mut result = TopologyAnalysis::default();
+        let sorted = |mut v: Vec<String>| {
+            v.sort();
+            v
+        };
+
+        let isolated = |v: usize| self.succ[v].is_empty() && self.pred[v].is_empty();
+        result.disconnected_units = sorted(
+            (0..self.len())
+                .filter(|&v| self.kinds[v] == VertexKind::Unit && isolated(v))
+                .map(|v| self.ids[v].clone())
+                .collect(),
+        );
+        let streams = (0..self.len()).filter(|&v| self.kinds[v] == VertexKind::Stream);
+        result.streams_without_source = sorted(
+            streams
+                .clone()
+                .filter(|&v| self.pred[v].is_empty())
+                .map(|v| self.ids[v].clone())
+                .collect(),
+        );
+        result.streams_without_destination = sorted(
+            streams
+                .filter(|&v| self.succ[v].is_empty())
+                .map(|v| self.ids[v].clone())
+                .collect(),
+        );
+
+        // Tarjan 输出为逆拓扑序，反转后即上游在前
+        let mut sccs = self.tarjan(&vec![true; self.len()]);
+        sccs.reverse();
+        for scc in sccs {
+            if !self.is_cycle(&scc) {
+                result.calculation_order.push(self.ids[scc[0]].clone());
+                continue;
+            }
+            let tears = self.select_tears(&scc);
+            let order = self.order_within(&scc, &tears);
+            result
+                .calculation_order
+                .extend(order.iter().map(|&v| self.ids[v].clone()));
+            result
+                .tear_streams
+                .extend(tears.iter().map(|&v| self.ids[v].clone()));
+            result
+                .recycle_loops
+                .push(sorted(scc.iter().map(|&v| self.ids[v].clone()).collect()));
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_recycle_loop_tear_and_order() {
+        // feed -> mix -> s1 -> reactor -> s2 -> split -> product
+        //                                       split -> recycle -> mix
+        let mut net = ProcessNetwork::new();
+        for unit in ["mix", "reactor", "split", "idle"] {
+            net.add_vertex(unit, VertexKind::Unit);
+        }
+        for stream in ["feed", "s1", "s2", "recycle", "product"] {
+            net.add_vertex(stream, VertexKind::Stream);
+        }
+        for (a, b) in [
+            ("feed", "mix"),
+            ("mix", "s1"),
+            ("s1", "reactor"),
+            ("reactor", "s2"),
+            ("s2", "split"),
+            ("split", "product"),
+            ("split", "recycle"),
+            ("recycle", "mix"),
+        ] {
+            net.add_edge(a, b);
+        }
+
+        let result = net.analyze();
+        assert_eq!(result.disconnected_units, vec!["idle"]);
+        assert_eq!(result.streams_without_source, vec!["feed"]);
+        assert_eq!(result.streams_without_destination, vec!["product"]);
+        assert_eq!(result.recycle_loops.len(), 1);
+        assert_eq!(result.recycle_loops[0].len(), 6);
+        assert_eq!(result.tear_streams.len(), 1);
+
+        // 断裂后回路内顺序必须满足除断裂物流外的所有依赖
+        let pos = |id: &str| {
+            result
+                .calculation_order
+                .iter()
+                .position(|x| x == id)
+                .unwrap()
+        };
+        assert_eq!(result.calculation_order.len(), net.len());
+        assert!(pos("feed") < pos("mix"));
+        assert!(pos("split") < pos("product"));
+        let tear = result.tear_streams[0].as_str();
+        for (a, b) in [
+            ("mix", "s1"),
+            ("s1", "reactor"),
+            ("reactor", "s2"),
+            ("s2", "split"),
+            ("split", "recycle"),
+            ("recycle", "mix"),
+        ] {
+            if b != tear {
+                assert!(pos(a) < pos(b), "{} should precede {}", a, b);
+            }
+        }
+    }
+}
@@ -0,0 +1,92 @@
+// ======================================
+// 亨利常数
+// - 温度关联：ln H = A + B/T + C·ln T + D·T + E/T²（T 为 K）
+// - 混合溶剂：ln H_i,mix = Σ x_j·ln H_ij / Σ x_j（仅计入有数据的溶剂）
+// - 溶解度：x_i = p_i / H_i
+// ======================================
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct HenryCoefficients {
+    pub a: f64,
+    pub b: f64,
+    pub c: f64,
+    pub d: f64,
+    pub e: f64,
+    /// 适用温度范围 K，上下限均为 0 视为未给出
+    pub t_lower: f64,
+    pub t_upper: f64,
+}
+
+impl HenryCoefficients {
+    pub fn ln_henry(&self, t: f64) -> Result<f64, String> {
+        if !(t.is_finite() && t > 0.0) {
+            return Err(format!("温度必须为正数（K），当前为 {}", t));
+        }
+        let v = self.a + self.b / t + self.c * t.ln() + self.d * t + self.e / (t * t);
+        if v.is_finite() {
+            Ok(v)
+        } else {
+            Err(format!("T = {} K 时亨利常数无法计算", t))
+        }
+    }
+
+    pub fn has_range(&self) -> bool {
+        self.t_upper > self.t_lower
+    }
+
+    pub fn in_range(&self, t: f64) -> bool {
+        !self.has_range() || (self.t_lower..=self.t_upper).contains(&t)
+    }
+}
+
+/// 混合溶剂的 ln H：各溶剂按摩尔分数加权，权重和须为正
+pub fn mixed_ln_henry(parts: &[(f64, f64)]) -> Result<f64, String> {
+    if parts.iter().any(|(x, _)| !(x.is_finite() && *x >= 0.0)) {
+        return Err("溶剂摩尔分数不能为负".to_string());
+    }
+    let total: f64 = parts.iter().map(|(x, _)| x).sum();
+    if total <= 0.0 {
+        return Err("有亨利常数数据的溶剂摩尔分数之和为 0".to_string());
+    }
+    Ok(parts.iter().map(|(x, ln_h)| x * ln_h).sum::<f64>() / total)
+}
+
+/// 给定分压下的溶解度（液相摩尔分数），分压与亨利常数须为同一压力单位
+pub fn solubility(partial_pressure: f64, henry: f64) -> Result<f64, String> {
+    if !(partial_pressure >= 0.0 && henry > 0.0) {
+        return Err("分压不能为负且亨利常数须为正".to_string());
+    }
+    Ok(partial_pressure / henry)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_henry_mix_and_solubility() {
+        let c = HenryCoefficients {
+            a: 20.0,
+            b: -1000.0,
+            c: 0.5,
+            d: 0.001,
+            e: 1.0e4,
+            t_lower: 273.15,
+            t_upper: 353.15,
+        };
+        let t: f64 = 300.0;
+        let expected = 20.0 - 1000.0 / t + 0.5 * t.ln() + 0.001 * t + 1.0e4 / (t * t);
+        assert!((c.ln_henry(t).unwrap() - expected).abs() < 1e-12);
+        assert!(c.in_range(t) && !c.in_range(400.0));
+        assert!(HenryCoefficients::default().in_range(1000.0));
+        assert!(c.ln_henry(0.0).is_err());
+
+        let mixed = mixed_ln_henry(&[(0.25, 10.0), (0.5, 13.0)]).unwrap();
+        assert!((mixed - 12.0).abs() < 1e-12);
+        assert!(mixed_ln_henry(&[(0.0, 1.0)]).is_err());
+
+        let h = c.ln_henry(t).unwrap().exp();
+        assert!((solubility(1.0e5, h).unwrap() * h - 1.0e5).abs() < 1e-6);
+        assert!(solubility(1.0, 0.0).is_err());
+    }
+}
@@ -0,0 +1,169 @@
+// ======================================
+// 非线性最小二乘（Levenberg-Marquardt）
+// 残差函数返回 None 表示参数不可行，步长会被拒绝并加大阻尼
+// ======================================
+
+const MAX_ITERATIONS: usize = 200;
+/// 数值雅可比的相对步长
+const JACOBIAN_STEP: f64 = 1e-6;
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct LeastSquaresResult {
+    pub params: Vec<f64>,
+    pub residuals: Vec<f64>,
+    /// 残差平方和
+    pub sum_squares: f64,
+    pub iterations: usize,
+    pub converged: bool,
+}
+
+fn sum_squares(r: &[f64]) -> f64 {
+    r.iter().map(|v| v * v).sum()
+}
+
+/// 高斯消元求解 n 维线性方程组（部分主元）
+fn solve_linear(mut a: Vec<Vec<f64>>, mut b: Vec<f64>) -> Option<Vec<f64>> {
+    let n = b.len();
+    for col in 0..n {
+        let pivot = (col..n).max_by(|&i, &j| a[i][col].abs().total_cmp(&a[j][col].abs()))?;
+        if a[pivot][col].abs() < 1e-300 {
+            return None;
+        }
+        a.swap(col, pivot);
+        b.swap(col, pivot);
+        let (upper, lower) = a.split_at_mut(col + 1);
+        let pivot_row = &upper[col];
+        for (offset, row) in lower.iter_mut().enumerate() {
+            let factor = row[col] / pivot_row[col];
+            for (v, p) in row[col..].iter_mut().zip(&pivot_row[col..]) {
+                *v -= factor * p;
+            }
+            b[col + 1 + offset] -= factor * b[col];
+        }
+    }
+    let mut x = vec![0.0; n];
+    for row in (0..n).rev() {
+        let tail: f64 = (row + 1..n).map(|k| a[row][k] * x[k]).sum();
+        x[row] = (b[row] - tail) / a[row][row];
+    }
+    Some(x)
+}
+
+/// 最小化 Σ r(p)²，初值不可行时返回错误
+pub fn levenberg_marquardt(
+    residuals: impl Fn(&[f64]) -> Option<Vec<f64>>,
+    initial: &[f64],
+    tolerance: f64,
+) -> Result<LeastSquaresResult, String> {
+    let n = initial.len();
+    let mut params = initial.to_vec();
+    let mut r = residuals(&params).ok_or("初值处残差无法计算")?;
+    if r.iter().any(|v| !v.is_finite()) {
+        return Err("初值处残差无效".to_string());
+    }
+    let mut ss = sum_squares(&r);
+    let mut lambda = 1e-3;
+    let mut converged = false;
+    let mut iterations = 0;
+
+    while iterations < MAX_ITERATIONS {
+        iterations += 1;
+        // 数值雅可比 J[k][p] = ∂r_k / ∂p
+        let mut jac = vec![vec![0.0; n]; r.len()];
+        for p in 0..n {
+            let h = JACOBIAN_STEP * params[p].abs().max(1.0);
+            let mut shifted = params.clone();
+            shifted[p] += h;
+            let Some(rp) = residuals(&shifted) else {
+                return Err("雅可比计算失败，参数接近不可行区域".to_string());
+            };
+            for (k, row) in jac.iter_mut().enumerate() {
+                row[p] = (rp[k] - r[k]) / h;
+            }
+        }
+        let jtj: Vec<Vec<f64>> = (0..n)
+            .map(|a| {
+                (0..n)
+                    .map(|b| jac.iter().map(|row| row[a] * row[b]).sum())
+                    .collect()
+            })
+            .collect();
+        let jtr: Vec<f64> = (0..n)
+            .map(|a| jac.iter().zip(&r).map(|(row, rk)| row[a] * rk).sum())
+            .collect();
+
+        let mut improved = false;
+        while lambda < 1e12 {
+            let mut lhs = jtj.clone();
+            for (a, row) in lhs.iter_mut().enumerate() {
+                row[a] += lambda * jtj[a][a].max(1e-12);
+            }
+            let rhs: Vec<f64> = jtr.iter().map(|v| -v).collect();
+            let Some(step) = solve_linear(lhs, rhs) else {
+                lambda *= 10.0;
+                continue;
+            };
+            let trial: Vec<f64> = params.iter().zip(&step).map(|(p, s)| p + s).collect();
+            match residuals(&trial) {
+                Some(rt) if rt.iter().all(|v| v.is_finite()) && sum_squares(&rt) < ss => {
+                    let new_ss = sum_squares(&rt);
+                    let step_small = step
+                        .iter()
+                        .zip(&trial)
+                        .all(|(s, p)| s.abs() <= tolerance * (p.abs() + tolerance));
+                    let gain_small = ss - new_ss <= tolerance * ss.max(f64::MIN_POSITIVE);
+                    params = trial;
+                    r = rt;
+                    ss = new_ss;
+                    lambda = (lambda / 10.0).max(1e-12);
+                    improved = true;
+                    converged = step_small || gain_small;
+                    break;
+                }
+                _ => lambda *= 10.0,
+            }
+        }
+        // 阻尼已无法再降低残差：位于极小点
+        if !improved {
+            converged = true;
+        }
+        if converged {
+            break;
+        }
+    }
+    Ok(LeastSquaresResult {
+        params,
+        residuals: r,
+        sum_squares: ss,
+        iterations,
+        converged,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fit_exponential_decay() {
+        // y = 2.5·exp(-0.7·x)
+        let xs: Vec<f64> = (0..20).map(|i| i as f64 * 0.25).collect();
+        let ys: Vec<f64> = xs.iter().map(|x| 2.5 * (-0.7 * x).exp()).collect();
+        let fit = levenberg_marquardt(
+            |p| {
+                Some(
+                    xs.iter()
+                        .zip(&ys)
+                        .map(|(x, y)| p[0] * (-p[1] * x).exp() - y)
+                        .collect(),
+                )
+            },
+            &[1.0, 0.1],
+            1e-12,
+        )
+        .unwrap();
+        assert!(fit.converged);
+        assert!((fit.params[0] - 2.5).abs() < 1e-6 && (fit.params[1] - 0.7).abs() < 1e-6);
+        assert!(levenberg_marquardt(|_| None, &[0.0], 1e-8).is_err());
+    }
+}
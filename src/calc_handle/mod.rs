@@ -0,0 +1,12 @@
+pub mod activity_model;
+pub mod binary_estimation;
+pub mod binary_regression;
+pub mod cubic_eos;
+pub mod flowsheet_topology;
+pub mod henry;
+pub mod least_squares;
+pub mod petroleum_assay;
+pub mod property_equation;
+pub mod reaction;
+pub mod unifac;
+pub mod unit_conversion;
@@ -0,0 +1,510 @@
+// ======================================
+// 石油馏分表征
+// - 蒸馏曲线换算：ASTM D86 / D2887 → TBP（Daubert 1994，API 3A1.1 / 3A3.2）
+// - 按等温度间隔切割 TBP 曲线为虚拟组分，Watson K 取常数并由总体比重反算
+// - 临界性质：Riazi-Daubert / Lee-Kesler / Twu，偏心因子 Lee-Kesler，理想气体热容 Kesler-Lee
+// 内部温度 K、压力 Pa；关联式原始单位（°F、°R、psia）在各函数内换算
+// ======================================
+
+const R: f64 = 8_314.462_618; // J/(kmol·K)
+const PSIA_TO_PA: f64 = 6_894.757_293;
+const ATM_TO_PA: f64 = 101_325.0;
+const FT3_PER_LBMOL_TO_M3_PER_KMOL: f64 = 0.062_427_961;
+const BTU_PER_LB_R_TO_J_PER_KG_K: f64 = 4_186.8;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DistillationType {
+    Tbp,
+    D86,
+    D2887,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CriticalMethod {
+    #[default]
+    RiaziDaubert,
+    LeeKesler,
+    Twu,
+}
+
+/// 蒸馏曲线点：馏出体积分数 %，温度 K
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CurvePoint {
+    pub percent: f64,
+    pub t: f64,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct PseudoComponent {
+    /// 正常沸点（切割中点温度）K
+    pub tb: f64,
+    pub sg: f64,
+    pub mw: f64,
+    pub tc: f64,
+    pub pc: f64,
+    /// 临界体积 m³/kmol
+    pub vc: f64,
+    pub omega: f64,
+    pub watson_k: f64,
+    pub volume_fraction: f64,
+    pub mass_fraction: f64,
+    pub mole_fraction: f64,
+    /// 理想气体热容 Cp = a + b·T + c·T²，J/(kmol·K)，T 为 K
+    pub ideal_gas_cp: [f64; 3],
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct AssayResult {
+    /// 换算后的 TBP 曲线
+    pub tbp: Vec<CurvePoint>,
+    pub watson_k: f64,
+    pub components: Vec<PseudoComponent>,
+}
+
+pub struct AssayInput<'a> {
+    pub distillation: DistillationType,
+    pub curve: &'a [CurvePoint],
+    /// 总体比重（60°F/60°F）
+    pub bulk_sg: f64,
+    pub cuts: usize,
+    pub critical: CriticalMethod,
+}
+
+pub fn api_to_sg(api: f64) -> f64 {
+    141.5 / (api + 131.5)
+}
+
+pub fn sg_to_api(sg: f64) -> f64 {
+    141.5 / sg - 131.5
+}
+
+fn k_to_f(t: f64) -> f64 {
+    t * 1.8 - 459.67
+}
+
+fn f_to_k(t: f64) -> f64 {
+    (t + 459.67) / 1.8
+}
+
+/// 曲线在给定体积分数处的温度，两端按最近两点线性外推
+fn temperature_at(curve: &[CurvePoint], percent: f64) -> f64 {
+    let k = curve
+        .windows(2)
+        .position(|w| percent <= w[1].percent)
+        .unwrap_or(curve.len() - 2);
+    let (a, b) = (curve[k], curve[k + 1]);
+    a.t + (b.t - a.t) * (percent - a.percent) / (b.percent - a.percent)
+}
+
+/// 曲线上给定温度对应的馏出体积分数
+fn percent_at(curve: &[CurvePoint], t: f64) -> f64 {
+    let k = curve
+        .windows(2)
+        .position(|w| t <= w[1].t)
+        .unwrap_or(curve.len() - 2);
+    let (a, b) = (curve[k], curve[k + 1]);
+    a.percent + (b.percent - a.percent) * (t - a.t) / (b.t - a.t)
+}
+
+fn validate_curve(curve: &[CurvePoint]) -> Result<(), String> {
+    if curve.len() < 2 {
+        return Err("蒸馏曲线至少需要两个点".to_string());
+    }
+    for p in curve {
+        if !((0.0..=100.0).contains(&p.percent) && p.t.is_finite() && p.t > 0.0) {
+            return Err(format!("蒸馏曲线点无效: {}% / {} K", p.percent, p.t));
+        }
+    }
+    if curve
+        .windows(2)
+        .any(|w| w[1].percent <= w[0].percent || w[1].t <= w[0].t)
+    {
+        return Err("蒸馏曲线的体积分数与温度须严格递增".to_string());
+    }
+    Ok(())
+}
+
+/// Daubert 分段换算：以 50% 点为基准，各段温差 ΔTBP = A·ΔT^B（°F）
+fn daubert(
+    curve: &[CurvePoint],
+    percents: &[f64],
+    anchor: impl Fn(f64) -> Result<f64, String>,
+    segments: &[(f64, f64)],
+) -> Result<Vec<CurvePoint>, String> {
+    let t: Vec<f64> = percents
+        .iter()
+        .map(|&p| k_to_f(temperature_at(curve, p)))
+        .collect();
+    let mid = percents.iter().position(|&p| p == 50.0).unwrap_or(0);
+    let mut tbp = vec![0.0; t.len()];
+    tbp[mid] = anchor(t[mid])?;
+    // segments[k] 对应 percents[k] → percents[k + 1]
+    for k in (0..mid).rev() {
+        let (a, b) = segments[k];
+        tbp[k] = tbp[k + 1] - a * (t[k + 1] - t[k]).powf(b);
+    }
+    for k in mid..t.len() - 1 {
+        let (a, b) = segments[k];
+        tbp[k + 1] = tbp[k] + a * (t[k + 1] - t[k]).powf(b);
+    }
+    Ok(percents
+        .iter()
+        .zip(tbp)
+        .map(|(&percent, t)| CurvePoint {
+            percent,
+            t: f_to_k(t),
+        })
+        .collect())
+}
+
+/// 蒸馏曲线换算为 TBP 曲线（0% 与 100% 点缺失时外推）
+pub fn to_tbp(
+    distillation: DistillationType,
+    curve: &[CurvePoint],
+) -> Result<Vec<CurvePoint>, String> {
+    validate_curve(curve)?;
+    let tbp = match distillation {
+        DistillationType::Tbp => {
+            let mut points = curve.to_vec();
+            if points[0].percent > 0.0 {
+                points.insert(
+                    0,
+                    CurvePoint {
+                        percent: 0.0,
+                        t: temperature_at(curve, 0.0),
+                    },
+                );
+            }
+            if points[points.len() - 1].percent < 100.0 {
+                points.push(CurvePoint {
+                    percent: 100.0,
+                    t: temperature_at(curve, 100.0),
+                });
+            }
+            points
+        }
+        DistillationType::D86 => daubert(
+            curve,
+            &[0.0, 10.0, 30.0, 50.0, 70.0, 90.0, 100.0],
+            |t50| {
+                if t50 <= 0.0 {
+                    return Err("D86 50% 点须高于 0 °F".to_string());
+                }
+                Ok(0.8718 * t50.powf(1.0258))
+            },
+            &[
+                (7.4012, 0.60244),
+                (4.9004, 0.71644),
+                (3.0305, 0.80076),
+                (2.5282, 0.82002),
+                (3.0419, 0.75497),
+                (0.11798, 1.6606),
+            ],
+        )?,
+        DistillationType::D2887 => daubert(
+            curve,
+            &[0.0, 10.0, 30.0, 50.0, 70.0, 90.0, 95.0, 100.0],
+            Ok,
+            &[
+                (0.15779, 1.4296),
+                (0.011903, 2.0253),
+                (0.05342, 1.6988),
+                (0.19861, 1.3975),
+                (0.31915, 1.2938),
+                (0.97476, 0.8723),
+                (0.02172, 1.9733),
+            ],
+        )?,
+    };
+    if tbp
+        .windows(2)
+        .any(|w| w[1].t <= w[0].t || !w[1].t.is_finite())
+        || tbp[0].t <= 0.0
+    {
+        return Err("换算后的 TBP 曲线不单调，请检查蒸馏数据".to_string());
+    }
+    Ok(tbp)
+}
+
+struct Critical {
+    mw: f64,
+    tc: f64,
+    pc: f64,
+    vc: Option<f64>,
+}
+
+/// Riazi-Daubert (1980)，Tb 为 K，Pc 原式为 bar
+fn riazi_daubert(tb: f64, sg: f64) -> Critical {
+    Critical {
+        mw: 1.6607e-4 * tb.powf(2.1962) * sg.powf(-1.0164),
+        tc: 19.06232 * tb.powf(0.58848) * sg.powf(0.3596),
+        pc: 5.53027e7 * tb.powf(-2.3125) * sg.powf(2.3201) * 1e5,
+        vc: None,
+    }
+}
+
+/// Kesler-Lee (1976)，Tb 为 °R，Pc 原式为 psia
+fn lee_kesler(tb: f64, sg: f64) -> Critical {
+    let tb = tb * 1.8;
+    let tc = 341.7 + 811.0 * sg + (0.4244 + 0.1174 * sg) * tb + (0.4669 - 3.2623 * sg) * 1e5 / tb;
+    let ln_pc = 8.3634 - 0.0566 / sg - (0.24244 + 2.2898 / sg + 0.11857 / (sg * sg)) * 1e-3 * tb
+        + (1.4685 + 3.648 / sg + 0.47227 / (sg * sg)) * 1e-7 * tb * tb
+        - (0.42019 + 1.6977 / (sg * sg)) * 1e-10 * tb.powi(3);
+    let mw = -12272.6
+        + 9486.4 * sg
+        + (4.6523 - 3.3287 * sg) * tb
+        + (1.0 - 0.77084 * sg - 0.02058 * sg * sg) * (1.3437 - 720.79 / tb) * 1e7 / tb
+        + (1.0 - 0.80882 * sg + 0.02226 * sg * sg) * (1.8828 - 181.98 / tb) * 1e12 / tb.powi(3);
+    Critical {
+        mw,
+        tc: tc / 1.8,
+        pc: ln_pc.exp() * PSIA_TO_PA,
+        vc: None,
+    }
+}
+
+/// Twu (1984) 正构烷烃参比 + 比重修正，Tb 为 °R
+fn twu(tb: f64, sg: f64) -> Result<Critical, String> {
+    let tb = tb * 1.8;
+    let tc0 = tb
+        / (0.533272 + 0.191017e-3 * tb + 0.779681e-7 * tb * tb - 0.284376e-10 * tb.powi(3)
+            + 0.959468e28 / tb.powi(13));
+    let a = 1.0 - tb / tc0;
+    let sg0 = 0.843593 - 0.128624 * a - 3.36159 * a.powi(3) - 13749.5 * a.powi(12);
+    let pc0 = (3.83354 + 1.19629 * a.sqrt() + 34.8888 * a + 36.1952 * a * a + 104.193 * a.powi(4))
+        .powi(2);
+    let vc0 =
+        (1.0 - (0.419869 - 0.505839 * a - 1.56436 * a.powi(3) - 9481.70 * a.powi(14))).powi(-8);
+    // 参比正构烷烃分子量：牛顿迭代 θ = ln M°
+    let f = |th: f64| {
+        (5.71419 + 2.71579 * th - 0.286590 * th * th - 39.8544 / th - 0.122488 / (th * th)).exp()
+            - 24.7522 * th
+            + 35.3155 * th * th
+            - tb
+    };
+    let mut th = (tb / (10.44 - 0.0052 * tb)).ln();
+    let mut converged = false;
+    for _ in 0..100 {
+        let d = (f(th + 1e-7) - f(th)) / 1e-7;
+        let step = f(th) / d;
+        if !step.is_finite() {
+            break;
+        }
+        th -= step;
+        if step.abs() < 1e-12 {
+            converged = true;
+            break;
+        }
+    }
+    if !converged {
+        return Err(format!("Twu 参比分子量迭代不收敛（Tb = {:.1} °R）", tb));
+    }
+
+    let sq = tb.sqrt();
+    let ratio = |f: f64| ((1.0 + 2.0 * f) / (1.0 - 2.0 * f)).powi(2);
+    let d_t = (5.0 * (sg0 - sg)).exp() - 1.0;
+    let f_t = d_t * (-0.362456 / sq + (0.0398285 - 0.948125 / sq) * d_t);
+    let tc = tc0 * ratio(f_t);
+    let d_v = (4.0 * (sg0 * sg0 - sg * sg)).exp() - 1.0;
+    let f_v = d_v * (0.466590 / sq + (-0.182421 + 3.01721 / sq) * d_v);
+    let vc = vc0 * ratio(f_v);
+    let d_p = (0.5 * (sg0 - sg)).exp() - 1.0;
+    let f_p = d_p
+        * ((2.53262 - 46.1955 / sq - 0.00127885 * tb)
+            + (-11.4277 + 252.140 / sq + 0.00230535 * tb) * d_p);
+    let pc = pc0 * (tc / tc0) * (vc0 / vc) * ratio(f_p);
+    let d_m = d_t;
+    let f_m = d_m * ((0.0123420 - 0.328086 / sq).abs() + (-0.0175691 + 0.143979 / sq) * d_m);
+    let mw = (th * ratio(f_m)).exp();
+    Ok(Critical {
+        mw,
+        tc: tc / 1.8,
+        pc: pc * PSIA_TO_PA,
+        vc: Some(vc * FT3_PER_LBMOL_TO_M3_PER_KMOL),
+    })
+}
+
+/// Lee-Kesler 偏心因子，Tbr > 0.8 时改用 Kesler-Lee 的 Watson K 关联
+fn lee_kesler_omega(tb: f64, tc: f64, pc: f64, watson_k: f64) -> f64 {
+    let tbr = tb / tc;
+    if tbr > 0.8 {
+        return -7.904 + 0.1352 * watson_k - 0.007465 * watson_k * watson_k
+            + 8.359 * tbr
+            + (1.408 - 0.01063 * watson_k) / tbr;
+    }
+    let (ln_tbr, tbr6) = (tbr.ln(), tbr.powi(6));
+    (-(pc / ATM_TO_PA).ln() - 5.92714 + 6.09648 / tbr + 1.28862 * ln_tbr - 0.169347 * tbr6)
+        / (15.2518 - 15.6875 / tbr - 13.4721 * ln_tbr + 0.43577 * tbr6)
+}
+
+/// Kesler-Lee 理想气体热容，返回 J/(kmol·K) 关于 T(K) 的二次多项式系数
+fn ideal_gas_cp(watson_k: f64, omega: f64, mw: f64) -> [f64; 3] {
+    let a = [
+        -0.33886 + 0.02827 * watson_k,
+        -(0.9291 - 1.1543 * watson_k + 0.0368 * watson_k * watson_k) * 1e-4,
+        -1.6658e-7,
+    ];
+    let b = [
+        -0.26105 + 0.59332 * omega,
+        -(4.92 - 8.45 * omega) * 1e-4,
+        -(1.887 - 2.4043 * omega) * 1e-7,
+    ];
+    let c = if (10.0..=12.8).contains(&watson_k) && omega > 0.0 {
+        ((12.8 - watson_k) * (10.0 - watson_k) / (10.0 * omega)).powi(2)
+    } else {
+        0.0
+    };
+    // 原式 Btu/(lb·°R)、T 为 °R
+    let scale = BTU_PER_LB_R_TO_J_PER_KG_K * mw;
+    [
+        scale * (a[0] - c * b[0]),
+        scale * (a[1] - c * b[1]) * 1.8,
+        scale * (a[2] - c * b[2]) * 1.8 * 1.8,
+    ]
+}
+
+/// 由蒸馏曲线与总体比重生成虚拟组分
+pub fn characterize(input: &AssayInput) -> Result<AssayResult, String> {
+    if input.cuts == 0 {
+        return Err("切割数须大于 0".to_string());
+    }
+    if !(input.bulk_sg.is_finite() && input.bulk_sg > 0.0) {
+        return Err("总体比重须为正数".to_string());
+    }
+    let tbp = to_tbp(input.distillation, input.curve)?;
+    let (ibp, fbp) = (tbp[0].t, tbp[tbp.len() - 1].t);
+
+    // 等温度间隔切割，沸点取切割体积中点处的 TBP 温度
+    let width = (fbp - ibp) / input.cuts as f64;
+    let mut cuts = Vec::with_capacity(input.cuts);
+    for k in 0..input.cuts {
+        let lo = percent_at(&tbp, ibp + width * k as f64);
+        let hi = percent_at(&tbp, ibp + width * (k + 1) as f64);
+        let volume = (hi - lo) / 100.0;
+        if volume > 0.0 {
+            cuts.push((temperature_at(&tbp, (lo + hi) / 2.0), volume));
+        }
+    }
+
+    // SG_i = Tb_i^⅓ / Kw（Tb 为 °R），体积加权等于总体比重
+    let watson_k = cuts
+        .iter()
+        .map(|(tb, v)| v * (tb * 1.8).cbrt())
+        .sum::<f64>()
+        / input.bulk_sg;
+
+    let mut components = Vec::with_capacity(cuts.len());
+    for (tb, volume) in cuts {
+        let sg = (tb * 1.8).cbrt() / watson_k;
+        let critical = match input.critical {
+            CriticalMethod::RiaziDaubert => riazi_daubert(tb, sg),
+            CriticalMethod::LeeKesler => lee_kesler(tb, sg),
+            CriticalMethod::Twu => twu(tb, sg)?,
+        };
+        if !(critical.mw > 0.0 && critical.tc > tb && critical.pc > 0.0) {
+            return Err(format!(
+                "沸点 {:.1} K、比重 {:.4} 的馏分超出关联式适用范围",
+                tb, sg
+            ));
+        }
+        let omega = lee_kesler_omega(tb, critical.tc, critical.pc, watson_k);
+        // 未给出临界体积的方法按 Pitzer 临界压缩因子估算
+        let vc = critical
+            .vc
+            .unwrap_or_else(|| (0.2918 - 0.0928 * omega) * R * critical.tc / critical.pc);
+        components.push(PseudoComponent {
+            tb,
+            sg,
+            mw: critical.mw,
+            tc: critical.tc,
+            pc: critical.pc,
+            vc,
+            omega,
+            watson_k,
+            volume_fraction: volume,
+            mass_fraction: volume * sg,
+            mole_fraction: volume * sg / critical.mw,
+            ideal_gas_cp: ideal_gas_cp(watson_k, omega, critical.mw),
+        });
+    }
+    let mass: f64 = components.iter().map(|c| c.mass_fraction).sum();
+    let moles: f64 = components.iter().map(|c| c.mole_fraction).sum();
+    for c in &mut components {
+        c.mass_fraction /= mass;
+        c.mole_fraction /= moles;
+    }
+
+    Ok(AssayResult {
+        tbp,
+        watson_k,
+        components,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decane_cut_and_assay() {
+        // 正癸烷：M 142.3，Tc 617.7 K，Pc 21.1 bar，ω 0.49
+        let (tb, sg) = (447.3, 0.734);
+        for c in [
+            riazi_daubert(tb, sg),
+            lee_kesler(tb, sg),
+            twu(tb, sg).unwrap(),
+        ] {
+            assert!((c.mw - 142.3).abs() < 12.0, "{}", c.mw);
+            assert!((c.tc - 617.7).abs() < 5.0, "{}", c.tc);
+            assert!((c.pc / 1e5 - 21.1).abs() < 1.5, "{}", c.pc);
+        }
+        let lk = lee_kesler(tb, sg);
+        let kw = (tb * 1.8).cbrt() / sg;
+        assert!((lee_kesler_omega(tb, lk.tc, lk.pc, kw) - 0.49).abs() < 0.02);
+        let cp = ideal_gas_cp(kw, 0.49, 142.3);
+        let cp300 = cp[0] + cp[1] * 300.0 + cp[2] * 300.0 * 300.0;
+        assert!((cp300 / 1000.0 - 233.0).abs() < 10.0, "{}", cp300);
+
+        let curve: Vec<CurvePoint> = [
+            (10.0, 360.0),
+            (30.0, 390.0),
+            (50.0, 420.0),
+            (70.0, 455.0),
+            (90.0, 500.0),
+        ]
+        .iter()
+        .map(|&(percent, t)| CurvePoint { percent, t })
+        .collect();
+        for distillation in [
+            DistillationType::Tbp,
+            DistillationType::D86,
+            DistillationType::D2887,
+        ] {
+            let result = characterize(&AssayInput {
+                distillation,
+                curve: &curve,
+                bulk_sg: api_to_sg(55.0),
+                cuts: 8,
+                critical: CriticalMethod::Twu,
+            })
+            .unwrap();
+            assert_eq!(result.components.len(), 8);
+            let bulk: f64 = result
+                .components
+                .iter()
+                .map(|c| c.volume_fraction * c.sg)
+                .sum();
+            assert!((bulk - api_to_sg(55.0)).abs() < 1e-9);
+            let total: f64 = result.components.iter().map(|c| c.mole_fraction).sum();
+            assert!((total - 1.0).abs() < 1e-12);
+            assert!(
+                result
+                    .components
+                    .windows(2)
+                    .all(|w| w[1].mw > w[0].mw && w[1].tc > w[0].tc)
+            );
+        }
+        assert!((sg_to_api(api_to_sg(30.0)) - 30.0).abs() < 1e-12);
+        assert!(to_tbp(DistillationType::Tbp, &curve[..1]).is_err());
+    }
+}
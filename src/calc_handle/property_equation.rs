@@ -0,0 +1,145 @@
+// ======================================
+// 纯组分温度关联方程（DIPPR 形式）求值
+// 温度单位为 K，方程系数按 A B C D E F G H K L 顺序存放
+// ======================================
+
+/// 方程系数 A..L
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct EquationCoefficients(pub [f64; 10]);
+
+impl EquationCoefficients {
+    fn a(&self) -> f64 {
+        self.0[0]
+    }
+    fn b(&self) -> f64 {
+        self.0[1]
+    }
+    fn c(&self) -> f64 {
+        self.0[2]
+    }
+    fn d(&self) -> f64 {
+        self.0[3]
+    }
+    fn e(&self) -> f64 {
+        self.0[4]
+    }
+    fn f(&self) -> f64 {
+        self.0[5]
+    }
+}
+
+/// 按方程编号求值
+///
+/// - 100: A + B·T + C·T² + D·T³ + E·T⁴
+/// - 101: exp(A + B/T + C·ln T + D·T^E)
+/// - 102: A·T^B / (1 + C/T + D/T²)
+/// - 103: A + B·exp(-C / T^D)
+/// - 104: A + B/T + C/T³ + D/T⁸ + E/T⁹
+/// - 105: A / B^(1 + (1 - T/C)^D)
+/// - 106: A·(1 - Tr)^(B + C·Tr + D·Tr² + E·Tr³)，Tr = T / F（F 为临界温度）
+/// - 107: A + B·[(C/T)/sinh(C/T)]² + D·[(E/T)/cosh(E/T)]²
+/// - 114: A²/τ + B - 2AC·τ - AD·τ² - C²τ³/3 - CDτ⁴/2 - D²τ⁵/5，τ = 1 - T/F
+/// - 116: A + B·τ^0.35 + C·τ^(2/3) + D·τ + E·τ^(4/3)，τ = 1 - T/F
+pub fn evaluate_equation(
+    function_code: i32,
+    coefficients: &EquationCoefficients,
+    t: f64,
+) -> Result<f64, String> {
+    if !(t.is_finite() && t > 0.0) {
+        return Err(format!("温度必须为正数（K）: {}", t));
+    }
+    let p = coefficients;
+    let reduced = || -> Result<f64, String> {
+        if p.f() <= 0.0 {
+            return Err(format!(
+                "方程 {} 需要在系数 F 中给出临界温度",
+                function_code
+            ));
+        }
+        Ok(t / p.f())
+    };
+    let value = match function_code {
+        100 => p.a() + t * (p.b() + t * (p.c() + t * (p.d() + t * p.e()))),
+        101 => (p.a() + p.b() / t + p.c() * t.ln() + p.d() * t.powf(p.e())).exp(),
+        102 => p.a() * t.powf(p.b()) / (1.0 + p.c() / t + p.d() / (t * t)),
+        103 => p.a() + p.b() * (-p.c() / t.powf(p.d())).exp(),
+        104 => p.a() + p.b() / t + p.c() / t.powi(3) + p.d() / t.powi(8) + p.e() / t.powi(9),
+        105 => {
+            if p.c() == 0.0 {
+                return Err("方程 105 的系数 C 不能为 0".to_string());
+            }
+            p.a() / p.b().powf(1.0 + (1.0 - t / p.c()).powf(p.d()))
+        }
+        106 => {
+            let tr = reduced()?;
+            p.a() * (1.0 - tr).powf(p.b() + tr * (p.c() + tr * (p.d() + tr * p.e())))
+        }
+        107 => {
+            let x = p.c() / t;
+            let y = p.e() / t;
+            let sinh_term = if x == 0.0 { 1.0 } else { x / x.sinh() };
+            let cosh_term = y / y.cosh();
+            p.a() + p.b() * sinh_term * sinh_term + p.d() * cosh_term * cosh_term
+        }
+        114 => {
+            let tau = 1.0 - reduced()?;
+            let (a, b, c, d) = (p.a(), p.b(), p.c(), p.d());
+            a * a / tau + b
+                - 2.0 * a * c * tau
+                - a * d * tau.powi(2)
+                - c * c * tau.powi(3) / 3.0
+                - c * d * tau.powi(4) / 2.0
+                - d * d * tau.powi(5) / 5.0
+        }
+        116 => {
+            let tau = 1.0 - reduced()?;
+            p.a()
+                + p.b() * tau.powf(0.35)
+                + p.c() * tau.powf(2.0 / 3.0)
+                + p.d() * tau
+                + p.e() * tau.powf(4.0 / 3.0)
+        }
+        other => return Err(format!("不支持的方程编号: {}", other)),
+    };
+    if value.is_finite() {
+        Ok(value)
+    } else {
+        Err(format!("方程 {} 在 T = {} K 处无有效值", function_code, t))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn coeffs(v: &[f64]) -> EquationCoefficients {
+        let mut c = [0.0; 10];
+        c[..v.len()].copy_from_slice(v);
+        EquationCoefficients(c)
+    }
+
+    #[test]
+    fn test_dippr_forms() {
+        let close = |a: f64, b: f64, tol: f64| (a - b).abs() <= tol * b.abs();
+
+        // 水的饱和蒸气压（DIPPR 101），373.15 K 约 101325 Pa
+        let water_vp = coeffs(&[73.649, -7258.2, -7.3037, 4.1653e-6, 2.0]);
+        let p = evaluate_equation(101, &water_vp, 373.15).unwrap();
+        assert!(close(p, 101_325.0, 0.01), "{}", p);
+
+        // 水的液体密度（DIPPR 105，kmol/m³），298.15 K 约 55.3
+        let water_rho = coeffs(&[5.459, 0.30542, 647.13, 0.081]);
+        let rho = evaluate_equation(105, &water_rho, 298.15).unwrap();
+        assert!(close(rho, 55.3, 0.01), "{}", rho);
+
+        // 100 为多项式
+        assert_eq!(
+            evaluate_equation(100, &coeffs(&[1.0, 2.0, 3.0]), 2.0).unwrap(),
+            17.0
+        );
+        // 106 需要临界温度
+        assert!(evaluate_equation(106, &coeffs(&[1.0, 0.5]), 300.0).is_err());
+        assert!(evaluate_equation(999, &coeffs(&[]), 300.0).is_err());
+        assert!(evaluate_equation(100, &coeffs(&[1.0]), -1.0).is_err());
+    }
+}
@@ -0,0 +1,294 @@
+// ======================================
+// 反应方程式解析与元素守恒
+// - 方程式："2 C2H6 + 7 O2 -> 4 CO2 + 6 H2O"，箭头支持 -> => = → 与可逆 <-> <=> ⇌
+// - 化学式：元素符号 + 数量，支持括号嵌套与结晶水（CuSO4·5H2O）
+// - 化学计量系数：反应物为负、产物为正
+// ======================================
+
+use std::collections::BTreeMap;
+
+/// 元素 → 原子数
+pub type ElementCounts = BTreeMap<String, f64>;
+
+/// 元素守恒允许的误差
+pub const BALANCE_TOLERANCE: f64 = 1e-9;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ReactionTerm {
+    pub species: String,
+    /// 带符号的化学计量系数
+    pub coefficient: f64,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ParsedEquation {
+    pub terms: Vec<ReactionTerm>,
+    pub reversible: bool,
+}
+
+const ARROWS: &[(&str, bool)] = &[
+    ("<=>", true),
+    ("<->", true),
+    ("⇌", true),
+    ("->", false),
+    ("=>", false),
+    ("→", false),
+    ("=", false),
+];
+
+fn parse_coefficient(s: &str) -> Option<f64> {
+    match s.split_once('/') {
+        Some((n, d)) => {
+            let (n, d) = (n.parse::<f64>().ok()?, d.parse::<f64>().ok()?);
+            (d != 0.0).then(|| n / d)
+        }
+        None => s.parse().ok(),
+    }
+}
+
+/// 单项："2 C2H6"、"0.5O2"、"1/2 O2"、"H2O"。
+/// 开头的数字后面是空白、大写字母或括号时才作为系数，CAS 号（7732-18-5）与 1-butene 这类名称整体作为组分
+fn parse_term(term: &str, sign: f64) -> Result<ReactionTerm, String> {
+    let term = term.trim();
+    let split = term
+        .find(|c: char| !(c.is_ascii_digit() || c == '.' || c == '/'))
+        .unwrap_or(term.len());
+    let is_coefficient = term[split..]
+        .chars()
+        .next()
+        .is_none_or(|c| c.is_whitespace() || c.is_ascii_uppercase() || c == '(' || c == '[');
+    let (number, species) = term.split_at(if is_coefficient { split } else { 0 });
+    let species = species.trim();
+    if species.is_empty() {
+        return Err(format!("“{}” 缺少组分", term));
+    }
+    let coefficient = if number.is_empty() {
+        1.0
+    } else {
+        parse_coefficient(number).ok_or_else(|| format!("“{}” 的化学计量系数无效", term))?
+    };
+    if !(coefficient.is_finite() && coefficient > 0.0) {
+        return Err(format!("“{}” 的化学计量系数须为正数", term));
+    }
+    Ok(ReactionTerm {
+        species: species.to_string(),
+        coefficient: sign * coefficient,
+    })
+}
+
+fn parse_side(side: &str, sign: f64) -> Result<Vec<ReactionTerm>, String> {
+    let normalized = side.split_whitespace().collect::<Vec<_>>().join(" ");
+    if normalized.is_empty() {
+        return Err("方程式一侧没有组分".to_string());
+    }
+    // 以两侧带空格的 + 分隔，避免与离子符号（Na+）混淆
+    normalized
+        .split(" + ")
+        .map(|t| parse_term(t, sign))
+        .collect()
+}
+
+/// 解析反应方程式，同一组分在两侧出现时合并为净系数
+pub fn parse_equation(equation: &str) -> Result<ParsedEquation, String> {
+    let (pos, arrow, reversible) = ARROWS
+        .iter()
+        .find_map(|&(a, r)| equation.find(a).map(|p| (p, a, r)))
+        .ok_or("方程式缺少反应箭头（->、=>、= 或 <=>）")?;
+    let (left, right) = (&equation[..pos], &equation[pos + arrow.len()..]);
+    if ARROWS
+        .iter()
+        .any(|(a, _)| left.contains(a) || right.contains(a))
+    {
+        return Err("方程式中只能有一个反应箭头".to_string());
+    }
+    let mut terms: Vec<ReactionTerm> = Vec::new();
+    for term in parse_side(left, -1.0)?
+        .into_iter()
+        .chain(parse_side(right, 1.0)?)
+    {
+        match terms.iter_mut().find(|t| t.species == term.species) {
+            Some(t) => t.coefficient += term.coefficient,
+            None => terms.push(term),
+        }
+    }
+    Ok(ParsedEquation { terms, reversible })
+}
+
+fn parse_group(chars: &[char], pos: &mut usize, depth: usize) -> Result<ElementCounts, String> {
+    let mut counts = ElementCounts::new();
+    let read_number = |pos: &mut usize| -> Option<f64> {
+        let start = *pos;
+        while *pos < chars.len() && (chars[*pos].is_ascii_digit() || chars[*pos] == '.') {
+            *pos += 1;
+        }
+        (start < *pos).then(|| chars[start..*pos].iter().collect::<String>().parse().ok())?
+    };
+    while *pos < chars.len() {
+        let c = chars[*pos];
+        match c {
+            'A'..='Z' => {
+                let mut symbol = c.to_string();
+                *pos += 1;
+                while *pos < chars.len() && chars[*pos].is_ascii_lowercase() {
+                    symbol.push(chars[*pos]);
+                    *pos += 1;
+                }
+                let n = read_number(pos).unwrap_or(1.0);
+                *counts.entry(symbol).or_default() += n;
+            }
+            '(' | '[' => {
+                *pos += 1;
+                let inner = parse_group(chars, pos, depth + 1)?;
+                let n = read_number(pos).unwrap_or(1.0);
+                for (e, v) in inner {
+                    *counts.entry(e).or_default() += v * n;
+                }
+            }
+            ')' | ']' => {
+                if depth == 0 {
+                    return Err("化学式括号不匹配".to_string());
+                }
+                *pos += 1;
+                return Ok(counts);
+            }
+            _ => return Err(format!("化学式中有无法识别的字符 “{}”", c)),
+        }
+    }
+    if depth > 0 {
+        return Err("化学式括号不匹配".to_string());
+    }
+    Ok(counts)
+}
+
+/// 解析化学式为元素组成
+pub fn parse_formula(formula: &str) -> Result<ElementCounts, String> {
+    let formula: String = formula.chars().filter(|c| !c.is_whitespace()).collect();
+    if formula.is_empty() {
+        return Err("化学式为空".to_string());
+    }
+    let mut total = ElementCounts::new();
+    // 结晶水等加合部分：CuSO4·5H2O
+    for part in formula.split(['·', '*', '•']) {
+        let chars: Vec<char> = part.chars().collect();
+        let split = chars
+            .iter()
+            .position(|c| !(c.is_ascii_digit() || *c == '.'))
+            .unwrap_or(chars.len());
+        let multiplier = if split == 0 {
+            1.0
+        } else {
+            chars[..split]
+                .iter()
+                .collect::<String>()
+                .parse::<f64>()
+                .map_err(|_| format!("化学式 “{}” 的系数无效", part))?
+        };
+        let mut pos = split;
+        let counts = parse_group(&chars, &mut pos, 0)?;
+        if counts.is_empty() {
+            return Err(format!("化学式 “{}” 中没有元素", part));
+        }
+        for (e, v) in counts {
+            *total.entry(e).or_default() += v * multiplier;
+        }
+    }
+    Ok(total)
+}
+
+/// 各元素的 Σ νi·ni，守恒时全部为 0
+pub fn element_balance(terms: &[(f64, &ElementCounts)]) -> ElementCounts {
+    let mut balance = ElementCounts::new();
+    for (nu, counts) in terms {
+        for (e, n) in counts.iter() {
+            *balance.entry(e.clone()).or_default() += nu * n;
+        }
+    }
+    balance
+}
+
+/// 标准反应热 ΔHr = Σ νi·ΔHf,i
+pub fn heat_of_reaction(terms: &[(f64, f64)]) -> f64 {
+    terms.iter().map(|(nu, hf)| nu * hf).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_and_balance() {
+        let eq = parse_equation("2 C2H6 + 7 O2 -> 4 CO2 + 6 H2O").unwrap();
+        assert!(!eq.reversible);
+        let nu: Vec<f64> = eq.terms.iter().map(|t| t.coefficient).collect();
+        assert_eq!(nu, vec![-2.0, -7.0, 4.0, 6.0]);
+
+        let formulas: Vec<ElementCounts> = eq
+            .terms
+            .iter()
+            .map(|t| parse_formula(&t.species).unwrap())
+            .collect();
+        let terms: Vec<(f64, &ElementCounts)> = nu.iter().copied().zip(&formulas).collect();
+        assert!(
+            element_balance(&terms)
+                .values()
+                .all(|v| v.abs() < BALANCE_TOLERANCE)
+        );
+
+        let bad = parse_equation("C2H6 + O2 <=> CO2 + H2O").unwrap();
+        assert!(bad.reversible);
+        let formulas: Vec<ElementCounts> = bad
+            .terms
+            .iter()
+            .map(|t| parse_formula(&t.species).unwrap())
+            .collect();
+        let terms: Vec<(f64, &ElementCounts)> = bad
+            .terms
+            .iter()
+            .map(|t| t.coefficient)
+            .zip(&formulas)
+            .collect();
+        assert_eq!(element_balance(&terms)["C"], -1.0);
+
+        let half = parse_equation("CO + 1/2 O2 = CO2").unwrap();
+        assert_eq!(half.terms[1].coefficient, -0.5);
+
+        let hydrate = parse_formula("CuSO4·5H2O").unwrap();
+        assert_eq!(hydrate["H"], 10.0);
+        assert_eq!(hydrate["O"], 9.0);
+        assert_eq!(parse_formula("Ca(OH)2").unwrap()["O"], 2.0);
+        assert!(parse_formula("Ca(OH2").is_err());
+        assert!(parse_equation("A + B").is_err());
+        assert!(parse_equation("A -> B -> C").is_err());
+        assert!(parse_equation("A = B -> C").is_err());
+
+        // CH4 + 2 O2 -> CO2 + 2 H2O，ΔHf（kJ/mol）：-74.87、0、-393.51、-241.83
+        let dh = heat_of_reaction(&[(-1.0, -74.87), (-2.0, 0.0), (1.0, -393.51), (2.0, -241.83)]);
+        assert!((dh + 802.3).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_parse_cas_and_numbered_names() {
+        let eq = parse_equation("7732-18-5 + 124-38-9 -> 2 463-79-6").unwrap();
+        let species: Vec<&str> = eq.terms.iter().map(|t| t.species.as_str()).collect();
+        assert_eq!(species, vec!["7732-18-5", "124-38-9", "463-79-6"]);
+        assert_eq!(eq.terms[0].coefficient, -1.0);
+        assert_eq!(eq.terms[2].coefficient, 2.0);
+
+        let eq = parse_equation("2 1-butene -> 1,3-butadiene + 2H2").unwrap();
+        let terms: Vec<(&str, f64)> = eq
+            .terms
+            .iter()
+            .map(|t| (t.species.as_str(), t.coefficient))
+            .collect();
+        assert_eq!(
+            terms,
+            vec![("1-butene", -2.0), ("1,3-butadiene", 1.0), ("H2", 2.0)]
+        );
+        assert_eq!(
+            parse_equation("2(CH3)2O -> B").unwrap().terms[0].coefficient,
+            -2.0
+        );
+        // 只有数字没有组分
+        assert!(parse_equation("2 -> B").is_err());
+    }
+}
@@ -0,0 +1,300 @@
+// ======================================
+// UNIFAC 基团贡献法（原始 VLE 版本，Hansen 等 1991 参数表的常用子集）
+// 组合项与 UNIQUAC 相同（r、q 由基团加和），剩余项按基团活度系数计算
+// ======================================
+
+use crate::calc_handle::activity_model::{
+    ActivityInput, ActivityModel, UniquacStructure, activity_coefficients,
+};
+use std::collections::HashMap;
+
+/// 子基团：编号、名称、所属主基团、体积参数 R、表面积参数 Q
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct UnifacSubgroup {
+    pub id: u32,
+    pub name: &'static str,
+    pub main_group: u32,
+    pub r: f64,
+    pub q: f64,
+}
+
+macro_rules! subgroups {
+    ($(($id:expr, $name:expr, $main:expr, $r:expr, $q:expr)),* $(,)?) => {
+        &[$(UnifacSubgroup { id: $id, name: $name, main_group: $main, r: $r, q: $q }),*]
+    };
+}
+
+pub const SUBGROUPS: &[UnifacSubgroup] = subgroups![
+    (1, "CH3", 1, 0.9011, 0.848),
+    (2, "CH2", 1, 0.6744, 0.540),
+    (3, "CH", 1, 0.4469, 0.228),
+    (4, "C", 1, 0.2195, 0.000),
+    (5, "CH2=CH", 2, 1.3454, 1.176),
+    (6, "CH=CH", 2, 1.1167, 0.867),
+    (7, "CH2=C", 2, 1.1173, 0.988),
+    (8, "CH=C", 2, 0.8886, 0.676),
+    (70, "C=C", 2, 0.6605, 0.485),
+    (9, "ACH", 3, 0.5313, 0.400),
+    (10, "AC", 3, 0.3652, 0.120),
+    (11, "ACCH3", 4, 1.2663, 0.968),
+    (12, "ACCH2", 4, 1.0396, 0.660),
+    (13, "ACCH", 4, 0.8121, 0.348),
+    (14, "OH", 5, 1.0000, 1.200),
+    (15, "CH3OH", 6, 1.4311, 1.432),
+    (16, "H2O", 7, 0.9200, 1.400),
+    (18, "CH3CO", 9, 1.6724, 1.488),
+    (19, "CH2CO", 9, 1.4457, 1.180),
+    (21, "CH3COO", 11, 1.9031, 1.728),
+    (22, "CH2COO", 11, 1.6764, 1.420),
+    (24, "CH3O", 13, 1.1450, 1.088),
+    (25, "CH2O", 13, 0.9183, 0.780),
+    (26, "CH-O", 13, 0.6908, 0.468),
+    (27, "THF", 13, 0.9183, 1.100),
+];
+
+/// 主基团交互参数 (m, n, a_mn, a_nm)，单位 K
+const INTERACTIONS: &[(u32, u32, f64, f64)] = &[
+    (1, 2, 86.02, -35.36),
+    (1, 3, 61.13, -11.12),
+    (1, 4, 76.50, -69.70),
+    (1, 5, 986.5, 156.4),
+    (1, 6, 697.2, 16.51),
+    (1, 7, 1318.0, 300.0),
+    (1, 9, 476.4, 26.76),
+    (1, 11, 232.1, 114.8),
+    (1, 13, 251.5, 83.36),
+    (2, 3, 38.81, 3.446),
+    (2, 4, 74.15, -113.6),
+    (2, 5, 524.1, 457.0),
+    (2, 6, 787.6, -12.52),
+    (2, 7, 270.6, 496.1),
+    (2, 9, 182.6, 42.92),
+    (2, 11, 37.85, 132.1),
+    (2, 13, 214.5, 26.51),
+    (3, 4, 167.0, -146.8),
+    (3, 5, 636.1, 89.60),
+    (3, 6, 637.4, -50.00),
+    (3, 7, 903.8, 362.3),
+    (3, 9, 25.77, 140.1),
+    (3, 11, 5.994, 85.84),
+    (3, 13, 32.14, 52.13),
+    (4, 5, 803.2, 25.82),
+    (4, 6, 603.3, -44.50),
+    (4, 7, 5695.0, 377.6),
+    (4, 9, -52.10, 365.8),
+    (4, 11, 5688.0, -170.0),
+    (4, 13, 213.1, 65.69),
+    (5, 6, -137.1, 249.1),
+    (5, 7, 353.5, -229.1),
+    (5, 9, 84.00, 164.5),
+    (5, 11, 101.1, 245.4),
+    (5, 13, 28.06, 237.7),
+    (6, 7, -181.0, 289.6),
+    (6, 9, 23.39, 108.7),
+    (6, 11, -10.72, 249.6),
+    (6, 13, -128.6, 238.4),
+    (7, 9, -195.4, 472.5),
+    (7, 11, 14.42, 200.8),
+    (7, 13, 540.5, -314.7),
+    (9, 11, -213.7, 372.2),
+    (9, 13, -103.6, 191.1),
+    (11, 13, -235.7, 461.3),
+];
+
+pub fn find_subgroup(id: u32) -> Option<&'static UnifacSubgroup> {
+    SUBGROUPS.iter().find(|s| s.id == id)
+}
+
+fn interaction(m: u32, n: u32) -> Option<f64> {
+    if m == n {
+        return Some(0.0);
+    }
+    INTERACTIONS.iter().find_map(|&(a, b, amn, anm)| {
+        if (a, b) == (m, n) {
+            Some(amn)
+        } else if (a, b) == (n, m) {
+            Some(anm)
+        } else {
+            None
+        }
+    })
+}
+
+/// 组分的基团组成：(子基团编号, 个数)
+pub type GroupCounts = Vec<(u32, f64)>;
+
+/// 针对一组组分预处理好的 UNIFAC 模型
+#[derive(Clone, Debug)]
+pub struct Unifac {
+    /// 混合物中出现的子基团
+    groups: Vec<&'static UnifacSubgroup>,
+    /// nu[i][k]：组分 i 中子基团 k 的个数
+    nu: Vec<Vec<f64>>,
+    /// a[m][k]：子基团所属主基团之间的交互参数
+    a: Vec<Vec<f64>>,
+    structure: Vec<UniquacStructure>,
+}
+
+impl Unifac {
+    pub fn new(components: &[GroupCounts]) -> Result<Self, String> {
+        let mut index: HashMap<u32, usize> = HashMap::new();
+        let mut groups: Vec<&'static UnifacSubgroup> = Vec::new();
+        for (i, counts) in components.iter().enumerate() {
+            if counts.is_empty() {
+                return Err(format!("第 {} 个组分没有 UNIFAC 基团", i + 1));
+            }
+            for &(id, _) in counts {
+                if index.contains_key(&id) {
+                    continue;
+                }
+                let group = find_subgroup(id).ok_or(format!("不支持的 UNIFAC 子基团: {}", id))?;
+                index.insert(id, groups.len());
+                groups.push(group);
+            }
+        }
+        let nu: Vec<Vec<f64>> = components
+            .iter()
+            .map(|counts| {
+                let mut row = vec![0.0; groups.len()];
+                for &(id, count) in counts {
+                    row[index[&id]] += count;
+                }
+                row
+            })
+            .collect();
+        let mut a = vec![vec![0.0; groups.len()]; groups.len()];
+        for (m, gm) in groups.iter().enumerate() {
+            for (k, gk) in groups.iter().enumerate() {
+                a[m][k] = interaction(gm.main_group, gk.main_group).ok_or(format!(
+                    "缺少 UNIFAC 主基团 {} - {} 的交互参数",
+                    gm.main_group, gk.main_group
+                ))?;
+            }
+        }
+        let structure = nu
+            .iter()
+            .map(|row| UniquacStructure {
+                r: row.iter().zip(&groups).map(|(v, g)| v * g.r).sum(),
+                q: row.iter().zip(&groups).map(|(v, g)| v * g.q).sum(),
+            })
+            .collect();
+        Ok(Self {
+            groups,
+            nu,
+            a,
+            structure,
+        })
+    }
+
+    /// 由基团加和得到的 UNIQUAC 结构参数 r、q
+    pub fn structure(&self) -> &[UniquacStructure] {
+        &self.structure
+    }
+
+    /// 给定基团摩尔数时的基团活度系数 ln Γk
+    fn ln_group_gamma(&self, group_moles: &[f64], t: f64) -> Vec<f64> {
+        let total: f64 = group_moles.iter().sum();
+        let area: Vec<f64> = group_moles
+            .iter()
+            .zip(&self.groups)
+            .map(|(v, g)| g.q * v / total)
+            .collect();
+        let sum_area: f64 = area.iter().sum();
+        let theta: Vec<f64> = area.iter().map(|v| v / sum_area).collect();
+        let g = self.groups.len();
+        let psi: Vec<Vec<f64>> = (0..g)
+            .map(|m| (0..g).map(|k| (-self.a[m][k] / t).exp()).collect())
+            .collect();
+        let sum_theta_psi: Vec<f64> = (0..g)
+            .map(|k| (0..g).map(|m| theta[m] * psi[m][k]).sum())
+            .collect();
+        (0..g)
+            .map(|k| {
+                let tail: f64 = (0..g)
+                    .map(|m| theta[m] * psi[k][m] / sum_theta_psi[m])
+                    .sum();
+                self.groups[k].q * (1.0 - sum_theta_psi[k].ln() - tail)
+            })
+            .collect()
+    }
+
+    /// 计算各组分的 ln γ（组成会先归一化）
+    pub fn ln_gamma(&self, x: &[f64], t: f64) -> Result<Vec<f64>, String> {
+        let n = self.nu.len();
+        if x.len() != n {
+            return Err("摩尔分数个数与组分数不一致".to_string());
+        }
+        // 组合项：无相互作用的 UNIQUAC
+        let none = vec![vec![None; n]; n];
+        let combinatorial = activity_coefficients(
+            ActivityModel::Uniquac,
+            &ActivityInput {
+                temperature: t,
+                x,
+                pairs: &none,
+                structure: &self.structure,
+            },
+        )?
+        .ln_gamma;
+
+        let total: f64 = x.iter().sum();
+        let g = self.groups.len();
+        let mixture_moles: Vec<f64> = (0..g)
+            .map(|k| (0..n).map(|i| self.nu[i][k] * x[i] / total).sum())
+            .collect();
+        let mixture = self.ln_group_gamma(&mixture_moles, t);
+        let result: Vec<f64> = (0..n)
+            .map(|i| {
+                // 纯组分参考态只包含该组分自身的基团
+                let pure = self.ln_group_gamma(&self.nu[i], t);
+                let residual: f64 = (0..g)
+                    .filter(|&k| self.nu[i][k] > 0.0)
+                    .map(|k| self.nu[i][k] * (mixture[k] - pure[k]))
+                    .sum();
+                combinatorial[i] + residual
+            })
+            .collect();
+        if result.iter().any(|v| !v.is_finite()) {
+            return Err("UNIFAC 计算结果无效".to_string());
+        }
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unifac_consistency() {
+        let ethanol: GroupCounts = vec![(1, 1.0), (2, 1.0), (14, 1.0)];
+        let water: GroupCounts = vec![(16, 1.0)];
+        let model = Unifac::new(&[ethanol, water]).unwrap();
+        let s = model.structure();
+        assert!((s[0].r - 2.5755).abs() < 1e-9 && (s[0].q - 2.588).abs() < 1e-9);
+
+        // 纯组分 ln γ = 0
+        let pure = model.ln_gamma(&[1.0, 0.0], 340.0).unwrap();
+        assert!(pure[0].abs() < 1e-12);
+
+        // 乙醇-水为正偏差体系
+        let mid = model.ln_gamma(&[0.5, 0.5], 340.0).unwrap();
+        assert!(mid.iter().all(|v| *v > 0.0), "{:?}", mid);
+
+        // Gibbs-Duhem 数值校验
+        let (h, x1) = (1e-6, 0.3);
+        let lo = model.ln_gamma(&[x1 - h, 1.0 - x1 + h], 340.0).unwrap();
+        let hi = model.ln_gamma(&[x1 + h, 1.0 - x1 - h], 340.0).unwrap();
+        let gd = x1 * (hi[0] - lo[0]) + (1.0 - x1) * (hi[1] - lo[1]);
+        assert!(gd.abs() < 1e-8, "{}", gd);
+
+        // 同一主基团的烷烃混合物没有剩余项
+        let hexane: GroupCounts = vec![(1, 2.0), (2, 4.0)];
+        let heptane: GroupCounts = vec![(1, 2.0), (2, 5.0)];
+        let alkanes = Unifac::new(&[hexane, heptane]).unwrap();
+        let lg = alkanes.ln_gamma(&[0.5, 0.5], 300.0).unwrap();
+        assert!(lg.iter().all(|v| v.abs() < 0.01));
+
+        assert!(Unifac::new(&[vec![(999, 1.0)]]).is_err());
+    }
+}
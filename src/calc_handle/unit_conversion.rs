@@ -0,0 +1,415 @@
+use std::collections::{BTreeMap, HashMap};
+
+// ======================================
+// 单位换算：同一单位类别内 SI 值 = 数值 × factor + offset
+// 内置常用类别，配置库 conf_unit_conversion_entity 中的数据可覆盖或扩展
+// ======================================
+
+/// 单位换算系数
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct UnitFactor {
+    pub factor: f64,
+    pub offset: f64,
+}
+
+impl UnitFactor {
+    pub fn si_value(self, value: f64) -> f64 {
+        value * self.factor + self.offset
+    }
+
+    pub fn unit_value(self, si_value: f64) -> f64 {
+        (si_value - self.offset) / self.factor
+    }
+}
+
+/// 统一单位写法（全角符号、上标等）
+pub fn normalize_unit(unit: &str) -> String {
+    unit.trim()
+        .replace('℃', "°C")
+        .replace('℉', "°F")
+        .replace('³', "3")
+        .replace('²', "2")
+        .replace('·', "*")
+}
+
+const GAUGE_OFFSET: f64 = 101_325.0;
+
+/// (单位, factor, offset)
+type UnitEntry = (&'static str, f64, f64);
+
+/// 内置单位表：(类别, [(单位, factor, offset)])，第一项为 SI 基准单位
+const BUILTIN_UNITS: &[(&str, &[UnitEntry])] = &[
+    (
+        "temperature",
+        &[
+            ("K", 1.0, 0.0),
+            ("°C", 1.0, 273.15),
+            ("°F", 5.0 / 9.0, 273.15 - 32.0 * 5.0 / 9.0),
+            ("R", 5.0 / 9.0, 0.0),
+        ],
+    ),
+    (
+        "temperature_difference",
+        &[
+            ("ΔK", 1.0, 0.0),
+            ("Δ°C", 1.0, 0.0),
+            ("Δ°F", 5.0 / 9.0, 0.0),
+            ("ΔR", 5.0 / 9.0, 0.0),
+        ],
+    ),
+    (
+        "pressure",
+        &[
+            ("Pa", 1.0, 0.0),
+            ("kPa", 1e3, 0.0),
+            ("MPa", 1e6, 0.0),
+            ("bar", 1e5, 0.0),
+            ("atm", 101_325.0, 0.0),
+            ("psi", 6_894.757_293_168, 0.0),
+            ("mmHg", 133.322_387_415, 0.0),
+            ("kgf/cm2", 98_066.5, 0.0),
+            ("Pa(g)", 1.0, GAUGE_OFFSET),
+            ("kPa(g)", 1e3, GAUGE_OFFSET),
+            ("MPa(g)", 1e6, GAUGE_OFFSET),
+            ("bar(g)", 1e5, GAUGE_OFFSET),
+            ("psig", 6_894.757_293_168, GAUGE_OFFSET),
+        ],
+    ),
+    (
+        "mass_flow",
+        &[
+            ("kg/s", 1.0, 0.0),
+            ("kg/h", 1.0 / 3600.0, 0.0),
+            ("kg/min", 1.0 / 60.0, 0.0),
+            ("g/s", 1e-3, 0.0),
+            ("t/h", 1000.0 / 3600.0, 0.0),
+            ("lb/h", 0.453_592_37 / 3600.0, 0.0),
+        ],
+    ),
+    (
+        "molar_flow",
+        &[
+            ("mol/s", 1.0, 0.0),
+            ("kmol/s", 1e3, 0.0),
+            ("kmol/h", 1e3 / 3600.0, 0.0),
+            ("mol/h", 1.0 / 3600.0, 0.0),
+            ("kmol/min", 1e3 / 60.0, 0.0),
+        ],
+    ),
+    (
+        "volume_flow",
+        &[
+            ("m3/s", 1.0, 0.0),
+            ("m3/h", 1.0 / 3600.0, 0.0),
+            ("m3/min", 1.0 / 60.0, 0.0),
+            ("L/s", 1e-3, 0.0),
+            ("L/min", 1e-3 / 60.0, 0.0),
+            ("L/h", 1e-3 / 3600.0, 0.0),
+        ],
+    ),
+    (
+        "energy",
+        &[
+            ("J", 1.0, 0.0),
+            ("kJ", 1e3, 0.0),
+            ("MJ", 1e6, 0.0),
+            ("kcal", 4_184.0, 0.0),
+            ("kWh", 3.6e6, 0.0),
+            ("Btu", 1_055.055_852_62, 0.0),
+        ],
+    ),
+    (
+        "power",
+        &[
+            ("W", 1.0, 0.0),
+            ("kW", 1e3, 0.0),
+            ("MW", 1e6, 0.0),
+            ("kJ/h", 1e3 / 3600.0, 0.0),
+            ("MJ/h", 1e6 / 3600.0, 0.0),
+            ("kcal/h", 4_184.0 / 3600.0, 0.0),
+            ("Btu/h", 1_055.055_852_62 / 3600.0, 0.0),
+        ],
+    ),
+    (
+        "length",
+        &[
+            ("m", 1.0, 0.0),
+            ("mm", 1e-3, 0.0),
+            ("cm", 1e-2, 0.0),
+            ("km", 1e3, 0.0),
+            ("in", 0.0254, 0.0),
+            ("ft", 0.3048, 0.0),
+        ],
+    ),
+    (
+        "area",
+        &[
+            ("m2", 1.0, 0.0),
+            ("cm2", 1e-4, 0.0),
+            ("mm2", 1e-6, 0.0),
+            ("ft2", 0.092_903_04, 0.0),
+        ],
+    ),
+    (
+        "volume",
+        &[
+            ("m3", 1.0, 0.0),
+            ("L", 1e-3, 0.0),
+            ("mL", 1e-6, 0.0),
+            ("ft3", 0.028_316_846_592, 0.0),
+        ],
+    ),
+    (
+        "mass",
+        &[
+            ("kg", 1.0, 0.0),
+            ("g", 1e-3, 0.0),
+            ("t", 1e3, 0.0),
+            ("lb", 0.453_592_37, 0.0),
+        ],
+    ),
+    (
+        "time",
+        &[
+            ("s", 1.0, 0.0),
+            ("min", 60.0, 0.0),
+            ("h", 3600.0, 0.0),
+            ("d", 86_400.0, 0.0),
+        ],
+    ),
+    (
+        "density",
+        &[
+            ("kg/m3", 1.0, 0.0),
+            ("g/cm3", 1e3, 0.0),
+            ("g/L", 1.0, 0.0),
+            ("lb/ft3", 16.018_463_373_96, 0.0),
+        ],
+    ),
+    (
+        "molar_enthalpy",
+        &[
+            ("J/mol", 1.0, 0.0),
+            ("kJ/mol", 1e3, 0.0),
+            ("kJ/kmol", 1.0, 0.0),
+            ("J/kmol", 1e-3, 0.0),
+            ("kcal/kmol", 4.184, 0.0),
+        ],
+    ),
+    (
+        "mass_enthalpy",
+        &[
+            ("J/kg", 1.0, 0.0),
+            ("kJ/kg", 1e3, 0.0),
+            ("kcal/kg", 4_184.0, 0.0),
+            ("Btu/lb", 2_326.0, 0.0),
+        ],
+    ),
+    (
+        "fraction",
+        &[("1", 1.0, 0.0), ("%", 0.01, 0.0), ("ppm", 1e-6, 0.0)],
+    ),
+];
+
+/// 单位注册表：类别 → 单位 → 换算系数
+#[derive(Clone, Debug, Default)]
+pub struct UnitRegistry {
+    categories: BTreeMap<String, HashMap<String, UnitFactor>>,
+}
+
+impl UnitRegistry {
+    /// 仅包含内置单位
+    pub fn builtin() -> Self {
+        let mut registry = Self::default();
+        for (category, units) in BUILTIN_UNITS {
+            for (unit, factor, offset) in units.iter() {
+                registry.insert(
+                    category,
+                    unit,
+                    UnitFactor {
+                        factor: *factor,
+                        offset: *offset,
+                    },
+                );
+            }
+        }
+        registry
+    }
+
+    /// 添加或覆盖一个单位
+    pub fn insert(&mut self, category: &str, unit: &str, factor: UnitFactor) {
+        self.categories
+            .entry(category.trim().to_string())
+            .or_default()
+            .insert(normalize_unit(unit), factor);
+    }
+
+    pub fn has_category(&self, category: &str) -> bool {
+        self.categories.contains_key(category.trim())
+    }
+
+    fn lookup(&self, category: &str, unit: &str) -> Result<UnitFactor, String> {
+        let units = self
+            .categories
+            .get(category.trim())
+            .ok_or_else(|| format!("未知的单位类别: {}", category))?;
+        units
+            .get(&normalize_unit(unit))
+            .copied()
+            .ok_or_else(|| format!("单位 {} 不属于类别 {}", unit, category))
+    }
+
+    /// 包含该单位的全部类别
+    pub fn categories_of(&self, unit: &str) -> Vec<&str> {
+        let unit = normalize_unit(unit);
+        self.categories
+            .iter()
+            .filter(|(_, units)| units.contains_key(&unit))
+            .map(|(c, _)| c.as_str())
+            .collect()
+    }
+
+    /// 确定两个单位共同所属的类别；未指定类别且存在多个候选时，换算结果一致才视为确定
+    fn resolve_category(
+        &self,
+        from: &str,
+        to: &str,
+        category: Option<&str>,
+    ) -> Result<String, String> {
+        if let Some(c) = category.filter(|c| !c.trim().is_empty()) {
+            return Ok(c.trim().to_string());
+        }
+        let from_categories = self.categories_of(from);
+        if from_categories.is_empty() {
+            return Err(format!("未知单位: {}", from));
+        }
+        let to_categories = self.categories_of(to);
+        if to_categories.is_empty() {
+            return Err(format!("未知单位: {}", to));
+        }
+        let shared: Vec<&str> = from_categories
+            .iter()
+            .copied()
+            .filter(|c| to_categories.contains(c))
+            .collect();
+        match shared.as_slice() {
+            [] => Err(format!(
+                "单位量纲不一致: {}（{}）无法换算为 {}（{}）",
+                from,
+                from_categories.join("/"),
+                to,
+                to_categories.join("/")
+            )),
+            [only] => Ok(only.to_string()),
+            [first, rest @ ..] => {
+                let probe = |c: &str| -> Result<(f64, f64), String> {
+                    let (a, b) = (self.lookup(c, from)?, self.lookup(c, to)?);
+                    Ok((b.unit_value(a.si_value(0.0)), b.unit_value(a.si_value(1.0))))
+                };
+                let expected = probe(first)?;
+                for c in rest {
+                    let got = probe(c)?;
+                    if !approx_eq(got.0, expected.0) || !approx_eq(got.1, expected.1) {
+                        return Err(format!(
+                            "单位 {} → {} 属于多个类别（{}），请指定类别",
+                            from,
+                            to,
+                            shared.join("/")
+                        ));
+                    }
+                }
+                Ok(first.to_string())
+            }
+        }
+    }
+
+    /// 单位换算
+    pub fn convert(
+        &self,
+        value: f64,
+        from: &str,
+        to: &str,
+        category: Option<&str>,
+    ) -> Result<f64, String> {
+        if normalize_unit(from) == normalize_unit(to) && category.is_none() {
+            return Ok(value);
+        }
+        let category = self.resolve_category(from, to, category)?;
+        let (a, b) = (self.lookup(&category, from)?, self.lookup(&category, to)?);
+        Ok(b.unit_value(a.si_value(value)))
+    }
+
+    /// 由 SI 基准单位换算为指定单位；类别不在注册表中时按单位本身推断类别
+    pub fn convert_from_si(&self, value: f64, to: &str, category: &str) -> Result<f64, String> {
+        Ok(self.factor_of(to, category)?.unit_value(value))
+    }
+
+    /// 由指定单位换算为 SI 基准单位
+    pub fn convert_to_si(&self, value: f64, from: &str, category: &str) -> Result<f64, String> {
+        Ok(self.factor_of(from, category)?.si_value(value))
+    }
+
+    fn factor_of(&self, unit: &str, category: &str) -> Result<UnitFactor, String> {
+        if self.has_category(category) {
+            return self.lookup(category, unit);
+        }
+        let candidates = self.categories_of(unit);
+        let Some(first) = candidates.first() else {
+            return Err(format!("未知单位: {}", unit));
+        };
+        let factor = self.lookup(first, unit)?;
+        for c in &candidates[1..] {
+            let other = self.lookup(c, unit)?;
+            if !approx_eq(other.factor, factor.factor) || !approx_eq(other.offset, factor.offset) {
+                return Err(format!(
+                    "单位 {} 属于多个类别（{}），请在配置中为类别 {} 设置换算系数",
+                    unit,
+                    candidates.join("/"),
+                    category
+                ));
+            }
+        }
+        Ok(factor)
+    }
+}
+
+fn approx_eq(a: f64, b: f64) -> bool {
+    (a - b).abs() <= 1e-12 * a.abs().max(b.abs()).max(1.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_convert_and_dimension_check() {
+        let r = UnitRegistry::builtin();
+        let close = |a: f64, b: f64| (a - b).abs() < 1e-9 * b.abs().max(1.0);
+
+        assert!(close(r.convert(100.0, "°C", "°F", None).unwrap(), 212.0));
+        assert!(close(
+            r.convert(0.0, "℃", "K", Some("temperature")).unwrap(),
+            273.15
+        ));
+        assert!(close(r.convert(10.0, "Δ°C", "ΔK", None).unwrap(), 10.0));
+        // 指定类别时单位必须属于该类别
+        assert!(
+            r.convert(10.0, "°C", "K", Some("temperature_difference"))
+                .is_err()
+        );
+        assert!(close(r.convert(1.0, "bar", "kPa", None).unwrap(), 100.0));
+        assert!(close(
+            r.convert(0.0, "kPa(g)", "kPa", None).unwrap(),
+            101.325
+        ));
+        assert!(close(r.convert(3600.0, "kg/h", "kg/s", None).unwrap(), 1.0));
+
+        assert!(r.convert(1.0, "kg/h", "kPa", None).is_err());
+        assert!(r.convert(1.0, "furlong", "m", None).is_err());
+
+        assert!(close(
+            r.convert_from_si(101_325.0, "atm", "unknown_code").unwrap(),
+            1.0
+        ));
+    }
+}
@@ -37,6 +37,56 @@ impl fmt::Display for SyncError {
     }
 }
 
+/// 快照数据读取错误：区分数据库错误与数据损坏，损坏时不再返回空字符串
+#[derive(Debug)]
+pub enum ShutterDataError {
+    Db(DbErr),                 // 数据库读写失败
+    ChecksumMismatch,          // 存储的校验和与数据不一致
+    Decompress(String),        // zstd 解压失败
+    InvalidUtf8,               // 解压结果不是 UTF-8 文本
+    Manifest(String),          // 数据块清单无法解析
+    MissingChunk(String),      // 清单引用的数据块不存在
+    CorruptedChunk(String),    // 数据块内容与哈希不符
+    MissingDictionary(String), // 数据块使用的压缩字典不存在
+}
+
+impl ShutterDataError {
+    /// 是否为数据损坏（而非数据库访问失败）
+    pub fn is_corruption(&self) -> bool {
+        !matches!(self, ShutterDataError::Db(_))
+    }
+}
+
+impl fmt::Display for ShutterDataError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ShutterDataError::Db(e) => write!(f, "数据库错误：{}", e),
+            ShutterDataError::ChecksumMismatch => write!(f, "校验和不一致"),
+            ShutterDataError::Decompress(e) => write!(f, "解压失败：{}", e),
+            ShutterDataError::InvalidUtf8 => write!(f, "数据不是有效的 UTF-8 文本"),
+            ShutterDataError::Manifest(e) => write!(f, "数据块清单损坏：{}", e),
+            ShutterDataError::MissingChunk(h) => write!(f, "缺少数据块 {}", h),
+            ShutterDataError::CorruptedChunk(h) => write!(f, "数据块 {} 已损坏", h),
+            ShutterDataError::MissingDictionary(id) => write!(f, "缺少压缩字典 {}", id),
+        }
+    }
+}
+
+impl From<DbErr> for ShutterDataError {
+    fn from(err: DbErr) -> Self {
+        ShutterDataError::Db(err)
+    }
+}
+
+impl From<ShutterDataError> for DbErr {
+    fn from(err: ShutterDataError) -> Self {
+        match err {
+            ShutterDataError::Db(e) => e,
+            other => DbErr::Custom(format!("快照数据损坏：{}", other)),
+        }
+    }
+}
+
 /// 将 sea_orm::DbErr 转换为 napi::Error，以便在 JS 中抛出异常
 pub fn handle_db_err(e: DbErr) -> Error {
     // 简化错误处理，使用 Debug 格式输出 DbErr
@@ -1,5 +1,6 @@
 #![deny(clippy::all)]
 pub mod api_database;
+mod calc_handle;
 mod error_handle;
 pub mod service_database;
 mod tool_handle;
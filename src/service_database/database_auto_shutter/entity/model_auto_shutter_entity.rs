@@ -36,6 +36,10 @@ pub struct Model {
 
     #[sea_orm(column_name = "state_desc")]
     pub state_desc: Option<String>,
+
+    /// 固定的快照不会被保留策略删除或被轮转覆盖
+    #[sea_orm(default = false)]
+    pub pinned: bool,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -0,0 +1,24 @@
+use crate::service_database::shutter_chunk::migration::*;
+use sea_orm_migration::prelude::*;
+
+// 自动快照内容寻址存储：数据块表、字典表，数据表增加 format 列
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        create_chunk_tables(manager).await?;
+        add_format_column(manager, ModelAutoShutterDataEntity::Table).await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        drop_format_column(manager, ModelAutoShutterDataEntity::Table).await?;
+        drop_chunk_tables(manager).await
+    }
+}
+
+#[derive(Iden)]
+enum ModelAutoShutterDataEntity {
+    Table,
+}
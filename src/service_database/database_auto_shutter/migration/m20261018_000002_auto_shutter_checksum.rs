@@ -0,0 +1,22 @@
+use crate::service_database::shutter_chunk::migration::*;
+use sea_orm_migration::prelude::*;
+
+// 数据表增加 checksum 列，用于快照完整性校验
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        add_checksum_column(manager, ModelAutoShutterDataEntity::Table).await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        drop_checksum_column(manager, ModelAutoShutterDataEntity::Table).await
+    }
+}
+
+#[derive(Iden)]
+enum ModelAutoShutterDataEntity {
+    Table,
+}
@@ -0,0 +1,41 @@
+use sea_orm_migration::prelude::*;
+
+// 自动快照主表增加 pinned 列：固定的快照不会被保留策略删除或被轮转覆盖
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(ModelAutoShutterEntity::Table)
+                    .add_column(
+                        ColumnDef::new(ModelAutoShutterEntity::Pinned)
+                            .boolean()
+                            .not_null()
+                            .default(false),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(ModelAutoShutterEntity::Table)
+                    .drop_column(ModelAutoShutterEntity::Pinned)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(Iden)]
+enum ModelAutoShutterEntity {
+    Table,
+    Pinned,
+}
@@ -1,12 +1,20 @@
 pub use sea_orm_migration::prelude::*;
 
 mod m20260107_000001_create_tables;
+mod m20261018_000001_auto_shutter_chunks;
+mod m20261018_000002_auto_shutter_checksum;
+mod m20261018_000003_auto_shutter_pinned;
 
 pub struct Migrator;
 
 #[async_trait::async_trait]
 impl MigratorTrait for Migrator {
     fn migrations() -> Vec<Box<dyn MigrationTrait>> {
-        vec![Box::new(m20260107_000001_create_tables::Migration)]
+        vec![
+            Box::new(m20260107_000001_create_tables::Migration),
+            Box::new(m20261018_000001_auto_shutter_chunks::Migration),
+            Box::new(m20261018_000002_auto_shutter_checksum::Migration),
+            Box::new(m20261018_000003_auto_shutter_pinned::Migration),
+        ]
     }
 }
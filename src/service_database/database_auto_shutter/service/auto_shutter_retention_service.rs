@@ -0,0 +1,137 @@
+use crate::service_database::database_auto_shutter::db_auto_shutter_connection::get_auto_shutter_db;
+use crate::service_database::database_auto_shutter::entity::model_auto_shutter_entity::{
+    Column as MainColumn, Entity as MainEntity,
+};
+use crate::service_database::database_auto_shutter::service::auto_shutter_service::delete_model_auto_shutter_entities;
+use crate::service_database::database_business::service::model_config::model_config_service::{
+    AutoShutterParams, get_model_config_auto_shutter_config,
+};
+use crate::tool_handle::retention_tool::{
+    RetentionItem, RetentionPolicy, RetentionTier, expired_items,
+};
+use chrono::Utc;
+use napi_derive::napi;
+use once_cell::sync::Lazy;
+use sea_orm::{ColumnTrait, DbErr, EntityTrait, QueryFilter, QuerySelect};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::time::Duration;
+use tokio::task::JoinHandle;
+use tokio::time::MissedTickBehavior;
+
+// ======================================
+// 自动快照保留策略
+// 按模型配置（AutoShutterParams.retention、auto_count）删除过期的自动快照，固定的快照不受影响。
+// 后台任务在插件内按固定周期执行，不依赖前端调用更新
+// ======================================
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[napi(object, namespace = "autoShutter")]
+pub struct AutoShutterRetentionResult {
+    pub model_id: String,
+    pub deleted: u32,
+    /// 执行后剩余的快照数（含固定的）
+    pub kept: u32,
+}
+
+/// 各模型正在运行的保留策略任务
+static RETENTION_TASKS: Lazy<std::sync::Mutex<HashMap<String, JoinHandle<()>>>> =
+    Lazy::new(|| std::sync::Mutex::new(HashMap::new()));
+
+const MINUTE_MS: i64 = 60_000;
+
+fn to_policy(params: &AutoShutterParams) -> RetentionPolicy {
+    let max_count = (params.auto_count > 0).then_some(params.auto_count as usize);
+    match &params.retention {
+        Some(retention) => RetentionPolicy {
+            keep_all_ms: retention.keep_all_minutes as i64 * MINUTE_MS,
+            tiers: retention
+                .tiers
+                .iter()
+                .map(|t| RetentionTier {
+                    max_age_ms: t.max_age_minutes as i64 * MINUTE_MS,
+                    interval_ms: t.interval_minutes as i64 * MINUTE_MS,
+                })
+                .collect(),
+            max_count,
+        },
+        None => RetentionPolicy {
+            max_count,
+            ..Default::default()
+        },
+    }
+}
+
+/// 按模型当前配置执行一次保留策略
+pub async fn enforce_auto_shutter_retention(
+    model_id: String,
+) -> Result<AutoShutterRetentionResult, DbErr> {
+    let params = get_model_config_auto_shutter_config(model_id.clone()).await?;
+    let db = get_auto_shutter_db().await?;
+    let rows: Vec<(i32, i64, bool)> = MainEntity::find()
+        .select_only()
+        .column(MainColumn::Id)
+        .column(MainColumn::UpdateAt)
+        .column(MainColumn::Pinned)
+        .filter(MainColumn::ModelId.eq(model_id.clone()))
+        .into_tuple()
+        .all(db)
+        .await?;
+    let items: Vec<RetentionItem> = rows
+        .into_iter()
+        .map(|(id, timestamp_ms, pinned)| RetentionItem {
+            id,
+            timestamp_ms,
+            pinned,
+        })
+        .collect();
+
+    let expired = expired_items(Utc::now().timestamp_millis(), &items, &to_policy(&params));
+    let deleted = if expired.is_empty() {
+        0
+    } else {
+        delete_model_auto_shutter_entities(expired, model_id.clone()).await?
+    };
+    Ok(AutoShutterRetentionResult {
+        model_id,
+        deleted,
+        kept: items.len() as u32 - deleted,
+    })
+}
+
+/// 启动模型的后台保留策略任务，每 interval_seconds 秒执行一次；
+/// 已有任务时替换为新的周期。返回是否替换了已有任务
+pub async fn start_auto_shutter_retention_task(
+    model_id: String,
+    interval_seconds: u32,
+) -> Result<bool, DbErr> {
+    let period = Duration::from_secs(interval_seconds.max(1) as u64);
+    let task_model_id = model_id.clone();
+    let handle = tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(period);
+        ticker.set_missed_tick_behavior(MissedTickBehavior::Skip);
+        loop {
+            ticker.tick().await;
+            if let Err(e) = enforce_auto_shutter_retention(task_model_id.clone()).await {
+                eprintln!(
+                    "[AutoShutter] 模型 {} 执行保留策略失败: {}",
+                    task_model_id, e
+                );
+            }
+        }
+    });
+    let previous = RETENTION_TASKS.lock().unwrap().insert(model_id, handle);
+    if let Some(previous) = &previous {
+        previous.abort();
+    }
+    Ok(previous.is_some())
+}
+
+/// 停止模型的后台保留策略任务，返回是否有任务被停止
+pub async fn stop_auto_shutter_retention_task(model_id: String) -> Result<bool, DbErr> {
+    let handle = RETENTION_TASKS.lock().unwrap().remove(&model_id);
+    if let Some(handle) = &handle {
+        handle.abort();
+    }
+    Ok(handle.is_some())
+}
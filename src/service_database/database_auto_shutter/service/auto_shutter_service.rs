@@ -1,37 +1,38 @@
+use crate::service_database::database_auto_shutter::service::auto_shutter_retention_service::enforce_auto_shutter_retention;
+use crate::service_database::database_business::service::model_config::model_config_service::get_model_config_auto_shutter_config;
+use crate::service_database::database_shutter::service::shutter_integrity_service::{
+    ShutterDamage, ShutterRecoveryResult, ShutterSource,
+};
+use crate::service_database::database_shutter::service::shutter_transfer_service::ShutterTransferMeta;
 use crate::{
+    error_handle::err_handle::ShutterDataError,
     service_database::database_auto_shutter::db_auto_shutter_connection::get_auto_shutter_db,
+    service_database::shutter_chunk::chunk_store::{
+        RawSnapshot, ShutterChunkStats, ShutterDictInfo, chunk_stats, export_raw_snapshot,
+        import_raw_chunks, load_snapshot, release_objects, store_snapshot, train_dictionary,
+    },
+    service_database::shutter_chunk::snapshot_table::{
+        check_snapshot_row, compact_snapshot_storage, verify_snapshot_rows,
+    },
     tool_handle::time_tool::integer_to_string,
 };
 use chrono::Utc;
 use napi_derive::napi;
 use sea_orm::{
-    ActiveModelTrait, ColumnTrait, DbErr, EntityTrait, FromQueryResult, IntoActiveModel, Order,
-    PaginatorTrait, QueryFilter, QueryOrder, QuerySelect, Set, TransactionError, TransactionTrait,
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, DbErr, EntityTrait, FromQueryResult,
+    IntoActiveModel, Order, PaginatorTrait, QueryFilter, QueryOrder, QuerySelect, Set,
+    TransactionError, TransactionTrait, sea_query::Expr,
 };
 use serde::{Deserialize, Serialize};
 
 // 引入拆分后的两个实体
 use crate::service_database::database_auto_shutter::entity::model_auto_shutter_data_entity::{
-    ActiveModel as DataActiveModel, Entity as DataEntity,
+    ActiveModel as DataActiveModel, Column as DataColumn, Entity as DataEntity,
 };
 use crate::service_database::database_auto_shutter::entity::model_auto_shutter_entity::{
-    ActiveModel as MainActiveModel, Column as MainColumn, Entity as MainEntity,
+    ActiveModel as MainActiveModel, Column as MainColumn, Entity as MainEntity, Model as MainModel,
 };
 
-// ======================================
-// 内部工具：压缩与解压
-// ======================================
-use zstd::{decode_all, encode_all};
-fn compress_data(data: &str) -> Vec<u8> {
-    // 压缩级别 3 是性能和压缩率的最佳平衡点
-    encode_all(data.as_bytes(), 3).unwrap_or_default()
-}
-
-fn decompress_data(data: &[u8]) -> String {
-    let decoded = decode_all(data).unwrap_or_default();
-    String::from_utf8(decoded).unwrap_or_default()
-}
-
 // ======================================
 // DTO 定义
 // ======================================
@@ -67,6 +68,7 @@ pub struct AutoShutterListItem {
     pub update_at: String,
     pub sim_time: String,
     pub base_state_code: String,
+    pub pinned: bool,
 }
 
 #[derive(Clone, Debug, FromQueryResult)]
@@ -75,6 +77,7 @@ struct AutoShutterSelectModel {
     pub update_at: i64,
     pub sim_time: String,
     pub base_state_code: String,
+    pub pinned: bool,
 }
 
 // ======================================
@@ -99,28 +102,7 @@ pub async fn read_one_model_auto_shutter_entity_cache(
     let db = get_auto_shutter_db().await?;
 
     db.transaction::<_, i32, DbErr>(|txn| {
-        Box::pin(async move {
-            // 1. 插入主表元数据
-            let main_item = MainActiveModel {
-                model_id: Set(model_id),
-                update_at: Set(Utc::now().timestamp_millis()),
-                sim_time: Set(data.sim_time),
-                base_state_code: Set(data.base_state_code),
-                ..Default::default()
-            };
-            let main_res = MainEntity::insert(main_item).exec(txn).await?;
-            let new_id = main_res.last_insert_id;
-
-            // 2. 插入大数据表 (压缩后存入)
-            let data_item = DataActiveModel {
-                id: Set(new_id),
-                objects: Set(compress_data(&data.objects)),
-                sysvars: Set(compress_data(&data.sysvars)),
-            };
-            DataEntity::insert(data_item).exec(txn).await?;
-
-            Ok(new_id)
-        })
+        Box::pin(async move { insert_auto_shutter(txn, data, model_id).await })
     })
     .await
     .map_err(|e| match e {
@@ -129,19 +111,58 @@ pub async fn read_one_model_auto_shutter_entity_cache(
     })
 }
 
+/// 追加一条自动快照，返回新记录 ID；由调用方控制事务
+async fn insert_auto_shutter<C: ConnectionTrait>(
+    db: &C,
+    data: AutoShutterData,
+    model_id: String,
+) -> Result<i32, DbErr> {
+    let stored = store_snapshot(db, &model_id, &data.objects, &data.sysvars).await?;
+    // 1. 插入主表元数据
+    let main_item = MainActiveModel {
+        model_id: Set(model_id),
+        update_at: Set(Utc::now().timestamp_millis()),
+        sim_time: Set(data.sim_time),
+        base_state_code: Set(data.base_state_code),
+        ..Default::default()
+    };
+    let new_id = MainEntity::insert(main_item).exec(db).await?.last_insert_id;
+
+    // 2. 插入大数据表 (压缩后存入)
+    let data_item = DataActiveModel {
+        id: Set(new_id),
+        objects: Set(stored.objects),
+        sysvars: Set(stored.sysvars),
+        format: Set(stored.format),
+        checksum: Set(stored.checksum),
+    };
+    DataEntity::insert(data_item).exec(db).await?;
+
+    Ok(new_id)
+}
+
 /// 更新自动快照 (高并发安全 + 二进制压缩)
 pub async fn update_model_auto_shutter_entity_cache(
     data: AutoShutterData,
     model_id: String,
 ) -> Result<i32, DbErr> {
+    // 配置了保留策略时按时间顺序追加，过期快照由保留策略删除
+    let params = get_model_config_auto_shutter_config(model_id.clone()).await?;
+    if params.retention.is_some() {
+        let id = read_one_model_auto_shutter_entity_cache(data, model_id.clone()).await?;
+        enforce_auto_shutter_retention(model_id).await?;
+        return Ok(id);
+    }
+
     let db = get_auto_shutter_db().await?;
 
     let updated_id = db
         .transaction::<_, i32, DbErr>(|txn| {
             Box::pin(async move {
-                // 找到最旧的一条记录进行覆盖（轮转逻辑）
+                // 找到最旧的一条未固定记录进行覆盖（轮转逻辑）
                 let target_record = MainEntity::find()
-                    .filter(MainColumn::ModelId.eq(model_id))
+                    .filter(MainColumn::ModelId.eq(model_id.clone()))
+                    .filter(MainColumn::Pinned.eq(false))
                     .order_by_asc(MainColumn::UpdateAt)
                     .lock_exclusive()
                     .one(txn)
@@ -157,17 +178,26 @@ pub async fn update_model_auto_shutter_entity_cache(
                     active_main.base_state_code = Set(data.base_state_code);
                     active_main.update(txn).await?;
 
-                    // 2. 更新数据表 (压缩)
+                    // 2. 更新数据表 (压缩)：先写入新数据块再释放被覆盖快照的引用
+                    let old = DataEntity::find_by_id(tid).one(txn).await?;
+                    let stored =
+                        store_snapshot(txn, &model_id, &data.objects, &data.sysvars).await?;
                     let active_data = DataActiveModel {
                         id: Set(tid),
-                        objects: Set(compress_data(&data.objects)),
-                        sysvars: Set(compress_data(&data.sysvars)),
+                        objects: Set(stored.objects),
+                        sysvars: Set(stored.sysvars),
+                        format: Set(stored.format),
+                        checksum: Set(stored.checksum),
                     };
                     DataEntity::update(active_data).exec(txn).await?;
+                    if let Some(old) = old {
+                        release_objects(txn, &model_id, old.format, &old.objects).await?;
+                    }
 
                     Ok(tid)
                 } else {
-                    Err(DbErr::RecordNotFound("未找到快照记录".to_string()))
+                    // 全部快照都已固定，没有可覆盖的记录时追加一条，自动保存不中断
+                    insert_auto_shutter(txn, data, model_id).await
                 }
             })
         })
@@ -181,6 +211,8 @@ pub async fn update_model_auto_shutter_entity_cache(
 }
 
 /// 获取快照列表 (仅查主表，不加载大数据字段，速度极快)
+///
+/// auto_count 只限制未固定的快照，固定的快照全部返回
 pub async fn get_all_model_auto_shutter_entity_list_cache(
     order_flag: String,
     auto_count: u32,
@@ -193,18 +225,36 @@ pub async fn get_all_model_auto_shutter_entity_list_cache(
         Order::Desc
     };
 
-    let result = MainEntity::find()
-        .select_only()
-        .column(MainColumn::Id)
-        .column(MainColumn::UpdateAt)
-        .column(MainColumn::SimTime)
-        .column(MainColumn::BaseStateCode)
-        .filter(MainColumn::ModelId.eq(model_id))
-        .order_by(MainColumn::UpdateAt, order)
+    let query = || {
+        MainEntity::find()
+            .select_only()
+            .column(MainColumn::Id)
+            .column(MainColumn::UpdateAt)
+            .column(MainColumn::SimTime)
+            .column(MainColumn::BaseStateCode)
+            .column(MainColumn::Pinned)
+            .filter(MainColumn::ModelId.eq(model_id.clone()))
+            .order_by(MainColumn::UpdateAt, order.clone())
+    };
+    let mut result = query()
+        .filter(MainColumn::Pinned.eq(false))
         .limit(auto_count as u64)
         .into_model::<AutoShutterSelectModel>()
         .all(db)
         .await?;
+    result.extend(
+        query()
+            .filter(MainColumn::Pinned.eq(true))
+            .into_model::<AutoShutterSelectModel>()
+            .all(db)
+            .await?,
+    );
+    // 两部分合并后仍按更新时间排序
+    if matches!(order, Order::Asc) {
+        result.sort_by_key(|r| r.update_at);
+    } else {
+        result.sort_by_key(|r| std::cmp::Reverse(r.update_at));
+    }
 
     Ok(result
         .into_iter()
@@ -213,6 +263,7 @@ pub async fn get_all_model_auto_shutter_entity_list_cache(
             update_at: integer_to_string(ele.update_at),
             sim_time: ele.sim_time,
             base_state_code: ele.base_state_code,
+            pinned: ele.pinned,
         })
         .collect())
 }
@@ -233,11 +284,20 @@ pub async fn get_model_auto_shutter_entity_by_id_cache(
 
     match result {
         Some((main, Some(data))) => {
+            let (objects, sysvars) = load_snapshot(
+                db,
+                &main.model_id,
+                data.format,
+                &data.objects,
+                &data.sysvars,
+                &data.checksum,
+            )
+            .await?;
             Ok(FullCacheData {
                 id: main.id,
                 model_id: main.model_id,
-                objects: decompress_data(&data.objects), // 解压
-                sysvars: decompress_data(&data.sysvars), // 解压
+                objects,
+                sysvars,
                 update_at: integer_to_string(main.update_at),
                 sim_time: main.sim_time,
                 base_state_code: main.base_state_code,
@@ -249,3 +309,402 @@ pub async fn get_model_auto_shutter_entity_by_id_cache(
         _ => Err(DbErr::RecordNotFound(format!("ID {} not found", id))),
     }
 }
+
+/// 自动快照归档项（保留原始毫秒时间戳，用于工程导入导出）
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct AutoShutterArchiveItem {
+    pub objects: String,
+    pub sysvars: String,
+    pub update_at: i64,
+    pub sim_time: String,
+    pub base_state_code: String,
+    pub user_name: Option<String>,
+    pub state_index: Option<i32>,
+    pub state_desc: Option<String>,
+    #[serde(default)]
+    pub pinned: bool,
+}
+
+/// 读取模型下全部自动快照（含解压后的数据，用于工程导出）
+pub async fn get_all_model_auto_shutter_entity_full(
+    model_id: String,
+) -> Result<Vec<AutoShutterArchiveItem>, DbErr> {
+    let db = get_auto_shutter_db().await?;
+    load_model_auto_shutters_full(db, &model_id).await
+}
+
+/// 读取模型下全部自动快照并还原数据，任一快照损坏时返回错误并指明是哪一条
+pub async fn load_model_auto_shutters_full<C: ConnectionTrait>(
+    db: &C,
+    model_id: &str,
+) -> Result<Vec<AutoShutterArchiveItem>, DbErr> {
+    let results = MainEntity::find()
+        .filter(MainColumn::ModelId.eq(model_id))
+        .order_by_asc(MainColumn::UpdateAt)
+        .find_also_related(DataEntity)
+        .all(db)
+        .await?;
+
+    let mut list = Vec::with_capacity(results.len());
+    for (main, data) in results {
+        let Some(data) = data else { continue };
+        let (objects, sysvars) = load_snapshot(
+            db,
+            &main.model_id,
+            data.format,
+            &data.objects,
+            &data.sysvars,
+            &data.checksum,
+        )
+        .await
+        .map_err(|e| match e {
+            ShutterDataError::Db(e) => e,
+            e => DbErr::Custom(format!("自动快照 {} 数据损坏：{}", main.id, e)),
+        })?;
+        list.push(AutoShutterArchiveItem {
+            objects,
+            sysvars,
+            update_at: main.update_at,
+            sim_time: main.sim_time,
+            base_state_code: main.base_state_code,
+            user_name: main.user_name,
+            state_index: main.state_index,
+            state_desc: main.state_desc,
+            pinned: main.pinned,
+        });
+    }
+    Ok(list)
+}
+
+/// 批量导入自动快照（工程导入），挂到新的 model_id 下
+pub async fn import_model_auto_shutter_entities(
+    items: Vec<AutoShutterArchiveItem>,
+    model_id: String,
+) -> Result<u32, DbErr> {
+    let db = get_auto_shutter_db().await?;
+
+    db.transaction::<_, u32, DbErr>(|txn| {
+        Box::pin(async move { insert_model_auto_shutters(txn, items, &model_id).await })
+    })
+    .await
+    .map_err(|e| match e {
+        TransactionError::Connection(e) => e,
+        TransactionError::Transaction(e) => e,
+    })
+}
+
+/// 写入一批自动快照并挂到 model_id 下；由调用方控制事务
+pub async fn insert_model_auto_shutters<C: ConnectionTrait>(
+    db: &C,
+    items: Vec<AutoShutterArchiveItem>,
+    model_id: &str,
+) -> Result<u32, DbErr> {
+    let mut count = 0;
+    for item in items {
+        let stored = store_snapshot(db, model_id, &item.objects, &item.sysvars).await?;
+        let main_item = MainActiveModel {
+            model_id: Set(model_id.to_string()),
+            update_at: Set(item.update_at),
+            sim_time: Set(item.sim_time),
+            base_state_code: Set(item.base_state_code),
+            user_name: Set(item.user_name),
+            state_index: Set(item.state_index),
+            state_desc: Set(item.state_desc),
+            pinned: Set(item.pinned),
+            ..Default::default()
+        };
+        let new_id = MainEntity::insert(main_item).exec(db).await?.last_insert_id;
+
+        let data_item = DataActiveModel {
+            id: Set(new_id),
+            objects: Set(stored.objects),
+            sysvars: Set(stored.sysvars),
+            format: Set(stored.format),
+            checksum: Set(stored.checksum),
+        };
+        DataEntity::insert(data_item).exec(db).await?;
+        count += 1;
+    }
+    Ok(count)
+}
+
+/// 把模型下旧格式（整体压缩）的自动快照转存为数据块清单，返回转换的快照数
+pub async fn compact_model_auto_shutter_storage(model_id: String) -> Result<u32, DbErr> {
+    let db = get_auto_shutter_db().await?;
+    compact_snapshot_storage::<DataEntity, _>(db, &model_id).await
+}
+
+/// 以模型已有自动快照数据训练 zstd 字典
+pub async fn train_model_auto_shutter_dictionary(
+    model_id: String,
+    max_size: Option<u32>,
+) -> Result<ShutterDictInfo, DbErr> {
+    let db = get_auto_shutter_db().await?;
+    train_dictionary(db, &model_id, max_size).await
+}
+
+/// 模型自动快照数据块统计
+pub async fn get_model_auto_shutter_chunk_stats(
+    model_id: String,
+) -> Result<ShutterChunkStats, DbErr> {
+    let db = get_auto_shutter_db().await?;
+    chunk_stats(db, &model_id).await
+}
+
+fn full_cache_data(main: MainModel, objects: String, sysvars: String) -> FullCacheData {
+    FullCacheData {
+        id: main.id,
+        model_id: main.model_id,
+        objects,
+        sysvars,
+        update_at: integer_to_string(main.update_at),
+        sim_time: main.sim_time,
+        base_state_code: main.base_state_code,
+        user_name: main.user_name,
+        state_index: main.state_index,
+        state_desc: main.state_desc,
+    }
+}
+
+/// 校验模型下全部自动快照，返回 (检查数, 未记录校验和数, 损坏条目)
+pub async fn verify_model_auto_shutter_entities(
+    model_id: String,
+) -> Result<(u32, u32, Vec<ShutterDamage>), DbErr> {
+    let db = get_auto_shutter_db().await?;
+    let rows = MainEntity::find()
+        .filter(MainColumn::ModelId.eq(model_id.clone()))
+        .order_by_asc(MainColumn::UpdateAt)
+        .find_also_related(DataEntity)
+        .all(db)
+        .await?;
+
+    let (checked, unverified, damaged) =
+        verify_snapshot_rows::<DataEntity, _, _>(db, &model_id, rows).await?;
+    let damaged = damaged
+        .into_iter()
+        .map(|(main, reason)| ShutterDamage {
+            source: ShutterSource::Auto,
+            id: main.id.to_string(),
+            name: None,
+            update_at: integer_to_string(main.update_at),
+            reason,
+        })
+        .collect();
+    Ok((checked, unverified, damaged))
+}
+
+/// 最近的完好自动快照：同一状态优先，其次与 target（毫秒）时间最接近，target 为空时取最新的
+pub async fn find_nearest_good_auto_shutter(
+    model_id: &str,
+    target: Option<i64>,
+    base_state_code: &str,
+    exclude: Option<i32>,
+) -> Result<Option<FullCacheData>, DbErr> {
+    let db = get_auto_shutter_db().await?;
+    let mut candidates = MainEntity::find()
+        .filter(MainColumn::ModelId.eq(model_id))
+        .order_by_desc(MainColumn::UpdateAt)
+        .all(db)
+        .await?;
+    candidates.retain(|m| Some(m.id) != exclude);
+    candidates.sort_by_key(|m| {
+        (
+            m.base_state_code != base_state_code,
+            target.map_or(0, |t| (m.update_at - t).abs()),
+        )
+    });
+
+    for main in candidates {
+        let Some(d) = DataEntity::find_by_id(main.id).one(db).await? else {
+            continue;
+        };
+        if let Ok((objects, sysvars)) =
+            check_snapshot_row::<DataEntity, _>(db, model_id, Some(&d)).await?
+        {
+            return Ok(Some(full_cache_data(main, objects, sysvars)));
+        }
+    }
+    Ok(None)
+}
+
+/// 读取自动快照，损坏时回退到时间最接近的其他完好自动快照
+pub async fn recover_model_auto_shutter_entity(
+    id: i32,
+    model_id: String,
+) -> Result<ShutterRecoveryResult, DbErr> {
+    let db = get_auto_shutter_db().await?;
+    let (main, data) = MainEntity::find_by_id(id)
+        .filter(MainColumn::ModelId.eq(model_id.clone()))
+        .find_also_related(DataEntity)
+        .one(db)
+        .await?
+        .ok_or_else(|| DbErr::RecordNotFound(format!("ID {} not found", id)))?;
+
+    let result = check_snapshot_row::<DataEntity, _>(db, &model_id, data.as_ref()).await?;
+    let reason = match result {
+        Ok((objects, sysvars)) => {
+            return Ok(ShutterRecoveryResult::intact(full_cache_data(
+                main, objects, sysvars,
+            )));
+        }
+        Err(reason) => reason,
+    };
+    let source = find_nearest_good_auto_shutter(
+        &model_id,
+        Some(main.update_at),
+        &main.base_state_code,
+        Some(id),
+    )
+    .await?
+    .ok_or_else(|| {
+        DbErr::Custom(format!(
+            "自动快照 {} 已损坏（{}），且没有可用于恢复的自动快照",
+            id, reason
+        ))
+    })?;
+    Ok(ShutterRecoveryResult::recovered(reason, source))
+}
+
+/// 固定 / 取消固定自动快照，固定的快照不会被保留策略删除或被轮转覆盖
+pub async fn pin_model_auto_shutter_entity(
+    id: i32,
+    model_id: String,
+    pinned: bool,
+) -> Result<bool, DbErr> {
+    let db = get_auto_shutter_db().await?;
+    let res = MainEntity::update_many()
+        .col_expr(MainColumn::Pinned, Expr::value(pinned))
+        .filter(MainColumn::Id.eq(id))
+        .filter(MainColumn::ModelId.eq(model_id))
+        .exec(db)
+        .await?;
+    Ok(res.rows_affected > 0)
+}
+
+/// 批量删除自动快照并释放数据块引用，固定的快照跳过。返回删除数
+pub async fn delete_model_auto_shutter_entities(
+    ids: Vec<i32>,
+    model_id: String,
+) -> Result<u32, DbErr> {
+    let db = get_auto_shutter_db().await?;
+
+    db.transaction::<_, u32, DbErr>(|txn| {
+        Box::pin(async move {
+            let targets: Vec<i32> = MainEntity::find()
+                .select_only()
+                .column(MainColumn::Id)
+                .filter(MainColumn::Id.is_in(ids))
+                .filter(MainColumn::ModelId.eq(model_id.clone()))
+                .filter(MainColumn::Pinned.eq(false))
+                .into_tuple()
+                .all(txn)
+                .await?;
+            // 数据表级联删除，先取出清单再删主表
+            let datas = DataEntity::find()
+                .filter(DataColumn::Id.is_in(targets.clone()))
+                .all(txn)
+                .await?;
+            let res = MainEntity::delete_many()
+                .filter(MainColumn::Id.is_in(targets.clone()))
+                .exec(txn)
+                .await?;
+            for data in datas {
+                match release_objects(txn, &model_id, data.format, &data.objects).await {
+                    // 清单已损坏时无法释放引用，不影响删除
+                    Err(e) if e.is_corruption() => {}
+                    other => other?,
+                }
+            }
+            DataEntity::delete_many()
+                .filter(DataColumn::Id.is_in(targets))
+                .exec(txn)
+                .await?;
+            Ok(res.rows_affected as u32)
+        })
+    })
+    .await
+    .map_err(|e| match e {
+        TransactionError::Connection(e) => e,
+        TransactionError::Transaction(e) => e,
+    })
+}
+
+/// 读出自动快照的元数据与原始压缩数据（不解压），用于复制到手动快照库
+pub async fn export_model_auto_shutter_raw(
+    id: i32,
+    model_id: String,
+) -> Result<(ShutterTransferMeta, RawSnapshot), DbErr> {
+    let db = get_auto_shutter_db().await?;
+    let (main, data) = MainEntity::find_by_id(id)
+        .filter(MainColumn::ModelId.eq(model_id))
+        .find_also_related(DataEntity)
+        .one(db)
+        .await?
+        .ok_or_else(|| DbErr::RecordNotFound(format!("ID {} not found", id)))?;
+    let data = data.ok_or_else(|| DbErr::RecordNotFound(format!("自动快照 {} 缺少数据", id)))?;
+    let raw = export_raw_snapshot(
+        db,
+        &main.model_id,
+        data.format,
+        data.objects,
+        data.sysvars,
+        data.checksum,
+    )
+    .await?;
+    Ok((
+        ShutterTransferMeta {
+            update_at: main.update_at,
+            base_state_code: main.base_state_code,
+            user_name: main.user_name,
+            state_index: main.state_index,
+            state_desc: main.state_desc,
+        },
+        raw,
+    ))
+}
+
+/// 以原始压缩数据新增一条自动快照（连同数据块），返回新 ID
+pub async fn import_model_auto_shutter_raw(
+    model_id: String,
+    meta: ShutterTransferMeta,
+    raw: RawSnapshot,
+    sim_time: String,
+    pinned: bool,
+) -> Result<i32, DbErr> {
+    let db = get_auto_shutter_db().await?;
+
+    db.transaction::<_, i32, DbErr>(|txn| {
+        Box::pin(async move {
+            import_raw_chunks(txn, &raw).await?;
+            let main_item = MainActiveModel {
+                model_id: Set(model_id),
+                update_at: Set(meta.update_at),
+                sim_time: Set(sim_time),
+                base_state_code: Set(meta.base_state_code),
+                user_name: Set(meta.user_name),
+                state_index: Set(meta.state_index),
+                state_desc: Set(meta.state_desc),
+                pinned: Set(pinned),
+                ..Default::default()
+            };
+            let new_id = MainEntity::insert(main_item)
+                .exec(txn)
+                .await?
+                .last_insert_id;
+            let data_item = DataActiveModel {
+                id: Set(new_id),
+                objects: Set(raw.objects),
+                sysvars: Set(raw.sysvars),
+                format: Set(raw.format),
+                checksum: Set(raw.checksum),
+            };
+            DataEntity::insert(data_item).exec(txn).await?;
+            Ok(new_id)
+        })
+    })
+    .await
+    .map_err(|e| match e {
+        TransactionError::Connection(e) => e,
+        TransactionError::Transaction(e) => e,
+    })
+}
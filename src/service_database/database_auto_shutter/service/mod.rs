@@ -1 +1,2 @@
+pub mod auto_shutter_retention_service;
 pub mod auto_shutter_service;
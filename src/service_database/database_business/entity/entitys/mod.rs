@@ -1,48 +1,13 @@
 //! `SeaORM` Entity, @generated by sea-orm-codegen 1.1.19
 
-pub mod prelude;
-
 pub mod model_aiuo_entity;
-pub mod model_compound_all_detail;
-pub mod model_compound_channel;
-pub mod model_compound_henry;
-pub mod model_compound_henry_detail;
-pub mod model_compound_oil;
-pub mod model_config;
 pub mod model_element_entity_connection;
 pub mod model_energy_entity;
-pub mod model_file_comparison_entity;
-pub mod model_fluid_package_binary_nrtl_entity;
-pub mod model_fluid_package_binary_nrtl_rk_entity;
-pub mod model_fluid_package_binary_pr_entity;
-pub mod model_fluid_package_binary_psrk_entity;
-pub mod model_fluid_package_binary_rk_entity;
-pub mod model_fluid_package_binary_srk_entity;
-pub mod model_fluid_package_binary_uniquac_entity;
-pub mod model_fluid_package_binary_wilsion_entity;
-pub mod model_fluid_package_entity;
-pub mod model_global_script_entity;
 pub mod model_graphic_edge_attributes;
 pub mod model_graphic_entity_scriptuo_custom;
 pub mod model_graphic_mark_node_attributes;
 pub mod model_graphic_node_attributes;
-pub mod model_graphic_page_entity;
-pub mod model_initialize_data_in_cold_state;
 pub mod model_logic_entity;
 pub mod model_material_entity;
-pub mod model_model;
 pub mod model_module_entity;
-pub mod model_name_generator;
-pub mod model_pf_model_params;
-pub mod model_pf_var_default_params_entity;
-pub mod model_physical_property_calc_entity;
-pub mod model_reaction_detail;
-pub mod model_reaction_package;
 pub mod model_sensor_entity;
-pub mod model_status_information_entity;
-pub mod model_status_params_entity;
-pub mod model_system_variable;
-pub mod model_trend_chart_config_entity;
-pub mod model_unit_item;
-pub mod model_unit_set;
-pub mod model_variable_choose_entity;
@@ -1,12 +1,17 @@
 //! `SeaORM` Entity, @generated by sea-orm-codegen 1.1.19
 
+use napi_derive::napi;
 use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq)]
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize)]
 #[sea_orm(table_name = "model_graphic_entity_scriptuo_custom")]
+#[napi(object, namespace = "flowsheet", js_name = "FlowsheetScriptUo")]
 pub struct Model {
     #[sea_orm(primary_key, auto_increment = false)]
     pub id: String,
+    // 图元 ID 只在模型内唯一（模型另存为时保持不变），主键为 (id, model_id)
+    #[sea_orm(primary_key, auto_increment = false)]
     pub model_id: String,
     pub name: String,
     pub r#type: String,
@@ -69,4 +69,5 @@ pub enum BinaryCols {
     MaxT,
     IsDefault,
     IsDefaultId,
+    Source,
 }
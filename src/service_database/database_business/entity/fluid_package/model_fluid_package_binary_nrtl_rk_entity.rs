@@ -37,6 +37,9 @@ pub struct Model {
     pub max_t: String,
     pub is_default: i32,
     pub is_default_id: String,
+    /// 参数来源，旧数据缺省为空串（视同 DATABANK）
+    #[serde(default)]
+    pub source: String,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
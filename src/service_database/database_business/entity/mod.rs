@@ -1,4 +1,5 @@
 pub mod component_channel;
+pub mod entitys;
 pub mod fluid_package;
 pub mod model_config;
 pub mod model_util_handle;
@@ -1,6 +1,9 @@
+pub mod model_dcs_tag_entity;
 pub mod model_global_script_entity;
 pub mod model_initialize_data_in_cold_state_entity;
 pub mod model_status_information_entity;
 pub mod model_status_params_entity;
 pub mod model_trend_chart_config_entity;
+pub mod model_undo_history_config_entity;
+pub mod model_undo_history_entity;
 pub mod model_variable_choose_entity;
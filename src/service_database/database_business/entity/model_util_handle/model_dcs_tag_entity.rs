@@ -0,0 +1,29 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+// DCS 位号：量程、工程单位、报警限与图元变量映射，模型内按 tag_name 唯一
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "model_dcs_tag_entity")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    pub model_id: String,
+    pub tag_name: String,
+    pub description: String,
+    pub type_num: i32,
+    pub type_name: String,
+    pub unit: String,
+    pub range_low: Option<f64>,
+    pub range_high: Option<f64>,
+    pub alarm_hh: Option<f64>,
+    pub alarm_h: Option<f64>,
+    pub alarm_l: Option<f64>,
+    pub alarm_ll: Option<f64>,
+    pub graphic_id: String,
+    pub variable_path: String,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,16 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "model_undo_history_config_entity")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub model_id: String,
+    pub max_steps: i32, // 0 表示不限制
+    pub persist: bool,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
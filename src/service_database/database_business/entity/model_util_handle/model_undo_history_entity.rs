@@ -0,0 +1,25 @@
+use chrono::NaiveDateTime;
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "model_undo_history_entity")]
+pub struct Model {
+    // 与缓存库中的撤销记录 id 保持一致，不自增；缓存库 id 重启后重新分配，主键为 (id, model_id)
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: i32,
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub model_id: String,
+    pub step_id: i32,
+    pub table_name: String,
+    pub op_type: String,
+    pub old_data: String,
+    pub new_data: String,
+    pub status: i32, // 0=正常,1=已撤销,2=已重做
+    pub operator_at: NaiveDateTime,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,143 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // 撤销历史持久化表（与缓存库 model_undo_entity_cache 结构一致，id 由缓存库分配，
+        // 重启后会重复使用，因此主键为 (id, model_id)）
+        manager
+            .create_table(
+                Table::create()
+                    .table(ModelUndoHistoryEntity::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(ModelUndoHistoryEntity::Id)
+                            .integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ModelUndoHistoryEntity::ModelId)
+                            .string_len(64)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ModelUndoHistoryEntity::StepId)
+                            .integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(
+                        ColumnDef::new(ModelUndoHistoryEntity::TableName)
+                            .string_len(64)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ModelUndoHistoryEntity::OpType)
+                            .string_len(32)
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(ModelUndoHistoryEntity::OldData).text())
+                    .col(ColumnDef::new(ModelUndoHistoryEntity::NewData).text())
+                    .col(
+                        ColumnDef::new(ModelUndoHistoryEntity::Status)
+                            .integer()
+                            .default(0),
+                    )
+                    .col(
+                        ColumnDef::new(ModelUndoHistoryEntity::OperatorAt)
+                            .date_time()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .primary_key(
+                        Index::create()
+                            .col(ModelUndoHistoryEntity::Id)
+                            .col(ModelUndoHistoryEntity::ModelId),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-undo_history-model_id")
+                    .table(ModelUndoHistoryEntity::Table)
+                    .col(ModelUndoHistoryEntity::ModelId)
+                    .to_owned(),
+            )
+            .await?;
+
+        // 撤销历史配置（按模型），重启后先读取配置再决定是否恢复历史
+        manager
+            .create_table(
+                Table::create()
+                    .table(ModelUndoHistoryConfigEntity::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(ModelUndoHistoryConfigEntity::ModelId)
+                            .string_len(64)
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(ModelUndoHistoryConfigEntity::MaxSteps)
+                            .integer()
+                            .not_null()
+                            .default(10),
+                    )
+                    .col(
+                        ColumnDef::new(ModelUndoHistoryConfigEntity::Persist)
+                            .boolean()
+                            .not_null()
+                            .default(false),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(
+                Table::drop()
+                    .table(ModelUndoHistoryConfigEntity::Table)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .drop_table(
+                Table::drop()
+                    .table(ModelUndoHistoryEntity::Table)
+                    .to_owned(),
+            )
+            .await?;
+        Ok(())
+    }
+}
+
+#[derive(Iden)]
+enum ModelUndoHistoryEntity {
+    Table,
+    Id,
+    ModelId,
+    StepId,
+    TableName,
+    OpType,
+    OldData,
+    NewData,
+    Status,
+    OperatorAt,
+}
+
+#[derive(Iden)]
+enum ModelUndoHistoryConfigEntity {
+    Table,
+    ModelId,
+    MaxSteps,
+    Persist,
+}
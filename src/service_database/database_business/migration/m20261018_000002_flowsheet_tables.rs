@@ -0,0 +1,1226 @@
+use crate::service_database::until_handle::drop_tables;
+use sea_orm_migration::prelude::*;
+
+// 流程图拓扑表：单元（物流、能流、逻辑、传感器、AIUO、模块、自定义脚本）、连接关系与画布图元
+// 图元 ID 只在模型内唯一，所有表以 (id, model_id) 为联合主键
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(ModelAiuoEntity::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(ModelAiuoEntity::Id).string().not_null())
+                    .col(ColumnDef::new(ModelAiuoEntity::ModelId).string().not_null())
+                    .col(
+                        ColumnDef::new(ModelAiuoEntity::Name)
+                            .string()
+                            .not_null()
+                            .default(""),
+                    )
+                    .col(
+                        ColumnDef::new(ModelAiuoEntity::Type)
+                            .string()
+                            .not_null()
+                            .default(""),
+                    )
+                    .col(
+                        ColumnDef::new(ModelAiuoEntity::Actived)
+                            .integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(
+                        ColumnDef::new(ModelAiuoEntity::ConstParams)
+                            .text()
+                            .not_null()
+                            .default(""),
+                    )
+                    .col(
+                        ColumnDef::new(ModelAiuoEntity::Params)
+                            .text()
+                            .not_null()
+                            .default(""),
+                    )
+                    .col(
+                        ColumnDef::new(ModelAiuoEntity::CommIn)
+                            .text()
+                            .not_null()
+                            .default(""),
+                    )
+                    .col(
+                        ColumnDef::new(ModelAiuoEntity::CommOut)
+                            .text()
+                            .not_null()
+                            .default(""),
+                    )
+                    .col(
+                        ColumnDef::new(ModelAiuoEntity::Status)
+                            .integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(
+                        ColumnDef::new(ModelAiuoEntity::CreateAt)
+                            .string()
+                            .not_null()
+                            .default(""),
+                    )
+                    .col(
+                        ColumnDef::new(ModelAiuoEntity::Script)
+                            .text()
+                            .not_null()
+                            .default(""),
+                    )
+                    .primary_key(
+                        Index::create()
+                            .col(ModelAiuoEntity::Id)
+                            .col(ModelAiuoEntity::ModelId),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_table(
+                Table::create()
+                    .table(ModelElementEntityConnection::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(ModelElementEntityConnection::Id)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ModelElementEntityConnection::ModelId)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ModelElementEntityConnection::InPortId)
+                            .string()
+                            .not_null()
+                            .default(""),
+                    )
+                    .col(
+                        ColumnDef::new(ModelElementEntityConnection::OutPortId)
+                            .string()
+                            .not_null()
+                            .default(""),
+                    )
+                    .col(
+                        ColumnDef::new(ModelElementEntityConnection::InPortFrom)
+                            .string()
+                            .not_null()
+                            .default(""),
+                    )
+                    .col(
+                        ColumnDef::new(ModelElementEntityConnection::OutPortTo)
+                            .string()
+                            .not_null()
+                            .default(""),
+                    )
+                    .col(
+                        ColumnDef::new(ModelElementEntityConnection::SortNum)
+                            .integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .primary_key(
+                        Index::create()
+                            .col(ModelElementEntityConnection::Id)
+                            .col(ModelElementEntityConnection::ModelId),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_table(
+                Table::create()
+                    .table(ModelEnergyEntity::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(ModelEnergyEntity::Id).string().not_null())
+                    .col(
+                        ColumnDef::new(ModelEnergyEntity::ModelId)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ModelEnergyEntity::Name)
+                            .string()
+                            .not_null()
+                            .default(""),
+                    )
+                    .col(
+                        ColumnDef::new(ModelEnergyEntity::Type)
+                            .string()
+                            .not_null()
+                            .default(""),
+                    )
+                    .col(
+                        ColumnDef::new(ModelEnergyEntity::Actived)
+                            .integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(
+                        ColumnDef::new(ModelEnergyEntity::Datas)
+                            .text()
+                            .not_null()
+                            .default(""),
+                    )
+                    .col(
+                        ColumnDef::new(ModelEnergyEntity::Status)
+                            .integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(
+                        ColumnDef::new(ModelEnergyEntity::CreateAt)
+                            .string()
+                            .not_null()
+                            .default(""),
+                    )
+                    .primary_key(
+                        Index::create()
+                            .col(ModelEnergyEntity::Id)
+                            .col(ModelEnergyEntity::ModelId),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_table(
+                Table::create()
+                    .table(ModelGraphicEdgeAttributes::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(ModelGraphicEdgeAttributes::Id)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ModelGraphicEdgeAttributes::GraphicPageId)
+                            .string()
+                            .not_null()
+                            .default(""),
+                    )
+                    .col(
+                        ColumnDef::new(ModelGraphicEdgeAttributes::ModelId)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ModelGraphicEdgeAttributes::Shape)
+                            .string()
+                            .not_null()
+                            .default(""),
+                    )
+                    .col(
+                        ColumnDef::new(ModelGraphicEdgeAttributes::Source)
+                            .string()
+                            .not_null()
+                            .default(""),
+                    )
+                    .col(
+                        ColumnDef::new(ModelGraphicEdgeAttributes::Target)
+                            .string()
+                            .not_null()
+                            .default(""),
+                    )
+                    .col(
+                        ColumnDef::new(ModelGraphicEdgeAttributes::SourceId)
+                            .string()
+                            .not_null()
+                            .default(""),
+                    )
+                    .col(
+                        ColumnDef::new(ModelGraphicEdgeAttributes::ZIndex)
+                            .integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(
+                        ColumnDef::new(ModelGraphicEdgeAttributes::Vertices)
+                            .text()
+                            .not_null()
+                            .default(""),
+                    )
+                    .col(
+                        ColumnDef::new(ModelGraphicEdgeAttributes::Label)
+                            .string()
+                            .not_null()
+                            .default(""),
+                    )
+                    .col(
+                        ColumnDef::new(ModelGraphicEdgeAttributes::Data)
+                            .text()
+                            .not_null()
+                            .default(""),
+                    )
+                    .primary_key(
+                        Index::create()
+                            .col(ModelGraphicEdgeAttributes::Id)
+                            .col(ModelGraphicEdgeAttributes::ModelId),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-model_graphic_edge_attributes-page")
+                    .table(ModelGraphicEdgeAttributes::Table)
+                    .col(ModelGraphicEdgeAttributes::ModelId)
+                    .col(ModelGraphicEdgeAttributes::GraphicPageId)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_table(
+                Table::create()
+                    .table(ModelGraphicEntityScriptuoCustom::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(ModelGraphicEntityScriptuoCustom::Id)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ModelGraphicEntityScriptuoCustom::ModelId)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ModelGraphicEntityScriptuoCustom::Name)
+                            .string()
+                            .not_null()
+                            .default(""),
+                    )
+                    .col(
+                        ColumnDef::new(ModelGraphicEntityScriptuoCustom::Type)
+                            .string()
+                            .not_null()
+                            .default(""),
+                    )
+                    .col(
+                        ColumnDef::new(ModelGraphicEntityScriptuoCustom::Actived)
+                            .integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(
+                        ColumnDef::new(ModelGraphicEntityScriptuoCustom::Params)
+                            .text()
+                            .not_null()
+                            .default(""),
+                    )
+                    .col(
+                        ColumnDef::new(ModelGraphicEntityScriptuoCustom::ConstParams)
+                            .text()
+                            .not_null()
+                            .default(""),
+                    )
+                    .col(
+                        ColumnDef::new(ModelGraphicEntityScriptuoCustom::Status)
+                            .integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(
+                        ColumnDef::new(ModelGraphicEntityScriptuoCustom::CreateAt)
+                            .string()
+                            .not_null()
+                            .default(""),
+                    )
+                    .col(
+                        ColumnDef::new(ModelGraphicEntityScriptuoCustom::Script)
+                            .text()
+                            .not_null()
+                            .default(""),
+                    )
+                    .col(
+                        ColumnDef::new(ModelGraphicEntityScriptuoCustom::InputData)
+                            .text()
+                            .not_null()
+                            .default(""),
+                    )
+                    .col(
+                        ColumnDef::new(ModelGraphicEntityScriptuoCustom::OutputData)
+                            .text()
+                            .not_null()
+                            .default(""),
+                    )
+                    .col(
+                        ColumnDef::new(ModelGraphicEntityScriptuoCustom::CommPv)
+                            .text()
+                            .not_null()
+                            .default(""),
+                    )
+                    .primary_key(
+                        Index::create()
+                            .col(ModelGraphicEntityScriptuoCustom::Id)
+                            .col(ModelGraphicEntityScriptuoCustom::ModelId),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_table(
+                Table::create()
+                    .table(ModelGraphicMarkNodeAttributes::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(ModelGraphicMarkNodeAttributes::Id)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ModelGraphicMarkNodeAttributes::GraphicPageId)
+                            .string()
+                            .not_null()
+                            .default(""),
+                    )
+                    .col(
+                        ColumnDef::new(ModelGraphicMarkNodeAttributes::ModelId)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ModelGraphicMarkNodeAttributes::Position)
+                            .string()
+                            .not_null()
+                            .default(""),
+                    )
+                    .col(
+                        ColumnDef::new(ModelGraphicMarkNodeAttributes::Size)
+                            .string()
+                            .not_null()
+                            .default(""),
+                    )
+                    .col(
+                        ColumnDef::new(ModelGraphicMarkNodeAttributes::ZIndex)
+                            .integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(
+                        ColumnDef::new(ModelGraphicMarkNodeAttributes::Shape)
+                            .string()
+                            .not_null()
+                            .default(""),
+                    )
+                    .col(
+                        ColumnDef::new(ModelGraphicMarkNodeAttributes::Attrs)
+                            .string()
+                            .not_null()
+                            .default(""),
+                    )
+                    .primary_key(
+                        Index::create()
+                            .col(ModelGraphicMarkNodeAttributes::Id)
+                            .col(ModelGraphicMarkNodeAttributes::ModelId),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-model_graphic_mark_node_attributes-page")
+                    .table(ModelGraphicMarkNodeAttributes::Table)
+                    .col(ModelGraphicMarkNodeAttributes::ModelId)
+                    .col(ModelGraphicMarkNodeAttributes::GraphicPageId)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_table(
+                Table::create()
+                    .table(ModelGraphicNodeAttributes::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(ModelGraphicNodeAttributes::Id)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ModelGraphicNodeAttributes::GraphicPageId)
+                            .string()
+                            .not_null()
+                            .default(""),
+                    )
+                    .col(
+                        ColumnDef::new(ModelGraphicNodeAttributes::ModelId)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ModelGraphicNodeAttributes::SourceId)
+                            .string()
+                            .not_null()
+                            .default(""),
+                    )
+                    .col(
+                        ColumnDef::new(ModelGraphicNodeAttributes::Position)
+                            .string()
+                            .not_null()
+                            .default(""),
+                    )
+                    .col(
+                        ColumnDef::new(ModelGraphicNodeAttributes::Size)
+                            .string()
+                            .not_null()
+                            .default(""),
+                    )
+                    .col(
+                        ColumnDef::new(ModelGraphicNodeAttributes::Angle)
+                            .integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(
+                        ColumnDef::new(ModelGraphicNodeAttributes::ZIndex)
+                            .integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(
+                        ColumnDef::new(ModelGraphicNodeAttributes::Parent)
+                            .string()
+                            .not_null()
+                            .default(""),
+                    )
+                    .col(
+                        ColumnDef::new(ModelGraphicNodeAttributes::Children)
+                            .text()
+                            .not_null()
+                            .default(""),
+                    )
+                    .col(
+                        ColumnDef::new(ModelGraphicNodeAttributes::Ports)
+                            .text()
+                            .not_null()
+                            .default(""),
+                    )
+                    .col(
+                        ColumnDef::new(ModelGraphicNodeAttributes::Shape)
+                            .string()
+                            .not_null()
+                            .default(""),
+                    )
+                    .col(
+                        ColumnDef::new(ModelGraphicNodeAttributes::Flip)
+                            .integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(
+                        ColumnDef::new(ModelGraphicNodeAttributes::Rotate)
+                            .integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(
+                        ColumnDef::new(ModelGraphicNodeAttributes::RelationNodeId)
+                            .integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(
+                        ColumnDef::new(ModelGraphicNodeAttributes::PointName)
+                            .string()
+                            .not_null()
+                            .default(""),
+                    )
+                    .col(
+                        ColumnDef::new(ModelGraphicNodeAttributes::PointType)
+                            .integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(
+                        ColumnDef::new(ModelGraphicNodeAttributes::LastOpenedTab)
+                            .string()
+                            .not_null()
+                            .default(""),
+                    )
+                    .primary_key(
+                        Index::create()
+                            .col(ModelGraphicNodeAttributes::Id)
+                            .col(ModelGraphicNodeAttributes::ModelId),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-model_graphic_node_attributes-page")
+                    .table(ModelGraphicNodeAttributes::Table)
+                    .col(ModelGraphicNodeAttributes::ModelId)
+                    .col(ModelGraphicNodeAttributes::GraphicPageId)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_table(
+                Table::create()
+                    .table(ModelLogicEntity::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(ModelLogicEntity::Id).string().not_null())
+                    .col(
+                        ColumnDef::new(ModelLogicEntity::ModelId)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ModelLogicEntity::Name)
+                            .string()
+                            .not_null()
+                            .default(""),
+                    )
+                    .col(
+                        ColumnDef::new(ModelLogicEntity::Type)
+                            .string()
+                            .not_null()
+                            .default(""),
+                    )
+                    .col(
+                        ColumnDef::new(ModelLogicEntity::Actived)
+                            .integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(
+                        ColumnDef::new(ModelLogicEntity::ConstParams)
+                            .text()
+                            .not_null()
+                            .default(""),
+                    )
+                    .col(
+                        ColumnDef::new(ModelLogicEntity::Params)
+                            .text()
+                            .not_null()
+                            .default(""),
+                    )
+                    .col(
+                        ColumnDef::new(ModelLogicEntity::InputData)
+                            .text()
+                            .not_null()
+                            .default(""),
+                    )
+                    .col(
+                        ColumnDef::new(ModelLogicEntity::OutputData)
+                            .text()
+                            .not_null()
+                            .default(""),
+                    )
+                    .col(
+                        ColumnDef::new(ModelLogicEntity::Status)
+                            .integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(
+                        ColumnDef::new(ModelLogicEntity::CreateAt)
+                            .string()
+                            .not_null()
+                            .default(""),
+                    )
+                    .col(ColumnDef::new(ModelLogicEntity::InPortNumber).integer())
+                    .col(
+                        ColumnDef::new(ModelLogicEntity::Script)
+                            .text()
+                            .not_null()
+                            .default(""),
+                    )
+                    .col(
+                        ColumnDef::new(ModelLogicEntity::CommPv)
+                            .text()
+                            .not_null()
+                            .default(""),
+                    )
+                    .primary_key(
+                        Index::create()
+                            .col(ModelLogicEntity::Id)
+                            .col(ModelLogicEntity::ModelId),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_table(
+                Table::create()
+                    .table(ModelMaterialEntity::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(ModelMaterialEntity::Id).string().not_null())
+                    .col(
+                        ColumnDef::new(ModelMaterialEntity::ModelId)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ModelMaterialEntity::Name)
+                            .string()
+                            .not_null()
+                            .default(""),
+                    )
+                    .col(
+                        ColumnDef::new(ModelMaterialEntity::Type)
+                            .string()
+                            .not_null()
+                            .default(""),
+                    )
+                    .col(
+                        ColumnDef::new(ModelMaterialEntity::Actived)
+                            .integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(
+                        ColumnDef::new(ModelMaterialEntity::FluidPackageId)
+                            .string()
+                            .not_null()
+                            .default(""),
+                    )
+                    .col(
+                        ColumnDef::new(ModelMaterialEntity::Status)
+                            .integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(
+                        ColumnDef::new(ModelMaterialEntity::CreateAt)
+                            .string()
+                            .not_null()
+                            .default(""),
+                    )
+                    .col(
+                        ColumnDef::new(ModelMaterialEntity::FlashType)
+                            .string()
+                            .not_null()
+                            .default(""),
+                    )
+                    .col(
+                        ColumnDef::new(ModelMaterialEntity::PressureFixed)
+                            .integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(
+                        ColumnDef::new(ModelMaterialEntity::FlowFixed)
+                            .integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(
+                        ColumnDef::new(ModelMaterialEntity::PfDisconnected)
+                            .integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(
+                        ColumnDef::new(ModelMaterialEntity::MassFlow)
+                            .integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(
+                        ColumnDef::new(ModelMaterialEntity::Phases)
+                            .text()
+                            .not_null()
+                            .default(""),
+                    )
+                    .col(
+                        ColumnDef::new(ModelMaterialEntity::Feed)
+                            .text()
+                            .not_null()
+                            .default(""),
+                    )
+                    .col(
+                        ColumnDef::new(ModelMaterialEntity::Product)
+                            .text()
+                            .not_null()
+                            .default(""),
+                    )
+                    .col(
+                        ColumnDef::new(ModelMaterialEntity::Script)
+                            .text()
+                            .not_null()
+                            .default(""),
+                    )
+                    .col(
+                        ColumnDef::new(ModelMaterialEntity::CurrentMoleOrMass)
+                            .string()
+                            .not_null()
+                            .default(""),
+                    )
+                    .col(
+                        ColumnDef::new(ModelMaterialEntity::CopyPruductForm)
+                            .integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(
+                        ColumnDef::new(ModelMaterialEntity::CopyFeedForm)
+                            .integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(
+                        ColumnDef::new(ModelMaterialEntity::FeedOrProductFlashType)
+                            .string()
+                            .not_null()
+                            .default(""),
+                    )
+                    .primary_key(
+                        Index::create()
+                            .col(ModelMaterialEntity::Id)
+                            .col(ModelMaterialEntity::ModelId),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_table(
+                Table::create()
+                    .table(ModelModuleEntity::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(ModelModuleEntity::Id).string().not_null())
+                    .col(
+                        ColumnDef::new(ModelModuleEntity::ModelId)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ModelModuleEntity::Name)
+                            .string()
+                            .not_null()
+                            .default(""),
+                    )
+                    .col(
+                        ColumnDef::new(ModelModuleEntity::Type)
+                            .string()
+                            .not_null()
+                            .default(""),
+                    )
+                    .col(
+                        ColumnDef::new(ModelModuleEntity::Actived)
+                            .integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(
+                        ColumnDef::new(ModelModuleEntity::ConstParams)
+                            .text()
+                            .not_null()
+                            .default(""),
+                    )
+                    .col(
+                        ColumnDef::new(ModelModuleEntity::Params)
+                            .text()
+                            .not_null()
+                            .default(""),
+                    )
+                    .col(
+                        ColumnDef::new(ModelModuleEntity::Holdups)
+                            .text()
+                            .not_null()
+                            .default(""),
+                    )
+                    .col(
+                        ColumnDef::new(ModelModuleEntity::Status)
+                            .integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(
+                        ColumnDef::new(ModelModuleEntity::CreateAt)
+                            .string()
+                            .not_null()
+                            .default(""),
+                    )
+                    .col(
+                        ColumnDef::new(ModelModuleEntity::Script)
+                            .text()
+                            .not_null()
+                            .default(""),
+                    )
+                    .col(
+                        ColumnDef::new(ModelModuleEntity::ReactionPackage)
+                            .text()
+                            .not_null()
+                            .default(""),
+                    )
+                    .col(
+                        ColumnDef::new(ModelModuleEntity::Reactions)
+                            .text()
+                            .not_null()
+                            .default(""),
+                    )
+                    .col(
+                        ColumnDef::new(ModelModuleEntity::CommPv)
+                            .text()
+                            .not_null()
+                            .default(""),
+                    )
+                    .col(
+                        ColumnDef::new(ModelModuleEntity::CommOp)
+                            .text()
+                            .not_null()
+                            .default(""),
+                    )
+                    .primary_key(
+                        Index::create()
+                            .col(ModelModuleEntity::Id)
+                            .col(ModelModuleEntity::ModelId),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_table(
+                Table::create()
+                    .table(ModelSensorEntity::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(ModelSensorEntity::Id).string().not_null())
+                    .col(
+                        ColumnDef::new(ModelSensorEntity::ModelId)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ModelSensorEntity::Name)
+                            .string()
+                            .not_null()
+                            .default(""),
+                    )
+                    .col(
+                        ColumnDef::new(ModelSensorEntity::Type)
+                            .string()
+                            .not_null()
+                            .default(""),
+                    )
+                    .col(
+                        ColumnDef::new(ModelSensorEntity::Actived)
+                            .integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(
+                        ColumnDef::new(ModelSensorEntity::ConstParams)
+                            .text()
+                            .not_null()
+                            .default(""),
+                    )
+                    .col(
+                        ColumnDef::new(ModelSensorEntity::Params)
+                            .text()
+                            .not_null()
+                            .default(""),
+                    )
+                    .col(
+                        ColumnDef::new(ModelSensorEntity::CommPv)
+                            .text()
+                            .not_null()
+                            .default(""),
+                    )
+                    .col(
+                        ColumnDef::new(ModelSensorEntity::CasnoNames)
+                            .text()
+                            .not_null()
+                            .default(""),
+                    )
+                    .col(
+                        ColumnDef::new(ModelSensorEntity::Status)
+                            .integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(
+                        ColumnDef::new(ModelSensorEntity::CreateAt)
+                            .string()
+                            .not_null()
+                            .default(""),
+                    )
+                    .col(
+                        ColumnDef::new(ModelSensorEntity::Script)
+                            .text()
+                            .not_null()
+                            .default(""),
+                    )
+                    .col(
+                        ColumnDef::new(ModelSensorEntity::SelectVarId)
+                            .string()
+                            .not_null()
+                            .default(""),
+                    )
+                    .col(
+                        ColumnDef::new(ModelSensorEntity::UnitCode)
+                            .string()
+                            .not_null()
+                            .default(""),
+                    )
+                    .col(
+                        ColumnDef::new(ModelSensorEntity::SrcUnit)
+                            .string()
+                            .not_null()
+                            .default(""),
+                    )
+                    .col(
+                        ColumnDef::new(ModelSensorEntity::DestUnit)
+                            .string()
+                            .not_null()
+                            .default(""),
+                    )
+                    .col(
+                        ColumnDef::new(ModelSensorEntity::IsShowLine)
+                            .integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .primary_key(
+                        Index::create()
+                            .col(ModelSensorEntity::Id)
+                            .col(ModelSensorEntity::ModelId),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let all_tables: &[&(dyn Iden + Sync)] = &[
+            &ModelAiuoEntity::Table,
+            &ModelElementEntityConnection::Table,
+            &ModelEnergyEntity::Table,
+            &ModelGraphicEdgeAttributes::Table,
+            &ModelGraphicEntityScriptuoCustom::Table,
+            &ModelGraphicMarkNodeAttributes::Table,
+            &ModelGraphicNodeAttributes::Table,
+            &ModelLogicEntity::Table,
+            &ModelMaterialEntity::Table,
+            &ModelModuleEntity::Table,
+            &ModelSensorEntity::Table,
+        ];
+        drop_tables(manager, all_tables).await
+    }
+}
+
+#[derive(Iden)]
+enum ModelAiuoEntity {
+    Table,
+    Id,
+    ModelId,
+    Name,
+    Type,
+    Actived,
+    ConstParams,
+    Params,
+    #[iden = "commIn"]
+    CommIn,
+    #[iden = "commOut"]
+    CommOut,
+    Status,
+    CreateAt,
+    Script,
+}
+
+#[derive(Iden)]
+enum ModelElementEntityConnection {
+    Table,
+    Id,
+    ModelId,
+    InPortId,
+    OutPortId,
+    InPortFrom,
+    OutPortTo,
+    #[iden = "sortNum"]
+    SortNum,
+}
+
+#[derive(Iden)]
+enum ModelEnergyEntity {
+    Table,
+    Id,
+    ModelId,
+    Name,
+    Type,
+    Actived,
+    Datas,
+    Status,
+    CreateAt,
+}
+
+#[derive(Iden)]
+enum ModelGraphicEdgeAttributes {
+    Table,
+    Id,
+    GraphicPageId,
+    ModelId,
+    Shape,
+    Source,
+    Target,
+    #[iden = "sourceId"]
+    SourceId,
+    #[iden = "zIndex"]
+    ZIndex,
+    Vertices,
+    Label,
+    Data,
+}
+
+#[derive(Iden)]
+enum ModelGraphicEntityScriptuoCustom {
+    Table,
+    Id,
+    ModelId,
+    Name,
+    Type,
+    Actived,
+    Params,
+    ConstParams,
+    Status,
+    CreateAt,
+    Script,
+    InputData,
+    OutputData,
+    CommPv,
+}
+
+#[derive(Iden)]
+enum ModelGraphicMarkNodeAttributes {
+    Table,
+    Id,
+    GraphicPageId,
+    ModelId,
+    Position,
+    Size,
+    ZIndex,
+    Shape,
+    Attrs,
+}
+
+#[derive(Iden)]
+enum ModelGraphicNodeAttributes {
+    Table,
+    Id,
+    GraphicPageId,
+    ModelId,
+    #[iden = "sourceId"]
+    SourceId,
+    Position,
+    Size,
+    Angle,
+    ZIndex,
+    Parent,
+    Children,
+    Ports,
+    Shape,
+    Flip,
+    Rotate,
+    RelationNodeId,
+    PointName,
+    PointType,
+    LastOpenedTab,
+}
+
+#[derive(Iden)]
+enum ModelLogicEntity {
+    Table,
+    Id,
+    ModelId,
+    Name,
+    Type,
+    Actived,
+    ConstParams,
+    Params,
+    #[iden = "inputData"]
+    InputData,
+    #[iden = "outputData"]
+    OutputData,
+    Status,
+    CreateAt,
+    InPortNumber,
+    Script,
+    CommPv,
+}
+
+#[derive(Iden)]
+enum ModelMaterialEntity {
+    Table,
+    Id,
+    ModelId,
+    Name,
+    Type,
+    Actived,
+    #[iden = "fluidPackage_id"]
+    FluidPackageId,
+    Status,
+    CreateAt,
+    #[iden = "flashType"]
+    FlashType,
+    #[iden = "pressureFixed"]
+    PressureFixed,
+    #[iden = "flowFixed"]
+    FlowFixed,
+    #[iden = "pfDisconnected"]
+    PfDisconnected,
+    #[iden = "massFlow"]
+    MassFlow,
+    Phases,
+    Feed,
+    Product,
+    Script,
+    CurrentMoleOrMass,
+    CopyPruductForm,
+    CopyFeedForm,
+    FeedOrProductFlashType,
+}
+
+#[derive(Iden)]
+enum ModelModuleEntity {
+    Table,
+    Id,
+    ModelId,
+    Name,
+    Type,
+    Actived,
+    ConstParams,
+    Params,
+    Holdups,
+    Status,
+    CreateAt,
+    Script,
+    ReactionPackage,
+    Reactions,
+    CommPv,
+    CommOp,
+}
+
+#[derive(Iden)]
+enum ModelSensorEntity {
+    Table,
+    Id,
+    ModelId,
+    Name,
+    Type,
+    Actived,
+    ConstParams,
+    Params,
+    CommPv,
+    CasnoNames,
+    Status,
+    CreateAt,
+    Script,
+    SelectVarId,
+    UnitCode,
+    SrcUnit,
+    DestUnit,
+    IsShowLine,
+}
@@ -0,0 +1,53 @@
+use crate::service_database::database_business::entity::fluid_package::model_fluid_package_binary_handle::*;
+use sea_orm_migration::prelude::*;
+
+// 二元参数来源标记：DATABANK（物性库 / 手工录入）、ESTIMATED（估算）、REGRESSED（实验数据回归）
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+const TABLES: [BinaryTables; 8] = [
+    BinaryTables::Nrtl,
+    BinaryTables::NrtlRk,
+    BinaryTables::Pr,
+    BinaryTables::Psrk,
+    BinaryTables::Rk,
+    BinaryTables::Srk,
+    BinaryTables::Uniquac,
+    BinaryTables::Wilson,
+];
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        for table in TABLES {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(table)
+                        .add_column(
+                            ColumnDef::new(BinaryCols::Source)
+                                .string()
+                                .not_null()
+                                .default("DATABANK"),
+                        )
+                        .to_owned(),
+                )
+                .await?;
+        }
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        for table in TABLES {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(table)
+                        .drop_column(BinaryCols::Source)
+                        .to_owned(),
+                )
+                .await?;
+        }
+        Ok(())
+    }
+}
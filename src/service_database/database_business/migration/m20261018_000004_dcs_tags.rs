@@ -0,0 +1,129 @@
+use sea_orm_migration::prelude::*;
+
+// DCS 位号表：每个模型一套位号，(model_id, tag_name) 唯一
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(ModelDcsTagEntity::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(ModelDcsTagEntity::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(ModelDcsTagEntity::ModelId)
+                            .string_len(64)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ModelDcsTagEntity::TagName)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ModelDcsTagEntity::Description)
+                            .string()
+                            .not_null()
+                            .default(""),
+                    )
+                    .col(
+                        ColumnDef::new(ModelDcsTagEntity::TypeNum)
+                            .integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(
+                        ColumnDef::new(ModelDcsTagEntity::TypeName)
+                            .string()
+                            .not_null()
+                            .default(""),
+                    )
+                    .col(
+                        ColumnDef::new(ModelDcsTagEntity::Unit)
+                            .string()
+                            .not_null()
+                            .default(""),
+                    )
+                    .col(ColumnDef::new(ModelDcsTagEntity::RangeLow).double())
+                    .col(ColumnDef::new(ModelDcsTagEntity::RangeHigh).double())
+                    .col(ColumnDef::new(ModelDcsTagEntity::AlarmHh).double())
+                    .col(ColumnDef::new(ModelDcsTagEntity::AlarmH).double())
+                    .col(ColumnDef::new(ModelDcsTagEntity::AlarmL).double())
+                    .col(ColumnDef::new(ModelDcsTagEntity::AlarmLl).double())
+                    .col(
+                        ColumnDef::new(ModelDcsTagEntity::GraphicId)
+                            .string()
+                            .not_null()
+                            .default(""),
+                    )
+                    .col(
+                        ColumnDef::new(ModelDcsTagEntity::VariablePath)
+                            .string()
+                            .not_null()
+                            .default(""),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-dcs_tag-model_id-tag_name")
+                    .table(ModelDcsTagEntity::Table)
+                    .col(ModelDcsTagEntity::ModelId)
+                    .col(ModelDcsTagEntity::TagName)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-dcs_tag-model_id-graphic_id")
+                    .table(ModelDcsTagEntity::Table)
+                    .col(ModelDcsTagEntity::ModelId)
+                    .col(ModelDcsTagEntity::GraphicId)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(ModelDcsTagEntity::Table).to_owned())
+            .await?;
+        Ok(())
+    }
+}
+
+#[derive(Iden)]
+enum ModelDcsTagEntity {
+    Table,
+    Id,
+    ModelId,
+    TagName,
+    Description,
+    TypeNum,
+    TypeName,
+    Unit,
+    RangeLow,
+    RangeHigh,
+    AlarmHh,
+    AlarmH,
+    AlarmL,
+    AlarmLl,
+    GraphicId,
+    VariablePath,
+}
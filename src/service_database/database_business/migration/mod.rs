@@ -1,11 +1,21 @@
 pub use sea_orm_migration::prelude::*;
 
 mod m20251225_000001_create_tables;
+mod m20261018_000001_undo_history;
+mod m20261018_000002_flowsheet_tables;
+mod m20261018_000003_binary_source;
+mod m20261018_000004_dcs_tags;
 pub struct Migrator;
 
 #[async_trait::async_trait]
 impl MigratorTrait for Migrator {
     fn migrations() -> Vec<Box<dyn MigrationTrait>> {
-        vec![Box::new(m20251225_000001_create_tables::Migration)]
+        vec![
+            Box::new(m20251225_000001_create_tables::Migration),
+            Box::new(m20261018_000001_undo_history::Migration),
+            Box::new(m20261018_000002_flowsheet_tables::Migration),
+            Box::new(m20261018_000003_binary_source::Migration),
+            Box::new(m20261018_000004_dcs_tags::Migration),
+        ]
     }
 }
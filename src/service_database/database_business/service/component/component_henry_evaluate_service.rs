@@ -0,0 +1,265 @@
+use crate::calc_handle::henry::{HenryCoefficients, mixed_ln_henry, solubility};
+use crate::service_database::database_business::service::component::component_henry_detail_service::{
+    ModelComponentHenryDetailDTO, select_by_only_henry_ids,
+};
+use crate::service_database::database_config::service::conf_unit_conversion_service::get_unit_registry;
+use crate::service_database::database_physical_property::service::physical_enum::ExtrapolationPolicy;
+use napi_derive::napi;
+use sea_orm::DbErr;
+use serde::{Deserialize, Serialize};
+
+// ======================================
+// 亨利常数计算与气体溶解度
+// 温度 K；系数按 coefficient_unit（默认 Pa）给出 H，结果换算到 unit
+// ======================================
+
+const DEFAULT_HENRY_UNIT: &str = "Pa";
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[napi(object, namespace = "henry", js_name = "HenrySolventFraction")]
+pub struct HenrySolventFraction {
+    #[napi(js_name = "casNo")]
+    pub cas_no: String,
+    /// 溶剂摩尔分数，单一溶剂可不给出
+    #[napi(js_name = "moleFraction")]
+    pub mole_fraction: Option<f64>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[napi(object, namespace = "henry", js_name = "HenryEvaluateQuery")]
+pub struct HenryEvaluateQuery {
+    /// 亨利组 ID（model_compound_henry）
+    #[napi(js_name = "henryId")]
+    pub henry_id: String,
+    #[napi(js_name = "soluteCas")]
+    pub solute_cas: String,
+    pub solvents: Vec<HenrySolventFraction>,
+    /// 温度 K
+    pub temperatures: Vec<f64>,
+    /// 超出适用温度范围时的处理方式，默认外推
+    pub policy: Option<ExtrapolationPolicy>,
+    /// 系数对应的亨利常数单位，默认 Pa
+    #[napi(js_name = "coefficientUnit")]
+    pub coefficient_unit: Option<String>,
+    /// 结果单位，默认同 coefficient_unit
+    pub unit: Option<String>,
+    /// 溶质分压，给出时计算溶解度
+    #[napi(js_name = "partialPressure")]
+    pub partial_pressure: Option<f64>,
+    /// 分压单位，默认同 unit
+    #[napi(js_name = "partialPressureUnit")]
+    pub partial_pressure_unit: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[napi(object, namespace = "henry", js_name = "HenrySolventValue")]
+pub struct HenrySolventValue {
+    #[napi(js_name = "casNo")]
+    pub cas_no: String,
+    /// 溶质在该溶剂中的亨利常数，被拒绝或无法计算时为空
+    pub value: Option<f64>,
+    #[napi(js_name = "outOfRange")]
+    pub out_of_range: bool,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[napi(object, namespace = "henry", js_name = "HenryEvaluatePoint")]
+pub struct HenryEvaluatePoint {
+    pub temperature: f64,
+    /// 混合溶剂亨利常数
+    pub value: Option<f64>,
+    /// 任一溶剂超出适用温度范围
+    #[napi(js_name = "outOfRange")]
+    pub out_of_range: bool,
+    /// 给定分压下的溶解度（液相摩尔分数）
+    pub solubility: Option<f64>,
+    pub solvents: Vec<HenrySolventValue>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[napi(object, namespace = "henry", js_name = "HenryEvaluateResult")]
+pub struct HenryEvaluateResult {
+    #[napi(js_name = "henryId")]
+    pub henry_id: String,
+    #[napi(js_name = "soluteCas")]
+    pub solute_cas: String,
+    /// 结果单位
+    pub unit: String,
+    /// 实际参与加权的溶剂 CAS 号
+    #[napi(js_name = "usedSolvents")]
+    pub used_solvents: Vec<String>,
+    pub points: Vec<HenryEvaluatePoint>,
+    pub warnings: Vec<String>,
+}
+
+fn henry_err(msg: impl std::fmt::Display) -> DbErr {
+    DbErr::Custom(format!("亨利常数计算错误: {}", msg))
+}
+
+fn coefficients_of(d: &ModelComponentHenryDetailDTO) -> HenryCoefficients {
+    HenryCoefficients {
+        a: d.aij,
+        b: d.bij,
+        c: d.cij,
+        d: d.dij,
+        e: d.eij,
+        t_lower: d.tlower,
+        t_upper: d.tupper,
+    }
+}
+
+/// 溶剂摩尔分数：单一溶剂缺省为 1，多溶剂必须全部给出
+fn solvent_weights(solvents: &[HenrySolventFraction]) -> Result<Vec<f64>, DbErr> {
+    if solvents.is_empty() {
+        return Err(henry_err("至少需要一个溶剂"));
+    }
+    if solvents.len() == 1 {
+        return Ok(vec![solvents[0].mole_fraction.unwrap_or(1.0)]);
+    }
+    solvents
+        .iter()
+        .map(|s| {
+            s.mole_fraction
+                .ok_or_else(|| henry_err(format!("混合溶剂中 {} 未给出摩尔分数", s.cas_no)))
+        })
+        .collect()
+}
+
+/// 计算溶质在（混合）溶剂中的亨利常数，可同时给出指定分压下的溶解度
+pub async fn evaluate_henry_constant(
+    query: HenryEvaluateQuery,
+) -> Result<HenryEvaluateResult, DbErr> {
+    let weights = solvent_weights(&query.solvents)?;
+    let registry = get_unit_registry().await?;
+    let policy = query.policy.unwrap_or_default();
+    let coefficient_unit = query
+        .coefficient_unit
+        .clone()
+        .filter(|u| !u.trim().is_empty())
+        .unwrap_or_else(|| DEFAULT_HENRY_UNIT.to_string());
+    let unit = query
+        .unit
+        .clone()
+        .filter(|u| !u.trim().is_empty())
+        .unwrap_or_else(|| coefficient_unit.clone());
+    let to_unit = |v: f64| {
+        registry
+            .convert(v, &coefficient_unit, &unit, Some("pressure"))
+            .map_err(henry_err)
+    };
+    // 分压换算到系数单位后与 H 直接相除
+    let partial_pressure = match query.partial_pressure {
+        Some(p) => {
+            let from = query
+                .partial_pressure_unit
+                .clone()
+                .filter(|u| !u.trim().is_empty())
+                .unwrap_or_else(|| unit.clone());
+            Some(
+                registry
+                    .convert(p, &from, &coefficient_unit, Some("pressure"))
+                    .map_err(henry_err)?,
+            )
+        }
+        None => None,
+    };
+
+    let details = select_by_only_henry_ids(vec![query.henry_id.clone()]).await?;
+    let mut warnings = Vec::new();
+    // 每个溶剂取一条记录，同一组分对有多条时以 is_default = 1 的为准
+    let mut solvents = Vec::new();
+    for (solvent, &x) in query.solvents.iter().zip(&weights) {
+        let found = details
+            .iter()
+            .filter(|d| d.component_i == query.solute_cas && d.component_j == solvent.cas_no)
+            .max_by_key(|d| d.is_default == 1);
+        match found {
+            Some(d) => solvents.push((solvent.cas_no.clone(), x, coefficients_of(d))),
+            None => warnings.push(format!(
+                "亨利组中没有 {} 在 {} 中的数据，已从加权中排除",
+                query.solute_cas, solvent.cas_no
+            )),
+        }
+    }
+    if solvents.is_empty() {
+        return Err(DbErr::RecordNotFound(format!(
+            "亨利组 {} 中没有溶质 {} 在所给溶剂中的数据",
+            query.henry_id, query.solute_cas
+        )));
+    }
+
+    let mut out_of_range_count = 0;
+    let mut points = Vec::with_capacity(query.temperatures.len());
+    for &t in &query.temperatures {
+        let mut values = Vec::with_capacity(solvents.len());
+        let mut parts = Vec::with_capacity(solvents.len());
+        for (cas_no, x, c) in &solvents {
+            let out_of_range = !c.in_range(t);
+            let t_eval = match (out_of_range, policy) {
+                (true, ExtrapolationPolicy::Reject) => None,
+                (true, ExtrapolationPolicy::Clamp) => Some(t.clamp(c.t_lower, c.t_upper)),
+                _ => Some(t),
+            };
+            let ln_h = match t_eval.map(|t| c.ln_henry(t)) {
+                Some(Ok(v)) => Some(v),
+                Some(Err(e)) => {
+                    warnings.push(format!("{}: {}", cas_no, e));
+                    None
+                }
+                None => None,
+            };
+            if let Some(v) = ln_h {
+                parts.push((*x, v));
+            }
+            values.push(HenrySolventValue {
+                cas_no: cas_no.clone(),
+                value: ln_h.map(|v| to_unit(v.exp())).transpose()?,
+                out_of_range,
+            });
+        }
+        let out_of_range = values.iter().any(|v| v.out_of_range);
+        if out_of_range {
+            out_of_range_count += 1;
+        }
+        // 任一溶剂无值时混合值不计算，避免权重悄然改变
+        let henry = if parts.len() == solvents.len() {
+            Some(mixed_ln_henry(&parts).map_err(henry_err)?.exp())
+        } else {
+            None
+        };
+        let solubility = match (henry, partial_pressure) {
+            (Some(h), Some(p)) => Some(solubility(p, h).map_err(henry_err)?),
+            _ => None,
+        };
+        points.push(HenryEvaluatePoint {
+            temperature: t,
+            value: henry.map(to_unit).transpose()?,
+            out_of_range,
+            solubility,
+            solvents: values,
+        });
+    }
+    if out_of_range_count > 0 {
+        let action = match policy {
+            ExtrapolationPolicy::Extrapolate => "已外推",
+            ExtrapolationPolicy::Clamp => "已取边界值",
+            ExtrapolationPolicy::Reject => "未计算",
+        };
+        warnings.insert(
+            0,
+            format!(
+                "{} 个温度点超出亨利常数适用范围，{}",
+                out_of_range_count, action
+            ),
+        );
+    }
+
+    Ok(HenryEvaluateResult {
+        henry_id: query.henry_id,
+        solute_cas: query.solute_cas,
+        unit,
+        used_solvents: solvents.into_iter().map(|(cas_no, _, _)| cas_no).collect(),
+        points,
+        warnings,
+    })
+}
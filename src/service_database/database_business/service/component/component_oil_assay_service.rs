@@ -0,0 +1,293 @@
+use crate::calc_handle::petroleum_assay::{
+    AssayInput, CriticalMethod, CurvePoint, DistillationType, PseudoComponent, api_to_sg,
+    characterize, sg_to_api,
+};
+use crate::service_database::database_business::service::component::component_oil_service::{
+    ModelComponentOilDTO, get_oils_by_channel_id, insert_model_component_oil,
+};
+use crate::service_database::database_config::service::conf_unit_conversion_service::get_unit_registry;
+use crate::tool_handle::id_tool::new_id;
+use napi_derive::napi;
+use sea_orm::DbErr;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+
+// ======================================
+// 石油馏分表征：蒸馏曲线 + 比重 → 虚拟组分，可写入组分通道（model_compound_oil）
+// 基础物性以 [{code, name, value, unitType}] 形式保存，与物性库基础物性一致
+// ======================================
+
+/// Kesler-Lee 理想气体热容关联的适用温度范围（0 ~ 1400 °F）K
+const CP_T_MIN: f64 = 255.37;
+const CP_T_MAX: f64 = 1033.15;
+
+#[napi(string_enum, namespace = "petroleumAssay")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AssayDistillationType {
+    TBP,
+    D86,
+    D2887,
+}
+
+#[napi(string_enum, namespace = "petroleumAssay")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum AssayCriticalMethod {
+    #[default]
+    RiaziDaubert,
+    LeeKesler,
+    Twu,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[napi(object, namespace = "petroleumAssay", js_name = "AssayCurvePoint")]
+pub struct AssayCurvePoint {
+    /// 馏出体积分数 %
+    #[napi(js_name = "volumePercent")]
+    pub volume_percent: f64,
+    pub temperature: f64,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[napi(object, namespace = "petroleumAssay", js_name = "PetroleumAssayQuery")]
+pub struct PetroleumAssayQuery {
+    /// 虚拟组分名称前缀，默认 PC
+    pub name: Option<String>,
+    #[napi(js_name = "distillationType")]
+    pub distillation_type: AssayDistillationType,
+    pub curve: Vec<AssayCurvePoint>,
+    /// 曲线温度单位，默认 K
+    #[napi(js_name = "temperatureUnit")]
+    pub temperature_unit: Option<String>,
+    /// 总体比重（60°F/60°F），与 API 度二选一
+    #[napi(js_name = "specificGravity")]
+    pub specific_gravity: Option<f64>,
+    #[napi(js_name = "apiGravity")]
+    pub api_gravity: Option<f64>,
+    /// 切割数
+    #[napi(js_name = "cutCount")]
+    pub cut_count: u32,
+    #[napi(js_name = "criticalMethod")]
+    pub critical_method: Option<AssayCriticalMethod>,
+    /// 写入的组分通道，不给出时只计算
+    #[napi(js_name = "compoundChannelId")]
+    pub compound_channel_id: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[napi(object, namespace = "petroleumAssay", js_name = "AssayPseudoComponent")]
+pub struct AssayPseudoComponent {
+    /// 写入后的记录 ID，只计算时为空
+    pub id: Option<String>,
+    pub name: String,
+    /// 正常沸点 K
+    #[napi(js_name = "normalBoilingPoint")]
+    pub normal_boiling_point: f64,
+    #[napi(js_name = "specificGravity")]
+    pub specific_gravity: f64,
+    #[napi(js_name = "apiGravity")]
+    pub api_gravity: f64,
+    #[napi(js_name = "molecularWeight")]
+    pub molecular_weight: f64,
+    /// 临界温度 K、临界压力 Pa、临界体积 m³/kmol
+    #[napi(js_name = "criticalTemperature")]
+    pub critical_temperature: f64,
+    #[napi(js_name = "criticalPressure")]
+    pub critical_pressure: f64,
+    #[napi(js_name = "criticalVolume")]
+    pub critical_volume: f64,
+    #[napi(js_name = "acentricFactor")]
+    pub acentric_factor: f64,
+    #[napi(js_name = "watsonK")]
+    pub watson_k: f64,
+    #[napi(js_name = "volumeFraction")]
+    pub volume_fraction: f64,
+    #[napi(js_name = "massFraction")]
+    pub mass_fraction: f64,
+    #[napi(js_name = "moleFraction")]
+    pub mole_fraction: f64,
+    /// 理想气体热容 Cp = a + b·T + c·T²，J/(kmol·K)，T 为 K
+    #[napi(js_name = "idealGasCp")]
+    pub ideal_gas_cp: Vec<f64>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[napi(object, namespace = "petroleumAssay", js_name = "PetroleumAssayResult")]
+pub struct PetroleumAssayResult {
+    /// 换算后的 TBP 曲线（K）
+    #[napi(js_name = "tbpCurve")]
+    pub tbp_curve: Vec<AssayCurvePoint>,
+    #[napi(js_name = "watsonK")]
+    pub watson_k: f64,
+    /// 写入时本次馏分的组号
+    pub group: Option<i32>,
+    pub components: Vec<AssayPseudoComponent>,
+}
+
+fn assay_err(msg: impl std::fmt::Display) -> DbErr {
+    DbErr::Custom(format!("石油馏分表征错误: {}", msg))
+}
+
+fn bulk_sg(query: &PetroleumAssayQuery) -> Result<f64, DbErr> {
+    match (query.specific_gravity, query.api_gravity) {
+        (Some(sg), _) => Ok(sg),
+        (None, Some(api)) => Ok(api_to_sg(api)),
+        (None, None) => Err(assay_err("需要给出总体比重或 API 度")),
+    }
+}
+
+fn pseudo_component(name: String, c: &PseudoComponent) -> AssayPseudoComponent {
+    AssayPseudoComponent {
+        id: None,
+        name,
+        normal_boiling_point: c.tb,
+        specific_gravity: c.sg,
+        api_gravity: sg_to_api(c.sg),
+        molecular_weight: c.mw,
+        critical_temperature: c.tc,
+        critical_pressure: c.pc,
+        critical_volume: c.vc,
+        acentric_factor: c.omega,
+        watson_k: c.watson_k,
+        volume_fraction: c.volume_fraction,
+        mass_fraction: c.mass_fraction,
+        mole_fraction: c.mole_fraction,
+        ideal_gas_cp: c.ideal_gas_cp.to_vec(),
+    }
+}
+
+/// 虚拟组分的基础物性 JSON
+fn base_properties(c: &AssayPseudoComponent) -> Value {
+    let item = |code: &str, name: &str, value: f64, unit: &str| json!({ "code": code, "name": name, "value": value, "unitType": unit });
+    json!([
+        item("MW", "分子量", c.molecular_weight, "kg/kmol"),
+        item("TB", "正常沸点", c.normal_boiling_point, "K"),
+        item("SG", "比重", c.specific_gravity, ""),
+        item("API", "API 度", c.api_gravity, ""),
+        item("TC", "临界温度", c.critical_temperature, "K"),
+        item("PC", "临界压力", c.critical_pressure, "Pa"),
+        item("VC", "临界体积", c.critical_volume, "m3/kmol"),
+        item("OMEGA", "偏心因子", c.acentric_factor, ""),
+        item("WATSON_K", "Watson K", c.watson_k, ""),
+        item("VOLUME_FRACTION", "体积分数", c.volume_fraction, ""),
+        item("MASS_FRACTION", "质量分数", c.mass_fraction, ""),
+        item("MOLE_FRACTION", "摩尔分数", c.mole_fraction, ""),
+    ])
+}
+
+/// 理想气体热容方程 JSON，字段与物性库温度关联方程一致（方程 100：多项式）
+fn temperature_equations(c: &AssayPseudoComponent) -> Value {
+    let k = |i: usize| c.ideal_gas_cp.get(i).copied().unwrap_or(0.0);
+    json!([{
+        "code": "CPIG",
+        "name": "理想气体热容",
+        "unit": "J/(kmol·K)",
+        "functionCode": 100,
+        "coefficientA": k(0),
+        "coefficientB": k(1),
+        "coefficientC": k(2),
+        "coefficientD": 0.0,
+        "coefficientE": 0.0,
+        "coefficientF": 0.0,
+        "coefficientG": 0.0,
+        "coefficientH": 0.0,
+        "coefficientK": 0.0,
+        "coefficientL": 0.0,
+        "minApplicableTemperature": CP_T_MIN,
+        "minApplicableTemperatureUnit": "K",
+        "maxApplicableTemperature": CP_T_MAX,
+        "maxApplicableTemperatureUnit": "K",
+    }])
+}
+
+/// 表征石油馏分，给出组分通道时写入 model_compound_oil（同一次表征共用一个组号）
+pub async fn characterize_petroleum_assay(
+    query: PetroleumAssayQuery,
+) -> Result<PetroleumAssayResult, DbErr> {
+    let registry = get_unit_registry().await?;
+    let temperature_unit = query.temperature_unit.clone().unwrap_or_default();
+    let mut curve = Vec::with_capacity(query.curve.len());
+    for p in &query.curve {
+        let t = if temperature_unit.trim().is_empty() {
+            p.temperature
+        } else {
+            registry
+                .convert(p.temperature, &temperature_unit, "K", Some("temperature"))
+                .map_err(assay_err)?
+        };
+        curve.push(CurvePoint {
+            percent: p.volume_percent,
+            t,
+        });
+    }
+    let result = characterize(&AssayInput {
+        distillation: match query.distillation_type {
+            AssayDistillationType::TBP => DistillationType::Tbp,
+            AssayDistillationType::D86 => DistillationType::D86,
+            AssayDistillationType::D2887 => DistillationType::D2887,
+        },
+        curve: &curve,
+        bulk_sg: bulk_sg(&query)?,
+        cuts: query.cut_count as usize,
+        critical: match query.critical_method.unwrap_or_default() {
+            AssayCriticalMethod::RiaziDaubert => CriticalMethod::RiaziDaubert,
+            AssayCriticalMethod::LeeKesler => CriticalMethod::LeeKesler,
+            AssayCriticalMethod::Twu => CriticalMethod::Twu,
+        },
+    })
+    .map_err(assay_err)?;
+
+    // 名称按 Aspen 习惯以沸点（°C）区分：PC152C
+    let prefix = query
+        .name
+        .clone()
+        .filter(|s| !s.trim().is_empty())
+        .unwrap_or_else(|| "PC".to_string());
+    let mut components: Vec<AssayPseudoComponent> = result
+        .components
+        .iter()
+        .map(|c| pseudo_component(format!("{}{:.0}C", prefix, c.tb - 273.15), c))
+        .collect();
+
+    let group = match query.compound_channel_id.clone() {
+        Some(channel_id) => {
+            let existing = get_oils_by_channel_id(channel_id.clone()).await?;
+            let group = existing.iter().map(|o| o.group).max().unwrap_or(0) + 1;
+            let mut rows = Vec::with_capacity(components.len());
+            for c in &mut components {
+                let id = new_id();
+                let base = base_properties(c).to_string();
+                rows.push(ModelComponentOilDTO {
+                    id: id.clone(),
+                    compound_channel_id: channel_id.clone(),
+                    name: c.name.clone(),
+                    internal_name: c.name.clone(),
+                    formula: String::new(),
+                    // 虚拟组分没有 CAS 号，以记录 ID 作为唯一标识
+                    cas_no: id.clone(),
+                    group,
+                    base_physical_property: base.clone(),
+                    default_physical_property: base,
+                    temperature_equation_property: temperature_equations(c).to_string(),
+                });
+                c.id = Some(id);
+            }
+            insert_model_component_oil(rows).await?;
+            Some(group)
+        }
+        None => None,
+    };
+
+    Ok(PetroleumAssayResult {
+        tbp_curve: result
+            .tbp
+            .iter()
+            .map(|p| AssayCurvePoint {
+                volume_percent: p.percent,
+                temperature: p.t,
+            })
+            .collect(),
+        watson_k: result.watson_k,
+        group,
+        components,
+    })
+}
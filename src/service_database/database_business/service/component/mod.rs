@@ -2,5 +2,7 @@
 pub mod component_all_detail_service;
 pub mod component_channel_service;
 pub mod component_henry_detail_service;
+pub mod component_henry_evaluate_service;
 pub mod component_henry_service;
+pub mod component_oil_assay_service;
 pub mod component_oil_service;
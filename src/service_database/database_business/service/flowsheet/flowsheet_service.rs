@@ -0,0 +1,383 @@
+use crate::service_database::database_business::db_business_connection::get_business_db;
+use crate::service_database::database_business::entity::entitys::{
+    model_aiuo_entity as aiuo, model_element_entity_connection as connection,
+    model_energy_entity as energy, model_graphic_edge_attributes as edge,
+    model_graphic_entity_scriptuo_custom as script_uo,
+    model_graphic_mark_node_attributes as mark_node, model_graphic_node_attributes as node,
+    model_logic_entity as logic, model_material_entity as material, model_module_entity as module,
+    model_sensor_entity as sensor,
+};
+use crate::tool_handle::id_tool::IdRemap;
+use napi_derive::napi;
+use sea_orm::sea_query::OnConflict;
+use sea_orm::{
+    ConnectionTrait, DbErr, EntityTrait, ExprTrait, IdenStatic, IntoActiveModel, Iterable,
+    PrimaryKeyToColumn, QueryFilter, TransactionError, TransactionTrait, entity::prelude::*,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+// 实体结构直接作为 NAPI 对象使用
+pub use aiuo::Model as FlowsheetAiuo;
+pub use connection::Model as FlowsheetConnection;
+pub use edge::Model as FlowsheetEdge;
+pub use energy::Model as FlowsheetEnergy;
+pub use logic::Model as FlowsheetLogic;
+pub use mark_node::Model as FlowsheetMarkNode;
+pub use material::Model as FlowsheetMaterial;
+pub use module::Model as FlowsheetModule;
+pub use node::Model as FlowsheetNode;
+pub use script_uo::Model as FlowsheetScriptUo;
+pub use sensor::Model as FlowsheetSensor;
+
+// ======================================
+// 流程图拓扑：单元、连接关系与画布图元的增删改查
+// 所有表以 (id, model_id) 为联合主键，保存即 upsert
+// ======================================
+
+// 批量写入分片大小，避免超出 SQLite 单条语句的参数上限
+const SAVE_CHUNK_SIZE: usize = 100;
+
+/// 按主键 upsert 一批记录，返回写入条数
+async fn upsert_rows<E, C>(db: &C, rows: Vec<E::Model>) -> Result<u32, DbErr>
+where
+    E: EntityTrait,
+    E::Model: IntoActiveModel<E::ActiveModel>,
+    C: ConnectionTrait,
+{
+    let count = rows.len() as u32;
+    let pk_columns: Vec<E::Column> = E::PrimaryKey::iter().map(|pk| pk.into_column()).collect();
+    let update_columns: Vec<E::Column> = E::Column::iter()
+        .filter(|c| !pk_columns.iter().any(|pk| pk.as_str() == c.as_str()))
+        .collect();
+
+    let mut rows = rows.into_iter().peekable();
+    while rows.peek().is_some() {
+        let chunk: Vec<E::ActiveModel> = rows
+            .by_ref()
+            .take(SAVE_CHUNK_SIZE)
+            .map(IntoActiveModel::into_active_model)
+            .collect();
+        E::insert_many(chunk)
+            .on_conflict(
+                OnConflict::columns(pk_columns.clone())
+                    .update_columns(update_columns.clone())
+                    .to_owned(),
+            )
+            .exec_without_returning(db)
+            .await?;
+    }
+    Ok(count)
+}
+
+macro_rules! impl_flowsheet_service {
+    ($service_name:ident, $entity:ident) => {
+        pub struct $service_name;
+
+        impl $service_name {
+            /// 批量保存（存在则更新，不存在则插入）
+            pub async fn batch_save(datas: Vec<$entity::Model>) -> Result<u32, DbErr> {
+                let db = get_business_db().await?;
+                upsert_rows::<$entity::Entity, _>(db, datas).await
+            }
+
+            /// 查询模型下的全部记录
+            pub async fn find_by_model_id(model_id: String) -> Result<Vec<$entity::Model>, DbErr> {
+                let db = get_business_db().await?;
+                $entity::Entity::find()
+                    .filter($entity::Column::ModelId.eq(model_id))
+                    .all(db)
+                    .await
+            }
+
+            /// 根据 ID 查询
+            pub async fn find_by_ids(
+                model_id: String,
+                ids: Vec<String>,
+            ) -> Result<Vec<$entity::Model>, DbErr> {
+                let db = get_business_db().await?;
+                $entity::Entity::find()
+                    .filter($entity::Column::ModelId.eq(model_id))
+                    .filter($entity::Column::Id.is_in(ids))
+                    .all(db)
+                    .await
+            }
+
+            /// 根据 ID 删除，返回删除条数
+            pub async fn delete_by_ids(model_id: String, ids: Vec<String>) -> Result<u32, DbErr> {
+                let db = get_business_db().await?;
+                let res = $entity::Entity::delete_many()
+                    .filter($entity::Column::ModelId.eq(model_id))
+                    .filter($entity::Column::Id.is_in(ids))
+                    .exec(db)
+                    .await?;
+                Ok(res.rows_affected as u32)
+            }
+
+            /// 删除模型下的全部记录，返回删除条数
+            pub async fn delete_by_model_id(model_id: String) -> Result<u32, DbErr> {
+                let db = get_business_db().await?;
+                let res = $entity::Entity::delete_many()
+                    .filter($entity::Column::ModelId.eq(model_id))
+                    .exec(db)
+                    .await?;
+                Ok(res.rows_affected as u32)
+            }
+        }
+    };
+    // 画布图元：额外支持按画布页查询/删除
+    ($service_name:ident, $entity:ident, page) => {
+        impl_flowsheet_service!($service_name, $entity);
+
+        impl $service_name {
+            /// 查询画布页下的全部图元
+            pub async fn find_by_page_id(
+                model_id: String,
+                graphic_page_id: String,
+            ) -> Result<Vec<$entity::Model>, DbErr> {
+                let db = get_business_db().await?;
+                $entity::Entity::find()
+                    .filter($entity::Column::ModelId.eq(model_id))
+                    .filter($entity::Column::GraphicPageId.eq(graphic_page_id))
+                    .all(db)
+                    .await
+            }
+
+            /// 删除画布页下的全部图元，返回删除条数
+            pub async fn delete_by_page_id(
+                model_id: String,
+                graphic_page_id: String,
+            ) -> Result<u32, DbErr> {
+                let db = get_business_db().await?;
+                let res = $entity::Entity::delete_many()
+                    .filter($entity::Column::ModelId.eq(model_id))
+                    .filter($entity::Column::GraphicPageId.eq(graphic_page_id))
+                    .exec(db)
+                    .await?;
+                Ok(res.rows_affected as u32)
+            }
+        }
+    };
+}
+
+impl_flowsheet_service!(MaterialService, material);
+impl_flowsheet_service!(EnergyService, energy);
+impl_flowsheet_service!(LogicService, logic);
+impl_flowsheet_service!(SensorService, sensor);
+impl_flowsheet_service!(AiuoService, aiuo);
+impl_flowsheet_service!(ModuleService, module);
+impl_flowsheet_service!(ScriptUoService, script_uo);
+impl_flowsheet_service!(ConnectionService, connection);
+impl_flowsheet_service!(NodeService, node, page);
+impl_flowsheet_service!(MarkNodeService, mark_node, page);
+impl_flowsheet_service!(EdgeService, edge, page);
+
+impl ConnectionService {
+    /// 查询与指定单元相连的全部连接（作为来源或去向）
+    pub async fn find_by_unit_id(
+        model_id: String,
+        unit_id: String,
+    ) -> Result<Vec<connection::Model>, DbErr> {
+        let db = get_business_db().await?;
+        connection::Entity::find()
+            .filter(connection::Column::ModelId.eq(model_id))
+            .filter(
+                connection::Column::InPortFrom
+                    .eq(unit_id.clone())
+                    .or(connection::Column::OutPortTo.eq(unit_id)),
+            )
+            .all(db)
+            .await
+    }
+}
+
+/// 模型的完整流程图
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[napi(object, namespace = "flowsheet")]
+pub struct FlowsheetGraph {
+    pub materials: Vec<material::Model>,
+    pub energies: Vec<energy::Model>,
+    pub logics: Vec<logic::Model>,
+    pub sensors: Vec<sensor::Model>,
+    pub aiuos: Vec<aiuo::Model>,
+    pub modules: Vec<module::Model>,
+    pub script_uos: Vec<script_uo::Model>,
+    pub connections: Vec<connection::Model>,
+    pub nodes: Vec<node::Model>,
+    pub mark_nodes: Vec<mark_node::Model>,
+    pub edges: Vec<edge::Model>,
+}
+
+// 流程图各表统一处理
+macro_rules! for_each_flowsheet_table {
+    ($mac:ident) => {
+        $mac!(materials, material);
+        $mac!(energies, energy);
+        $mac!(logics, logic);
+        $mac!(sensors, sensor);
+        $mac!(aiuos, aiuo);
+        $mac!(modules, module);
+        $mac!(script_uos, script_uo);
+        $mac!(connections, connection);
+        $mac!(nodes, node);
+        $mac!(mark_nodes, mark_node);
+        $mac!(edges, edge);
+    };
+}
+
+impl FlowsheetGraph {
+    /// 各表行数统计
+    pub fn table_counts(&self) -> BTreeMap<String, u32> {
+        let mut counts = BTreeMap::new();
+        macro_rules! count {
+            ($field:ident, $entity:ident) => {
+                counts.insert(
+                    $entity::Entity.table_name().to_string(),
+                    self.$field.len() as u32,
+                );
+            };
+        }
+        for_each_flowsheet_table!(count);
+        counts
+    }
+
+    /// 校验每一行都属于 model_id：主键含 model_id，混入其他模型的行会写进（或覆盖）那个模型
+    pub fn check_model_id(&self, model_id: &str) -> Result<(), DbErr> {
+        macro_rules! check {
+            ($field:ident, $entity:ident) => {
+                if let Some(r) = self.$field.iter().find(|r| r.model_id != model_id) {
+                    return Err(DbErr::Custom(format!(
+                        "流程图错误: {} 中的 {} 属于模型 {}，不能保存到模型 {}",
+                        $entity::Entity.table_name(),
+                        r.id,
+                        r.model_id,
+                        model_id
+                    )));
+                }
+            };
+        }
+        for_each_flowsheet_table!(check);
+        Ok(())
+    }
+
+    /// 改挂到新模型下（模型另存为 / 导入），图元 ID 保持不变，只改写画布页引用
+    pub fn rebind(&mut self, new_model_id: &str, ids: &IdRemap) {
+        macro_rules! rebind {
+            ($field:ident, $entity:ident) => {
+                for r in self.$field.iter_mut() {
+                    r.model_id = new_model_id.to_string();
+                }
+            };
+        }
+        for_each_flowsheet_table!(rebind);
+        for r in self.nodes.iter_mut() {
+            r.graphic_page_id = ids.get(&r.graphic_page_id);
+        }
+        for r in self.mark_nodes.iter_mut() {
+            r.graphic_page_id = ids.get(&r.graphic_page_id);
+        }
+        for r in self.edges.iter_mut() {
+            r.graphic_page_id = ids.get(&r.graphic_page_id);
+        }
+    }
+}
+
+/// 读取模型的完整流程图
+pub async fn load_flowsheet<C: ConnectionTrait>(
+    db: &C,
+    model_id: &str,
+) -> Result<FlowsheetGraph, DbErr> {
+    let mut graph = FlowsheetGraph::default();
+    macro_rules! load {
+        ($field:ident, $entity:ident) => {
+            graph.$field = $entity::Entity::find()
+                .filter($entity::Column::ModelId.eq(model_id))
+                .all(db)
+                .await?;
+        };
+    }
+    for_each_flowsheet_table!(load);
+    Ok(graph)
+}
+
+/// 写入流程图（upsert，调用方负责开启事务），返回写入条数
+pub async fn save_flowsheet<C: ConnectionTrait>(
+    db: &C,
+    graph: FlowsheetGraph,
+) -> Result<u32, DbErr> {
+    let mut count = 0;
+    macro_rules! save {
+        ($field:ident, $entity:ident) => {
+            count += upsert_rows::<$entity::Entity, _>(db, graph.$field).await?;
+        };
+    }
+    for_each_flowsheet_table!(save);
+    Ok(count)
+}
+
+/// 删除模型的完整流程图，返回删除条数
+pub async fn delete_flowsheet<C: ConnectionTrait>(db: &C, model_id: &str) -> Result<u32, DbErr> {
+    let mut count = 0;
+    macro_rules! delete {
+        ($field:ident, $entity:ident) => {
+            count += $entity::Entity::delete_many()
+                .filter($entity::Column::ModelId.eq(model_id))
+                .exec(db)
+                .await?
+                .rows_affected as u32;
+        };
+    }
+    for_each_flowsheet_table!(delete);
+    Ok(count)
+}
+
+/// 查询模型的完整流程图
+pub async fn get_flowsheet(model_id: String) -> Result<FlowsheetGraph, DbErr> {
+    let db = get_business_db().await?;
+    load_flowsheet(db, &model_id).await
+}
+
+/// 在一个事务内保存完整流程图，所有行都必须属于 model_id
+pub async fn save_flowsheet_graph(model_id: String, graph: FlowsheetGraph) -> Result<u32, DbErr> {
+    graph.check_model_id(&model_id)?;
+    let db = get_business_db().await?;
+    db.transaction::<_, u32, DbErr>(|txn| Box::pin(async move { save_flowsheet(txn, graph).await }))
+        .await
+        .map_err(|e| match e {
+            TransactionError::Connection(e) => e,
+            TransactionError::Transaction(e) => e,
+        })
+}
+
+/// 用给定流程图整体替换模型现有流程图，所有行都必须属于 model_id
+pub async fn replace_flowsheet_graph(
+    model_id: String,
+    graph: FlowsheetGraph,
+) -> Result<u32, DbErr> {
+    graph.check_model_id(&model_id)?;
+    let db = get_business_db().await?;
+    db.transaction::<_, u32, DbErr>(|txn| {
+        Box::pin(async move {
+            delete_flowsheet(txn, &model_id).await?;
+            save_flowsheet(txn, graph).await
+        })
+    })
+    .await
+    .map_err(|e| match e {
+        TransactionError::Connection(e) => e,
+        TransactionError::Transaction(e) => e,
+    })
+}
+
+/// 删除模型的完整流程图
+pub async fn delete_flowsheet_graph(model_id: String) -> Result<u32, DbErr> {
+    let db = get_business_db().await?;
+    db.transaction::<_, u32, DbErr>(|txn| {
+        Box::pin(async move { delete_flowsheet(txn, &model_id).await })
+    })
+    .await
+    .map_err(|e| match e {
+        TransactionError::Connection(e) => e,
+        TransactionError::Transaction(e) => e,
+    })
+}
@@ -0,0 +1,186 @@
+use crate::calc_handle::flowsheet_topology::{ProcessNetwork, VertexKind};
+use crate::service_database::database_business::db_business_connection::get_business_db;
+use crate::service_database::database_business::service::flowsheet::flowsheet_service::{
+    FlowsheetGraph, load_flowsheet,
+};
+use napi_derive::napi;
+use sea_orm::DbErr;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+
+// ======================================
+// 流程图拓扑分析：由连接表与画布连线构建过程网络并分析
+// ======================================
+
+/// 未被任何连接引用的端口
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[napi(object, namespace = "flowsheet")]
+pub struct FlowsheetOrphanPort {
+    pub node_id: String,
+    /// 图元对应的单元 / 物流 ID
+    pub element_id: String,
+    pub port_id: String,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[napi(object, namespace = "flowsheet")]
+pub struct FlowsheetTopologyReport {
+    pub vertex_count: u32,
+    pub edge_count: u32,
+    /// 没有任何连接的单元
+    pub disconnected_units: Vec<String>,
+    /// 缺少进料端的物流
+    pub streams_without_source: Vec<String>,
+    /// 缺少产品端的物流
+    pub streams_without_destination: Vec<String>,
+    pub orphan_ports: Vec<FlowsheetOrphanPort>,
+    /// 回路（强连通分量）
+    pub recycle_loops: Vec<Vec<String>>,
+    /// 建议断裂物流
+    pub tear_streams: Vec<String>,
+    /// 建议计算顺序
+    pub calculation_order: Vec<String>,
+}
+
+/// 解析连线端点：X6 格式 {"cell": "...", "port": "..."} 或直接为图元 ID
+fn parse_endpoint(text: &str) -> (String, String) {
+    match serde_json::from_str::<Value>(text) {
+        Ok(Value::Object(map)) => {
+            let field = |k: &str| {
+                map.get(k)
+                    .and_then(Value::as_str)
+                    .unwrap_or_default()
+                    .to_string()
+            };
+            (field("cell"), field("port"))
+        }
+        Ok(Value::String(s)) => (s, String::new()),
+        _ => (text.trim().to_string(), String::new()),
+    }
+}
+
+/// 解析图元端口：{"items": [{"id": ...}]} 或 [{"id": ...}]
+fn parse_port_ids(text: &str) -> Vec<String> {
+    let items = match serde_json::from_str::<Value>(text) {
+        Ok(Value::Object(mut map)) => match map.remove("items") {
+            Some(Value::Array(items)) => items,
+            _ => vec![],
+        },
+        Ok(Value::Array(items)) => items,
+        _ => vec![],
+    };
+    items
+        .iter()
+        .filter_map(|p| p.get("id").and_then(Value::as_str))
+        .map(str::to_string)
+        .collect()
+}
+
+/// 由流程图构建过程网络
+pub fn build_process_network(graph: &FlowsheetGraph) -> ProcessNetwork {
+    let mut net = ProcessNetwork::new();
+    macro_rules! vertices {
+        ($field:ident, $kind:expr) => {
+            for r in graph.$field.iter() {
+                net.add_vertex(&r.id, $kind);
+            }
+        };
+    }
+    vertices!(materials, VertexKind::Stream);
+    vertices!(energies, VertexKind::Stream);
+    vertices!(modules, VertexKind::Unit);
+    vertices!(logics, VertexKind::Unit);
+    vertices!(sensors, VertexKind::Unit);
+    vertices!(aiuos, VertexKind::Unit);
+    vertices!(script_uos, VertexKind::Unit);
+
+    // 连接表：in_port_from（上游）→ out_port_to（下游）
+    for c in graph.connections.iter() {
+        net.add_edge(&c.in_port_from, &c.out_port_to);
+    }
+
+    // 画布连线：端点为图元，经图元的 source_id 映射到单元；连线自身绑定物流时插入物流顶点
+    let element_of: HashMap<&str, &str> = graph
+        .nodes
+        .iter()
+        .map(|n| {
+            let element = if n.source_id.is_empty() {
+                n.id.as_str()
+            } else {
+                n.source_id.as_str()
+            };
+            (n.id.as_str(), element)
+        })
+        .collect();
+    let resolve =
+        |cell: &str| -> String { element_of.get(cell).copied().unwrap_or(cell).to_string() };
+    for e in graph.edges.iter() {
+        let from = resolve(&parse_endpoint(&e.source).0);
+        let to = resolve(&parse_endpoint(&e.target).0);
+        if !e.source_id.is_empty() && e.source_id != from && e.source_id != to {
+            net.add_edge(&from, &e.source_id);
+            net.add_edge(&e.source_id, &to);
+        } else {
+            net.add_edge(&from, &to);
+        }
+    }
+    net
+}
+
+/// 找出图元上定义但未被任何连接或连线引用的端口
+fn find_orphan_ports(graph: &FlowsheetGraph) -> Vec<FlowsheetOrphanPort> {
+    let mut used: HashSet<String> = HashSet::new();
+    for c in graph.connections.iter() {
+        used.insert(c.in_port_id.clone());
+        used.insert(c.out_port_id.clone());
+    }
+    for e in graph.edges.iter() {
+        used.insert(parse_endpoint(&e.source).1);
+        used.insert(parse_endpoint(&e.target).1);
+    }
+
+    let mut orphans: Vec<FlowsheetOrphanPort> = graph
+        .nodes
+        .iter()
+        .flat_map(|n| {
+            parse_port_ids(&n.ports)
+                .into_iter()
+                .filter(|p| !used.contains(p))
+                .map(|port_id| FlowsheetOrphanPort {
+                    node_id: n.id.clone(),
+                    element_id: n.source_id.clone(),
+                    port_id,
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect();
+    orphans.sort_by(|a, b| (&a.node_id, &a.port_id).cmp(&(&b.node_id, &b.port_id)));
+    orphans
+}
+
+/// 分析流程图拓扑
+pub fn analyze_flowsheet(graph: &FlowsheetGraph) -> FlowsheetTopologyReport {
+    let net = build_process_network(graph);
+    let analysis = net.analyze();
+    FlowsheetTopologyReport {
+        vertex_count: net.len() as u32,
+        edge_count: net.edge_count() as u32,
+        disconnected_units: analysis.disconnected_units,
+        streams_without_source: analysis.streams_without_source,
+        streams_without_destination: analysis.streams_without_destination,
+        orphan_ports: find_orphan_ports(graph),
+        recycle_loops: analysis.recycle_loops,
+        tear_streams: analysis.tear_streams,
+        calculation_order: analysis.calculation_order,
+    }
+}
+
+/// 读取模型流程图并分析拓扑
+pub async fn analyze_flowsheet_topology(
+    model_id: String,
+) -> Result<FlowsheetTopologyReport, DbErr> {
+    let db = get_business_db().await?;
+    let graph = load_flowsheet(db, &model_id).await?;
+    Ok(analyze_flowsheet(&graph))
+}
@@ -0,0 +1,2 @@
+pub mod flowsheet_service;
+pub mod flowsheet_topology_service;
@@ -1,4 +1,8 @@
+pub mod model_fluid_package_binary_estimate_service;
+pub mod model_fluid_package_binary_regression_service;
 pub mod model_fluid_package_binary_service;
 pub mod model_fluid_package_service;
+pub mod model_fluid_package_thermo_service;
 pub mod model_reaction_detail_service;
 pub mod model_reaction_package_service;
+pub mod model_reaction_validate_service;
@@ -0,0 +1,431 @@
+use crate::calc_handle::activity_model::{ActivityModel, PairCoefficients};
+use crate::calc_handle::binary_estimation::{
+    DEFAULT_NRTL_ALPHA, chueh_prausnitz_kij, estimate_activity_pair,
+};
+use crate::calc_handle::unifac::{GroupCounts, Unifac};
+use crate::service_database::database_business::service::component::component_all_detail_service::get_normal_detail_by_channel_id;
+use crate::service_database::database_business::service::fluid_package::model_fluid_package_binary_service::*;
+use crate::service_database::database_business::service::fluid_package::model_fluid_package_service::get_fluid_package_by_id;
+use crate::service_database::database_business::service::fluid_package::model_fluid_package_thermo_service::{
+    find_base, load_component_base, package_func_codes,
+};
+use crate::service_database::database_physical_property::service::physical_component_base_service::PhysicalBaseDTO;
+use crate::service_database::database_physical_property::service::physical_enum::BinaryFuncCode;
+use crate::tool_handle::id_tool::new_id;
+use napi_derive::napi;
+use crate::service_database::database_business::db_business_connection::get_business_db;
+use sea_orm::{ConnectionTrait, DbErr, TransactionTrait};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value, json};
+use std::collections::HashSet;
+
+// ======================================
+// 流体包缺失二元参数估算
+// 活度系数模型按 UNIFAC 回归，立方型状态方程按 Chueh-Prausnitz 或 kij = 0，PSRK 以零参数占位
+// 估算结果写入 model_fluid_package_binary_*，source = ESTIMATED
+// ======================================
+
+/// 物性库中 UNIFAC 基团的编码前缀，如 UNIFAC_1 = 2 表示含 2 个 CH3
+const UNIFAC_GROUP_PREFIXES: [&str; 2] = ["UNIFAC_", "UFGRP_"];
+
+/// 未指定时的估算温度范围 K
+const DEFAULT_T_MIN: f64 = 273.15;
+const DEFAULT_T_MAX: f64 = 373.15;
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[napi(object, namespace = "modelFluidPackageBinary")]
+pub struct BinaryEstimateOptions {
+    /// 需要估算的二元方法，默认取流体包物性计算方法中选用的方法
+    pub func_codes: Option<Vec<BinaryFuncCode>>,
+    /// 回归温度范围 K，默认 273.15 ~ 373.15
+    pub temperature_min: Option<f64>,
+    pub temperature_max: Option<f64>,
+    /// NRTL 非随机参数，默认 0.3
+    pub nrtl_alpha: Option<f64>,
+    /// 先删除已有的估算参数再重新估算
+    pub replace_estimated: Option<bool>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[napi(object, namespace = "modelFluidPackageBinary")]
+pub struct EstimatedBinaryPair {
+    pub func_code: BinaryFuncCode,
+    pub compound_i: String,
+    pub compound_i_name: String,
+    pub compound_j: String,
+    pub compound_j_name: String,
+    /// 估算方法：UNIFAC / CHUEH_PRAUSNITZ / ZERO
+    pub method: String,
+    /// 写入的参数（列名 → 数值）
+    pub parameters: Value,
+    /// UNIFAC 回归 ln γ 残差均方根
+    pub rms: Option<f64>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[napi(object, namespace = "modelFluidPackageBinary")]
+pub struct SkippedBinaryPair {
+    pub func_code: BinaryFuncCode,
+    pub compound_i: String,
+    pub compound_j: String,
+    pub reason: String,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[napi(object, namespace = "modelFluidPackageBinary")]
+pub struct BinaryEstimateReport {
+    pub package_id: String,
+    /// 已估算并写入的组分对
+    pub filled: Vec<EstimatedBinaryPair>,
+    /// 无法估算的组分对
+    pub skipped: Vec<SkippedBinaryPair>,
+    /// 删除的旧估算记录数
+    pub replaced: u32,
+}
+
+fn estimate_err(msg: impl std::fmt::Display) -> DbErr {
+    DbErr::Custom(format!("二元参数估算错误: {}", msg))
+}
+
+/// 组分：CAS 号、名称与物性库基础物性
+struct Compound {
+    cas_no: String,
+    name: String,
+    base: Vec<PhysicalBaseDTO>,
+}
+
+/// 读取物性库中的 UNIFAC 基团组成
+fn unifac_groups(base: &[PhysicalBaseDTO]) -> GroupCounts {
+    base.iter()
+        .filter_map(|b| {
+            let code = b.code.to_ascii_uppercase();
+            let id = UNIFAC_GROUP_PREFIXES
+                .iter()
+                .find_map(|p| code.strip_prefix(p))?
+                .parse::<u32>()
+                .ok()?;
+            (b.value > 0.0).then_some((id, b.value))
+        })
+        .collect()
+}
+
+/// 流体包中已有参数的组分对（无序）；skip_estimated 为 true 时不计入估算的参数（将被替换）
+macro_rules! existing_pairs {
+    ($service:ident, $package_id:expr, $skip_estimated:expr) => {
+        $service::find_by_package_id($package_id)
+            .await?
+            .into_iter()
+            .filter(|m| {
+                !($skip_estimated
+                    && BinaryParameterSource::parse(&m.source) == BinaryParameterSource::Estimated)
+            })
+            .map(|m| unordered(&m.compound_i, &m.compound_j))
+            .collect::<HashSet<_>>()
+    };
+}
+
+fn unordered(a: &str, b: &str) -> (String, String) {
+    if a <= b {
+        (a.to_string(), b.to_string())
+    } else {
+        (b.to_string(), a.to_string())
+    }
+}
+
+async fn load_existing_pairs(
+    package_id: &str,
+    func_code: BinaryFuncCode,
+    skip_estimated: bool,
+) -> Result<HashSet<(String, String)>, DbErr> {
+    let id = package_id.to_string();
+    let skip = skip_estimated;
+    Ok(match func_code {
+        BinaryFuncCode::PR => existing_pairs!(PrService, id, skip),
+        BinaryFuncCode::RK => existing_pairs!(RkService, id, skip),
+        BinaryFuncCode::SRK => existing_pairs!(SrkService, id, skip),
+        BinaryFuncCode::NRTL => existing_pairs!(NrtlService, id, skip),
+        BinaryFuncCode::NRTLRK => existing_pairs!(NrtlRkService, id, skip),
+        BinaryFuncCode::WILSON => existing_pairs!(WilsonService, id, skip),
+        BinaryFuncCode::UNIQUAC => existing_pairs!(UniquacService, id, skip),
+        BinaryFuncCode::PSRK => existing_pairs!(PsrkService, id, skip),
+    })
+}
+
+async fn delete_estimated<C: ConnectionTrait>(
+    db: &C,
+    package_id: &str,
+    func_code: BinaryFuncCode,
+) -> Result<u64, DbErr> {
+    let id = package_id.to_string();
+    let source = BinaryParameterSource::Estimated;
+    match func_code {
+        BinaryFuncCode::PR => PrService::delete_by_package_id_and_source(db, id, source).await,
+        BinaryFuncCode::RK => RkService::delete_by_package_id_and_source(db, id, source).await,
+        BinaryFuncCode::SRK => SrkService::delete_by_package_id_and_source(db, id, source).await,
+        BinaryFuncCode::NRTL => NrtlService::delete_by_package_id_and_source(db, id, source).await,
+        BinaryFuncCode::NRTLRK => {
+            NrtlRkService::delete_by_package_id_and_source(db, id, source).await
+        }
+        BinaryFuncCode::WILSON => {
+            WilsonService::delete_by_package_id_and_source(db, id, source).await
+        }
+        BinaryFuncCode::UNIQUAC => {
+            UniquacService::delete_by_package_id_and_source(db, id, source).await
+        }
+        BinaryFuncCode::PSRK => PsrkService::delete_by_package_id_and_source(db, id, source).await,
+    }
+}
+
+async fn insert_rows<C: ConnectionTrait>(
+    db: &C,
+    func_code: BinaryFuncCode,
+    rows: Vec<Value>,
+) -> Result<u64, DbErr> {
+    match func_code {
+        BinaryFuncCode::PR => PrService::insert_full_rows(db, rows).await,
+        BinaryFuncCode::RK => RkService::insert_full_rows(db, rows).await,
+        BinaryFuncCode::SRK => SrkService::insert_full_rows(db, rows).await,
+        BinaryFuncCode::NRTL => NrtlService::insert_full_rows(db, rows).await,
+        BinaryFuncCode::NRTLRK => NrtlRkService::insert_full_rows(db, rows).await,
+        BinaryFuncCode::WILSON => WilsonService::insert_full_rows(db, rows).await,
+        BinaryFuncCode::UNIQUAC => UniquacService::insert_full_rows(db, rows).await,
+        BinaryFuncCode::PSRK => PsrkService::insert_full_rows(db, rows).await,
+    }
+}
+
+fn activity_model_of(func_code: BinaryFuncCode) -> Option<ActivityModel> {
+    match func_code {
+        BinaryFuncCode::NRTL | BinaryFuncCode::NRTLRK => Some(ActivityModel::Nrtl),
+        BinaryFuncCode::WILSON => Some(ActivityModel::Wilson),
+        BinaryFuncCode::UNIQUAC => Some(ActivityModel::Uniquac),
+        _ => None,
+    }
+}
+
+/// 活度系数模型的参数列：i → j 写入 *ij，j → i 写入 *ji；NRTL 的 α 只存 cij
+pub(crate) fn activity_parameters(
+    func_code: BinaryFuncCode,
+    forward: PairCoefficients,
+    backward: PairCoefficients,
+) -> Map<String, Value> {
+    let mut p = Map::new();
+    let mut put = |k: &str, v: f64| {
+        p.insert(k.to_string(), json!(v));
+    };
+    put("aij", forward.a);
+    put("aji", backward.a);
+    put("bij", forward.b);
+    put("bji", backward.b);
+    put("cij", forward.c);
+    if func_code != BinaryFuncCode::NRTL && func_code != BinaryFuncCode::NRTLRK {
+        put("cji", backward.c);
+        put("dji", backward.d);
+    }
+    put("dij", forward.d);
+    put("eij", forward.e);
+    put("eji", backward.e);
+    put("fij", forward.f);
+    put("fji", backward.f);
+    p
+}
+
+/// 组装完整记录：覆盖所有二元参数表的字段，参数以字符串存储，未给出的参数列为 "0"
+fn full_row(
+    package_id: &str,
+    ci: &Compound,
+    cj: &Compound,
+    parameters: &Map<String, Value>,
+    t_range: (String, String),
+) -> Value {
+    let mut row = Map::new();
+    for col in [
+        "aij", "aji", "bij", "bji", "cij", "cji", "dij", "dji", "eij", "eji", "fij", "fji", "kaij",
+        "kbij", "kcij", "tij", "tji", "vij", "vji",
+    ] {
+        let value = parameters.get(col).and_then(Value::as_f64).unwrap_or(0.0);
+        row.insert(col.to_string(), json!(value.to_string()));
+    }
+    row.extend([
+        ("id".to_string(), json!(new_id())),
+        ("fluid_package_id".to_string(), json!(package_id)),
+        ("compound_i".to_string(), json!(ci.cas_no)),
+        ("compound_i_name".to_string(), json!(ci.name)),
+        ("compound_j".to_string(), json!(cj.cas_no)),
+        ("compound_j_name".to_string(), json!(cj.name)),
+        ("min_t".to_string(), json!(t_range.0)),
+        ("max_t".to_string(), json!(t_range.1)),
+        ("is_default".to_string(), json!(1)),
+        ("is_default_id".to_string(), json!("")),
+        (
+            "source".to_string(),
+            json!(BinaryParameterSource::Estimated.as_str()),
+        ),
+    ]);
+    Value::Object(row)
+}
+
+/// 单个组分对的估算结果
+struct PairEstimate {
+    method: &'static str,
+    parameters: Map<String, Value>,
+    rms: Option<f64>,
+}
+
+/// 估算单个组分对，失败时返回无法估算的原因
+fn estimate_pair(
+    func_code: BinaryFuncCode,
+    ci: &Compound,
+    cj: &Compound,
+    temperatures: &[f64],
+    nrtl_alpha: f64,
+) -> Result<PairEstimate, String> {
+    if let Some(model) = activity_model_of(func_code) {
+        let gi = unifac_groups(&ci.base);
+        let gj = unifac_groups(&cj.base);
+        for (c, g) in [(ci, &gi), (cj, &gj)] {
+            if g.is_empty() {
+                return Err(format!("物性库中没有组分 {} 的 UNIFAC 基团", c.cas_no));
+            }
+        }
+        let unifac = Unifac::new(&[gi, gj])?;
+        let est = estimate_activity_pair(model, &unifac, temperatures, nrtl_alpha)?;
+        return Ok(PairEstimate {
+            method: "UNIFAC",
+            parameters: activity_parameters(func_code, est.forward, est.backward),
+            rms: Some(est.rms),
+        });
+    }
+    let mut parameters = Map::new();
+    let method = match func_code {
+        BinaryFuncCode::PSRK => {
+            // PSRK 混合规则本身由 UNIFAC 基团给出，组分对参数以零占位
+            "ZERO"
+        }
+        _ => {
+            // 关联式对体积单位是零次齐次的，直接使用物性库数值
+            let vc = |c: &Compound| find_base(&c.base, &["VC", "CRITICAL_VOLUME"]).map(|b| b.value);
+            match vc(ci)
+                .zip(vc(cj))
+                .and_then(|(a, b)| chueh_prausnitz_kij(a, b))
+            {
+                Some(kij) => {
+                    parameters.insert("kaij".to_string(), json!(kij));
+                    "CHUEH_PRAUSNITZ"
+                }
+                None => {
+                    parameters.insert("kaij".to_string(), json!(0.0));
+                    "ZERO"
+                }
+            }
+        }
+    };
+    Ok(PairEstimate {
+        method,
+        parameters,
+        rms: None,
+    })
+}
+
+/// 检测流体包缺失的二元参数组分对并估算写入
+pub async fn estimate_missing_binary_parameters(
+    package_id: String,
+    options: Option<BinaryEstimateOptions>,
+) -> Result<BinaryEstimateReport, DbErr> {
+    let options = options.unwrap_or_default();
+    let package = get_fluid_package_by_id(package_id.clone())
+        .await?
+        .ok_or_else(|| estimate_err(format!("流体包 {} 不存在", package_id)))?;
+    let func_codes = match options.func_codes {
+        Some(codes) => codes,
+        None => package_func_codes(&package_id).await?,
+    };
+    if func_codes.is_empty() {
+        return Err(estimate_err(format!(
+            "流体包 {} 未选用任何二元方法，请指定 func_codes",
+            package_id
+        )));
+    }
+
+    let t_min = options.temperature_min.unwrap_or(DEFAULT_T_MIN);
+    let t_max = options.temperature_max.unwrap_or(DEFAULT_T_MAX);
+    if !(t_min > 0.0 && t_max >= t_min) {
+        return Err(estimate_err(format!(
+            "估算温度范围无效: [{}, {}] K",
+            t_min, t_max
+        )));
+    }
+    let temperatures = if t_max > t_min {
+        vec![t_min, (t_min + t_max) / 2.0, t_max]
+    } else {
+        vec![t_min]
+    };
+    let nrtl_alpha = options.nrtl_alpha.unwrap_or(DEFAULT_NRTL_ALPHA);
+
+    let detail = get_normal_detail_by_channel_id(package.compound_channel_id).await?;
+    let cas_list: Vec<String> = detail.iter().map(|d| d.cas_no.clone()).collect();
+    let compounds: Vec<Compound> = detail
+        .into_iter()
+        .zip(load_component_base(&cas_list).await?)
+        .map(|(d, base)| Compound {
+            cas_no: d.cas_no,
+            name: d.name,
+            base,
+        })
+        .collect();
+
+    let mut report = BinaryEstimateReport {
+        package_id: package_id.clone(),
+        ..Default::default()
+    };
+    let replace = options.replace_estimated.unwrap_or(false);
+    let db = get_business_db().await?;
+    for func_code in func_codes {
+        let existing = load_existing_pairs(&package_id, func_code, replace).await?;
+        let mut rows = Vec::new();
+        for (i, ci) in compounds.iter().enumerate() {
+            for cj in compounds.iter().skip(i + 1) {
+                if ci.cas_no == cj.cas_no || existing.contains(&unordered(&ci.cas_no, &cj.cas_no)) {
+                    continue;
+                }
+                match estimate_pair(func_code, ci, cj, &temperatures, nrtl_alpha) {
+                    Ok(PairEstimate {
+                        method,
+                        parameters,
+                        rms,
+                    }) => {
+                        let t_range = if rms.is_some() {
+                            (t_min.to_string(), t_max.to_string())
+                        } else {
+                            (String::new(), String::new())
+                        };
+                        rows.push(full_row(&package_id, ci, cj, &parameters, t_range));
+                        report.filled.push(EstimatedBinaryPair {
+                            func_code,
+                            compound_i: ci.cas_no.clone(),
+                            compound_i_name: ci.name.clone(),
+                            compound_j: cj.cas_no.clone(),
+                            compound_j_name: cj.name.clone(),
+                            method: method.to_string(),
+                            parameters: Value::Object(parameters),
+                            rms,
+                        });
+                    }
+                    Err(reason) => report.skipped.push(SkippedBinaryPair {
+                        func_code,
+                        compound_i: ci.cas_no.clone(),
+                        compound_j: cj.cas_no.clone(),
+                        reason,
+                    }),
+                }
+            }
+        }
+        // 删除旧估算与写入新估算在同一事务中，写入失败时保留原有的估算参数
+        let txn = db.begin().await?;
+        if replace {
+            report.replaced += delete_estimated(&txn, &package_id, func_code).await? as u32;
+        }
+        insert_rows(&txn, func_code, rows).await?;
+        txn.commit().await?;
+    }
+    Ok(report)
+}
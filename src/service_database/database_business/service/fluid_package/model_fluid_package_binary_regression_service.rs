@@ -0,0 +1,524 @@
+use crate::calc_handle::activity_model::{ActivityModel, PairCoefficients, UniquacStructure};
+use crate::calc_handle::binary_estimation::DEFAULT_NRTL_ALPHA;
+use crate::calc_handle::binary_regression::{
+    FitParameter, LlePoint, RegressionProblem, VlePoint, regress_binary,
+};
+use crate::service_database::database_business::service::fluid_package::model_fluid_package_binary_estimate_service::activity_parameters;
+use crate::service_database::database_business::service::fluid_package::model_fluid_package_binary_service::*;
+use crate::service_database::database_business::service::fluid_package::model_fluid_package_thermo_service::load_uniquac_structure;
+use crate::service_database::database_physical_property::service::physical_component_temperature_equation_service::{
+    TemperatureEquationEvalQuery, evaluate_physical_temperature_equation,
+};
+use crate::service_database::database_physical_property::service::physical_enum::BinaryFuncCode;
+use crate::tool_handle::id_tool::new_id;
+use napi_derive::napi;
+use sea_orm::DbErr;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+
+// ======================================
+// 由实验 VLE / LLE 数据回归 NRTL / Wilson / UNIQUAC 二元参数，并可写入流体包
+// 温度 K，压力 Pa；饱和蒸气压未给出时按物性库温度关联方程计算
+// ======================================
+
+/// 未指定时使用的饱和蒸气压性质编码
+const DEFAULT_VAPOR_PRESSURE_CODE: &str = "VP";
+
+#[napi(string_enum, namespace = "modelFluidPackageBinary")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BinaryFitParameter {
+    Aij,
+    Aji,
+    Bij,
+    Bji,
+    /// NRTL 为非随机参数 α，Wilson / UNIQUAC 为 cij·ln T 项
+    Cij,
+}
+
+impl From<BinaryFitParameter> for FitParameter {
+    fn from(p: BinaryFitParameter) -> Self {
+        match p {
+            BinaryFitParameter::Aij => FitParameter::Aij,
+            BinaryFitParameter::Aji => FitParameter::Aji,
+            BinaryFitParameter::Bij => FitParameter::Bij,
+            BinaryFitParameter::Bji => FitParameter::Bji,
+            BinaryFitParameter::Cij => FitParameter::Cij,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[napi(object, namespace = "modelFluidPackageBinary")]
+pub struct BinaryVlePoint {
+    /// 温度 K
+    pub temperature: f64,
+    /// 压力 Pa
+    pub pressure: f64,
+    /// 液相中组分 i 的摩尔分数
+    pub x1: f64,
+    /// 汽相中组分 i 的摩尔分数，T-P-x 数据可不给出
+    pub y1: Option<f64>,
+    /// 组分 i、j 的饱和蒸气压 Pa，默认按物性库计算
+    pub psat1: Option<f64>,
+    pub psat2: Option<f64>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[napi(object, namespace = "modelFluidPackageBinary")]
+pub struct BinaryLlePoint {
+    /// 温度 K
+    pub temperature: f64,
+    /// 两液相中组分 i 的摩尔分数
+    pub x1_phase_a: f64,
+    pub x1_phase_b: f64,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[napi(object, namespace = "modelFluidPackageBinary")]
+pub struct BinaryRegressionQuery {
+    /// NRTL / NRTLRK / WILSON / UNIQUAC
+    pub func_code: BinaryFuncCode,
+    /// 组分 i、j 的 CAS 号
+    pub compound_i: String,
+    pub compound_j: String,
+    pub compound_i_name: Option<String>,
+    pub compound_j_name: Option<String>,
+    pub vle_points: Option<Vec<BinaryVlePoint>>,
+    pub lle_points: Option<Vec<BinaryLlePoint>>,
+    /// 回归的参数，默认 bij、bji
+    pub fit_parameters: Option<Vec<BinaryFitParameter>>,
+    /// 参数初值（列名 → 数值，如 aij、cij），NRTL 的 cij 默认 0.3
+    pub initial: Option<HashMap<String, f64>>,
+    /// 饱和蒸气压的温度关联方程性质编码，默认 VP
+    pub vapor_pressure_code: Option<String>,
+    /// UNIQUAC 结构参数 r、q（组分 i、j），默认从物性库读取
+    pub uniquac_r: Option<Vec<f64>>,
+    pub uniquac_q: Option<Vec<f64>>,
+    /// 回归后写入的流体包
+    pub save_to_package_id: Option<String>,
+    /// 回归未收敛时仍写入流体包，默认 false（未收敛时拒绝写入）
+    pub save_unconverged: Option<bool>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[napi(object, namespace = "modelFluidPackageBinary")]
+pub struct BinaryVleResidual {
+    pub temperature: f64,
+    pub x1: f64,
+    pub pressure: f64,
+    pub pressure_calc: f64,
+    pub y1: Option<f64>,
+    pub y1_calc: f64,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[napi(object, namespace = "modelFluidPackageBinary")]
+pub struct BinaryLleResidual {
+    pub temperature: f64,
+    pub x1_phase_a: f64,
+    pub x1_phase_b: f64,
+    /// 组分 i、j 在两相中 ln 活度之差
+    pub residual1: f64,
+    pub residual2: f64,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[napi(object, namespace = "modelFluidPackageBinary")]
+pub struct BinaryRegressionResult {
+    pub func_code: BinaryFuncCode,
+    pub compound_i: String,
+    pub compound_j: String,
+    /// 回归得到的参数（列名 → 数值）
+    pub parameters: Value,
+    /// 泡点压力平均绝对相对偏差 %
+    pub aad_pressure: Option<f64>,
+    /// 汽相组成平均绝对偏差
+    pub aad_y: Option<f64>,
+    pub rmsd: f64,
+    pub iterations: u32,
+    pub converged: bool,
+    pub vle_residuals: Vec<BinaryVleResidual>,
+    pub lle_residuals: Vec<BinaryLleResidual>,
+    /// 写入流体包的记录 ID
+    pub saved_ids: Vec<String>,
+}
+
+fn regression_err(msg: impl std::fmt::Display) -> DbErr {
+    DbErr::Custom(format!("二元参数回归错误: {}", msg))
+}
+
+fn activity_model_of(func_code: BinaryFuncCode) -> Result<ActivityModel, DbErr> {
+    match func_code {
+        BinaryFuncCode::NRTL | BinaryFuncCode::NRTLRK => Ok(ActivityModel::Nrtl),
+        BinaryFuncCode::WILSON => Ok(ActivityModel::Wilson),
+        BinaryFuncCode::UNIQUAC => Ok(ActivityModel::Uniquac),
+        other => Err(regression_err(format!(
+            "{:?} 不是活度系数模型，无法回归",
+            other
+        ))),
+    }
+}
+
+/// 按物性库温度关联方程计算饱和蒸气压 Pa
+async fn vapor_pressures(
+    cas_no: &str,
+    code: &str,
+    temperatures: &[f64],
+) -> Result<Vec<f64>, DbErr> {
+    let curve = evaluate_physical_temperature_equation(TemperatureEquationEvalQuery {
+        component_id: None,
+        cas_no: Some(cas_no.to_string()),
+        code: code.to_string(),
+        temperatures: temperatures.to_vec(),
+        temperature_unit: None,
+        unit: Some("Pa".to_string()),
+        policy: None,
+        equation_id: None,
+    })
+    .await?;
+    curve
+        .points
+        .iter()
+        .map(|p| {
+            p.value.ok_or_else(|| {
+                regression_err(format!(
+                    "组分 {} 在 {} K 处的饱和蒸气压无法计算",
+                    cas_no, p.temperature
+                ))
+            })
+        })
+        .collect()
+}
+
+async fn build_vle_points(query: &BinaryRegressionQuery) -> Result<Vec<VlePoint>, DbErr> {
+    let points = query.vle_points.clone().unwrap_or_default();
+    let code = query
+        .vapor_pressure_code
+        .as_deref()
+        .unwrap_or(DEFAULT_VAPOR_PRESSURE_CODE);
+    let mut psat = [vec![], vec![]];
+    for (k, cas_no) in [&query.compound_i, &query.compound_j]
+        .into_iter()
+        .enumerate()
+    {
+        let given = |p: &BinaryVlePoint| if k == 0 { p.psat1 } else { p.psat2 };
+        let needed: Vec<f64> = points
+            .iter()
+            .filter(|p| given(p).is_none())
+            .map(|p| p.temperature)
+            .collect();
+        let mut computed = if needed.is_empty() {
+            vec![]
+        } else {
+            vapor_pressures(cas_no, code, &needed).await?
+        }
+        .into_iter();
+        psat[k] = points
+            .iter()
+            .map(|p| given(p).or_else(|| computed.next()).unwrap_or_default())
+            .collect();
+    }
+    Ok(points
+        .iter()
+        .enumerate()
+        .map(|(n, p)| VlePoint {
+            t: p.temperature,
+            p: p.pressure,
+            x1: p.x1,
+            y1: p.y1,
+            psat1: psat[0][n],
+            psat2: psat[1][n],
+        })
+        .collect())
+}
+
+async fn uniquac_structure(query: &BinaryRegressionQuery) -> Result<Vec<UniquacStructure>, DbErr> {
+    match (&query.uniquac_r, &query.uniquac_q) {
+        (Some(r), Some(q)) if r.len() == 2 && q.len() == 2 => Ok(r
+            .iter()
+            .zip(q)
+            .map(|(&r, &q)| UniquacStructure { r, q })
+            .collect()),
+        (None, None) => {
+            load_uniquac_structure(&[query.compound_i.clone(), query.compound_j.clone()]).await
+        }
+        _ => Err(regression_err(
+            "uniquac_r、uniquac_q 需同时给出两个组分的值",
+        )),
+    }
+}
+
+/// 回归初值：列名 → 系数
+fn initial_pair(
+    model: ActivityModel,
+    initial: &HashMap<String, f64>,
+) -> (PairCoefficients, PairCoefficients) {
+    let get = |k: &str| initial.get(k).copied().unwrap_or(0.0);
+    let alpha = if model == ActivityModel::Nrtl {
+        initial.get("cij").copied().unwrap_or(DEFAULT_NRTL_ALPHA)
+    } else {
+        get("cij")
+    };
+    let forward = PairCoefficients {
+        a: get("aij"),
+        b: get("bij"),
+        c: alpha,
+        d: get("dij"),
+        e: get("eij"),
+        f: get("fij"),
+    };
+    let backward = PairCoefficients {
+        a: get("aji"),
+        b: get("bji"),
+        c: if model == ActivityModel::Nrtl {
+            alpha
+        } else {
+            get("cji")
+        },
+        d: if model == ActivityModel::Nrtl {
+            forward.d
+        } else {
+            get("dji")
+        },
+        e: get("eji"),
+        f: get("fji"),
+    };
+    (forward, backward)
+}
+
+/// 流体包中已有的该组分对记录
+struct PairRow {
+    id: String,
+    /// 记录的 i、j 与回归方向相反
+    reversed: bool,
+    is_default: i32,
+    is_default_id: String,
+    compound_i_name: String,
+    compound_j_name: String,
+}
+
+macro_rules! pair_rows {
+    ($service:ident, $package_id:expr, $ci:expr, $cj:expr) => {
+        $service::find_by_package_id($package_id)
+            .await?
+            .into_iter()
+            .filter_map(|m| {
+                let reversed = if m.compound_i == $ci && m.compound_j == $cj {
+                    false
+                } else if m.compound_i == $cj && m.compound_j == $ci {
+                    true
+                } else {
+                    return None;
+                };
+                Some(PairRow {
+                    id: m.id,
+                    reversed,
+                    is_default: m.is_default,
+                    is_default_id: m.is_default_id,
+                    compound_i_name: m.compound_i_name,
+                    compound_j_name: m.compound_j_name,
+                })
+            })
+            .collect::<Vec<_>>()
+    };
+}
+
+/// 回归结果写入流体包：已有该组分对的记录全部更新，没有则新增一条默认记录
+async fn save_to_package(
+    package_id: String,
+    query: &BinaryRegressionQuery,
+    forward: PairCoefficients,
+    backward: PairCoefficients,
+    t_range: (f64, f64),
+) -> Result<Vec<String>, DbErr> {
+    let (ci, cj) = (query.compound_i.clone(), query.compound_j.clone());
+    let existing = match query.func_code {
+        BinaryFuncCode::NRTL => pair_rows!(NrtlService, package_id.clone(), ci, cj),
+        BinaryFuncCode::NRTLRK => pair_rows!(NrtlRkService, package_id.clone(), ci, cj),
+        BinaryFuncCode::WILSON => pair_rows!(WilsonService, package_id.clone(), ci, cj),
+        BinaryFuncCode::UNIQUAC => pair_rows!(UniquacService, package_id.clone(), ci, cj),
+        other => return Err(regression_err(format!("{:?} 不支持写入", other))),
+    };
+    let rows = if existing.is_empty() {
+        vec![PairRow {
+            id: new_id(),
+            reversed: false,
+            is_default: 1,
+            is_default_id: String::new(),
+            compound_i_name: ci.clone(),
+            compound_j_name: cj.clone(),
+        }]
+    } else {
+        existing
+    };
+
+    // 未给出名称时保留记录原有名称
+    let name_i = query.compound_i_name.clone().filter(|s| !s.is_empty());
+    let name_j = query.compound_j_name.clone().filter(|s| !s.is_empty());
+    let mut dtos = Vec::with_capacity(rows.len());
+    let mut ids = Vec::with_capacity(rows.len());
+    for row in rows {
+        let (first, second, f, b, first_name, second_name) = if row.reversed {
+            (&cj, &ci, backward, forward, &name_j, &name_i)
+        } else {
+            (&ci, &cj, forward, backward, &name_i, &name_j)
+        };
+        let params: Map<String, Value> = activity_parameters(query.func_code, f, b);
+        let col = |k: &str| {
+            Some(
+                params
+                    .get(k)
+                    .and_then(Value::as_f64)
+                    .unwrap_or(0.0)
+                    .to_string(),
+            )
+        };
+        dtos.push(BinaryParameterDto {
+            id: row.id.clone(),
+            fluid_package_id: package_id.clone(),
+            compound_i: first.clone(),
+            compound_j: second.clone(),
+            compound_i_name: first_name.clone().or(Some(row.compound_i_name)),
+            compound_j_name: second_name.clone().or(Some(row.compound_j_name)),
+            aij: col("aij"),
+            aji: col("aji"),
+            bij: col("bij"),
+            bji: col("bji"),
+            cij: col("cij"),
+            cji: col("cji"),
+            dij: col("dij"),
+            dji: col("dji"),
+            eij: col("eij"),
+            eji: col("eji"),
+            fij: col("fij"),
+            fji: col("fji"),
+            kaij: None,
+            kbij: None,
+            kcij: None,
+            tij: None,
+            tji: None,
+            vij: None,
+            vji: None,
+            min_t: Some(t_range.0.to_string()),
+            max_t: Some(t_range.1.to_string()),
+            is_default: Some(row.is_default),
+            is_default_id: Some(row.is_default_id),
+            source: Some(BinaryParameterSource::Regressed),
+        });
+        ids.push(row.id);
+    }
+    match query.func_code {
+        BinaryFuncCode::NRTL => NrtlService::batch_save(dtos).await?,
+        BinaryFuncCode::NRTLRK => NrtlRkService::batch_save(dtos).await?,
+        BinaryFuncCode::WILSON => WilsonService::batch_save(dtos).await?,
+        _ => UniquacService::batch_save(dtos).await?,
+    }
+    Ok(ids)
+}
+
+/// 回归二元参数，指定 save_to_package_id 时写入流体包（source = REGRESSED）；
+/// 未收敛的结果只有在 save_unconverged 为 true 时才写入
+pub async fn regress_binary_parameters(
+    query: BinaryRegressionQuery,
+) -> Result<BinaryRegressionResult, DbErr> {
+    let model = activity_model_of(query.func_code)?;
+    if query.compound_i == query.compound_j {
+        return Err(regression_err("组分 i、j 不能相同"));
+    }
+    let vle = build_vle_points(&query).await?;
+    let lle: Vec<LlePoint> = query
+        .lle_points
+        .iter()
+        .flatten()
+        .map(|p| LlePoint {
+            t: p.temperature,
+            x1_a: p.x1_phase_a,
+            x1_b: p.x1_phase_b,
+        })
+        .collect();
+    let structure = if model == ActivityModel::Uniquac {
+        uniquac_structure(&query).await?
+    } else {
+        vec![]
+    };
+    let fit: Vec<FitParameter> = query
+        .fit_parameters
+        .clone()
+        .unwrap_or_else(|| vec![BinaryFitParameter::Bij, BinaryFitParameter::Bji])
+        .into_iter()
+        .map(FitParameter::from)
+        .collect();
+    let (forward, backward) = initial_pair(model, &query.initial.clone().unwrap_or_default());
+
+    let result = regress_binary(&RegressionProblem {
+        model,
+        forward,
+        backward,
+        fit: &fit,
+        structure: &structure,
+        vle: &vle,
+        lle: &lle,
+    })
+    .map_err(regression_err)?;
+
+    let temperatures = vle.iter().map(|p| p.t).chain(lle.iter().map(|p| p.t));
+    let t_range = temperatures.fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), t| {
+        (lo.min(t), hi.max(t))
+    });
+    if query.save_to_package_id.is_some()
+        && !result.converged
+        && !query.save_unconverged.unwrap_or(false)
+    {
+        return Err(regression_err(format!(
+            "回归未收敛（迭代 {} 次），参数未写入流体包；确认使用该结果时设置 saveUnconverged",
+            result.iterations
+        )));
+    }
+    let saved_ids = match query.save_to_package_id.clone() {
+        Some(package_id) => {
+            save_to_package(package_id, &query, result.forward, result.backward, t_range).await?
+        }
+        None => vec![],
+    };
+
+    Ok(BinaryRegressionResult {
+        func_code: query.func_code,
+        parameters: Value::Object(activity_parameters(
+            query.func_code,
+            result.forward,
+            result.backward,
+        )),
+        aad_pressure: result.aad_pressure,
+        aad_y: result.aad_y,
+        rmsd: result.rmsd,
+        iterations: result.iterations as u32,
+        converged: result.converged,
+        vle_residuals: vle
+            .iter()
+            .zip(&result.vle)
+            .map(|(p, f)| BinaryVleResidual {
+                temperature: p.t,
+                x1: p.x1,
+                pressure: p.p,
+                pressure_calc: f.p_calc,
+                y1: p.y1,
+                y1_calc: f.y1_calc,
+            })
+            .collect(),
+        lle_residuals: lle
+            .iter()
+            .zip(&result.lle)
+            .map(|(p, f)| BinaryLleResidual {
+                temperature: p.t,
+                x1_phase_a: p.x1_a,
+                x1_phase_b: p.x1_b,
+                residual1: f.residual1,
+                residual2: f.residual2,
+            })
+            .collect(),
+        saved_ids,
+        compound_i: query.compound_i,
+        compound_j: query.compound_j,
+    })
+}
@@ -1,9 +1,47 @@
 use crate::service_database::database_business::db_business_connection::get_business_db;
 use napi_derive::napi;
 use sea_orm::{
-    ActiveModelTrait, ActiveValue::Unchanged, ExprTrait, QueryFilter, Set, entity::prelude::*,
+    ActiveModelTrait, ActiveValue, ActiveValue::Unchanged, ExprTrait, IntoActiveModel, Iterable,
+    QueryFilter, Set, entity::prelude::*,
 };
 use serde::{Deserialize, Serialize};
+
+/// 二元参数来源，数据库中以大写字符串存储
+#[napi(string_enum, namespace = "modelFluidPackageBinary")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum BinaryParameterSource {
+    /// 物性库
+    #[default]
+    Databank,
+    /// 由基团贡献法或经验关联式估算
+    Estimated,
+    /// 由实验数据回归
+    Regressed,
+    /// 用户手工录入或修改
+    User,
+}
+
+impl BinaryParameterSource {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Databank => "DATABANK",
+            Self::Estimated => "ESTIMATED",
+            Self::Regressed => "REGRESSED",
+            Self::User => "USER",
+        }
+    }
+
+    /// 解析存储值，空串或未知值视为 Databank
+    pub fn parse(value: &str) -> Self {
+        match value.trim().to_ascii_uppercase().as_str() {
+            "ESTIMATED" => Self::Estimated,
+            "REGRESSED" => Self::Regressed,
+            "USER" => Self::User,
+            _ => Self::Databank,
+        }
+    }
+}
+
 /// 通用二元交互参数DTO
 #[napi(
     object,
@@ -16,6 +54,8 @@ pub struct BinaryParameterDto {
     pub fluid_package_id: String,
     pub compound_i: String,
     pub compound_j: String,
+    pub compound_i_name: Option<String>,
+    pub compound_j_name: Option<String>,
     // 基础参数（Option 兼容不同表）
     pub aij: Option<String>,
     pub aji: Option<String>,
@@ -44,6 +84,8 @@ pub struct BinaryParameterDto {
 
     pub is_default: Option<i32>,
     pub is_default_id: Option<String>,
+    /// 参数来源：插入时缺省为 User（从物性库复制时为 Databank）；更新时未指定且参数值有变化则标记为 User
+    pub source: Option<BinaryParameterSource>,
 }
 
 macro_rules! fill_binary_fields {
@@ -156,6 +198,19 @@ macro_rules! fill_common_activity {
     };
 }
 
+/// 不属于参数值的列，修改这些列不改变参数来源
+const NON_VALUE_COLUMNS: [&str; 9] = [
+    "id",
+    "fluid_package_id",
+    "compound_i",
+    "compound_j",
+    "compound_i_name",
+    "compound_j_name",
+    "is_default",
+    "is_default_id",
+    "source",
+];
+
 macro_rules! impl_binary_service {
     ($service_name:ident, $entity:ident, $type:ident) => {
         pub struct $service_name;
@@ -182,12 +237,21 @@ macro_rules! impl_binary_service {
                 };
 
                 // 2. 填充其他业务字段
+                if let Some(v) = item.compound_i_name.clone() {
+                    am.compound_i_name = Set(v);
+                }
+                if let Some(v) = item.compound_j_name.clone() {
+                    am.compound_j_name = Set(v);
+                }
                 if let Some(v) = item.is_default {
                     am.is_default = Set(v);
                 }
                 if let Some(v) = item.is_default_id {
                     am.is_default_id = Set(v.clone());
                 }
+                if let Some(v) = item.source {
+                    am.source = Set(v.as_str().to_string());
+                }
 
                 // 3. 调用字段填充宏
                 fill_binary_fields!($type, &mut am, item);
@@ -195,18 +259,46 @@ macro_rules! impl_binary_service {
                 am
             }
 
-            /// 批量保存 (Save: Insert or Update)
+            /// 是否修改了参数值（不含名称、默认标记等列）
+            fn changes_values(am: &$entity::ActiveModel, old: &$entity::Model) -> bool {
+                $entity::Column::iter().any(|c| {
+                    !NON_VALUE_COLUMNS.contains(&c.as_str())
+                        && matches!(am.get(c), ActiveValue::Set(v) if v != old.get(c))
+                })
+            }
+
+            /// 批量保存 (Save: Insert or Update)，用户录入的参数：未指定 source 的新记录标记为 User
             pub async fn batch_save(datas: Vec<BinaryParameterDto>) -> Result<(), DbErr> {
+                Self::batch_save_as(datas, BinaryParameterSource::User).await
+            }
+
+            /// 批量保存，未指定 source 的新记录使用 insert_source
+            ///
+            /// 更新时未指定 source 且参数值有变化的记录标记为 User，手工修改过的估算参数不再显示为估算
+            pub async fn batch_save_as(
+                datas: Vec<BinaryParameterDto>,
+                insert_source: BinaryParameterSource,
+            ) -> Result<(), DbErr> {
                 let db = get_business_db().await?;
                 for item in datas {
                     let id = item.id.clone();
-                    let exists = $entity::Entity::find_by_id(id).one(db).await?.is_some();
-
-                    let am = Self::dto_to_active_model(item, exists);
-                    if exists {
-                        am.update(db).await?;
-                    } else {
-                        am.insert(db).await?;
+                    let existing = $entity::Entity::find_by_id(id).one(db).await?;
+                    let explicit_source = item.source.is_some();
+
+                    let mut am = Self::dto_to_active_model(item, existing.is_some());
+                    match existing {
+                        Some(old) => {
+                            if !explicit_source && Self::changes_values(&am, &old) {
+                                am.source = Set(BinaryParameterSource::User.as_str().to_string());
+                            }
+                            am.update(db).await?;
+                        }
+                        None => {
+                            if !explicit_source {
+                                am.source = Set(insert_source.as_str().to_string());
+                            }
+                            am.insert(db).await?;
+                        }
                     }
                 }
                 Ok(())
@@ -302,6 +394,40 @@ macro_rules! impl_binary_service {
                 Ok(true)
             }
 
+            /// 根据 FluidPackageId 与参数来源删除
+            pub async fn delete_by_package_id_and_source<C: ConnectionTrait>(
+                db: &C,
+                package_id: String,
+                source: BinaryParameterSource,
+            ) -> Result<u64, DbErr> {
+                let res = $entity::Entity::delete_many()
+                    .filter($entity::Column::FluidPackageId.eq(package_id))
+                    .filter($entity::Column::Source.eq(source.as_str()))
+                    .exec(db)
+                    .await?;
+                Ok(res.rows_affected)
+            }
+
+            /// 插入完整记录：JSON 键为实体字段名，必须覆盖该表全部字段，多余的键被忽略
+            pub async fn insert_full_rows<C: ConnectionTrait>(
+                db: &C,
+                rows: Vec<serde_json::Value>,
+            ) -> Result<u64, DbErr> {
+                let mut models = Vec::with_capacity(rows.len());
+                for row in rows {
+                    let model: $entity::Model =
+                        serde_json::from_value(row).map_err(|e| DbErr::Json(e.to_string()))?;
+                    models.push(model.into_active_model());
+                }
+                // 每行二十多个绑定参数，分批插入避免超过 SQLite 的变量数上限
+                for chunk in models.chunks(500) {
+                    $entity::Entity::insert_many(chunk.to_vec())
+                        .exec(db)
+                        .await?;
+                }
+                Ok(models.len() as u64)
+            }
+
             pub async fn delete_by_ids_and_func_code_no_default(
                 ids: Vec<String>,
             ) -> Result<bool, DbErr> {
@@ -375,3 +501,77 @@ impl_binary_service!(SrkService, srk, eos);
 
 // 4. PSRK
 impl_binary_service!(PsrkService, psrk, psrk);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::service_database::database_business::db_business_connection::initialize_business_db;
+    use serde_json::json;
+
+    fn nrtl_row(id: &str, aij: &str) -> BinaryParameterDto {
+        serde_json::from_value(json!({
+            "id": id,
+            "fluid_package_id": "binary_source_test",
+            "compound_i": "74-82-8",
+            "compound_j": "74-98-6",
+            "compound_i_name": "methane",
+            "compound_j_name": "propane",
+            "aij": aij,
+            "aji": "0",
+            "bij": "0",
+            "bji": "0",
+            "cij": "0.3",
+            "dij": "0",
+            "eij": "0",
+            "eji": "0",
+            "fij": "0",
+            "fji": "0",
+            "min_t": "",
+            "max_t": "",
+            "is_default": 0,
+            "is_default_id": "",
+        }))
+        .unwrap()
+    }
+
+    async fn source_of(id: &str) -> BinaryParameterSource {
+        let rows = NrtlService::find_by_ids(vec![id.to_string()])
+            .await
+            .unwrap();
+        BinaryParameterSource::parse(&rows[0].source)
+    }
+
+    #[tokio::test]
+    async fn test_insert_source_defaults() {
+        let path = std::env::temp_dir().join(format!("binary_test_{}.db", std::process::id()));
+        initialize_business_db(path.to_string_lossy().into_owned())
+            .await
+            .unwrap();
+        let (user_id, bank_id) = (
+            format!("bs_user_{}", std::process::id()),
+            format!("bs_bank_{}", std::process::id()),
+        );
+
+        NrtlService::batch_save(vec![nrtl_row(&user_id, "0.01")])
+            .await
+            .unwrap();
+        NrtlService::batch_save_as(
+            vec![nrtl_row(&bank_id, "0.02")],
+            BinaryParameterSource::Databank,
+        )
+        .await
+        .unwrap();
+        assert_eq!(source_of(&user_id).await, BinaryParameterSource::User);
+        assert_eq!(source_of(&bank_id).await, BinaryParameterSource::Databank);
+
+        // 物性库参数未改值时保持 Databank，改值后标记为 User
+        NrtlService::batch_save(vec![nrtl_row(&bank_id, "0.02")])
+            .await
+            .unwrap();
+        assert_eq!(source_of(&bank_id).await, BinaryParameterSource::Databank);
+        NrtlService::batch_save(vec![nrtl_row(&bank_id, "0.03")])
+            .await
+            .unwrap();
+        assert_eq!(source_of(&bank_id).await, BinaryParameterSource::User);
+    }
+}
@@ -0,0 +1,588 @@
+use crate::calc_handle::activity_model::{
+    ActivityInput, ActivityModel, GAS_CONSTANT, PairCoefficients, UniquacStructure,
+    activity_coefficients,
+};
+use crate::calc_handle::cubic_eos::{CriticalConstants, CubicEos, FlashPhase, flash_pt};
+use crate::service_database::database_business::service::fluid_package::model_fluid_package_binary_service::{
+    NrtlRkService, NrtlService, PrService, RkService, SrkService, UniquacService, WilsonService,
+};
+use crate::service_database::database_business::service::fluid_package::model_fluid_package_service::get_calc_functions_by_package_id;
+use crate::service_database::database_config::service::conf_unit_conversion_service::get_unit_registry;
+use crate::service_database::database_physical_property::service::physical_component_base_service::{
+    PhysicalBaseDTO, get_physical_base_by_compound_id,
+};
+use crate::service_database::database_physical_property::service::physical_component_information_service::get_physical_information_list_by_cas_no_list;
+use crate::service_database::database_physical_property::service::physical_enum::BinaryFuncCode;
+use napi_derive::napi;
+use sea_orm::DbErr;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+// ======================================
+// 流体包热力学预览计算：按流体包所选二元方法计算液相活度系数、立方型状态方程闪蒸
+// 组分以 CAS 号标识，与二元参数表中的 compound_i / compound_j 一致
+// ======================================
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[napi(
+    object,
+    namespace = "physicalCalc",
+    js_name = "ActivityCoefficientQuery"
+)]
+pub struct ActivityCoefficientQuery {
+    pub fluid_package_id: String,
+    /// 组分 CAS 号
+    pub components: Vec<String>,
+    pub mole_fractions: Vec<f64>,
+    /// 温度 K
+    pub temperature: f64,
+    /// 二元方法，默认取流体包物性计算方法中选用的活度系数模型
+    pub func_code: Option<BinaryFuncCode>,
+    /// UNIQUAC 结构参数 r、q，默认从物性库读取
+    pub uniquac_r: Option<Vec<f64>>,
+    pub uniquac_q: Option<Vec<f64>>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[napi(object, namespace = "physicalCalc", js_name = "BinaryPairRef")]
+pub struct BinaryPairRef {
+    pub compound_i: String,
+    pub compound_j: String,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[napi(
+    object,
+    namespace = "physicalCalc",
+    js_name = "ActivityCoefficientResult"
+)]
+pub struct ActivityCoefficientResult {
+    pub func_code: BinaryFuncCode,
+    pub components: Vec<String>,
+    pub gamma: Vec<f64>,
+    pub ln_gamma: Vec<f64>,
+    /// 超额吉布斯自由能 J/mol
+    pub excess_gibbs: f64,
+    /// Gᴱ/RT
+    pub excess_gibbs_rt: f64,
+    /// 缺少二元参数的组分对（按理想溶液处理）
+    pub missing_pairs: Vec<BinaryPairRef>,
+    pub warnings: Vec<String>,
+}
+
+fn thermo_err(msg: impl std::fmt::Display) -> DbErr {
+    DbErr::Custom(format!("热力学计算错误: {}", msg))
+}
+
+/// 一条二元参数记录：compound_i → compound_j 与反向的系数
+#[derive(Clone, Debug)]
+pub(crate) struct BinaryPairRow {
+    pub compound_i: String,
+    pub compound_j: String,
+    pub forward: PairCoefficients,
+    pub backward: PairCoefficients,
+    pub min_t: String,
+    pub max_t: String,
+    pub is_default: i32,
+}
+
+/// 参数以字符串存储，空串视为 0
+fn parse_param(value: &str, field: &str, row_id: &str) -> Result<f64, DbErr> {
+    let text = value.trim();
+    if text.is_empty() {
+        return Ok(0.0);
+    }
+    text.parse::<f64>().map_err(|_| {
+        thermo_err(format!(
+            "二元参数 {} 的 {} 不是数值: {}",
+            row_id, field, value
+        ))
+    })
+}
+
+/// 按字段名取出 a..e（及可选的 f）系数
+macro_rules! pair_coefficients {
+    ($m:expr, $a:ident, $b:ident, $c:ident, $d:ident, $e:ident $(, $f:ident)?) => {
+        PairCoefficients {
+            a: parse_param(&$m.$a, stringify!($a), &$m.id)?,
+            b: parse_param(&$m.$b, stringify!($b), &$m.id)?,
+            c: parse_param(&$m.$c, stringify!($c), &$m.id)?,
+            d: parse_param(&$m.$d, stringify!($d), &$m.id)?,
+            e: parse_param(&$m.$e, stringify!($e), &$m.id)?,
+            f: 0.0 $(+ parse_param(&$m.$f, stringify!($f), &$m.id)?)?,
+        }
+    };
+}
+
+macro_rules! load_pair_rows {
+    ($service:ident, $package_id:expr, |$m:ident| $forward:expr, $backward:expr) => {{
+        let mut rows = Vec::new();
+        for $m in $service::find_by_package_id($package_id).await? {
+            rows.push(BinaryPairRow {
+                forward: $forward,
+                backward: $backward,
+                compound_i: $m.compound_i,
+                compound_j: $m.compound_j,
+                min_t: $m.min_t,
+                max_t: $m.max_t,
+                is_default: $m.is_default,
+            });
+        }
+        rows
+    }};
+}
+
+/// 读取流体包的活度系数模型二元参数
+pub(crate) async fn load_activity_pairs(
+    package_id: String,
+    func_code: BinaryFuncCode,
+) -> Result<Vec<BinaryPairRow>, DbErr> {
+    // NRTL 的 α 与 d 为对称参数，只存 cij / dij
+    Ok(match func_code {
+        BinaryFuncCode::NRTL => load_pair_rows!(
+            NrtlService,
+            package_id,
+            |m| pair_coefficients!(m, aij, bij, cij, dij, eij, fij),
+            pair_coefficients!(m, aji, bji, cij, dij, eji, fji)
+        ),
+        BinaryFuncCode::NRTLRK => load_pair_rows!(
+            NrtlRkService,
+            package_id,
+            |m| pair_coefficients!(m, aij, bij, cij, dij, eij, fij),
+            pair_coefficients!(m, aji, bji, cij, dij, eji, fji)
+        ),
+        BinaryFuncCode::WILSON => load_pair_rows!(
+            WilsonService,
+            package_id,
+            |m| pair_coefficients!(m, aij, bij, cij, dij, eij),
+            pair_coefficients!(m, aji, bji, cji, dji, eji)
+        ),
+        BinaryFuncCode::UNIQUAC => load_pair_rows!(
+            UniquacService,
+            package_id,
+            |m| pair_coefficients!(m, aij, bij, cij, dij, eij),
+            pair_coefficients!(m, aji, bji, cji, dji, eji)
+        ),
+        other => {
+            return Err(thermo_err(format!(
+                "{:?} 不是活度系数模型，无法计算活度系数",
+                other
+            )));
+        }
+    })
+}
+
+/// 流体包物性计算方法中选用的二元方法（按物性计算方法顺序，去重）
+pub(crate) async fn package_func_codes(package_id: &str) -> Result<Vec<BinaryFuncCode>, DbErr> {
+    let mut codes = Vec::new();
+    for f in get_calc_functions_by_package_id(package_id.to_string()).await? {
+        let code: String = f
+            .calc_code
+            .chars()
+            .filter(|c| c.is_ascii_alphanumeric())
+            .collect::<String>()
+            .to_ascii_uppercase();
+        let func_code = match code.as_str() {
+            "NRTL" => BinaryFuncCode::NRTL,
+            "NRTLRK" => BinaryFuncCode::NRTLRK,
+            "WILSON" | "WILSION" => BinaryFuncCode::WILSON,
+            "UNIQUAC" => BinaryFuncCode::UNIQUAC,
+            "PR" => BinaryFuncCode::PR,
+            "SRK" => BinaryFuncCode::SRK,
+            "RK" => BinaryFuncCode::RK,
+            "PSRK" => BinaryFuncCode::PSRK,
+            _ => continue,
+        };
+        if !codes.contains(&func_code) {
+            codes.push(func_code);
+        }
+    }
+    Ok(codes)
+}
+
+/// 由流体包物性计算方法推断所选的二元方法（只在 candidates 中选择）
+async fn resolve_func_code(
+    package_id: &str,
+    candidates: &[BinaryFuncCode],
+) -> Result<BinaryFuncCode, DbErr> {
+    package_func_codes(package_id)
+        .await?
+        .into_iter()
+        .find(|code| candidates.contains(code))
+        .ok_or_else(|| {
+            thermo_err(format!(
+                "流体包 {} 未选用 {:?} 中的任何方法，请指定 func_code",
+                package_id, candidates
+            ))
+        })
+}
+
+/// 组装有序组分对参数矩阵，返回缺失组分对与温度范围警告
+pub(crate) fn build_pair_matrix(
+    components: &[String],
+    mut rows: Vec<BinaryPairRow>,
+    temperature: f64,
+) -> (
+    Vec<Vec<Option<PairCoefficients>>>,
+    Vec<BinaryPairRef>,
+    Vec<String>,
+) {
+    let n = components.len();
+    let index: HashMap<&str, usize> = components
+        .iter()
+        .enumerate()
+        .map(|(i, c)| (c.as_str(), i))
+        .collect();
+    let mut pairs = vec![vec![None; n]; n];
+    let mut ranges: Vec<Vec<Option<(String, String)>>> = vec![vec![None; n]; n];
+    // 同一组分对有多条记录时以 is_default = 1 的为准（排在最后覆盖）
+    rows.sort_by_key(|r| r.is_default == 1);
+    for row in rows {
+        let (Some(&i), Some(&j)) = (
+            index.get(row.compound_i.as_str()),
+            index.get(row.compound_j.as_str()),
+        ) else {
+            continue;
+        };
+        if i == j {
+            continue;
+        }
+        pairs[i][j] = Some(row.forward);
+        pairs[j][i] = Some(row.backward);
+        ranges[i.min(j)][i.max(j)] = Some((row.min_t, row.max_t));
+    }
+
+    let mut missing = Vec::new();
+    let mut warnings = Vec::new();
+    for i in 0..n {
+        for j in i + 1..n {
+            if pairs[i][j].is_none() {
+                missing.push(BinaryPairRef {
+                    compound_i: components[i].clone(),
+                    compound_j: components[j].clone(),
+                });
+            }
+            if let Some((min_t, max_t)) = &ranges[i][j]
+                && let (Ok(lo), Ok(hi)) = (min_t.trim().parse::<f64>(), max_t.trim().parse::<f64>())
+                && hi > lo
+                && (temperature < lo || temperature > hi)
+            {
+                warnings.push(format!(
+                    "组分对 {} - {} 的二元参数适用温度范围为 [{}, {}] K，当前 {} K",
+                    components[i], components[j], lo, hi, temperature
+                ));
+            }
+        }
+    }
+    (pairs, missing, warnings)
+}
+
+/// 按 CAS 号读取物性库中的组分基础物性（顺序与 components 一致，未收录的组分为空）
+pub(crate) async fn load_component_base(
+    components: &[String],
+) -> Result<Vec<Vec<PhysicalBaseDTO>>, DbErr> {
+    let info = get_physical_information_list_by_cas_no_list(components.to_vec()).await?;
+    let ids: HashMap<String, i32> = info.into_iter().map(|c| (c.cas_no, c.id)).collect();
+    let mut result = Vec::with_capacity(components.len());
+    for cas in components {
+        result.push(match ids.get(cas) {
+            Some(&id) => get_physical_base_by_compound_id(id).await?,
+            None => vec![],
+        });
+    }
+    Ok(result)
+}
+
+/// 按编码（不区分大小写）查找基础物性
+pub(crate) fn find_base<'a>(
+    base: &'a [PhysicalBaseDTO],
+    codes: &[&str],
+) -> Option<&'a PhysicalBaseDTO> {
+    base.iter()
+        .find(|b| codes.iter().any(|c| b.code.eq_ignore_ascii_case(c)))
+}
+
+/// 从物性库读取 UNIQUAC 结构参数 r、q
+pub(crate) async fn load_uniquac_structure(
+    components: &[String],
+) -> Result<Vec<UniquacStructure>, DbErr> {
+    let mut structure = Vec::with_capacity(components.len());
+    let mut missing = Vec::new();
+    for (cas, base) in components
+        .iter()
+        .zip(load_component_base(components).await?)
+    {
+        let find = |codes: &[&str]| find_base(&base, codes).map(|b| b.value);
+        match (find(&["UNIQUAC_R", "GMUQR"]), find(&["UNIQUAC_Q", "GMUQQ"])) {
+            (Some(r), Some(q)) => structure.push(UniquacStructure { r, q }),
+            _ => missing.push(cas.clone()),
+        }
+    }
+    if !missing.is_empty() {
+        return Err(thermo_err(format!(
+            "物性库缺少组分的 UNIQUAC r、q 参数: {}",
+            missing.join(", ")
+        )));
+    }
+    Ok(structure)
+}
+
+/// 计算流体包下指定组成与温度的活度系数
+pub async fn calc_activity_coefficients(
+    query: ActivityCoefficientQuery,
+) -> Result<ActivityCoefficientResult, DbErr> {
+    if query.components.len() != query.mole_fractions.len() {
+        return Err(thermo_err("组分数与摩尔分数个数不一致"));
+    }
+    let func_code = match query.func_code {
+        Some(code) => code,
+        None => {
+            resolve_func_code(
+                &query.fluid_package_id,
+                &[
+                    BinaryFuncCode::NRTL,
+                    BinaryFuncCode::NRTLRK,
+                    BinaryFuncCode::WILSON,
+                    BinaryFuncCode::UNIQUAC,
+                ],
+            )
+            .await?
+        }
+    };
+    let model = match func_code {
+        BinaryFuncCode::NRTL | BinaryFuncCode::NRTLRK => ActivityModel::Nrtl,
+        BinaryFuncCode::WILSON => ActivityModel::Wilson,
+        BinaryFuncCode::UNIQUAC => ActivityModel::Uniquac,
+        other => {
+            return Err(thermo_err(format!(
+                "{:?} 不是活度系数模型，无法计算活度系数",
+                other
+            )));
+        }
+    };
+
+    let rows = load_activity_pairs(query.fluid_package_id.clone(), func_code).await?;
+    let (pairs, missing_pairs, warnings) =
+        build_pair_matrix(&query.components, rows, query.temperature);
+
+    let structure = if model == ActivityModel::Uniquac {
+        match (&query.uniquac_r, &query.uniquac_q) {
+            (Some(r), Some(q)) if r.len() == query.components.len() && q.len() == r.len() => r
+                .iter()
+                .zip(q)
+                .map(|(&r, &q)| UniquacStructure { r, q })
+                .collect(),
+            (None, None) => load_uniquac_structure(&query.components).await?,
+            _ => return Err(thermo_err("UNIQUAC r、q 参数个数必须与组分数一致")),
+        }
+    } else {
+        vec![]
+    };
+
+    let result = activity_coefficients(
+        model,
+        &ActivityInput {
+            temperature: query.temperature,
+            x: &query.mole_fractions,
+            pairs: &pairs,
+            structure: &structure,
+        },
+    )
+    .map_err(thermo_err)?;
+
+    Ok(ActivityCoefficientResult {
+        func_code,
+        components: query.components,
+        gamma: result.gamma,
+        ln_gamma: result.ln_gamma,
+        excess_gibbs: result.ge_rt * GAS_CONSTANT * query.temperature,
+        excess_gibbs_rt: result.ge_rt,
+        missing_pairs,
+        warnings,
+    })
+}
+
+// ======================================
+// 立方型状态方程闪蒸
+// ======================================
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[napi(object, namespace = "physicalCalc", js_name = "FlashPTResult")]
+pub struct FlashPtResult {
+    pub func_code: BinaryFuncCode,
+    /// 相态：liquid / vapor / two-phase
+    pub phase: String,
+    pub components: Vec<String>,
+    /// 进料组成（归一化后）
+    pub feed: Vec<f64>,
+    /// 汽化率（摩尔）
+    pub vapor_fraction: f64,
+    /// 液相组成
+    pub liquid: Vec<f64>,
+    /// 气相组成
+    pub vapor: Vec<f64>,
+    pub k_values: Vec<f64>,
+    pub z_liquid: f64,
+    pub z_vapor: f64,
+    pub phi_liquid: Vec<f64>,
+    pub phi_vapor: Vec<f64>,
+    pub iterations: u32,
+    pub converged: bool,
+    /// 缺少 kij 的组分对（按 0 处理）
+    pub missing_pairs: Vec<BinaryPairRef>,
+    pub warnings: Vec<String>,
+}
+
+/// 读取流体包的状态方程 kij 参数：kij = kaij + kbij·T + kcij/T（对称）
+async fn load_eos_pairs(
+    package_id: String,
+    func_code: BinaryFuncCode,
+) -> Result<Vec<BinaryPairRow>, DbErr> {
+    macro_rules! kij_rows {
+        ($service:ident) => {
+            load_pair_rows!(
+                $service,
+                package_id,
+                |m| PairCoefficients {
+                    a: parse_param(&m.kaij, "kaij", &m.id)?,
+                    b: parse_param(&m.kbij, "kbij", &m.id)?,
+                    c: parse_param(&m.kcij, "kcij", &m.id)?,
+                    ..Default::default()
+                },
+                PairCoefficients::default()
+            )
+        };
+    }
+    let mut rows = match func_code {
+        BinaryFuncCode::PR => kij_rows!(PrService),
+        BinaryFuncCode::SRK => kij_rows!(SrkService),
+        BinaryFuncCode::RK => kij_rows!(RkService),
+        other => {
+            return Err(thermo_err(format!(
+                "{:?} 不是立方型状态方程，无法进行闪蒸",
+                other
+            )));
+        }
+    };
+    for row in rows.iter_mut() {
+        row.backward = row.forward;
+    }
+    Ok(rows)
+}
+
+/// 从物性库读取临界温度、临界压力与偏心因子，按基础物性的单位换算为 K、Pa
+async fn load_critical_constants(components: &[String]) -> Result<Vec<CriticalConstants>, DbErr> {
+    let registry = get_unit_registry().await?;
+    let to_si = |b: &PhysicalBaseDTO, si_unit: &str, category: &str| -> f64 {
+        if b.unit_type.trim().is_empty() {
+            return b.value;
+        }
+        registry
+            .convert(b.value, &b.unit_type, si_unit, Some(category))
+            .unwrap_or(b.value)
+    };
+    let mut constants = Vec::with_capacity(components.len());
+    let mut missing = Vec::new();
+    for (cas, base) in components
+        .iter()
+        .zip(load_component_base(components).await?)
+    {
+        let tc = find_base(&base, &["TC", "CRITICAL_TEMPERATURE"]);
+        let pc = find_base(&base, &["PC", "CRITICAL_PRESSURE"]);
+        let omega = find_base(&base, &["OMEGA", "ACENTRIC_FACTOR"]);
+        match (tc, pc, omega) {
+            (Some(tc), Some(pc), Some(omega)) => constants.push(CriticalConstants {
+                tc: to_si(tc, "K", "temperature"),
+                pc: to_si(pc, "Pa", "pressure"),
+                omega: omega.value,
+            }),
+            _ => missing.push(cas.clone()),
+        }
+    }
+    if !missing.is_empty() {
+        return Err(thermo_err(format!(
+            "物性库缺少组分的临界温度、临界压力或偏心因子: {}",
+            missing.join(", ")
+        )));
+    }
+    Ok(constants)
+}
+
+/// 流体包等温等压闪蒸（composition: CAS 号 → 摩尔分数，温度 K，压力 Pa）
+pub async fn flash_pt_by_package(
+    package_id: String,
+    composition: HashMap<String, f64>,
+    temperature: f64,
+    pressure: f64,
+    func_code: Option<BinaryFuncCode>,
+) -> Result<FlashPtResult, DbErr> {
+    let eos_codes = [BinaryFuncCode::PR, BinaryFuncCode::SRK, BinaryFuncCode::RK];
+    let func_code = match func_code {
+        Some(code) => code,
+        None => resolve_func_code(&package_id, &eos_codes).await?,
+    };
+    let eos = match func_code {
+        BinaryFuncCode::PR => CubicEos::Pr,
+        BinaryFuncCode::SRK => CubicEos::Srk,
+        BinaryFuncCode::RK => CubicEos::Rk,
+        other => {
+            return Err(thermo_err(format!(
+                "{:?} 不是立方型状态方程，无法进行闪蒸",
+                other
+            )));
+        }
+    };
+
+    // 组分按 CAS 号排序，保证结果顺序稳定
+    let mut feed: Vec<(String, f64)> = composition.into_iter().collect();
+    feed.sort_by(|a, b| a.0.cmp(&b.0));
+    let (components, z): (Vec<String>, Vec<f64>) = feed.into_iter().unzip();
+
+    let constants = load_critical_constants(&components).await?;
+    let rows = load_eos_pairs(package_id, func_code).await?;
+    let (pairs, missing_pairs, warnings) = build_pair_matrix(&components, rows, temperature);
+    let kij = pairs
+        .iter()
+        .map(|row| {
+            row.iter()
+                .map(|p| p.map_or(0.0, |p| p.a + p.b * temperature + p.c / temperature))
+                .collect()
+        })
+        .collect();
+
+    let result = flash_pt(eos, &constants, kij, &z, temperature, pressure).map_err(thermo_err)?;
+    let mut warnings = warnings;
+    if !result.converged {
+        warnings.push(match result.phase {
+            FlashPhase::TwoPhase => format!("闪蒸在 {} 次迭代后未收敛", result.iterations),
+            _ => format!(
+                "闪蒸在 {} 次迭代后未收敛，单相判断基于最后一次迭代",
+                result.iterations
+            ),
+        });
+    }
+    let total: f64 = z.iter().sum();
+    Ok(FlashPtResult {
+        func_code,
+        phase: match result.phase {
+            FlashPhase::Liquid => "liquid",
+            FlashPhase::Vapor => "vapor",
+            FlashPhase::TwoPhase => "two-phase",
+        }
+        .to_string(),
+        components,
+        feed: z.iter().map(|v| v / total).collect(),
+        vapor_fraction: result.vapor_fraction,
+        liquid: result.x,
+        vapor: result.y,
+        k_values: result.k,
+        z_liquid: result.z_liquid,
+        z_vapor: result.z_vapor,
+        phi_liquid: result.phi_liquid,
+        phi_vapor: result.phi_vapor,
+        iterations: result.iterations,
+        converged: result.converged,
+        missing_pairs,
+        warnings,
+    })
+}
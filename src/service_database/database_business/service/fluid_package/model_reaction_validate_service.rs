@@ -0,0 +1,684 @@
+use crate::calc_handle::reaction::{
+    BALANCE_TOLERANCE, ElementCounts, element_balance, heat_of_reaction, parse_equation,
+    parse_formula,
+};
+use crate::service_database::database_business::service::component::component_all_detail_service::get_all_detail_by_channel_id;
+use crate::service_database::database_business::service::component::component_oil_service::get_oils_by_channel_id;
+use crate::service_database::database_business::service::fluid_package::model_fluid_package_thermo_service::{
+    find_base, load_component_base,
+};
+use crate::service_database::database_business::service::fluid_package::model_reaction_detail_service::{
+    ModelReactionDetailDTO, get_model_reaction_details_by_reaction_id,
+};
+use crate::service_database::database_business::service::fluid_package::model_reaction_package_service::get_model_reaction_by_id;
+use crate::service_database::database_config::service::conf_unit_conversion_service::get_unit_registry;
+use napi_derive::napi;
+use sea_orm::DbErr;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+// ======================================
+// 反应校验：在交给求解器前解析方程式、检查元素守恒、计算标准反应热并校验动力学/平衡参数
+// 方程式中的组分按 CAS 号、内部名称、名称、化学式依次匹配反应包组分通道中的组分
+// base_info 约定（JSON 对象）：
+// - CONVERSION：conversion（0 ~ 1）、baseComponent（关键反应物）
+// - EQUILIBRIUM：keq（ln K = A + B/T + C·ln T + D·T，最多 4 个系数）或 useGibbs = true
+// - KINETIC / POWERLAW：preExponential（> 0）、activationEnergy（J/mol）、orders（组分 → 级数）
+// - LHHW：在 KINETIC 基础上增加 adsorption（非空数组）
+// ======================================
+
+/// 生成焓的基础物性编码
+const HEAT_OF_FORMATION_CODES: &[&str] = &["DHFORM", "HF", "DHF", "HEAT_OF_FORMATION"];
+/// 生成 Gibbs 自由能的基础物性编码
+const GIBBS_OF_FORMATION_CODES: &[&str] = &["DGFORM", "GF", "DGF", "GIBBS_OF_FORMATION"];
+
+#[napi(string_enum, namespace = "modelReactionValidate")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ReactionIssueSeverity {
+    Error,
+    Warning,
+}
+
+#[napi(string_enum, namespace = "modelReactionValidate")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ReactionIssueCode {
+    /// 方程式语法错误
+    EquationSyntax,
+    /// 组分通道中没有该组分
+    UnknownComponent,
+    /// 按化学式匹配到多个组分
+    AmbiguousComponent,
+    /// 组分缺少化学式，无法检查元素守恒
+    MissingFormula,
+    InvalidFormula,
+    ElementImbalance,
+    /// 组分缺少生成焓，无法计算反应热
+    MissingHeatOfFormation,
+    UnknownReactionType,
+    MissingParameter,
+    InvalidParameter,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[napi(object, namespace = "modelReactionValidate")]
+pub struct ReactionIssue {
+    pub severity: ReactionIssueSeverity,
+    pub code: ReactionIssueCode,
+    pub message: String,
+    /// 相关的方程式组分或参数名
+    pub target: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[napi(object, namespace = "modelReactionValidate")]
+pub struct ReactionSpecies {
+    /// 方程式中的写法
+    pub species: String,
+    /// 匹配到的组分 ID、CAS 号与名称
+    pub component_id: Option<String>,
+    pub cas_no: Option<String>,
+    pub name: Option<String>,
+    pub formula: Option<String>,
+    /// 化学计量系数，反应物为负
+    pub coefficient: f64,
+    /// 标准生成焓 J/mol
+    pub heat_of_formation: Option<f64>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[napi(object, namespace = "modelReactionValidate")]
+pub struct ElementResidual {
+    pub element: String,
+    /// Σ νi·ni，守恒时为 0
+    pub residual: f64,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[napi(object, namespace = "modelReactionValidate")]
+pub struct ReactionValidationResult {
+    pub detail_id: Option<String>,
+    pub name: String,
+    pub equation: String,
+    pub reaction_type: String,
+    /// 没有 Error 级问题
+    pub valid: bool,
+    pub reversible: bool,
+    pub species: Vec<ReactionSpecies>,
+    pub element_balance: Vec<ElementResidual>,
+    /// 所有组分都有化学式时给出
+    pub balanced: Option<bool>,
+    /// 标准反应热 J/mol（按方程式写法的反应进度）
+    pub heat_of_reaction: Option<f64>,
+    pub issues: Vec<ReactionIssue>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[napi(object, namespace = "modelReactionValidate")]
+pub struct ReactionCheckQuery {
+    pub reaction_package_id: String,
+    pub equation: String,
+    pub reaction_type: String,
+    /// 动力学/平衡参数 JSON，同 model_reaction_detail.base_info
+    pub base_info: Option<String>,
+    pub conc_basis: Option<String>,
+}
+
+/// 反应包组分通道中的一个组分
+struct ChannelComponent {
+    id: String,
+    cas_no: String,
+    base_cas_no: String,
+    name: String,
+    internal_name: String,
+    formula: String,
+    base_physical_property: String,
+}
+
+fn issue(
+    severity: ReactionIssueSeverity,
+    code: ReactionIssueCode,
+    message: impl Into<String>,
+    target: Option<&str>,
+) -> ReactionIssue {
+    ReactionIssue {
+        severity,
+        code,
+        message: message.into(),
+        target: target.map(str::to_string),
+    }
+}
+
+fn error(
+    code: ReactionIssueCode,
+    message: impl Into<String>,
+    target: Option<&str>,
+) -> ReactionIssue {
+    issue(ReactionIssueSeverity::Error, code, message, target)
+}
+
+fn warning(
+    code: ReactionIssueCode,
+    message: impl Into<String>,
+    target: Option<&str>,
+) -> ReactionIssue {
+    issue(ReactionIssueSeverity::Warning, code, message, target)
+}
+
+async fn load_channel_components(channel_id: &str) -> Result<Vec<ChannelComponent>, DbErr> {
+    let mut components: Vec<ChannelComponent> =
+        get_all_detail_by_channel_id(channel_id.to_string())
+            .await?
+            .into_iter()
+            .map(|c| ChannelComponent {
+                id: c.id,
+                cas_no: c.cas_no,
+                base_cas_no: c.base_cas_no,
+                name: c.name,
+                internal_name: c.internal_name,
+                formula: c.formula,
+                base_physical_property: c.base_physical_property,
+            })
+            .collect();
+    components.extend(
+        get_oils_by_channel_id(channel_id.to_string())
+            .await?
+            .into_iter()
+            .map(|o| ChannelComponent {
+                id: o.id,
+                cas_no: o.cas_no,
+                base_cas_no: String::new(),
+                name: o.name,
+                internal_name: o.internal_name,
+                formula: o.formula,
+                base_physical_property: o.base_physical_property,
+            }),
+    );
+    Ok(components)
+}
+
+/// 按 CAS 号、内部名称、名称、化学式依次匹配；化学式匹配到多个组分时视为不确定
+fn match_component<'a>(
+    components: &'a [ChannelComponent],
+    species: &str,
+) -> Result<&'a ChannelComponent, ReactionIssue> {
+    let eq = |a: &str| !a.is_empty() && a.eq_ignore_ascii_case(species);
+    let exact = components
+        .iter()
+        .find(|c| eq(&c.cas_no))
+        .or_else(|| components.iter().find(|c| eq(&c.internal_name)))
+        .or_else(|| components.iter().find(|c| eq(&c.name)));
+    if let Some(c) = exact {
+        return Ok(c);
+    }
+    let by_formula: Vec<&ChannelComponent> = components
+        .iter()
+        .filter(|c| !c.formula.is_empty() && c.formula == species)
+        .collect();
+    match by_formula.as_slice() {
+        [c] => Ok(c),
+        [] => Err(error(
+            ReactionIssueCode::UnknownComponent,
+            format!("组分通道中没有 “{}”", species),
+            Some(species),
+        )),
+        many => Err(error(
+            ReactionIssueCode::AmbiguousComponent,
+            format!(
+                "化学式 “{}” 对应多个组分（{}），请改用名称或 CAS 号",
+                species,
+                many.iter()
+                    .map(|c| c.name.as_str())
+                    .collect::<Vec<_>>()
+                    .join("、")
+            ),
+            Some(species),
+        )),
+    }
+}
+
+/// 组分自带基础物性（[{code, value, unitType}]）中按编码取值
+fn own_property(component: &ChannelComponent, codes: &[&str]) -> Option<(f64, String)> {
+    let items: Vec<Value> = serde_json::from_str(&component.base_physical_property).ok()?;
+    items.iter().find_map(|item| {
+        let code = item.get("code")?.as_str()?;
+        if !codes.iter().any(|c| code.eq_ignore_ascii_case(c)) {
+            return None;
+        }
+        let value = item.get("value")?.as_f64()?;
+        let unit = item
+            .get("unitType")
+            .and_then(Value::as_str)
+            .unwrap_or_default();
+        Some((value, unit.to_string()))
+    })
+}
+
+/// 组分的生成性质 J/mol：优先组分自带基础物性，其次物性库
+async fn formation_properties(
+    components: &[&ChannelComponent],
+    codes: &[&str],
+) -> Result<Vec<Option<f64>>, DbErr> {
+    let registry = get_unit_registry().await?;
+    let cas: Vec<String> = components
+        .iter()
+        .map(|c| {
+            if c.base_cas_no.is_empty() {
+                c.cas_no.clone()
+            } else {
+                c.base_cas_no.clone()
+            }
+        })
+        .collect();
+    let library = load_component_base(&cas).await?;
+    Ok(components
+        .iter()
+        .zip(&library)
+        .map(|(c, base)| {
+            let (value, unit) = own_property(c, codes)
+                .or_else(|| find_base(base, codes).map(|b| (b.value, b.unit_type.clone())))?;
+            Some(if unit.trim().is_empty() {
+                value
+            } else {
+                registry
+                    .convert(value, &unit, "J/mol", Some("molar_enthalpy"))
+                    .unwrap_or(value)
+            })
+        })
+        .collect())
+}
+
+fn number<'a>(params: &'a Map<String, Value>, key: &str) -> Option<&'a Value> {
+    params.get(key).filter(|v| !v.is_null())
+}
+
+/// 校验数值参数：缺失或非有限数时记录问题
+fn check_number(
+    params: &Map<String, Value>,
+    key: &str,
+    valid: impl Fn(f64) -> bool,
+    rule: &str,
+    issues: &mut Vec<ReactionIssue>,
+) -> Option<f64> {
+    match number(params, key) {
+        None => {
+            issues.push(error(
+                ReactionIssueCode::MissingParameter,
+                format!("缺少参数 {}", key),
+                Some(key),
+            ));
+            None
+        }
+        Some(v) => match v.as_f64().filter(|x| x.is_finite() && valid(*x)) {
+            Some(x) => Some(x),
+            None => {
+                issues.push(error(
+                    ReactionIssueCode::InvalidParameter,
+                    format!("参数 {} 须为{}，当前为 {}", key, rule, v),
+                    Some(key),
+                ));
+                None
+            }
+        },
+    }
+}
+
+fn is_species(species: &[ReactionSpecies], key: &str) -> bool {
+    species.iter().any(|s| {
+        s.species == key
+            || s.cas_no.as_deref() == Some(key)
+            || s.component_id.as_deref() == Some(key)
+            || s.name.as_deref() == Some(key)
+    })
+}
+
+fn check_kinetic(
+    params: &Map<String, Value>,
+    species: &[ReactionSpecies],
+    issues: &mut Vec<ReactionIssue>,
+) {
+    check_number(params, "preExponential", |x| x > 0.0, "正数", issues);
+    if let Some(e) = check_number(params, "activationEnergy", |_| true, "数值", issues)
+        && e < 0.0
+    {
+        issues.push(warning(
+            ReactionIssueCode::InvalidParameter,
+            "活化能为负，请确认",
+            Some("activationEnergy"),
+        ));
+    }
+    if let Some(orders) = number(params, "orders") {
+        match orders.as_object() {
+            Some(orders) => {
+                for (key, order) in orders {
+                    if !is_species(species, key) {
+                        issues.push(error(
+                            ReactionIssueCode::InvalidParameter,
+                            format!("反应级数中的 “{}” 不在方程式中", key),
+                            Some(key),
+                        ));
+                    } else if !order.as_f64().is_some_and(f64::is_finite) {
+                        issues.push(error(
+                            ReactionIssueCode::InvalidParameter,
+                            format!("“{}” 的反应级数须为数值", key),
+                            Some(key),
+                        ));
+                    }
+                }
+            }
+            None => issues.push(error(
+                ReactionIssueCode::InvalidParameter,
+                "orders 须为 组分 → 级数 的对象",
+                Some("orders"),
+            )),
+        }
+    }
+}
+
+/// 按反应类型校验 base_info 中的参数
+fn check_parameters(
+    reaction_type: &str,
+    base_info: &str,
+    conc_basis: &str,
+    species: &[ReactionSpecies],
+    issues: &mut Vec<ReactionIssue>,
+) {
+    let params: Map<String, Value> = if base_info.trim().is_empty() {
+        Map::new()
+    } else {
+        match serde_json::from_str::<Value>(base_info) {
+            Ok(Value::Object(map)) => map,
+            _ => {
+                issues.push(error(
+                    ReactionIssueCode::InvalidParameter,
+                    "反应参数（base_info）不是 JSON 对象",
+                    Some("base_info"),
+                ));
+                return;
+            }
+        }
+    };
+    match reaction_type.trim().to_ascii_uppercase().as_str() {
+        "CONVERSION" => {
+            check_number(
+                &params,
+                "conversion",
+                |x| (0.0..=1.0).contains(&x),
+                "0 ~ 1 之间的数",
+                issues,
+            );
+            match number(&params, "baseComponent").and_then(Value::as_str) {
+                None => issues.push(error(
+                    ReactionIssueCode::MissingParameter,
+                    "转化率反应缺少关键组分 baseComponent",
+                    Some("baseComponent"),
+                )),
+                Some(key) => {
+                    let reactant = species
+                        .iter()
+                        .any(|s| s.coefficient < 0.0 && is_species(std::slice::from_ref(s), key));
+                    if !reactant {
+                        issues.push(error(
+                            ReactionIssueCode::InvalidParameter,
+                            format!("关键组分 “{}” 不是该反应的反应物", key),
+                            Some("baseComponent"),
+                        ));
+                    }
+                }
+            }
+        }
+        "EQUILIBRIUM" => {
+            let use_gibbs = number(&params, "useGibbs").and_then(Value::as_bool) == Some(true);
+            match number(&params, "keq") {
+                Some(keq) => {
+                    let ok = keq.as_array().is_some_and(|a| {
+                        !a.is_empty()
+                            && a.len() <= 4
+                            && a.iter().all(|v| v.as_f64().is_some_and(f64::is_finite))
+                    });
+                    if !ok {
+                        issues.push(error(
+                            ReactionIssueCode::InvalidParameter,
+                            "keq 须为 1 ~ 4 个数值（ln K = A + B/T + C·ln T + D·T）",
+                            Some("keq"),
+                        ));
+                    }
+                }
+                None if use_gibbs => {}
+                None => issues.push(error(
+                    ReactionIssueCode::MissingParameter,
+                    "平衡反应需要给出 keq 或设置 useGibbs",
+                    Some("keq"),
+                )),
+            }
+        }
+        "KINETIC" | "POWERLAW" => check_kinetic(&params, species, issues),
+        "LHHW" => {
+            check_kinetic(&params, species, issues);
+            let adsorption = number(&params, "adsorption").and_then(Value::as_array);
+            if adsorption.is_none_or(Vec::is_empty) {
+                issues.push(error(
+                    ReactionIssueCode::MissingParameter,
+                    "LHHW 反应缺少吸附项 adsorption",
+                    Some("adsorption"),
+                ));
+            }
+        }
+        other => {
+            issues.push(error(
+                ReactionIssueCode::UnknownReactionType,
+                format!("未知的反应类型 “{}”", other),
+                Some("reaction_type"),
+            ));
+            return;
+        }
+    }
+    let kinetic = matches!(
+        reaction_type.trim().to_ascii_uppercase().as_str(),
+        "KINETIC" | "POWERLAW" | "LHHW"
+    );
+    if kinetic && conc_basis.trim().is_empty() {
+        issues.push(warning(
+            ReactionIssueCode::MissingParameter,
+            "动力学反应未指定浓度基准（conc_basis）",
+            Some("conc_basis"),
+        ));
+    }
+}
+
+/// 校验单条反应
+async fn check_reaction(
+    components: &[ChannelComponent],
+    detail: &ModelReactionDetailDTO,
+) -> Result<ReactionValidationResult, DbErr> {
+    let mut result = ReactionValidationResult {
+        detail_id: (!detail.id.is_empty()).then(|| detail.id.clone()),
+        name: detail.name.clone(),
+        equation: detail.equation.clone(),
+        reaction_type: detail.reaction_type.clone(),
+        valid: false,
+        reversible: false,
+        species: vec![],
+        element_balance: vec![],
+        balanced: None,
+        heat_of_reaction: None,
+        issues: vec![],
+    };
+    let parsed = match parse_equation(&detail.equation) {
+        Ok(p) => p,
+        Err(e) => {
+            result
+                .issues
+                .push(error(ReactionIssueCode::EquationSyntax, e, None));
+            return Ok(result);
+        }
+    };
+    result.reversible = parsed.reversible;
+
+    let mut matched = Vec::with_capacity(parsed.terms.len());
+    for term in &parsed.terms {
+        let component = match match_component(components, &term.species) {
+            Ok(c) => Some(c),
+            Err(e) => {
+                result.issues.push(e);
+                None
+            }
+        };
+        result.species.push(ReactionSpecies {
+            species: term.species.clone(),
+            component_id: component.map(|c| c.id.clone()),
+            cas_no: component.map(|c| c.cas_no.clone()),
+            name: component.map(|c| c.name.clone()),
+            formula: component
+                .map(|c| c.formula.clone())
+                .filter(|f| !f.is_empty()),
+            coefficient: term.coefficient,
+            heat_of_formation: None,
+        });
+        matched.push(component);
+    }
+
+    // 元素守恒
+    let mut formulas: Vec<(f64, ElementCounts)> = Vec::new();
+    for s in &result.species {
+        if s.component_id.is_none() {
+            continue;
+        }
+        match s.formula.as_deref().map(parse_formula) {
+            Some(Ok(counts)) => formulas.push((s.coefficient, counts)),
+            Some(Err(e)) => result.issues.push(error(
+                ReactionIssueCode::InvalidFormula,
+                format!("“{}” 的化学式无法解析: {}", s.species, e),
+                Some(&s.species),
+            )),
+            None => result.issues.push(warning(
+                ReactionIssueCode::MissingFormula,
+                format!("“{}” 没有化学式，未检查元素守恒", s.species),
+                Some(&s.species),
+            )),
+        }
+    }
+    if formulas.len() == result.species.len() {
+        let terms: Vec<(f64, &ElementCounts)> = formulas.iter().map(|(nu, c)| (*nu, c)).collect();
+        let balance = element_balance(&terms);
+        let unbalanced: Vec<String> = balance
+            .iter()
+            .filter(|(_, v)| v.abs() > BALANCE_TOLERANCE)
+            .map(|(e, v)| format!("{} {:+}", e, v))
+            .collect();
+        result.balanced = Some(unbalanced.is_empty());
+        if !unbalanced.is_empty() {
+            result.issues.push(error(
+                ReactionIssueCode::ElementImbalance,
+                format!("元素不守恒（产物 - 反应物）: {}", unbalanced.join("，")),
+                None,
+            ));
+        }
+        result.element_balance = balance
+            .into_iter()
+            .map(|(element, residual)| ElementResidual { element, residual })
+            .collect();
+    }
+
+    // 标准反应热
+    let found: Vec<&ChannelComponent> = matched.iter().flatten().copied().collect();
+    if found.len() == matched.len() {
+        let hf = formation_properties(&found, HEAT_OF_FORMATION_CODES).await?;
+        for (s, h) in result.species.iter_mut().zip(&hf) {
+            s.heat_of_formation = *h;
+            if h.is_none() {
+                result.issues.push(warning(
+                    ReactionIssueCode::MissingHeatOfFormation,
+                    format!("“{}” 缺少标准生成焓，未计算反应热", s.species),
+                    Some(&s.species),
+                ));
+            }
+        }
+        if hf.iter().all(Option::is_some) {
+            let terms: Vec<(f64, f64)> = result
+                .species
+                .iter()
+                .zip(&hf)
+                .map(|(s, h)| (s.coefficient, h.unwrap_or_default()))
+                .collect();
+            result.heat_of_reaction = Some(heat_of_reaction(&terms));
+        }
+        let gibbs_required = detail
+            .reaction_type
+            .trim()
+            .eq_ignore_ascii_case("EQUILIBRIUM")
+            && serde_json::from_str::<Value>(&detail.base_info)
+                .ok()
+                .and_then(|v| v.get("useGibbs").and_then(Value::as_bool))
+                == Some(true);
+        if gibbs_required {
+            let gf = formation_properties(&found, GIBBS_OF_FORMATION_CODES).await?;
+            for (s, g) in result.species.iter().zip(&gf) {
+                if g.is_none() {
+                    result.issues.push(error(
+                        ReactionIssueCode::MissingParameter,
+                        format!(
+                            "“{}” 缺少标准生成 Gibbs 自由能，无法由 Gibbs 能计算平衡常数",
+                            s.species
+                        ),
+                        Some(&s.species),
+                    ));
+                }
+            }
+        }
+    }
+
+    check_parameters(
+        &detail.reaction_type,
+        &detail.base_info,
+        &detail.conc_basis,
+        &result.species,
+        &mut result.issues,
+    );
+    result.valid = result
+        .issues
+        .iter()
+        .all(|i| i.severity != ReactionIssueSeverity::Error);
+    Ok(result)
+}
+
+async fn package_components(reaction_package_id: &str) -> Result<Vec<ChannelComponent>, DbErr> {
+    let package = get_model_reaction_by_id(reaction_package_id.to_string())
+        .await?
+        .ok_or_else(|| DbErr::RecordNotFound(format!("反应包 {} 不存在", reaction_package_id)))?;
+    load_channel_components(&package.compound_channel_id).await
+}
+
+/// 校验反应包下的全部反应
+pub async fn validate_reaction_package(
+    reaction_package_id: String,
+) -> Result<Vec<ReactionValidationResult>, DbErr> {
+    let components = package_components(&reaction_package_id).await?;
+    let mut results = Vec::new();
+    for detail in get_model_reaction_details_by_reaction_id(reaction_package_id).await? {
+        results.push(check_reaction(&components, &detail).await?);
+    }
+    Ok(results)
+}
+
+/// 保存前校验一条反应（方程式与参数尚未写入数据库）
+pub async fn check_reaction_equation(
+    query: ReactionCheckQuery,
+) -> Result<ReactionValidationResult, DbErr> {
+    let components = package_components(&query.reaction_package_id).await?;
+    let detail = ModelReactionDetailDTO {
+        id: String::new(),
+        reaction_package_id: query.reaction_package_id,
+        model_id: String::new(),
+        name: String::new(),
+        reaction_type: query.reaction_type,
+        level: 0,
+        equation: query.equation,
+        balance: String::new(),
+        reaction_heat: String::new(),
+        list: String::new(),
+        base_info: query.base_info.unwrap_or_default(),
+        reaction_name: String::new(),
+        conc_basis: query.conc_basis.unwrap_or_default(),
+    };
+    check_reaction(&components, &detail).await
+}
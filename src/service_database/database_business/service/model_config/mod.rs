@@ -1,3 +1,4 @@
+pub mod model_bundle_service;
 pub mod model_config_service;
 pub mod model_file_comparison_service;
 pub mod model_graphic_page_service;
@@ -0,0 +1,596 @@
+use std::collections::BTreeMap;
+
+use crate::service_database::database_business::db_business_connection::get_business_db;
+use crate::service_database::database_business::entity::component_channel::{
+    model_component_all_detail as all_detail, model_component_channel as channel,
+    model_component_henry as henry, model_component_henry_detail as henry_detail,
+    model_component_oil as oil,
+};
+use crate::service_database::database_business::entity::fluid_package::{
+    model_fluid_package_binary_nrtl_entity as nrtl,
+    model_fluid_package_binary_nrtl_rk_entity as nrtl_rk,
+    model_fluid_package_binary_pr_entity as pr, model_fluid_package_binary_psrk_entity as psrk,
+    model_fluid_package_binary_rk_entity as rk, model_fluid_package_binary_srk_entity as srk,
+    model_fluid_package_binary_uniquac_entity as uniquac,
+    model_fluid_package_binary_wilsion_entity as wilson,
+    model_fluid_package_entity as fluid_package,
+    model_physical_property_calc_entity as property_calc,
+    model_reaction_detail_entity as reaction_detail,
+    model_reaction_package_entity as reaction_package,
+};
+use crate::service_database::database_business::entity::model_config::{
+    model_config_entity as config, model_entity as model,
+    model_graphic_page_entity as graphic_page, model_name_generator_entity as name_generator,
+    model_pf_model_params as pf_model_params, model_pf_var_default_params_entity as pf_var_params,
+    model_system_variable_entity as system_variable, model_unit_item_entity as unit_item,
+    model_unit_set_entity as unit_set,
+};
+use crate::service_database::database_business::entity::model_util_handle::{
+    model_dcs_tag_entity as dcs_tag, model_global_script_entity as global_script,
+    model_initialize_data_in_cold_state_entity as cold_state,
+    model_status_information_entity as status_information,
+    model_status_params_entity as status_params, model_trend_chart_config_entity as trend_chart,
+    model_variable_choose_entity as variable_choose,
+};
+use crate::service_database::database_business::service::flowsheet::flowsheet_service::{
+    FlowsheetGraph, delete_flowsheet, load_flowsheet, save_flowsheet,
+};
+use crate::tool_handle::id_tool::{IdRemap, new_id};
+use sea_orm::{
+    ActiveValue::NotSet, ConnectionTrait, DbErr, EntityTrait, IntoActiveModel, QueryFilter,
+    TransactionError, TransactionTrait, entity::prelude::*,
+};
+use serde::{Deserialize, Serialize};
+
+// ======================================
+// 模型数据包：一个 model_id 在 business 库中的全部数据
+// 模型另存为（clone_model）与工程导入导出共用
+// ======================================
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct ModelBundle {
+    pub model: Option<model::Model>,
+    pub configs: Vec<config::Model>,
+    pub unit_sets: Vec<unit_set::Model>,
+    pub unit_items: Vec<unit_item::Model>,
+    pub system_variables: Vec<system_variable::Model>,
+    pub name_generators: Vec<name_generator::Model>,
+    pub pf_model_params: Vec<pf_model_params::Model>,
+    pub pf_var_params: Vec<pf_var_params::Model>,
+    pub graphic_pages: Vec<graphic_page::Model>,
+
+    pub compound_channels: Vec<channel::Model>,
+    pub compound_details: Vec<all_detail::Model>,
+    pub compound_henrys: Vec<henry::Model>,
+    pub compound_henry_details: Vec<henry_detail::Model>,
+    pub compound_oils: Vec<oil::Model>,
+
+    pub fluid_packages: Vec<fluid_package::Model>,
+    pub property_calcs: Vec<property_calc::Model>,
+    pub binary_nrtl: Vec<nrtl::Model>,
+    pub binary_nrtl_rk: Vec<nrtl_rk::Model>,
+    pub binary_pr: Vec<pr::Model>,
+    pub binary_psrk: Vec<psrk::Model>,
+    pub binary_rk: Vec<rk::Model>,
+    pub binary_srk: Vec<srk::Model>,
+    pub binary_uniquac: Vec<uniquac::Model>,
+    pub binary_wilson: Vec<wilson::Model>,
+    pub reaction_packages: Vec<reaction_package::Model>,
+    pub reaction_details: Vec<reaction_detail::Model>,
+
+    pub status_informations: Vec<status_information::Model>,
+    pub status_params: Vec<status_params::Model>,
+    pub global_scripts: Vec<global_script::Model>,
+    pub trend_charts: Vec<trend_chart::Model>,
+    pub variable_chooses: Vec<variable_choose::Model>,
+    pub cold_states: Vec<cold_state::Model>,
+    /// DCS 位号（早期归档中没有该字段）
+    #[serde(default)]
+    pub dcs_tags: Vec<dcs_tag::Model>,
+
+    /// 流程图拓扑（早期归档中没有该字段）
+    #[serde(default)]
+    pub flowsheet: FlowsheetGraph,
+}
+
+// 八张二元交互参数表结构一致，统一处理
+macro_rules! for_each_binary_table {
+    ($mac:ident) => {
+        $mac!(binary_nrtl, nrtl);
+        $mac!(binary_nrtl_rk, nrtl_rk);
+        $mac!(binary_pr, pr);
+        $mac!(binary_psrk, psrk);
+        $mac!(binary_rk, rk);
+        $mac!(binary_srk, srk);
+        $mac!(binary_uniquac, uniquac);
+        $mac!(binary_wilson, wilson);
+    };
+}
+
+/// 读取模型的全部业务数据
+pub async fn load_model_bundle<C: ConnectionTrait>(
+    db: &C,
+    model_id: &str,
+) -> Result<ModelBundle, DbErr> {
+    let mut bundle = ModelBundle {
+        model: model::Entity::find_by_id(model_id.to_string())
+            .one(db)
+            .await?,
+        ..Default::default()
+    };
+    if bundle.model.is_none() {
+        return Err(DbErr::RecordNotFound(format!("模型 {} 不存在", model_id)));
+    }
+
+    // 1. 直接挂在 model_id 下的表
+    macro_rules! by_model_id {
+        ($field:ident, $entity:ident) => {
+            bundle.$field = $entity::Entity::find()
+                .filter($entity::Column::ModelId.eq(model_id))
+                .all(db)
+                .await?;
+        };
+    }
+    by_model_id!(configs, config);
+    by_model_id!(unit_sets, unit_set);
+    by_model_id!(unit_items, unit_item);
+    by_model_id!(system_variables, system_variable);
+    by_model_id!(name_generators, name_generator);
+    by_model_id!(pf_model_params, pf_model_params);
+    by_model_id!(pf_var_params, pf_var_params);
+    by_model_id!(graphic_pages, graphic_page);
+    by_model_id!(compound_channels, channel);
+    by_model_id!(fluid_packages, fluid_package);
+    by_model_id!(reaction_packages, reaction_package);
+    by_model_id!(reaction_details, reaction_detail);
+    by_model_id!(status_informations, status_information);
+    by_model_id!(status_params, status_params);
+    by_model_id!(global_scripts, global_script);
+    by_model_id!(trend_charts, trend_chart);
+    by_model_id!(variable_chooses, variable_choose);
+    by_model_id!(cold_states, cold_state);
+    by_model_id!(dcs_tags, dcs_tag);
+
+    // 2. 组分通道下的表
+    let channel_ids: Vec<String> = bundle
+        .compound_channels
+        .iter()
+        .map(|c| c.id.clone())
+        .collect();
+    bundle.compound_details = all_detail::Entity::find()
+        .filter(all_detail::Column::CompoundChannelId.is_in(channel_ids.clone()))
+        .all(db)
+        .await?;
+    bundle.compound_henrys = henry::Entity::find()
+        .filter(henry::Column::CompoundChannelId.is_in(channel_ids.clone()))
+        .all(db)
+        .await?;
+    bundle.compound_oils = oil::Entity::find()
+        .filter(oil::Column::CompoundChannelId.is_in(channel_ids))
+        .all(db)
+        .await?;
+    let henry_ids: Vec<String> = bundle
+        .compound_henrys
+        .iter()
+        .map(|h| h.id.clone())
+        .collect();
+    bundle.compound_henry_details = henry_detail::Entity::find()
+        .filter(henry_detail::Column::CompoundHenryId.is_in(henry_ids))
+        .all(db)
+        .await?;
+
+    // 3. 物性包下的表
+    let package_ids: Vec<String> = bundle.fluid_packages.iter().map(|p| p.id.clone()).collect();
+    bundle.property_calcs = property_calc::Entity::find()
+        .filter(property_calc::Column::FluidPackageId.is_in(package_ids.clone()))
+        .all(db)
+        .await?;
+    macro_rules! by_package_id {
+        ($field:ident, $entity:ident) => {
+            bundle.$field = $entity::Entity::find()
+                .filter($entity::Column::FluidPackageId.is_in(package_ids.clone()))
+                .all(db)
+                .await?;
+        };
+    }
+    for_each_binary_table!(by_package_id);
+
+    // 4. 流程图拓扑
+    bundle.flowsheet = load_flowsheet(db, model_id).await?;
+
+    Ok(bundle)
+}
+
+impl ModelBundle {
+    /// 各表行数统计（用于清单 / 返回给前端）
+    pub fn table_counts(&self) -> BTreeMap<String, u32> {
+        let mut counts = BTreeMap::new();
+        counts.insert(
+            model::Entity.table_name().to_string(),
+            self.model.is_some() as u32,
+        );
+        macro_rules! count {
+            ($field:ident, $entity:ident) => {
+                counts.insert(
+                    $entity::Entity.table_name().to_string(),
+                    self.$field.len() as u32,
+                );
+            };
+        }
+        count!(configs, config);
+        count!(unit_sets, unit_set);
+        count!(unit_items, unit_item);
+        count!(system_variables, system_variable);
+        count!(name_generators, name_generator);
+        count!(pf_model_params, pf_model_params);
+        count!(pf_var_params, pf_var_params);
+        count!(graphic_pages, graphic_page);
+        count!(compound_channels, channel);
+        count!(compound_details, all_detail);
+        count!(compound_henrys, henry);
+        count!(compound_henry_details, henry_detail);
+        count!(compound_oils, oil);
+        count!(fluid_packages, fluid_package);
+        count!(property_calcs, property_calc);
+        count!(reaction_packages, reaction_package);
+        count!(reaction_details, reaction_detail);
+        count!(status_informations, status_information);
+        count!(status_params, status_params);
+        count!(global_scripts, global_script);
+        count!(trend_charts, trend_chart);
+        count!(variable_chooses, variable_choose);
+        count!(cold_states, cold_state);
+        count!(dcs_tags, dcs_tag);
+        for_each_binary_table!(count);
+        counts.extend(self.flowsheet.table_counts());
+        counts
+    }
+
+    /// 生成新的主键并改写所有交叉引用，返回改写后的数据包与 ID 映射表
+    ///
+    /// 自增主键的表在插入时由数据库重新分配，不进入映射表；
+    /// 图元 ID（graphic_id / unit_id 等）属于模型内部命名空间，保持不变。
+    pub fn remap(mut self, new_model_id: &str) -> (ModelBundle, IdRemap) {
+        let mut ids = IdRemap::default();
+
+        // 1. 先为所有字符串主键分配新 ID（引用可能先于被引用行出现）
+        macro_rules! fresh_ids {
+            ($field:ident) => {
+                for row in self.$field.iter() {
+                    ids.fresh(&row.id);
+                }
+            };
+            ($field:ident, $entity:ident) => {
+                fresh_ids!($field);
+            };
+        }
+        fresh_ids!(unit_sets);
+        fresh_ids!(unit_items);
+        fresh_ids!(system_variables);
+        fresh_ids!(pf_model_params);
+        fresh_ids!(graphic_pages);
+        fresh_ids!(compound_channels);
+        fresh_ids!(compound_details);
+        fresh_ids!(compound_henrys);
+        fresh_ids!(compound_henry_details);
+        fresh_ids!(compound_oils);
+        fresh_ids!(fluid_packages);
+        fresh_ids!(property_calcs);
+        fresh_ids!(reaction_packages);
+        fresh_ids!(reaction_details);
+        fresh_ids!(status_informations);
+        fresh_ids!(global_scripts);
+        fresh_ids!(variable_chooses);
+        for_each_binary_table!(fresh_ids);
+
+        // 2. 改写主键、model_id 与外键
+        if let Some(m) = self.model.as_mut() {
+            m.id = new_model_id.to_string();
+        }
+        macro_rules! rebind {
+            ($field:ident, |$row:ident| $body:block) => {
+                for $row in self.$field.iter_mut() {
+                    $row.model_id = new_model_id.to_string();
+                    $body
+                }
+            };
+        }
+        rebind!(configs, |r| {});
+        rebind!(name_generators, |r| {});
+        rebind!(pf_var_params, |r| {});
+        rebind!(status_params, |r| {});
+        rebind!(trend_charts, |r| {});
+        rebind!(dcs_tags, |r| {});
+        rebind!(unit_sets, |r| { r.id = ids.get(&r.id) });
+        rebind!(unit_items, |r| {
+            r.id = ids.get(&r.id);
+            r.set_id = ids.get(&r.set_id);
+        });
+        rebind!(system_variables, |r| { r.id = ids.get(&r.id) });
+        rebind!(pf_model_params, |r| { r.id = ids.get(&r.id) });
+        rebind!(graphic_pages, |r| { r.id = ids.get(&r.id) });
+        rebind!(compound_channels, |r| { r.id = ids.get(&r.id) });
+        rebind!(fluid_packages, |r| {
+            r.id = ids.get(&r.id);
+            r.compound_channel_id = ids.get(&r.compound_channel_id);
+            r.compound_henry_id = ids.get(&r.compound_henry_id);
+        });
+        rebind!(reaction_packages, |r| {
+            r.id = ids.get(&r.id);
+            r.compound_channel_id = ids.get(&r.compound_channel_id);
+            r.fluid_package_ids = ids.rewrite_list(&r.fluid_package_ids);
+        });
+        rebind!(reaction_details, |r| {
+            r.id = ids.get(&r.id);
+            r.reaction_package_id = ids.get(&r.reaction_package_id);
+        });
+        rebind!(status_informations, |r| { r.id = ids.get(&r.id) });
+        rebind!(global_scripts, |r| { r.id = ids.get(&r.id) });
+        rebind!(variable_chooses, |r| { r.id = ids.get(&r.id) });
+        rebind!(cold_states, |r| {
+            r.fluid_package_id = ids.get(&r.fluid_package_id);
+        });
+
+        for r in self.compound_details.iter_mut() {
+            r.id = ids.get(&r.id);
+            r.compound_channel_id = ids.get(&r.compound_channel_id);
+        }
+        for r in self.compound_henrys.iter_mut() {
+            r.id = ids.get(&r.id);
+            r.compound_channel_id = ids.get(&r.compound_channel_id);
+            r.compound_detail_ids = ids.rewrite_list(&r.compound_detail_ids);
+        }
+        for r in self.compound_henry_details.iter_mut() {
+            r.id = ids.get(&r.id);
+            r.compound_henry_id = ids.get(&r.compound_henry_id);
+            r.is_default_id = ids.get(&r.is_default_id);
+        }
+        for r in self.compound_oils.iter_mut() {
+            r.id = ids.get(&r.id);
+            r.compound_channel_id = ids.get(&r.compound_channel_id);
+        }
+        for r in self.property_calcs.iter_mut() {
+            r.id = ids.get(&r.id);
+            r.fluid_package_id = ids.get(&r.fluid_package_id);
+        }
+        macro_rules! rebind_binary {
+            ($field:ident, $entity:ident) => {
+                for r in self.$field.iter_mut() {
+                    r.id = ids.get(&r.id);
+                    r.fluid_package_id = ids.get(&r.fluid_package_id);
+                    r.is_default_id = ids.get(&r.is_default_id);
+                }
+            };
+        }
+        for_each_binary_table!(rebind_binary);
+        self.flowsheet.rebind(new_model_id, &ids);
+
+        (self, ids)
+    }
+}
+
+// 批量插入分片大小，避免超出 SQLite 单条语句的参数上限
+const INSERT_CHUNK_SIZE: usize = 200;
+
+/// 将数据包写入 business 库（调用方负责开启事务）
+pub async fn insert_model_bundle<C: ConnectionTrait>(
+    db: &C,
+    bundle: ModelBundle,
+) -> Result<(), DbErr> {
+    if let Some(m) = bundle.model {
+        model::Entity::insert(m.into_active_model())
+            .exec(db)
+            .await?;
+    }
+
+    // 字符串主键表：原样插入
+    macro_rules! insert_rows {
+        ($rows:expr, $entity:ident) => {
+            let rows: Vec<$entity::ActiveModel> = $rows
+                .into_iter()
+                .map(IntoActiveModel::into_active_model)
+                .collect();
+            for chunk in rows.chunks(INSERT_CHUNK_SIZE) {
+                $entity::Entity::insert_many(chunk.to_vec())
+                    .exec(db)
+                    .await?;
+            }
+        };
+    }
+    // 自增主键表：清空 id 交由数据库分配
+    macro_rules! insert_rows_auto_id {
+        ($rows:expr, $entity:ident) => {
+            let rows: Vec<$entity::ActiveModel> = $rows
+                .into_iter()
+                .map(|m| {
+                    let mut am = m.into_active_model();
+                    am.id = NotSet;
+                    am
+                })
+                .collect();
+            for chunk in rows.chunks(INSERT_CHUNK_SIZE) {
+                $entity::Entity::insert_many(chunk.to_vec())
+                    .exec(db)
+                    .await?;
+            }
+        };
+    }
+
+    insert_rows_auto_id!(bundle.configs, config);
+    insert_rows!(bundle.unit_sets, unit_set);
+    insert_rows!(bundle.unit_items, unit_item);
+    insert_rows!(bundle.system_variables, system_variable);
+    insert_rows_auto_id!(bundle.name_generators, name_generator);
+    insert_rows!(bundle.pf_model_params, pf_model_params);
+    insert_rows_auto_id!(bundle.pf_var_params, pf_var_params);
+    insert_rows!(bundle.graphic_pages, graphic_page);
+    insert_rows!(bundle.compound_channels, channel);
+    insert_rows!(bundle.compound_details, all_detail);
+    insert_rows!(bundle.compound_henrys, henry);
+    insert_rows!(bundle.compound_henry_details, henry_detail);
+    insert_rows!(bundle.compound_oils, oil);
+    insert_rows!(bundle.fluid_packages, fluid_package);
+    insert_rows!(bundle.property_calcs, property_calc);
+    insert_rows!(bundle.binary_nrtl, nrtl);
+    insert_rows!(bundle.binary_nrtl_rk, nrtl_rk);
+    insert_rows!(bundle.binary_pr, pr);
+    insert_rows!(bundle.binary_psrk, psrk);
+    insert_rows!(bundle.binary_rk, rk);
+    insert_rows!(bundle.binary_srk, srk);
+    insert_rows!(bundle.binary_uniquac, uniquac);
+    insert_rows!(bundle.binary_wilson, wilson);
+    insert_rows!(bundle.reaction_packages, reaction_package);
+    insert_rows!(bundle.reaction_details, reaction_detail);
+    insert_rows!(bundle.status_informations, status_information);
+    insert_rows_auto_id!(bundle.status_params, status_params);
+    insert_rows!(bundle.global_scripts, global_script);
+    insert_rows_auto_id!(bundle.trend_charts, trend_chart);
+    insert_rows!(bundle.variable_chooses, variable_choose);
+    insert_rows_auto_id!(bundle.cold_states, cold_state);
+    insert_rows_auto_id!(bundle.dcs_tags, dcs_tag);
+    save_flowsheet(db, bundle.flowsheet).await?;
+
+    Ok(())
+}
+
+/// 删除模型在 business 库中的全部数据（物理删除，用于导入失败时回滚）
+pub async fn delete_model_bundle<C: ConnectionTrait>(db: &C, model_id: &str) -> Result<(), DbErr> {
+    let bundle = load_model_bundle(db, model_id).await?;
+
+    macro_rules! delete_rows {
+        ($field:ident, $entity:ident) => {
+            let ids: Vec<_> = bundle.$field.iter().map(|r| r.id.clone()).collect();
+            for chunk in ids.chunks(INSERT_CHUNK_SIZE) {
+                $entity::Entity::delete_many()
+                    .filter($entity::Column::Id.is_in(chunk.to_vec()))
+                    .exec(db)
+                    .await?;
+            }
+        };
+    }
+    delete_rows!(configs, config);
+    delete_rows!(unit_sets, unit_set);
+    delete_rows!(unit_items, unit_item);
+    delete_rows!(system_variables, system_variable);
+    delete_rows!(name_generators, name_generator);
+    delete_rows!(pf_model_params, pf_model_params);
+    delete_rows!(pf_var_params, pf_var_params);
+    delete_rows!(graphic_pages, graphic_page);
+    delete_rows!(compound_channels, channel);
+    delete_rows!(compound_details, all_detail);
+    delete_rows!(compound_henrys, henry);
+    delete_rows!(compound_henry_details, henry_detail);
+    delete_rows!(compound_oils, oil);
+    delete_rows!(fluid_packages, fluid_package);
+    delete_rows!(property_calcs, property_calc);
+    delete_rows!(reaction_packages, reaction_package);
+    delete_rows!(reaction_details, reaction_detail);
+    delete_rows!(status_informations, status_information);
+    delete_rows!(status_params, status_params);
+    delete_rows!(global_scripts, global_script);
+    delete_rows!(trend_charts, trend_chart);
+    delete_rows!(variable_chooses, variable_choose);
+    delete_rows!(cold_states, cold_state);
+    delete_rows!(dcs_tags, dcs_tag);
+    for_each_binary_table!(delete_rows);
+    delete_flowsheet(db, model_id).await?;
+
+    model::Entity::delete_by_id(model_id.to_string())
+        .exec(db)
+        .await?;
+    Ok(())
+}
+
+/// 未删除的模型中是否已有该名称
+pub async fn model_name_taken<C: ConnectionTrait>(db: &C, name: &str) -> Result<bool, DbErr> {
+    let count = model::Entity::find()
+        .filter(model::Column::ModelName.eq(name))
+        .filter(model::Column::Status.eq(0))
+        .count(db)
+        .await?;
+    Ok(count > 0)
+}
+
+/// 名称已被占用时依次追加 " (2)"、" (3)"…，返回第一个未被占用的名称
+pub async fn unique_model_name<C: ConnectionTrait>(db: &C, name: &str) -> Result<String, DbErr> {
+    let mut candidate = name.to_string();
+    let mut n = 2;
+    while model_name_taken(db, &candidate).await? {
+        candidate = format!("{} ({})", name, n);
+        n += 1;
+    }
+    Ok(candidate)
+}
+
+/// 模型另存为：在一个事务内深拷贝模型及其全部关联数据，返回新模型 ID
+pub async fn clone_model(source_model_id: String, new_name: String) -> Result<String, DbErr> {
+    let db = get_business_db().await?;
+
+    db.transaction::<_, String, DbErr>(|txn| {
+        Box::pin(async move {
+            if model_name_taken(txn, &new_name).await? {
+                return Err(DbErr::Custom(format!("模型名称已存在: {}", new_name)));
+            }
+
+            let new_model_id = new_id();
+            let bundle = load_model_bundle(txn, &source_model_id).await?;
+            let (mut bundle, _) = bundle.remap(&new_model_id);
+            if let Some(m) = bundle.model.as_mut() {
+                let now = chrono::Utc::now().naive_utc();
+                m.model_name = new_name;
+                m.create_at = now;
+                m.update_at = now;
+                m.status = 0;
+            }
+            insert_model_bundle(txn, bundle).await?;
+            Ok(new_model_id)
+        })
+    })
+    .await
+    .map_err(|e| match e {
+        TransactionError::Connection(e) => e,
+        TransactionError::Transaction(e) => e,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::service_database::database_business::migration::Migrator;
+    use sea_orm::{ActiveModelTrait, Database};
+    use sea_orm_migration::MigratorTrait;
+
+    #[tokio::test]
+    async fn test_unique_model_name() {
+        let db = Database::connect("sqlite::memory:").await.unwrap();
+        Migrator::up(&db, None).await.unwrap();
+        let now = chrono::Utc::now().naive_utc();
+        for (id, name, status) in [("a", "demo", 0), ("b", "demo (2)", 0), ("c", "demo (3)", 1)] {
+            model::Model {
+                id: id.to_string(),
+                model_no: String::new(),
+                developer: String::new(),
+                model_name: name.to_string(),
+                standard_temperature: 0.0,
+                standard_temperature_unit: String::new(),
+                standard_pressure: 0.0,
+                standard_pressure_unit: String::new(),
+                grid_state: String::new(),
+                grid_color: String::new(),
+                grid_size: 0,
+                create_at: now,
+                update_at: now,
+                status,
+                default_model_id: String::new(),
+            }
+            .into_active_model()
+            .insert(&db)
+            .await
+            .unwrap();
+        }
+        // 已删除（status = 1）的模型不占用名称
+        assert_eq!(unique_model_name(&db, "demo").await.unwrap(), "demo (3)");
+        assert_eq!(unique_model_name(&db, "other").await.unwrap(), "other");
+        assert!(model_name_taken(&db, "demo (2)").await.unwrap());
+    }
+}
@@ -14,6 +14,32 @@ pub struct AutoShutterParams {
     pub auto_shutter: i32,
     pub auto_time_interval: i32,
     pub auto_count: i32,
+    /// 保留策略，为空时沿用 auto_count 条轮转覆盖
+    #[serde(default)]
+    pub retention: Option<AutoShutterRetention>,
+}
+
+/// 自动快照保留策略（祖父-父-子），auto_count 大于 0 时同时限制未固定快照的总数
+#[napi(object, namespace = "modelConfig", js_name = "AutoShutterRetention")]
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct AutoShutterRetention {
+    /// 最近多少分钟内的快照全部保留
+    pub keep_all_minutes: u32,
+    /// 之后的稀疏分层，超出最后一层的快照删除
+    pub tiers: Vec<AutoShutterRetentionTier>,
+}
+
+#[napi(
+    object,
+    namespace = "modelConfig",
+    js_name = "AutoShutterRetentionTier"
+)]
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct AutoShutterRetentionTier {
+    /// 该层覆盖到多少分钟前
+    pub max_age_minutes: u32,
+    /// 该层每多少分钟保留一条
+    pub interval_minutes: u32,
 }
 
 #[napi(object, namespace = "modelConfig", js_name = "ModelConfigDTO")]
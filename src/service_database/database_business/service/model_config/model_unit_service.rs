@@ -1,3 +1,4 @@
+use crate::calc_handle::unit_conversion::UnitRegistry;
 use crate::service_database::database_business::db_business_connection::get_business_db;
 use crate::service_database::database_business::entity::model_config::model_unit_item_entity::{
     ActiveModel as ItemActiveModel, Column as ItemColumn, Entity as ItemEntity, Model as ItemModel,
@@ -5,6 +6,7 @@ use crate::service_database::database_business::entity::model_config::model_unit
 use crate::service_database::database_business::entity::model_config::model_unit_set_entity::{
     ActiveModel as SetActiveModel, Column as SetColumn, Entity as SetEntity, Model as SetModel,
 };
+use crate::service_database::database_config::service::conf_unit_conversion_service::get_unit_registry;
 use napi_derive::napi;
 use sea_orm::{Set, TransactionTrait, entity::prelude::*};
 use serde::{Deserialize, Serialize};
@@ -368,3 +370,114 @@ pub async fn get_model_unit_set_items_by_model_id(
 
     Ok(res.into_iter().map(ModelUnitItemDTO::from).collect())
 }
+
+// ======================================
+// 参数批量换算：SI 值 ↔ 模型当前（默认）单位集中的显示单位
+// categories 为 参数名 → 单位类别编码，单位集中该类别编码对应的 value 即显示单位
+// ======================================
+
+#[derive(Clone, Copy)]
+enum UnitDirection {
+    FromSi,
+    ToSi,
+}
+
+/// 读取模型当前单位集：单位类别编码 → 单位
+async fn get_model_active_units(
+    model_id: String,
+) -> Result<std::collections::HashMap<String, String>, DbErr> {
+    let set = get_model_unit_set_one_by_model_id_and_default(model_id.clone()).await?;
+    let items = get_model_unit_items_by_set_id_and_model_id(set.id, model_id).await?;
+    Ok(items.into_iter().map(|i| (i.code, i.value)).collect())
+}
+
+/// 递归换算：对象中与 categories 匹配的字段（数值、数值字符串或其数组）按类别换算
+fn convert_params_value(
+    value: &mut serde_json::Value,
+    category: Option<&str>,
+    ctx: &(
+        &UnitRegistry,
+        &std::collections::HashMap<String, String>,
+        &std::collections::HashMap<String, String>,
+        UnitDirection,
+    ),
+) -> Result<(), DbErr> {
+    use serde_json::Value;
+    let (registry, categories, units, direction) = *ctx;
+    match value {
+        Value::Object(map) => {
+            for (key, v) in map.iter_mut() {
+                let c = categories.get(key).map(String::as_str).or(category);
+                convert_params_value(v, c, ctx)?;
+            }
+        }
+        Value::Array(items) => {
+            for v in items.iter_mut() {
+                convert_params_value(v, category, ctx)?;
+            }
+        }
+        Value::Number(_) | Value::String(_) => {
+            let Some(category) = category else {
+                return Ok(());
+            };
+            let number = match value {
+                Value::Number(n) => n.as_f64(),
+                Value::String(s) => s.trim().parse::<f64>().ok(),
+                _ => None,
+            };
+            let Some(number) = number else {
+                return Ok(());
+            };
+            let unit = units.get(category).ok_or_else(|| {
+                DbErr::Custom(format!("单位换算错误: 当前单位集中没有类别 {}", category))
+            })?;
+            let converted = match direction {
+                UnitDirection::FromSi => registry.convert_from_si(number, unit, category),
+                UnitDirection::ToSi => registry.convert_to_si(number, unit, category),
+            }
+            .map_err(|e| DbErr::Custom(format!("单位换算错误: {}", e)))?;
+            *value = match value {
+                Value::String(_) => Value::String(converted.to_string()),
+                _ => serde_json::Number::from_f64(converted)
+                    .map(Value::Number)
+                    .unwrap_or(Value::Null),
+            };
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+async fn convert_model_params(
+    model_id: String,
+    mut params: serde_json::Value,
+    categories: std::collections::HashMap<String, String>,
+    direction: UnitDirection,
+) -> Result<serde_json::Value, DbErr> {
+    let units = get_model_active_units(model_id).await?;
+    let registry = get_unit_registry().await?;
+    convert_params_value(
+        &mut params,
+        None,
+        &(&registry, &categories, &units, direction),
+    )?;
+    Ok(params)
+}
+
+/// 将 SI 参数对象换算为模型当前单位集下的数值
+pub async fn convert_model_params_from_si(
+    model_id: String,
+    params: serde_json::Value,
+    categories: std::collections::HashMap<String, String>,
+) -> Result<serde_json::Value, DbErr> {
+    convert_model_params(model_id, params, categories, UnitDirection::FromSi).await
+}
+
+/// 将模型当前单位集下的参数对象换算为 SI 数值
+pub async fn convert_model_params_to_si(
+    model_id: String,
+    params: serde_json::Value,
+    categories: std::collections::HashMap<String, String>,
+) -> Result<serde_json::Value, DbErr> {
+    convert_model_params(model_id, params, categories, UnitDirection::ToSi).await
+}
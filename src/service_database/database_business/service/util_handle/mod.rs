@@ -1,5 +1,8 @@
+pub mod model_dcs_tag_service;
 pub mod model_global_script_service;
 pub mod model_initialize_data_in_cold_state_service;
 pub mod model_status_handle_service;
 pub mod model_trend_chart_config_service;
+pub mod model_undo_apply_service;
+pub mod model_undo_history_service;
 pub mod model_variable_choose_service;
@@ -0,0 +1,669 @@
+use crate::service_database::database_business::db_business_connection::get_business_db;
+use crate::service_database::database_business::entity::model_util_handle::model_dcs_tag_entity::{
+    ActiveModel as TagActiveModel, Column as TagColumn, Entity as TagEntity, Model as TagModel,
+};
+use crate::service_database::database_cache::service::dcs_pointinfor_service::{
+    PositionData, update_or_insert_position_information_cache,
+};
+use crate::tool_handle::dcs_tag_import_tool::{
+    DcsTagField, DcsTagIssue, DcsTagRecord, DcsTagTable, WORKBOOK_EXTENSIONS, parse_dcs_tag_table,
+    parse_dcs_tag_workbook, validate_tag,
+};
+use napi_derive::napi;
+use sea_orm::{
+    ConnectionTrait, IntoActiveModel, NotSet, QueryFilter, QueryOrder, Set, TransactionError,
+    TransactionTrait, TryIntoModel, entity::prelude::*,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+// ======================================
+// DCS 位号库：每个模型一套持久化的位号（量程、工程单位、报警限、图元变量映射），
+// 可由组态软件导出的位号清单（分隔文本或电子表格）导入，并同步到缓存库的点位信息供运行时使用
+// ======================================
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[napi(object, namespace = "modelDcsTag", js_name = "ModelDcsTagDTO")]
+pub struct ModelDcsTagDTO {
+    /// 新增时传 0；保存时非 0 按 ID 更新（可修改位号名）
+    pub id: i32,
+    pub model_id: String,
+    pub tag_name: String,
+    pub description: String,
+    pub type_num: i32,
+    pub type_name: String,
+    pub unit: String,
+    pub range_low: Option<f64>,
+    pub range_high: Option<f64>,
+    pub alarm_hh: Option<f64>,
+    pub alarm_h: Option<f64>,
+    pub alarm_l: Option<f64>,
+    pub alarm_ll: Option<f64>,
+    /// 关联的图元 ID
+    pub graphic_id: String,
+    /// 图元下的变量路径
+    pub variable_path: String,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[napi(object, namespace = "modelDcsTag")]
+pub struct DcsTagColumnMapping {
+    /// 字段名，如 tagName / alarmHh / variable_path
+    pub field: String,
+    /// 文件中的列名
+    pub header: String,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[napi(object, namespace = "modelDcsTag")]
+pub struct DcsTagImportOptions {
+    /// 分隔文本的分隔符，默认按首行自动识别（逗号 / 制表符 / 分号）；电子表格文件忽略
+    pub delimiter: Option<String>,
+    /// 显式指定列名，未指定的字段按内置别名识别
+    pub column_map: Option<Vec<DcsTagColumnMapping>>,
+    /// 删除文件中没有的位号，默认 false
+    pub replace: Option<bool>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[napi(object, namespace = "modelDcsTag")]
+pub struct DcsTagImportIssue {
+    pub line: u32,
+    pub message: String,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[napi(object, namespace = "modelDcsTag")]
+pub struct DcsTagImportPreview {
+    /// 识别出的字段
+    pub columns: Vec<String>,
+    pub tags: Vec<ModelDcsTagDTO>,
+    pub skipped: u32,
+    pub issues: Vec<DcsTagImportIssue>,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[napi(object, namespace = "modelDcsTag")]
+pub struct DcsTagImportResult {
+    pub inserted: u32,
+    pub updated: u32,
+    pub deleted: u32,
+    pub skipped: u32,
+    pub issues: Vec<DcsTagImportIssue>,
+}
+
+fn dcs_tag_err(msg: impl std::fmt::Display) -> DbErr {
+    DbErr::Custom(format!("DCS 位号错误: {}", msg))
+}
+
+impl From<TagModel> for ModelDcsTagDTO {
+    fn from(m: TagModel) -> Self {
+        Self {
+            id: m.id,
+            model_id: m.model_id,
+            tag_name: m.tag_name,
+            description: m.description,
+            type_num: m.type_num,
+            type_name: m.type_name,
+            unit: m.unit,
+            range_low: m.range_low,
+            range_high: m.range_high,
+            alarm_hh: m.alarm_hh,
+            alarm_h: m.alarm_h,
+            alarm_l: m.alarm_l,
+            alarm_ll: m.alarm_ll,
+            graphic_id: m.graphic_id,
+            variable_path: m.variable_path,
+        }
+    }
+}
+
+impl From<ModelDcsTagDTO> for DcsTagRecord {
+    fn from(d: ModelDcsTagDTO) -> Self {
+        Self {
+            tag_name: d.tag_name.trim().to_string(),
+            description: d.description,
+            type_num: d.type_num,
+            type_name: d.type_name,
+            unit: d.unit,
+            range_low: d.range_low,
+            range_high: d.range_high,
+            alarm_hh: d.alarm_hh,
+            alarm_h: d.alarm_h,
+            alarm_l: d.alarm_l,
+            alarm_ll: d.alarm_ll,
+            graphic_id: d.graphic_id,
+            variable_path: d.variable_path,
+        }
+    }
+}
+
+impl ModelDcsTagDTO {
+    fn from_record(model_id: &str, tag: DcsTagRecord) -> Self {
+        Self {
+            id: 0,
+            model_id: model_id.to_string(),
+            tag_name: tag.tag_name,
+            description: tag.description,
+            type_num: tag.type_num,
+            type_name: tag.type_name,
+            unit: tag.unit,
+            range_low: tag.range_low,
+            range_high: tag.range_high,
+            alarm_hh: tag.alarm_hh,
+            alarm_h: tag.alarm_h,
+            alarm_l: tag.alarm_l,
+            alarm_ll: tag.alarm_ll,
+            graphic_id: tag.graphic_id,
+            variable_path: tag.variable_path,
+        }
+    }
+
+    fn into_active_model(self) -> TagActiveModel {
+        TagActiveModel {
+            id: if self.id == 0 { NotSet } else { Set(self.id) },
+            model_id: Set(self.model_id),
+            tag_name: Set(self.tag_name),
+            description: Set(self.description),
+            type_num: Set(self.type_num),
+            type_name: Set(self.type_name),
+            unit: Set(self.unit),
+            range_low: Set(self.range_low),
+            range_high: Set(self.range_high),
+            alarm_hh: Set(self.alarm_hh),
+            alarm_h: Set(self.alarm_h),
+            alarm_l: Set(self.alarm_l),
+            alarm_ll: Set(self.alarm_ll),
+            graphic_id: Set(self.graphic_id),
+            variable_path: Set(self.variable_path),
+        }
+    }
+}
+
+/// 只写入 columns 中的字段，其余字段保持原值
+fn apply_record(am: &mut TagActiveModel, tag: &DcsTagRecord, columns: &[DcsTagField]) {
+    for field in columns {
+        match field {
+            DcsTagField::TagName => am.tag_name.set_if_not_equals(tag.tag_name.clone()),
+            DcsTagField::Description => am.description.set_if_not_equals(tag.description.clone()),
+            DcsTagField::TypeNum => am.type_num.set_if_not_equals(tag.type_num),
+            DcsTagField::TypeName => am.type_name.set_if_not_equals(tag.type_name.clone()),
+            DcsTagField::Unit => am.unit.set_if_not_equals(tag.unit.clone()),
+            DcsTagField::RangeLow => am.range_low.set_if_not_equals(tag.range_low),
+            DcsTagField::RangeHigh => am.range_high.set_if_not_equals(tag.range_high),
+            DcsTagField::AlarmHh => am.alarm_hh.set_if_not_equals(tag.alarm_hh),
+            DcsTagField::AlarmH => am.alarm_h.set_if_not_equals(tag.alarm_h),
+            DcsTagField::AlarmL => am.alarm_l.set_if_not_equals(tag.alarm_l),
+            DcsTagField::AlarmLl => am.alarm_ll.set_if_not_equals(tag.alarm_ll),
+            DcsTagField::GraphicId => am.graphic_id.set_if_not_equals(tag.graphic_id.clone()),
+            DcsTagField::VariablePath => am
+                .variable_path
+                .set_if_not_equals(tag.variable_path.clone()),
+        }
+    }
+}
+
+#[derive(Default)]
+struct UpsertCount {
+    inserted: u32,
+    updated: u32,
+    deleted: u32,
+    /// 与已有位号合并后校验不通过、未写入的位号：(在 tags 中的下标, 原因)
+    rejected: Vec<(usize, String)>,
+}
+
+/// 按位号新增或更新；replace 为 true 时删除既不在 tags、也不在 kept 中的位号
+/// （kept 为文件中出现但该行被跳过的位号，一行写错不能导致已配置的位号被删）。
+/// 已有位号只写入 columns 中的字段，合并后的整行重新校验，不合法的位号保持原值
+async fn upsert_tags<C: ConnectionTrait>(
+    db: &C,
+    model_id: &str,
+    tags: Vec<DcsTagRecord>,
+    kept: &[String],
+    columns: &[DcsTagField],
+    replace: bool,
+) -> Result<UpsertCount, DbErr> {
+    let existing: HashMap<String, TagModel> = TagEntity::find()
+        .filter(TagColumn::ModelId.eq(model_id))
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|m| (m.tag_name.clone(), m))
+        .collect();
+    let incoming: HashSet<&str> = tags
+        .iter()
+        .map(|t| t.tag_name.as_str())
+        .chain(kept.iter().map(String::as_str))
+        .collect();
+
+    let mut count = UpsertCount::default();
+    if replace {
+        let to_delete: Vec<i32> = existing
+            .values()
+            .filter(|m| !incoming.contains(m.tag_name.as_str()))
+            .map(|m| m.id)
+            .collect();
+        for chunk in to_delete.chunks(500) {
+            count.deleted += TagEntity::delete_many()
+                .filter(TagColumn::Id.is_in(chunk.to_vec()))
+                .exec(db)
+                .await?
+                .rows_affected as u32;
+        }
+    }
+
+    let mut to_insert: Vec<TagActiveModel> = Vec::new();
+    for (index, tag) in tags.into_iter().enumerate() {
+        match existing.get(&tag.tag_name) {
+            Some(model) => {
+                let mut am = model.clone().into_active_model();
+                apply_record(&mut am, &tag, columns);
+                if !am.is_changed() {
+                    continue;
+                }
+                // 文件只有部分列时，与库中其余字段合并后的量程、报警限也必须合法
+                let merged = DcsTagRecord::from(ModelDcsTagDTO::from(am.clone().try_into_model()?));
+                if let Err(message) = validate_tag(&merged) {
+                    count.rejected.push((index, message));
+                    continue;
+                }
+                am.update(db).await?;
+                count.updated += 1;
+            }
+            None => {
+                let mut am = ModelDcsTagDTO {
+                    model_id: model_id.to_string(),
+                    ..Default::default()
+                }
+                .into_active_model();
+                apply_record(&mut am, &tag, &DcsTagField::ALL);
+                to_insert.push(am);
+                count.inserted += 1;
+            }
+        }
+    }
+    for chunk in to_insert.chunks(200) {
+        TagEntity::insert_many(chunk.to_vec()).exec(db).await?;
+    }
+    Ok(count)
+}
+
+/// 获取模型的全部位号，按位号排序
+pub async fn get_dcs_tags_by_model_id(model_id: String) -> Result<Vec<ModelDcsTagDTO>, DbErr> {
+    let db = get_business_db().await?;
+    let res = TagEntity::find()
+        .filter(TagColumn::ModelId.eq(model_id))
+        .order_by_asc(TagColumn::TagName)
+        .all(db)
+        .await?;
+    Ok(res.into_iter().map(ModelDcsTagDTO::from).collect())
+}
+
+/// 获取映射到某个图元的位号
+pub async fn get_dcs_tags_by_graphic_id(
+    model_id: String,
+    graphic_id: String,
+) -> Result<Vec<ModelDcsTagDTO>, DbErr> {
+    let db = get_business_db().await?;
+    let res = TagEntity::find()
+        .filter(TagColumn::ModelId.eq(model_id))
+        .filter(TagColumn::GraphicId.eq(graphic_id))
+        .order_by_asc(TagColumn::TagName)
+        .all(db)
+        .await?;
+    Ok(res.into_iter().map(ModelDcsTagDTO::from).collect())
+}
+
+/// 按位号名查询
+pub async fn get_dcs_tag_by_name(
+    model_id: String,
+    tag_name: String,
+) -> Result<Option<ModelDcsTagDTO>, DbErr> {
+    let db = get_business_db().await?;
+    let res = TagEntity::find()
+        .filter(TagColumn::ModelId.eq(model_id))
+        .filter(TagColumn::TagName.eq(tag_name))
+        .one(db)
+        .await?;
+    Ok(res.map(ModelDcsTagDTO::from))
+}
+
+/// 批量保存位号并校验量程与报警限，返回新增与有变化的条数：
+/// id 非 0 时按 ID 整行更新（可修改位号名），否则按位号名新增或整行更新
+pub async fn save_dcs_tags(model_id: String, tags: Vec<ModelDcsTagDTO>) -> Result<u32, DbErr> {
+    let mut names = HashSet::new();
+    let mut by_id = Vec::new();
+    let mut by_name = Vec::new();
+    for dto in tags {
+        let id = dto.id;
+        let tag = DcsTagRecord::from(dto);
+        validate_tag(&tag).map_err(dcs_tag_err)?;
+        if !names.insert(tag.tag_name.clone()) {
+            return Err(dcs_tag_err(format!("位号 {} 重复", tag.tag_name)));
+        }
+        if id == 0 {
+            by_name.push(tag);
+        } else {
+            by_id.push((id, tag));
+        }
+    }
+    let db = get_business_db().await?;
+    db.transaction::<_, u32, DbErr>(|txn| {
+        Box::pin(async move { save_tags(txn, &model_id, by_id, by_name).await })
+    })
+    .await
+    .map_err(|e| match e {
+        TransactionError::Connection(db_err) => db_err,
+        TransactionError::Transaction(db_err) => db_err,
+    })
+}
+
+/// 先按 ID 更新，改名后空出的位号名可以由本批新增的位号使用；返回新增与有变化的条数
+async fn save_tags<C: ConnectionTrait>(
+    db: &C,
+    model_id: &str,
+    by_id: Vec<(i32, DcsTagRecord)>,
+    by_name: Vec<DcsTagRecord>,
+) -> Result<u32, DbErr> {
+    let mut saved = 0;
+    for (id, tag) in by_id {
+        let model = TagEntity::find_by_id(id)
+            .filter(TagColumn::ModelId.eq(model_id))
+            .one(db)
+            .await?
+            .ok_or_else(|| dcs_tag_err(format!("位号 ID {} 不存在", id)))?;
+        if model.tag_name != tag.tag_name {
+            let taken = TagEntity::find()
+                .filter(TagColumn::ModelId.eq(model_id))
+                .filter(TagColumn::TagName.eq(tag.tag_name.clone()))
+                .one(db)
+                .await?;
+            if taken.is_some() {
+                return Err(dcs_tag_err(format!("位号 {} 已存在", tag.tag_name)));
+            }
+        }
+        let mut am = model.into_active_model();
+        apply_record(&mut am, &tag, &DcsTagField::ALL);
+        if am.is_changed() {
+            am.update(db).await?;
+            saved += 1;
+        }
+    }
+    let count = upsert_tags(db, model_id, by_name, &[], &DcsTagField::ALL, false).await?;
+    Ok(saved + count.inserted + count.updated)
+}
+
+/// 批量删除 (限定 model_id)
+pub async fn delete_dcs_tags_by_ids(model_id: String, ids: Vec<i32>) -> Result<bool, DbErr> {
+    let db = get_business_db().await?;
+    let res = TagEntity::delete_many()
+        .filter(TagColumn::ModelId.eq(model_id))
+        .filter(TagColumn::Id.is_in(ids))
+        .exec(db)
+        .await?;
+    Ok(res.rows_affected > 0)
+}
+
+fn parse_column_map(options: &DcsTagImportOptions) -> Result<Vec<(DcsTagField, String)>, DbErr> {
+    let mut column_map = Vec::new();
+    for mapping in options.column_map.iter().flatten() {
+        let field = DcsTagField::from_key(&mapping.field)
+            .ok_or_else(|| dcs_tag_err(format!("未知的位号字段: {}", mapping.field)))?;
+        column_map.push((field, mapping.header.clone()));
+    }
+    Ok(column_map)
+}
+
+fn parse_content(content: &str, options: &DcsTagImportOptions) -> Result<DcsTagTable, DbErr> {
+    let delimiter = match options.delimiter.as_deref() {
+        None | Some("") => None,
+        Some("\\t") | Some("tab") => Some('\t'),
+        Some(d) if d.chars().count() == 1 => d.chars().next(),
+        Some(d) => return Err(dcs_tag_err(format!("分隔符必须是单个字符: {}", d))),
+    };
+    parse_dcs_tag_table(content, delimiter, &parse_column_map(options)?).map_err(dcs_tag_err)
+}
+
+fn to_issues(issues: Vec<DcsTagIssue>) -> Vec<DcsTagImportIssue> {
+    issues
+        .into_iter()
+        .map(|i| DcsTagImportIssue {
+            line: i.line as u32,
+            message: i.message,
+        })
+        .collect()
+}
+
+/// 解析位号清单但不写库，用于导入前预览
+pub async fn preview_dcs_tag_import(
+    content: String,
+    options: Option<DcsTagImportOptions>,
+) -> Result<DcsTagImportPreview, DbErr> {
+    let table = parse_content(&content, &options.unwrap_or_default())?;
+    Ok(DcsTagImportPreview {
+        columns: table.columns.iter().map(|f| f.key().to_string()).collect(),
+        tags: table
+            .tags
+            .into_iter()
+            .map(|t| ModelDcsTagDTO::from_record("", t))
+            .collect(),
+        skipped: table.skipped as u32,
+        issues: to_issues(table.issues),
+    })
+}
+
+/// 写入解析好的位号表：已有位号只覆盖文件中出现的列，有问题的行跳过并在结果中列出
+async fn import_table(
+    model_id: String,
+    table: DcsTagTable,
+    replace: bool,
+) -> Result<DcsTagImportResult, DbErr> {
+    let db = get_business_db().await?;
+    let columns = table.columns;
+    let tags = table.tags;
+    let kept = table.skipped_names;
+    let count = db
+        .transaction::<_, UpsertCount, DbErr>(|txn| {
+            Box::pin(
+                async move { upsert_tags(txn, &model_id, tags, &kept, &columns, replace).await },
+            )
+        })
+        .await
+        .map_err(|e| match e {
+            TransactionError::Connection(db_err) => db_err,
+            TransactionError::Transaction(db_err) => db_err,
+        })?;
+
+    let mut issues = table.issues;
+    for (index, message) in &count.rejected {
+        issues.push(DcsTagIssue {
+            line: table.lines[*index],
+            message: message.clone(),
+        });
+    }
+    issues.sort_by_key(|i| i.line);
+    Ok(DcsTagImportResult {
+        inserted: count.inserted,
+        updated: count.updated,
+        deleted: count.deleted,
+        skipped: (table.skipped + count.rejected.len()) as u32,
+        issues: to_issues(issues),
+    })
+}
+
+/// 导入位号清单文本：已有位号只覆盖文件中出现的列，有问题的行跳过并在结果中列出
+pub async fn import_dcs_tags(
+    model_id: String,
+    content: String,
+    options: Option<DcsTagImportOptions>,
+) -> Result<DcsTagImportResult, DbErr> {
+    let options = options.unwrap_or_default();
+    let table = parse_content(&content, &options)?;
+    import_table(model_id, table, options.replace.unwrap_or(false)).await
+}
+
+/// 从文件导入位号清单：xlsx / xlsm / xlsb / xls / ods 读取第一个工作表，其余按 UTF-8 分隔文本解析
+pub async fn import_dcs_tags_from_file(
+    model_id: String,
+    path: String,
+    options: Option<DcsTagImportOptions>,
+) -> Result<DcsTagImportResult, DbErr> {
+    let options = options.unwrap_or_default();
+    let extension = std::path::Path::new(&path)
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(str::to_lowercase)
+        .unwrap_or_default();
+    let table = if WORKBOOK_EXTENSIONS.contains(&extension.as_str()) {
+        parse_dcs_tag_workbook(&path, &parse_column_map(&options)?).map_err(dcs_tag_err)?
+    } else {
+        let bytes =
+            std::fs::read(&path).map_err(|e| dcs_tag_err(format!("无法读取 {}: {}", path, e)))?;
+        let content = String::from_utf8(bytes).map_err(|_| {
+            dcs_tag_err(format!(
+                "{} 不是 UTF-8 编码，请以 UTF-8 CSV 或 xlsx 格式导出",
+                path
+            ))
+        })?;
+        parse_content(&content, &options)?
+    };
+    import_table(model_id, table, options.replace.unwrap_or(false)).await
+}
+
+/// 用模型的位号替换缓存库中的点位信息，返回同步的位号数
+pub async fn sync_dcs_tags_to_point_cache(model_id: String) -> Result<u32, DbErr> {
+    let tags = get_dcs_tags_by_model_id(model_id).await?;
+    let count = tags.len() as u32;
+    let data: Vec<PositionData> = tags
+        .into_iter()
+        .map(|t| PositionData {
+            name_display: if t.description.is_empty() {
+                t.tag_name.clone()
+            } else {
+                t.description
+            },
+            name: t.tag_name,
+            type_name: t.type_name,
+            type_num: t.type_num,
+        })
+        .collect();
+    update_or_insert_position_information_cache(data).await?;
+    Ok(count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::service_database::database_business::migration::Migrator;
+    use sea_orm::{Database, DatabaseConnection};
+    use sea_orm_migration::MigratorTrait;
+
+    async fn memory_db() -> DatabaseConnection {
+        let db = Database::connect("sqlite::memory:").await.unwrap();
+        Migrator::up(&db, None).await.unwrap();
+        db
+    }
+
+    fn tag(name: &str, alarm_h: Option<f64>, alarm_hh: Option<f64>) -> DcsTagRecord {
+        DcsTagRecord {
+            tag_name: name.to_string(),
+            alarm_h,
+            alarm_hh,
+            ..Default::default()
+        }
+    }
+
+    async fn find(db: &DatabaseConnection, name: &str) -> Option<TagModel> {
+        TagEntity::find()
+            .filter(TagColumn::TagName.eq(name))
+            .one(db)
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_partial_import_validates_merged_tag() {
+        let db = memory_db().await;
+        let tags = vec![tag("PI101", Some(90.0), Some(95.0))];
+        upsert_tags(&db, "m", tags, &[], &DcsTagField::ALL, false)
+            .await
+            .unwrap();
+
+        // 文件只有 H 列：与库中的 HH 合并后 H > HH，该位号不写入
+        let columns = [DcsTagField::TagName, DcsTagField::AlarmH];
+        let tags = vec![tag("PI101", Some(99.0), None)];
+        let count = upsert_tags(&db, "m", tags, &[], &columns, false)
+            .await
+            .unwrap();
+        assert_eq!(count.updated, 0);
+        assert_eq!(count.rejected.len(), 1);
+        assert_eq!(count.rejected[0].0, 0);
+        assert_eq!(find(&db, "PI101").await.unwrap().alarm_h, Some(90.0));
+
+        let tags = vec![tag("PI101", Some(93.0), None)];
+        let count = upsert_tags(&db, "m", tags, &[], &columns, false)
+            .await
+            .unwrap();
+        assert_eq!(count.updated, 1);
+        let saved = find(&db, "PI101").await.unwrap();
+        assert_eq!((saved.alarm_h, saved.alarm_hh), (Some(93.0), Some(95.0)));
+    }
+
+    #[tokio::test]
+    async fn test_save_by_id_renames_tag() {
+        let db = memory_db().await;
+        let tags = vec![tag("TI201", None, None), tag("TI202", None, None)];
+        upsert_tags(&db, "m", tags, &[], &DcsTagField::ALL, false)
+            .await
+            .unwrap();
+        let id = find(&db, "TI201").await.unwrap().id;
+
+        // 按 ID 改名，旧名由同批新增的位号使用
+        let saved = save_tags(
+            &db,
+            "m",
+            vec![(id, tag("TI203", None, None))],
+            vec![tag("TI201", None, None)],
+        )
+        .await
+        .unwrap();
+        assert_eq!(saved, 2);
+        assert_eq!(find(&db, "TI203").await.unwrap().id, id);
+        assert_ne!(find(&db, "TI201").await.unwrap().id, id);
+
+        // 改成已有的位号名
+        let result = save_tags(&db, "m", vec![(id, tag("TI202", None, None))], vec![]).await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_replace_keeps_skipped_tags() {
+        let db = memory_db().await;
+        let tags = vec![
+            tag("FI401", None, None),
+            tag("FI402", Some(10.0), Some(20.0)),
+            tag("FI403", None, None),
+        ];
+        upsert_tags(&db, "m", tags, &[], &DcsTagField::ALL, false)
+            .await
+            .unwrap();
+
+        // FI402 的报警限写错被跳过，整表替换时只删除文件中没有的 FI403
+        let text = "Tag,H,HH\nFI401,1,2\nFI402,abc,20\n";
+        let table = parse_dcs_tag_table(text, None, &[]).unwrap();
+        let count = upsert_tags(
+            &db,
+            "m",
+            table.tags,
+            &table.skipped_names,
+            &table.columns,
+            true,
+        )
+        .await
+        .unwrap();
+        assert_eq!(count.deleted, 1);
+        assert!(find(&db, "FI403").await.is_none());
+        assert_eq!(find(&db, "FI402").await.unwrap().alarm_h, Some(10.0));
+        assert_eq!(find(&db, "FI401").await.unwrap().alarm_hh, Some(2.0));
+    }
+}
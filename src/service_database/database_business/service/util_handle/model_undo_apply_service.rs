@@ -0,0 +1,294 @@
+use crate::service_database::database_business::db_business_connection::get_business_db;
+use crate::service_database::database_business::entity::component_channel::{
+    model_component_all_detail as all_detail, model_component_channel as channel,
+    model_component_henry as henry, model_component_henry_detail as henry_detail,
+    model_component_oil as oil,
+};
+use crate::service_database::database_business::entity::entitys::{
+    model_aiuo_entity as aiuo, model_element_entity_connection as connection,
+    model_energy_entity as energy, model_graphic_edge_attributes as edge,
+    model_graphic_entity_scriptuo_custom as script_uo,
+    model_graphic_mark_node_attributes as mark_node, model_graphic_node_attributes as node,
+    model_logic_entity as logic, model_material_entity as material, model_module_entity as module,
+    model_sensor_entity as sensor,
+};
+use crate::service_database::database_business::entity::fluid_package::{
+    model_fluid_package_binary_nrtl_entity as nrtl,
+    model_fluid_package_binary_nrtl_rk_entity as nrtl_rk,
+    model_fluid_package_binary_pr_entity as pr, model_fluid_package_binary_psrk_entity as psrk,
+    model_fluid_package_binary_rk_entity as rk, model_fluid_package_binary_srk_entity as srk,
+    model_fluid_package_binary_uniquac_entity as uniquac,
+    model_fluid_package_binary_wilsion_entity as wilson,
+    model_fluid_package_entity as fluid_package,
+    model_physical_property_calc_entity as property_calc,
+    model_reaction_detail_entity as reaction_detail,
+    model_reaction_package_entity as reaction_package,
+};
+use crate::service_database::database_business::entity::model_config::{
+    model_config_entity as config, model_entity as model,
+    model_graphic_page_entity as graphic_page, model_name_generator_entity as name_generator,
+    model_pf_model_params as pf_model_params, model_pf_var_default_params_entity as pf_var_params,
+    model_system_variable_entity as system_variable, model_unit_item_entity as unit_item,
+    model_unit_set_entity as unit_set,
+};
+use crate::service_database::database_business::entity::model_util_handle::{
+    model_dcs_tag_entity as dcs_tag, model_global_script_entity as global_script,
+    model_initialize_data_in_cold_state_entity as cold_state,
+    model_status_information_entity as status_information,
+    model_status_params_entity as status_params, model_trend_chart_config_entity as trend_chart,
+    model_variable_choose_entity as variable_choose,
+};
+use sea_orm::{
+    ActiveModelBehavior, ActiveModelTrait, ConnectionTrait, DbErr, EntityName, EntityTrait,
+    IntoActiveModel, TransactionError, TransactionTrait, TryIntoModel,
+};
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+// ======================================
+// 撤销/重做的服务端执行：根据撤销日志中的 op_type / table_name
+// 对业务库执行逆向（撤销）或正向（重做）的 INSERT / UPDATE / DELETE
+// ======================================
+
+/// 一条待执行的撤销日志
+#[derive(Clone, Debug)]
+pub struct UndoChange {
+    pub table_name: String,
+    pub op_type: String,
+    pub old_data: String,
+    pub new_data: String,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum UndoDirection {
+    Undo,
+    Redo,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum RowOp {
+    Insert,
+    Update,
+    Delete,
+}
+
+fn undo_err(msg: impl std::fmt::Display) -> DbErr {
+    DbErr::Custom(format!("撤销执行错误: {}", msg))
+}
+
+/// camelCase → snake_case（前端传入的数据可能是 NAPI 对象的驼峰字段）
+fn to_snake_case(name: &str) -> String {
+    let mut out = String::with_capacity(name.len() + 4);
+    for c in name.chars() {
+        if c.is_ascii_uppercase() {
+            if !out.is_empty() {
+                out.push('_');
+            }
+            out.push(c.to_ascii_lowercase());
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+/// 表名比较时忽略大小写风格与 `_entity` 后缀
+fn normalize_table_name(name: &str) -> String {
+    let snake = to_snake_case(name.trim());
+    snake
+        .strip_suffix("_entity")
+        .map(str::to_string)
+        .unwrap_or(snake)
+}
+
+/// 解析日志数据：单个对象或对象数组，空字符串视为无数据
+fn parse_rows(text: &str) -> Result<Vec<Value>, DbErr> {
+    if text.trim().is_empty() {
+        return Ok(vec![]);
+    }
+    let value: Value = serde_json::from_str(text).map_err(undo_err)?;
+    let rows = match value {
+        Value::Array(items) => items,
+        Value::Null => vec![],
+        other => vec![other],
+    };
+    rows.into_iter()
+        .map(|row| match row {
+            Value::Object(map) => Ok(Value::Object(
+                map.into_iter()
+                    .map(|(k, v)| (to_snake_case(&k), v))
+                    .collect(),
+            )),
+            _ => Err(undo_err("日志数据必须是 JSON 对象或对象数组")),
+        })
+        .collect()
+}
+
+/// 根据操作类型与方向，得到需要执行的操作及使用的数据
+fn resolve_op(change: &UndoChange, direction: UndoDirection) -> Result<(RowOp, &str), DbErr> {
+    let op = match change.op_type.trim().to_ascii_uppercase().as_str() {
+        "INSERT" | "ADD" | "CREATE" => RowOp::Insert,
+        "UPDATE" | "EDIT" => RowOp::Update,
+        "DELETE" | "REMOVE" => RowOp::Delete,
+        other => return Err(undo_err(format!("不支持的操作类型: {}", other))),
+    };
+    Ok(match (op, direction) {
+        (RowOp::Insert, UndoDirection::Undo) => (RowOp::Delete, &change.new_data),
+        (RowOp::Insert, UndoDirection::Redo) => (RowOp::Insert, &change.new_data),
+        (RowOp::Update, UndoDirection::Undo) => (RowOp::Update, &change.old_data),
+        (RowOp::Update, UndoDirection::Redo) => (RowOp::Update, &change.new_data),
+        (RowOp::Delete, UndoDirection::Undo) => (RowOp::Insert, &change.old_data),
+        (RowOp::Delete, UndoDirection::Redo) => (RowOp::Delete, &change.old_data),
+    })
+}
+
+async fn apply_rows<A, C>(db: &C, op: RowOp, rows: Vec<Value>) -> Result<(), DbErr>
+where
+    C: ConnectionTrait,
+    A: ActiveModelTrait
+        + ActiveModelBehavior
+        + Send
+        + TryIntoModel<<A::Entity as EntityTrait>::Model>,
+    <A::Entity as EntityTrait>::Model: IntoActiveModel<A> + Serialize + DeserializeOwned,
+{
+    for row in rows {
+        let active = A::from_json(row)?;
+        match op {
+            RowOp::Insert => {
+                A::Entity::insert(active).exec_without_returning(db).await?;
+            }
+            // UPDATE 只写入日志中出现的字段
+            RowOp::Update => {
+                active.update(db).await?;
+            }
+            // 记录已不存在时视为已删除
+            RowOp::Delete => {
+                active.delete(db).await?;
+            }
+        }
+    }
+    Ok(())
+}
+
+macro_rules! dispatch_undo_table {
+    ($db:expr, $table:expr, $op:expr, $rows:expr, [$($m:ident),* $(,)?]) => {{
+        $(
+            if $table == normalize_table_name($m::Entity.table_name()) {
+                return apply_rows::<$m::ActiveModel, _>($db, $op, $rows).await;
+            }
+        )*
+    }};
+}
+
+/// 执行单条撤销日志
+pub async fn apply_undo_change<C: ConnectionTrait>(
+    db: &C,
+    change: &UndoChange,
+    direction: UndoDirection,
+) -> Result<(), DbErr> {
+    let (op, data) = resolve_op(change, direction)?;
+    let rows = parse_rows(data)?;
+    if rows.is_empty() {
+        return Ok(());
+    }
+    let table = normalize_table_name(&change.table_name);
+    dispatch_undo_table!(
+        db,
+        table,
+        op,
+        rows,
+        [
+            model,
+            config,
+            unit_set,
+            unit_item,
+            system_variable,
+            name_generator,
+            pf_model_params,
+            pf_var_params,
+            graphic_page,
+            channel,
+            all_detail,
+            henry,
+            henry_detail,
+            oil,
+            fluid_package,
+            property_calc,
+            nrtl,
+            nrtl_rk,
+            pr,
+            psrk,
+            rk,
+            srk,
+            uniquac,
+            wilson,
+            reaction_package,
+            reaction_detail,
+            status_information,
+            status_params,
+            global_script,
+            trend_chart,
+            variable_choose,
+            cold_state,
+            dcs_tag,
+            material,
+            energy,
+            logic,
+            sensor,
+            aiuo,
+            module,
+            script_uo,
+            connection,
+            node,
+            mark_node,
+            edge,
+        ]
+    );
+    Err(undo_err(format!("不支持撤销的表: {}", change.table_name)))
+}
+
+/// 在一个事务内按顺序执行一组撤销日志（一个撤销步骤）
+pub async fn apply_undo_changes(
+    changes: Vec<UndoChange>,
+    direction: UndoDirection,
+) -> Result<(), DbErr> {
+    let db = get_business_db().await?;
+    db.transaction::<_, (), DbErr>(|txn| {
+        Box::pin(async move {
+            for change in &changes {
+                apply_undo_change(txn, change, direction).await?;
+            }
+            Ok(())
+        })
+    })
+    .await
+    .map_err(|e| match e {
+        TransactionError::Connection(e) => e,
+        TransactionError::Transaction(e) => e,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_and_parse() {
+        let change = UndoChange {
+            table_name: "modelStatusParamsEntity".to_string(),
+            op_type: "insert".to_string(),
+            old_data: String::new(),
+            new_data: r#"[{"id":1,"modelId":"m"}]"#.to_string(),
+        };
+        let (op, data) = resolve_op(&change, UndoDirection::Undo).unwrap();
+        assert_eq!(op, RowOp::Delete);
+        let rows = parse_rows(data).unwrap();
+        assert_eq!(rows[0]["model_id"], "m");
+        assert_eq!(
+            normalize_table_name(&change.table_name),
+            normalize_table_name("model_status_params_entity")
+        );
+        assert_eq!(normalize_table_name("model_unit_set"), "model_unit_set");
+    }
+}
@@ -0,0 +1,200 @@
+use crate::service_database::database_business::db_business_connection::get_business_db;
+use crate::service_database::database_business::entity::model_util_handle::model_undo_history_config_entity::{
+    ActiveModel as ConfigActiveModel, Column as ConfigColumn, Entity as ConfigEntity,
+    Model as ConfigModel,
+};
+use crate::service_database::database_business::entity::model_util_handle::model_undo_history_entity::{
+    ActiveModel as HistoryActiveModel, Column as HistoryColumn, Entity as HistoryEntity,
+    Model as HistoryModel,
+};
+use chrono::NaiveDateTime;
+use sea_orm::sea_query::OnConflict;
+use sea_orm::{
+    ActiveValue::Set, DbErr, QueryFilter, QueryOrder, TransactionError, TransactionTrait,
+    entity::prelude::*,
+};
+
+// ======================================
+// 撤销历史持久化：缓存库中的撤销记录按模型镜像到业务库，软件重启后可恢复
+// ======================================
+
+/// 撤销历史行（缓存库与业务库之间传递）
+#[derive(Clone, Debug)]
+pub struct UndoHistoryRow {
+    pub id: i32,
+    pub model_id: String,
+    pub step_id: i32,
+    pub table_name: String,
+    pub op_type: String,
+    pub old_data: String,
+    pub new_data: String,
+    pub status: i32,
+    pub operator_at: NaiveDateTime,
+}
+
+impl From<HistoryModel> for UndoHistoryRow {
+    fn from(m: HistoryModel) -> Self {
+        UndoHistoryRow {
+            id: m.id,
+            model_id: m.model_id,
+            step_id: m.step_id,
+            table_name: m.table_name,
+            op_type: m.op_type,
+            old_data: m.old_data,
+            new_data: m.new_data,
+            status: m.status,
+            operator_at: m.operator_at,
+        }
+    }
+}
+
+impl From<UndoHistoryRow> for HistoryActiveModel {
+    fn from(row: UndoHistoryRow) -> Self {
+        HistoryActiveModel {
+            id: Set(row.id),
+            model_id: Set(row.model_id),
+            step_id: Set(row.step_id),
+            table_name: Set(row.table_name),
+            op_type: Set(row.op_type),
+            old_data: Set(row.old_data),
+            new_data: Set(row.new_data),
+            status: Set(row.status),
+            operator_at: Set(row.operator_at),
+        }
+    }
+}
+
+const UPSERT_CHUNK_SIZE: usize = 200;
+
+async fn upsert_rows<C: ConnectionTrait>(db: &C, rows: Vec<UndoHistoryRow>) -> Result<(), DbErr> {
+    let mut rows = rows.into_iter().peekable();
+    while rows.peek().is_some() {
+        let chunk: Vec<HistoryActiveModel> = rows
+            .by_ref()
+            .take(UPSERT_CHUNK_SIZE)
+            .map(HistoryActiveModel::from)
+            .collect();
+        HistoryEntity::insert_many(chunk)
+            .on_conflict(
+                OnConflict::columns([HistoryColumn::Id, HistoryColumn::ModelId])
+                    .update_columns([
+                        HistoryColumn::StepId,
+                        HistoryColumn::TableName,
+                        HistoryColumn::OpType,
+                        HistoryColumn::OldData,
+                        HistoryColumn::NewData,
+                        HistoryColumn::Status,
+                        HistoryColumn::OperatorAt,
+                    ])
+                    .to_owned(),
+            )
+            .exec_without_returning(db)
+            .await?;
+    }
+    Ok(())
+}
+
+/// 新增或覆盖模型的撤销历史（rows 必须都属于 model_id）
+pub async fn upsert_undo_history(model_id: &str, rows: Vec<UndoHistoryRow>) -> Result<(), DbErr> {
+    if rows.is_empty() {
+        return Ok(());
+    }
+    if let Some(row) = rows.iter().find(|r| r.model_id != model_id) {
+        return Err(DbErr::Custom(format!(
+            "撤销记录 {} 属于模型 {}，不能写入模型 {} 的历史",
+            row.id, row.model_id, model_id
+        )));
+    }
+    let db = get_business_db().await?;
+    upsert_rows(db, rows).await
+}
+
+/// 按 id 删除模型的撤销历史
+pub async fn delete_undo_history_by_ids(model_id: String, ids: Vec<i32>) -> Result<u64, DbErr> {
+    if ids.is_empty() {
+        return Ok(0);
+    }
+    let db = get_business_db().await?;
+    let mut deleted = 0;
+    for chunk in ids.chunks(500) {
+        deleted += HistoryEntity::delete_many()
+            .filter(HistoryColumn::ModelId.eq(model_id.clone()))
+            .filter(HistoryColumn::Id.is_in(chunk.to_vec()))
+            .exec(db)
+            .await?
+            .rows_affected;
+    }
+    Ok(deleted)
+}
+
+/// 删除模型的全部撤销历史
+pub async fn delete_undo_history_by_model_id(model_id: String) -> Result<u64, DbErr> {
+    let db = get_business_db().await?;
+    let result = HistoryEntity::delete_many()
+        .filter(HistoryColumn::ModelId.eq(model_id))
+        .exec(db)
+        .await?;
+    Ok(result.rows_affected)
+}
+
+/// 查询模型的撤销历史（按 id 升序）
+pub async fn list_undo_history(model_id: String) -> Result<Vec<UndoHistoryRow>, DbErr> {
+    let db = get_business_db().await?;
+    let rows = HistoryEntity::find()
+        .filter(HistoryColumn::ModelId.eq(model_id))
+        .order_by_asc(HistoryColumn::Id)
+        .all(db)
+        .await?;
+    Ok(rows.into_iter().map(UndoHistoryRow::from).collect())
+}
+
+/// 用给定记录整体替换模型的撤销历史
+pub async fn replace_undo_history(
+    model_id: String,
+    rows: Vec<UndoHistoryRow>,
+) -> Result<(), DbErr> {
+    let db = get_business_db().await?;
+    db.transaction::<_, (), DbErr>(|txn| {
+        Box::pin(async move {
+            HistoryEntity::delete_many()
+                .filter(HistoryColumn::ModelId.eq(model_id))
+                .exec(txn)
+                .await?;
+            upsert_rows(txn, rows).await
+        })
+    })
+    .await
+    .map_err(|e| match e {
+        TransactionError::Connection(e) => e,
+        TransactionError::Transaction(e) => e,
+    })
+}
+
+/// 读取模型的撤销历史配置（未保存过时为 None）
+pub async fn load_undo_history_config(model_id: &str) -> Result<Option<ConfigModel>, DbErr> {
+    let db = get_business_db().await?;
+    ConfigEntity::find_by_id(model_id.to_string()).one(db).await
+}
+
+/// 保存模型的撤销历史配置
+pub async fn save_undo_history_config(
+    model_id: &str,
+    max_steps: u32,
+    persist: bool,
+) -> Result<(), DbErr> {
+    let db = get_business_db().await?;
+    let item = ConfigActiveModel {
+        model_id: Set(model_id.to_string()),
+        max_steps: Set(max_steps as i32),
+        persist: Set(persist),
+    };
+    ConfigEntity::insert(item)
+        .on_conflict(
+            OnConflict::column(ConfigColumn::ModelId)
+                .update_columns([ConfigColumn::MaxSteps, ConfigColumn::Persist])
+                .to_owned(),
+        )
+        .exec_without_returning(db)
+        .await?;
+    Ok(())
+}
@@ -131,6 +131,20 @@ pub async fn get_by_id_and_model(
     Ok(res.map(ModelVariableChooseDTO::from))
 }
 
+pub async fn get_by_ids_and_model(
+    ids: Vec<String>,
+    model_id: String,
+) -> Result<Vec<ModelVariableChooseDTO>, DbErr> {
+    let db = get_business_db().await?;
+    let res = ChooseEntity::find()
+        .filter(ChooseColumn::Id.is_in(ids))
+        .filter(ChooseColumn::ModelId.eq(model_id))
+        .order_by_asc(ChooseColumn::Sort)
+        .all(db)
+        .await?;
+    Ok(res.into_iter().map(ModelVariableChooseDTO::from).collect())
+}
+
 pub async fn get_by_model_and_type(
     model_id: String,
     r#type: String,
@@ -1,4 +1,7 @@
 use crate::service_database::database_cache::migration::Migrator;
+use crate::service_database::database_cache::service::cache_governance_service::{
+    CacheTableStats, cache_table_stats,
+};
 use napi_derive::napi;
 use sea_orm::{ConnectionTrait, Statement};
 use sea_orm_migration::MigratorTrait;
@@ -102,46 +105,57 @@ mod tests {
 
 #[napi(object, namespace = "initDB")]
 pub struct DbStats {
+    /// 连接池中正在使用的连接数
     pub active_connections: u32,
     pub idle_connections: u32,
+    pub max_connections: u32,
+    /// 内存库占用 (page_count * page_size)，包含空闲页
     pub memory_used_bytes: i64,
+    /// 空闲页字节数，可通过 shrink 回收
+    pub free_bytes: i64,
+    pub tables: Vec<CacheTableStats>,
 }
 
-pub async fn get_cache_db_stats() -> napi::Result<DbStats> {
-    let db = get_cache_db()
-        .await
-        .map_err(|e| napi::Error::from_reason(e.to_string()))?;
-
-    // 1. 获取 SQLite 内存占用 (page_count * page_size)
-    let page_count: i64 = db
+/// 读取返回单个整数的 PRAGMA
+pub(crate) async fn pragma_i64<C: ConnectionTrait>(
+    db: &C,
+    name: &str,
+) -> Result<i64, migration_orm::DbErr> {
+    let row = db
         .query_one_raw(Statement::from_string(
             db.get_database_backend(),
-            "PRAGMA page_count;",
+            format!("PRAGMA {};", name),
         ))
-        .await
-        .map_err(|e| napi::Error::from_reason(e.to_string()))?
-        .unwrap()
-        .try_get_by_index(0)
-        .unwrap_or(0);
+        .await?;
+    match row {
+        Some(row) => row.try_get_by_index(0),
+        None => Ok(0),
+    }
+}
 
-    let page_size: i64 = db
-        .query_one_raw(Statement::from_string(
-            db.get_database_backend(),
-            "PRAGMA page_size;",
-        ))
-        .await
-        .map_err(|e| napi::Error::from_reason(e.to_string()))?
-        .unwrap()
-        .try_get_by_index(0)
-        .unwrap_or(0);
+pub async fn get_cache_db_stats() -> napi::Result<DbStats> {
+    let to_napi = |e: migration_orm::DbErr| napi::Error::from_reason(e.to_string());
+    let db = get_cache_db().await.map_err(to_napi)?;
+
+    // 1. SQLite 内存占用与空闲页
+    let page_size = pragma_i64(db, "page_size").await.map_err(to_napi)?;
+    let page_count = pragma_i64(db, "page_count").await.map_err(to_napi)?;
+    let freelist_count = pragma_i64(db, "freelist_count").await.map_err(to_napi)?;
+
+    // 2. 连接池状态 (sqlx)
+    let pool = db.get_sqlite_connection_pool();
+    let size = pool.size();
+    let idle = pool.num_idle() as u32;
 
-    // 2. 获取连接池状态 (sqlx 内部状态)
-    // 注意：Sea-ORM 屏蔽了部分底层细节，这里可以通过简单的逻辑或尝试获取连接来估算
-    // 工业级通常会配合 metrics 库，这里我们先重点关注内存
+    // 3. 各缓存表的行数与估算字节数
+    let tables = cache_table_stats(db).await.map_err(to_napi)?;
 
     Ok(DbStats {
-        active_connections: 0, // Sea-ORM 原生较难直接拿，通常看 pool
-        idle_connections: 0,
+        active_connections: size.saturating_sub(idle),
+        idle_connections: idle,
+        max_connections: pool.options().get_max_connections(),
         memory_used_bytes: page_count * page_size,
+        free_bytes: freelist_count * page_size,
+        tables,
     })
 }
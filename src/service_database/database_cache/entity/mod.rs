@@ -1,4 +1,7 @@
 // pub mod model_auto_shutter_entity_cache;
 pub mod model_pointinfor_entity_cache;
+pub mod model_trend_block_entity_cache;
+pub mod model_trend_record_entity_cache;
+pub mod model_trend_variable_entity_cache;
 pub mod model_undo_entity_cache;
 pub mod model_variable_curve_entity_cache;
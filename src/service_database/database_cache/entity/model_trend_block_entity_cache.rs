@@ -0,0 +1,28 @@
+use sea_orm::entity::prelude::*;
+
+/// 趋势数据块：一个变量一段连续采样点，按列编码后 zstd 压缩
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "model_trend_block_entity_cache")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    pub model_id: String,
+    pub config_id: String,
+    pub variable_id: String,
+    /// 块内最小仿真时间
+    pub start_time: f64,
+    /// 块内最大仿真时间
+    pub end_time: f64,
+    pub point_count: i32,
+    pub min_value: f64,
+    pub max_value: f64,
+    /// 写入时间（毫秒）
+    pub create_at: i64,
+    #[sea_orm(column_type = "Blob")]
+    pub data: Vec<u8>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
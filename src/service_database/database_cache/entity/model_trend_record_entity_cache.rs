@@ -0,0 +1,19 @@
+use sea_orm::entity::prelude::*;
+
+/// 趋势写入记录：每次写入曲线数据一条，保留旧版曲线行的 ID、仿真时间与写入时间
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "model_trend_record_entity_cache")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    pub model_id: String,
+    pub config_id: String,
+    pub sim_time: f64,
+    /// 写入时间（毫秒）
+    pub create_at: i64,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
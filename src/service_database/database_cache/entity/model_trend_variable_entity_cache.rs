@@ -0,0 +1,18 @@
+use sea_orm::entity::prelude::*;
+
+/// 趋势配置下出现过的变量及其显示名称
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "model_trend_variable_entity_cache")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    pub model_id: String,
+    pub config_id: String,
+    pub variable_id: String,
+    pub name: String,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -7,6 +7,7 @@ pub struct Model {
     #[sea_orm(primary_key)]
     pub id: i32,
     pub model_id: String,
+    pub step_id: i32, // 所属撤销步骤，同一步骤内的记录一起撤销/重做
     pub table_name: String,
     pub op_type: String,
     pub old_data: String,
@@ -0,0 +1,64 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // 撤销步骤编号：同一步内的多条日志共享一个 step_id（取该步第一条日志的 id）
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(ModelUndoEntityCache::Table)
+                    .add_column(
+                        ColumnDef::new(ModelUndoEntityCache::StepId)
+                            .integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-undo-model_id-step_id")
+                    .table(ModelUndoEntityCache::Table)
+                    .col(ModelUndoEntityCache::ModelId)
+                    .col(ModelUndoEntityCache::StepId)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx-undo-model_id-step_id")
+                    .table(ModelUndoEntityCache::Table)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(ModelUndoEntityCache::Table)
+                    .drop_column(ModelUndoEntityCache::StepId)
+                    .to_owned(),
+            )
+            .await?;
+        Ok(())
+    }
+}
+
+#[derive(Iden)]
+enum ModelUndoEntityCache {
+    Table,
+    ModelId,
+    StepId,
+}
@@ -0,0 +1,201 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // 趋势数据块表：按 (model_id, config_id, variable_id, start_time) 做区间查询
+        manager
+            .create_table(
+                Table::create()
+                    .table(ModelTrendBlockEntityCache::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(ModelTrendBlockEntityCache::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(ModelTrendBlockEntityCache::ModelId)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ModelTrendBlockEntityCache::ConfigId)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ModelTrendBlockEntityCache::VariableId)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ModelTrendBlockEntityCache::StartTime)
+                            .double()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ModelTrendBlockEntityCache::EndTime)
+                            .double()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ModelTrendBlockEntityCache::PointCount)
+                            .integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ModelTrendBlockEntityCache::MinValue)
+                            .double()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ModelTrendBlockEntityCache::MaxValue)
+                            .double()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ModelTrendBlockEntityCache::CreateAt)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ModelTrendBlockEntityCache::Data)
+                            .blob()
+                            .not_null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-trend-block-series-start_time")
+                    .table(ModelTrendBlockEntityCache::Table)
+                    .col(ModelTrendBlockEntityCache::ModelId)
+                    .col(ModelTrendBlockEntityCache::ConfigId)
+                    .col(ModelTrendBlockEntityCache::VariableId)
+                    .col(ModelTrendBlockEntityCache::StartTime)
+                    .if_not_exists()
+                    .to_owned(),
+            )
+            .await?;
+        // 数据块按 (序列, start_time, create_at) 唯一，搬迁到磁盘归档中断后重试不会写入重复的数据块
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-trend-block-archive-key")
+                    .table(ModelTrendBlockEntityCache::Table)
+                    .col(ModelTrendBlockEntityCache::ModelId)
+                    .col(ModelTrendBlockEntityCache::ConfigId)
+                    .col(ModelTrendBlockEntityCache::VariableId)
+                    .col(ModelTrendBlockEntityCache::StartTime)
+                    .col(ModelTrendBlockEntityCache::CreateAt)
+                    .unique()
+                    .if_not_exists()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(ModelTrendVariableEntityCache::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(ModelTrendVariableEntityCache::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(ModelTrendVariableEntityCache::ModelId)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ModelTrendVariableEntityCache::ConfigId)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ModelTrendVariableEntityCache::VariableId)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ModelTrendVariableEntityCache::Name)
+                            .string()
+                            .not_null()
+                            .default(""),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-trend-variable-series")
+                    .table(ModelTrendVariableEntityCache::Table)
+                    .col(ModelTrendVariableEntityCache::ModelId)
+                    .col(ModelTrendVariableEntityCache::ConfigId)
+                    .col(ModelTrendVariableEntityCache::VariableId)
+                    .unique()
+                    .if_not_exists()
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(
+                Table::drop()
+                    .table(ModelTrendVariableEntityCache::Table)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .drop_table(
+                Table::drop()
+                    .table(ModelTrendBlockEntityCache::Table)
+                    .to_owned(),
+            )
+            .await?;
+        Ok(())
+    }
+}
+
+#[derive(Iden)]
+enum ModelTrendBlockEntityCache {
+    Table,
+    Id,
+    ModelId,
+    ConfigId,
+    VariableId,
+    StartTime,
+    EndTime,
+    PointCount,
+    MinValue,
+    MaxValue,
+    CreateAt,
+    Data,
+}
+
+#[derive(Iden)]
+enum ModelTrendVariableEntityCache {
+    Table,
+    Id,
+    ModelId,
+    ConfigId,
+    VariableId,
+    Name,
+}
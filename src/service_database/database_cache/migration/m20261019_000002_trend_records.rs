@@ -0,0 +1,79 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // 趋势写入记录表：每次写入曲线数据一条，查询旧版曲线行时按记录还原 ID 与写入时间
+        manager
+            .create_table(
+                Table::create()
+                    .table(ModelTrendRecordEntityCache::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(ModelTrendRecordEntityCache::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(ModelTrendRecordEntityCache::ModelId)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ModelTrendRecordEntityCache::ConfigId)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ModelTrendRecordEntityCache::SimTime)
+                            .double()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ModelTrendRecordEntityCache::CreateAt)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-trend-record-config")
+                    .table(ModelTrendRecordEntityCache::Table)
+                    .col(ModelTrendRecordEntityCache::ModelId)
+                    .col(ModelTrendRecordEntityCache::ConfigId)
+                    .if_not_exists()
+                    .to_owned(),
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(
+                Table::drop()
+                    .table(ModelTrendRecordEntityCache::Table)
+                    .to_owned(),
+            )
+            .await?;
+        Ok(())
+    }
+}
+
+#[derive(Iden)]
+enum ModelTrendRecordEntityCache {
+    Table,
+    Id,
+    ModelId,
+    ConfigId,
+    SimTime,
+    CreateAt,
+}
@@ -1,12 +1,30 @@
 pub use sea_orm_migration::prelude::*;
 
 mod m20251201_000001_create_tables;
+mod m20261018_000001_undo_step;
+mod m20261018_000002_trend_blocks;
+mod m20261019_000002_trend_records;
 
 pub struct Migrator;
 
 #[async_trait::async_trait]
 impl MigratorTrait for Migrator {
     fn migrations() -> Vec<Box<dyn MigrationTrait>> {
-        vec![Box::new(m20251201_000001_create_tables::Migration)]
+        vec![
+            Box::new(m20251201_000001_create_tables::Migration),
+            Box::new(m20261018_000001_undo_step::Migration),
+            Box::new(m20261018_000002_trend_blocks::Migration),
+            Box::new(m20261019_000002_trend_records::Migration),
+        ]
+    }
+}
+
+/// 磁盘趋势归档库只包含趋势数据块与变量两张表，表结构与缓存库一致
+pub struct TrendArchiveMigrator;
+
+#[async_trait::async_trait]
+impl MigratorTrait for TrendArchiveMigrator {
+    fn migrations() -> Vec<Box<dyn MigrationTrait>> {
+        vec![Box::new(m20261018_000002_trend_blocks::Migration)]
     }
 }
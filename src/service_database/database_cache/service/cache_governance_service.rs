@@ -0,0 +1,343 @@
+use crate::service_database::database_cache::db_cache_connection::{get_cache_db, pragma_i64};
+use crate::service_database::database_cache::entity::model_variable_curve_entity_cache::{
+    Column as CurveColumn, Entity as CurveEntity,
+};
+use crate::service_database::database_cache::service::trend_service::evict_trend_blocks;
+use crate::service_database::database_cache::service::undo_redo_service::evict_undo_steps;
+use crate::tool_handle::cache_limit_tool::{CacheLimit, EvictionUnit, eviction_count};
+use napi_derive::napi;
+use once_cell::sync::Lazy;
+use sea_orm::{ColumnTrait, ConnectionTrait, DbErr, EntityTrait, QueryFilter, Statement};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+use tokio::task::JoinHandle;
+use tokio::time::MissedTickBehavior;
+
+// ======================================
+// 缓存库容量治理
+// 缓存库是进程内共享的内存库，长时间运行的仿真会让撤销日志与曲线数据持续增长。
+// 按表统计行数与估算字节数；撤销日志与曲线数据可设置上限，超出时从最旧的数据开始淘汰
+// （撤销按整步淘汰，只释放缓存库，业务库中持久化的撤销历史不受影响；
+// 曲线先淘汰旧版按行存储的记录，再淘汰最旧的趋势数据块，打开了归档的模型搬到归档而不是删除）。
+// 删除后的空闲页不会自动归还，需要调用 shrink 整理内存库
+// ======================================
+
+/// 每行的估算额外开销：记录头、单元指针与主键索引
+const ROW_OVERHEAD_BYTES: i64 = 24;
+
+struct CacheTable {
+    name: &'static str,
+    /// 变长的文本 / 二进制列
+    text_columns: &'static [&'static str],
+    /// 整数与浮点列的字节数
+    fixed_bytes: i64,
+}
+
+impl CacheTable {
+    /// 单行估算字节数的 SQL 表达式
+    fn row_bytes_sql(&self) -> String {
+        let mut expr = (self.fixed_bytes + ROW_OVERHEAD_BYTES).to_string();
+        for column in self.text_columns {
+            expr.push_str(&format!(" + IFNULL(LENGTH(CAST({} AS BLOB)), 0)", column));
+        }
+        expr
+    }
+}
+
+const UNDO_TABLE: CacheTable = CacheTable {
+    name: "model_undo_entity_cache",
+    text_columns: &[
+        "model_id",
+        "table_name",
+        "op_type",
+        "old_data",
+        "new_data",
+        "operator_at",
+    ],
+    fixed_bytes: 3 * 8,
+};
+
+const CURVE_TABLE: CacheTable = CacheTable {
+    name: "model_variable_curve_entity_cache",
+    text_columns: &["sim_time", "datasets", "create_at", "model_id", "config_id"],
+    fixed_bytes: 8,
+};
+
+const TREND_BLOCK_TABLE: CacheTable = CacheTable {
+    name: "model_trend_block_entity_cache",
+    text_columns: &["model_id", "config_id", "variable_id", "data"],
+    fixed_bytes: 7 * 8,
+};
+
+const CACHE_TABLES: [CacheTable; 6] = [
+    UNDO_TABLE,
+    CURVE_TABLE,
+    TREND_BLOCK_TABLE,
+    CacheTable {
+        name: "model_trend_variable_entity_cache",
+        text_columns: &["model_id", "config_id", "variable_id", "name"],
+        fixed_bytes: 8,
+    },
+    CacheTable {
+        name: "model_trend_record_entity_cache",
+        text_columns: &["model_id", "config_id"],
+        fixed_bytes: 3 * 8,
+    },
+    CacheTable {
+        name: "model_position_information_entity",
+        text_columns: &["name", "name_display", "type_name"],
+        fixed_bytes: 2 * 8,
+    },
+];
+
+#[napi(string_enum, namespace = "initDB")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum CacheLimitKind {
+    /// 撤销日志
+    UndoLog,
+    /// 曲线数据（旧版按行存储的记录与趋势数据块）
+    Curve,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[napi(object, namespace = "initDB")]
+pub struct CacheTableStats {
+    pub table_name: String,
+    pub row_count: i64,
+    /// 按列长度估算的数据字节数，不含空闲页
+    pub estimated_bytes: i64,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[napi(object, namespace = "initDB")]
+pub struct CacheTableLimit {
+    pub kind: CacheLimitKind,
+    /// 为空时不限制行数
+    pub max_rows: Option<i64>,
+    /// 为空时不限制字节数
+    pub max_bytes: Option<i64>,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[napi(object, namespace = "initDB")]
+pub struct CacheEvictionResult {
+    pub undo_rows: u32,
+    pub curve_rows: u32,
+    /// 移出缓存的数据块数（打开了归档的模型搬到归档）
+    pub trend_blocks: u32,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[napi(object, namespace = "initDB")]
+pub struct CacheShrinkResult {
+    pub before_bytes: i64,
+    pub after_bytes: i64,
+}
+
+static CACHE_LIMITS: Lazy<Mutex<HashMap<CacheLimitKind, CacheLimit>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+static GOVERNANCE_TASK: Lazy<Mutex<Option<JoinHandle<()>>>> = Lazy::new(|| Mutex::new(None));
+
+/// 各缓存表的行数与估算字节数
+pub(crate) async fn cache_table_stats<C: ConnectionTrait>(
+    db: &C,
+) -> Result<Vec<CacheTableStats>, DbErr> {
+    let mut stats = Vec::with_capacity(CACHE_TABLES.len());
+    for table in &CACHE_TABLES {
+        let sql = format!(
+            "SELECT COUNT(*), IFNULL(SUM({}), 0) FROM {};",
+            table.row_bytes_sql(),
+            table.name
+        );
+        let row = db
+            .query_one_raw(Statement::from_string(db.get_database_backend(), sql))
+            .await?;
+        let (row_count, estimated_bytes) = match row {
+            Some(row) => (row.try_get_by_index(0)?, row.try_get_by_index(1)?),
+            None => (0, 0),
+        };
+        stats.push(CacheTableStats {
+            table_name: table.name.to_string(),
+            row_count,
+            estimated_bytes,
+        });
+    }
+    Ok(stats)
+}
+
+/// 查询淘汰单元 (键, 行数, 字节数)，按从旧到新排列
+async fn eviction_units<C: ConnectionTrait>(
+    db: &C,
+    sql: String,
+) -> Result<Vec<(i32, EvictionUnit)>, DbErr> {
+    let rows = db
+        .query_all_raw(Statement::from_string(db.get_database_backend(), sql))
+        .await?;
+    rows.into_iter()
+        .map(|row| {
+            Ok((
+                row.try_get_by_index(0)?,
+                EvictionUnit {
+                    rows: row.try_get_by_index(1)?,
+                    bytes: row.try_get_by_index(2)?,
+                },
+            ))
+        })
+        .collect()
+}
+
+/// 撤销日志按步骤淘汰，返回删除的记录数
+async fn enforce_undo_limit<C: ConnectionTrait>(db: &C, limit: CacheLimit) -> Result<u64, DbErr> {
+    let sql = format!(
+        "SELECT step_id, COUNT(*), SUM({}) FROM {} GROUP BY step_id ORDER BY step_id;",
+        UNDO_TABLE.row_bytes_sql(),
+        UNDO_TABLE.name
+    );
+    let units = eviction_units(db, sql).await?;
+    let count = eviction_count(&units.iter().map(|u| u.1).collect::<Vec<_>>(), limit);
+    if count == 0 {
+        return Ok(0);
+    }
+    evict_undo_steps(units[count - 1].0).await
+}
+
+/// 曲线数据先淘汰旧版记录再淘汰趋势数据块，返回 (删除的记录数, 移出缓存的数据块数)
+async fn enforce_curve_limit<C: ConnectionTrait>(
+    db: &C,
+    limit: CacheLimit,
+) -> Result<(u64, u64), DbErr> {
+    let legacy = eviction_units(
+        db,
+        format!(
+            "SELECT id, 1, {} FROM {} ORDER BY id;",
+            CURVE_TABLE.row_bytes_sql(),
+            CURVE_TABLE.name
+        ),
+    )
+    .await?;
+    let blocks = eviction_units(
+        db,
+        format!(
+            "SELECT id, 1, {} FROM {} ORDER BY id;",
+            TREND_BLOCK_TABLE.row_bytes_sql(),
+            TREND_BLOCK_TABLE.name
+        ),
+    )
+    .await?;
+    let units: Vec<EvictionUnit> = legacy.iter().chain(&blocks).map(|u| u.1).collect();
+    let count = eviction_count(&units, limit);
+
+    let legacy_count = count.min(legacy.len());
+    let mut curve_rows = 0;
+    if legacy_count > 0 {
+        curve_rows = CurveEntity::delete_many()
+            .filter(CurveColumn::Id.lte(legacy[legacy_count - 1].0))
+            .exec(db)
+            .await?
+            .rows_affected;
+    }
+    let block_count = count - legacy_count;
+    let mut trend_blocks = 0;
+    if block_count > 0 {
+        trend_blocks = evict_trend_blocks(blocks[block_count - 1].0).await?;
+    }
+    Ok((curve_rows, trend_blocks))
+}
+
+/// 设置一类缓存数据的上限并立即执行一次淘汰；行数与字节数都为空时取消上限
+pub async fn set_cache_limit(limit: CacheTableLimit) -> Result<CacheEvictionResult, DbErr> {
+    {
+        let mut limits = CACHE_LIMITS.lock().unwrap();
+        if limit.max_rows.is_none() && limit.max_bytes.is_none() {
+            limits.remove(&limit.kind);
+        } else {
+            limits.insert(
+                limit.kind,
+                CacheLimit {
+                    max_rows: limit.max_rows,
+                    max_bytes: limit.max_bytes,
+                },
+            );
+        }
+    }
+    enforce_cache_limits().await
+}
+
+/// 当前设置的全部上限
+pub fn get_cache_limits() -> Vec<CacheTableLimit> {
+    let mut limits: Vec<CacheTableLimit> = CACHE_LIMITS
+        .lock()
+        .unwrap()
+        .iter()
+        .map(|(kind, limit)| CacheTableLimit {
+            kind: *kind,
+            max_rows: limit.max_rows,
+            max_bytes: limit.max_bytes,
+        })
+        .collect();
+    limits.sort_by_key(|l| l.kind as u8);
+    limits
+}
+
+/// 按当前上限淘汰超出的缓存数据
+pub async fn enforce_cache_limits() -> Result<CacheEvictionResult, DbErr> {
+    let db = get_cache_db().await?;
+    let limits = CACHE_LIMITS.lock().unwrap().clone();
+    let mut result = CacheEvictionResult::default();
+    if let Some(limit) = limits.get(&CacheLimitKind::UndoLog) {
+        result.undo_rows = enforce_undo_limit(db, *limit).await? as u32;
+    }
+    if let Some(limit) = limits.get(&CacheLimitKind::Curve) {
+        let (curve_rows, trend_blocks) = enforce_curve_limit(db, *limit).await?;
+        result.curve_rows = curve_rows as u32;
+        result.trend_blocks = trend_blocks as u32;
+    }
+    Ok(result)
+}
+
+/// 启动后台容量治理任务，每 interval_seconds 秒按上限淘汰一次；
+/// 已有任务时替换为新的周期。返回是否替换了已有任务
+pub async fn start_cache_governance_task(interval_seconds: u32) -> Result<bool, DbErr> {
+    let period = Duration::from_secs(interval_seconds.max(1) as u64);
+    let handle = tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(period);
+        ticker.set_missed_tick_behavior(MissedTickBehavior::Skip);
+        loop {
+            ticker.tick().await;
+            if let Err(e) = enforce_cache_limits().await {
+                eprintln!("[CacheDB] 执行容量上限失败: {}", e);
+            }
+        }
+    });
+    let previous = GOVERNANCE_TASK.lock().unwrap().replace(handle);
+    if let Some(previous) = &previous {
+        previous.abort();
+    }
+    Ok(previous.is_some())
+}
+
+/// 停止后台容量治理任务，返回是否有任务被停止
+pub async fn stop_cache_governance_task() -> Result<bool, DbErr> {
+    let handle = GOVERNANCE_TASK.lock().unwrap().take();
+    if let Some(handle) = &handle {
+        handle.abort();
+    }
+    Ok(handle.is_some())
+}
+
+/// 整理内存库：VACUUM 回收空闲页，再让 SQLite 释放页缓存
+pub async fn shrink_cache_db() -> Result<CacheShrinkResult, DbErr> {
+    let db = get_cache_db().await?;
+    let page_size = pragma_i64(db, "page_size").await?;
+    let before_bytes = pragma_i64(db, "page_count").await? * page_size;
+    db.execute_unprepared("VACUUM;").await?;
+    db.execute_unprepared("PRAGMA shrink_memory;").await?;
+    let after_bytes = pragma_i64(db, "page_count").await? * page_size;
+    Ok(CacheShrinkResult {
+        before_bytes,
+        after_bytes,
+    })
+}
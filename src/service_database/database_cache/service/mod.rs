@@ -1,4 +1,8 @@
 // pub mod auto_shutter_cache_service;
+pub mod cache_governance_service;
 pub mod dcs_pointinfor_service;
+pub mod trend_archive_service;
+pub mod trend_service;
+pub mod trend_subscription_service;
 pub mod undo_redo_service;
 pub mod variable_curve_service;
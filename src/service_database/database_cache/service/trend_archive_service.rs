@@ -0,0 +1,383 @@
+use crate::service_database::database_cache::db_cache_connection::get_cache_db;
+use crate::service_database::database_cache::entity::model_trend_block_entity_cache::{
+    ActiveModel as BlockActiveModel, Column as BlockColumn, Entity as BlockEntity,
+};
+use crate::service_database::database_cache::entity::model_trend_variable_entity_cache::{
+    ActiveModel as VariableActiveModel, Column as VariableColumn, Entity as VariableEntity,
+};
+use crate::service_database::database_cache::migration::TrendArchiveMigrator;
+use crate::service_database::database_cache::service::trend_service::archive_trend_buffer;
+use napi_derive::napi;
+use once_cell::sync::Lazy;
+use sea_orm::sea_query::{Expr, OnConflict};
+use sea_orm::{
+    ActiveValue::NotSet, ColumnTrait, ConnectOptions, ConnectionTrait, Database,
+    DatabaseConnection, DbErr, EntityTrait, IntoActiveModel, PaginatorTrait, QueryFilter,
+    QueryOrder, QuerySelect, TransactionTrait,
+};
+use sea_orm_migration::MigratorTrait;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+
+// ======================================
+// 趋势磁盘归档
+// 缓存库是进程内的内存库，软件关闭后趋势数据随之丢失。
+// 为模型打开归档文件后，缓存中该模型的数据块超过阈值时，最旧的数据块搬到归档文件；
+// 关闭归档（或关闭全部数据库）时，缓冲与缓存中的剩余数据全部落盘。
+// 查询同时读取缓存与归档，调用方无需关心数据块所在位置
+// ======================================
+
+/// 默认溢出阈值：缓存中单个模型的趋势数据块超过 64 MiB 时开始搬迁
+pub const DEFAULT_SPILL_THRESHOLD_BYTES: i64 = 64 * 1024 * 1024;
+
+/// 每次搬迁的数据块数
+const MOVE_BATCH: usize = 256;
+
+#[derive(Clone)]
+struct TrendArchive {
+    db: DatabaseConnection,
+    file_path: String,
+    spill_threshold_bytes: i64,
+}
+
+/// 已打开的归档，按模型 ID 索引
+static TREND_ARCHIVES: Lazy<Mutex<HashMap<String, TrendArchive>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[napi(object, namespace = "variableCurveHandle")]
+pub struct TrendArchiveStatus {
+    pub model_id: String,
+    pub file_path: String,
+    pub spill_threshold_bytes: i64,
+    /// 缓存库中该模型的数据块字节数
+    pub cache_bytes: i64,
+    /// 归档文件中该模型的数据块数
+    pub archived_block_count: u32,
+}
+
+fn archive(model_id: &str) -> Option<TrendArchive> {
+    TREND_ARCHIVES.lock().unwrap().get(model_id).cloned()
+}
+
+/// 模型已打开的归档连接
+pub(crate) fn archive_db(model_id: &str) -> Option<DatabaseConnection> {
+    archive(model_id).map(|a| a.db)
+}
+
+async fn connect_archive(file_path: &str) -> Result<DatabaseConnection, DbErr> {
+    let path = std::path::Path::new(file_path);
+    if let Some(parent) = path.parent()
+        && !parent.as_os_str().is_empty()
+    {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| DbErr::Custom(format!("无法创建目录: {}", e)))?;
+    }
+
+    let mut opt = ConnectOptions::new(format!("sqlite://{}?mode=rwc", file_path));
+    opt.max_connections(1)
+        .min_connections(1)
+        .connect_timeout(Duration::from_secs(10))
+        .max_lifetime(None)
+        .idle_timeout(None)
+        .sqlx_logging(false);
+    let db = Database::connect(opt).await?;
+    TrendArchiveMigrator::up(&db, None).await?;
+    db.execute_unprepared("PRAGMA journal_mode = WAL;").await?;
+    db.execute_unprepared("PRAGMA synchronous = NORMAL;")
+        .await?;
+    Ok(db)
+}
+
+/// 各数据块的 (id, 字节数)，按写入顺序
+async fn block_sizes<C: ConnectionTrait>(db: &C, model_id: &str) -> Result<Vec<(i32, i64)>, DbErr> {
+    BlockEntity::find()
+        .select_only()
+        .column(BlockColumn::Id)
+        .column_as(Expr::cust("LENGTH(data)"), "bytes")
+        .filter(BlockColumn::ModelId.eq(model_id))
+        .order_by_asc(BlockColumn::Id)
+        .into_tuple()
+        .all(db)
+        .await
+}
+
+/// 把缓存库中模型最旧的数据块搬到归档文件，返回搬迁的数据块数。
+/// force 为 false 时只在超过阈值后搬迁，并搬到阈值的一半以下，避免每写一块就搬一次；
+/// force 为 true 时全部搬迁。
+/// 调用方需持有趋势写入缓冲的锁，保证查询不会在搬迁中途看到重复或缺失的数据块
+pub(crate) async fn spill_blocks<C: ConnectionTrait>(
+    cache: &C,
+    model_id: &str,
+    force: bool,
+) -> Result<u32, DbErr> {
+    let Some(archive) = archive(model_id) else {
+        return Ok(0);
+    };
+    let sizes = block_sizes(cache, model_id).await?;
+    let mut remaining: i64 = sizes.iter().map(|s| s.1).sum();
+    let target = if force {
+        0
+    } else if remaining > archive.spill_threshold_bytes {
+        archive.spill_threshold_bytes / 2
+    } else {
+        return Ok(0);
+    };
+    let mut ids = Vec::new();
+    for (id, bytes) in sizes {
+        if remaining <= target {
+            break;
+        }
+        remaining -= bytes;
+        ids.push(id);
+    }
+    move_blocks(cache, &archive, model_id, &ids).await
+}
+
+/// 把缓存库中模型的指定数据块搬到归档文件（缓存容量治理使用），返回搬迁的数据块数；
+/// 模型未打开归档时返回 None，由调用方决定如何处理这些数据块。
+/// 调用方需持有趋势写入缓冲的锁
+pub(crate) async fn spill_block_ids<C: ConnectionTrait>(
+    cache: &C,
+    model_id: &str,
+    ids: &[i32],
+) -> Result<Option<u32>, DbErr> {
+    let Some(archive) = archive(model_id) else {
+        return Ok(None);
+    };
+    move_blocks(cache, &archive, model_id, ids).await.map(Some)
+}
+
+async fn move_blocks<C: ConnectionTrait>(
+    cache: &C,
+    archive: &TrendArchive,
+    model_id: &str,
+    ids: &[i32],
+) -> Result<u32, DbErr> {
+    if ids.is_empty() {
+        return Ok(0);
+    }
+
+    // 变量显示名称与数据块在同一个归档事务中写入。数据块按唯一键忽略已存在的记录，
+    // 上次搬迁在删除缓存前中断时，重试不会在归档中产生重复数据
+    let variables = VariableEntity::find()
+        .filter(VariableColumn::ModelId.eq(model_id))
+        .all(cache)
+        .await?;
+    let txn = archive.db.begin().await?;
+    for v in variables {
+        VariableEntity::insert(VariableActiveModel {
+            id: NotSet,
+            ..v.into_active_model()
+        })
+        .on_conflict(
+            OnConflict::columns([
+                VariableColumn::ModelId,
+                VariableColumn::ConfigId,
+                VariableColumn::VariableId,
+            ])
+            .update_column(VariableColumn::Name)
+            .to_owned(),
+        )
+        .exec(&txn)
+        .await?;
+    }
+    let mut written = Vec::with_capacity(ids.len());
+    for chunk in ids.chunks(MOVE_BATCH) {
+        let blocks = BlockEntity::find()
+            .filter(BlockColumn::ModelId.eq(model_id))
+            .filter(BlockColumn::Id.is_in(chunk.to_vec()))
+            .order_by_asc(BlockColumn::Id)
+            .all(cache)
+            .await?;
+        written.extend(blocks.iter().map(|b| b.id));
+        let models: Vec<BlockActiveModel> = blocks
+            .into_iter()
+            .map(|b| BlockActiveModel {
+                id: NotSet,
+                ..b.into_active_model()
+            })
+            .collect();
+        BlockEntity::insert_many(models)
+            .on_conflict(
+                OnConflict::columns([
+                    BlockColumn::ModelId,
+                    BlockColumn::ConfigId,
+                    BlockColumn::VariableId,
+                    BlockColumn::StartTime,
+                    BlockColumn::CreateAt,
+                ])
+                .do_nothing()
+                .to_owned(),
+            )
+            .exec_without_returning(&txn)
+            .await?;
+    }
+    txn.commit().await?;
+
+    // 只删除已确认写入归档的数据块；失败时数据仍留在缓存，下次搬迁重试
+    for chunk in written.chunks(MOVE_BATCH) {
+        BlockEntity::delete_many()
+            .filter(BlockColumn::Id.is_in(chunk.to_vec()))
+            .exec(cache)
+            .await?;
+    }
+    Ok(written.len() as u32)
+}
+
+/// 为模型打开磁盘归档（文件不存在时创建），已打开时更新阈值。
+/// 归档中已有的数据立即可以查询与导出
+pub async fn open_trend_archive(
+    model_id: String,
+    file_path: String,
+    spill_threshold_bytes: Option<i64>,
+) -> Result<TrendArchiveStatus, DbErr> {
+    let threshold = spill_threshold_bytes
+        .filter(|t| *t > 0)
+        .unwrap_or(DEFAULT_SPILL_THRESHOLD_BYTES);
+    let reuse = archive(&model_id).filter(|a| a.file_path == file_path);
+    let db = match reuse {
+        Some(a) => a.db,
+        None => connect_archive(&file_path).await?,
+    };
+    let previous = TREND_ARCHIVES.lock().unwrap().insert(
+        model_id.clone(),
+        TrendArchive {
+            db,
+            file_path,
+            spill_threshold_bytes: threshold,
+        },
+    );
+    if let Some(previous) = previous {
+        // 切换到新的归档文件时，旧文件保持原样
+        let _ = previous
+            .db
+            .execute_unprepared("PRAGMA wal_checkpoint(TRUNCATE);")
+            .await;
+    }
+    get_trend_archive_status(model_id)
+        .await?
+        .ok_or_else(|| DbErr::Custom("趋势归档打开失败".to_string()))
+}
+
+/// 查询模型的归档状态，未打开归档时返回 None
+pub async fn get_trend_archive_status(
+    model_id: String,
+) -> Result<Option<TrendArchiveStatus>, DbErr> {
+    let Some(archive) = archive(&model_id) else {
+        return Ok(None);
+    };
+    let cache = get_cache_db().await?;
+    let cache_bytes = block_sizes(cache, &model_id)
+        .await?
+        .iter()
+        .map(|s| s.1)
+        .sum();
+    let archived = BlockEntity::find()
+        .filter(BlockColumn::ModelId.eq(model_id.clone()))
+        .count(&archive.db)
+        .await?;
+    Ok(Some(TrendArchiveStatus {
+        model_id,
+        file_path: archive.file_path,
+        spill_threshold_bytes: archive.spill_threshold_bytes,
+        cache_bytes,
+        archived_block_count: archived as u32,
+    }))
+}
+
+/// 把模型缓冲与缓存中的全部趋势数据写入归档，返回搬迁的数据块数；未打开归档时返回 0
+pub async fn spill_trend_archive(model_id: String) -> Result<u32, DbErr> {
+    if archive(&model_id).is_none() {
+        return Ok(0);
+    }
+    archive_trend_buffer(model_id).await
+}
+
+/// 数据全部落盘后关闭模型的归档，返回搬迁的数据块数
+pub async fn close_trend_archive(model_id: String) -> Result<u32, DbErr> {
+    let moved = spill_trend_archive(model_id.clone()).await?;
+    let removed = TREND_ARCHIVES.lock().unwrap().remove(&model_id);
+    if let Some(archive) = removed {
+        archive
+            .db
+            .execute_unprepared("PRAGMA wal_checkpoint(TRUNCATE);")
+            .await?;
+        archive.db.close().await?;
+    }
+    Ok(moved)
+}
+
+/// 关闭全部已打开的归档，软件退出前调用
+pub async fn close_all_trend_archives() {
+    let model_ids: Vec<String> = TREND_ARCHIVES.lock().unwrap().keys().cloned().collect();
+    for model_id in model_ids {
+        if let Err(e) = close_trend_archive(model_id.clone()).await {
+            eprintln!(
+                "❌ [TrendArchive] 模型 {} 的趋势归档关闭失败: {}",
+                model_id, e
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sea_orm::ActiveValue::Set;
+
+    fn block(model_id: &str, start_time: f64) -> BlockActiveModel {
+        BlockActiveModel {
+            model_id: Set(model_id.to_string()),
+            config_id: Set("c".to_string()),
+            variable_id: Set("v".to_string()),
+            start_time: Set(start_time),
+            end_time: Set(start_time + 1.0),
+            point_count: Set(1),
+            min_value: Set(0.0),
+            max_value: Set(0.0),
+            create_at: Set(1),
+            data: Set(vec![0; 8]),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_spill_retry_does_not_duplicate() {
+        let model_id = "trend_archive_retry_model";
+        let path = std::env::temp_dir().join(format!("trend_archive_{}.db", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let cache = get_cache_db().await.unwrap();
+        open_trend_archive(
+            model_id.to_string(),
+            path.to_string_lossy().to_string(),
+            None,
+        )
+        .await
+        .unwrap();
+
+        BlockEntity::insert_many([block(model_id, 0.0), block(model_id, 1.0)])
+            .exec(cache)
+            .await
+            .unwrap();
+        assert_eq!(spill_blocks(cache, model_id, true).await.unwrap(), 2);
+
+        // 模拟上次搬迁写入归档后删除缓存失败：同一数据块再次搬迁
+        BlockEntity::insert(block(model_id, 1.0))
+            .exec(cache)
+            .await
+            .unwrap();
+        assert_eq!(spill_blocks(cache, model_id, true).await.unwrap(), 1);
+        let status = get_trend_archive_status(model_id.to_string())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(status.archived_block_count, 2);
+        assert_eq!(status.cache_bytes, 0);
+
+        close_trend_archive(model_id.to_string()).await.unwrap();
+        let _ = std::fs::remove_file(&path);
+    }
+}
@@ -0,0 +1,521 @@
+use crate::service_database::database_cache::db_cache_connection::get_cache_db;
+use crate::service_database::database_cache::entity::model_trend_block_entity_cache::{
+    ActiveModel as BlockActiveModel, Column as BlockColumn, Entity as BlockEntity,
+    Model as BlockModel,
+};
+use crate::service_database::database_cache::entity::model_trend_record_entity_cache::{
+    ActiveModel as RecordActiveModel, Column as RecordColumn, Entity as RecordEntity,
+};
+use crate::service_database::database_cache::entity::model_trend_variable_entity_cache::{
+    ActiveModel as VariableActiveModel, Column as VariableColumn, Entity as VariableEntity,
+    Model as VariableModel,
+};
+use crate::service_database::database_cache::service::trend_archive_service::{
+    archive_db, spill_block_ids, spill_blocks,
+};
+use crate::service_database::database_cache::service::variable_curve_service::DatasetItem;
+use crate::tool_handle::trend_tool::{TrendPoint, decode_points, encode_points, lttb, min_max};
+use chrono::Utc;
+use napi_derive::napi;
+use once_cell::sync::Lazy;
+use sea_orm::sea_query::OnConflict;
+use sea_orm::{
+    ColumnTrait, ConnectionTrait, DbErr, EntityTrait, QueryFilter, QueryOrder, QuerySelect, Set,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use tokio::sync::Mutex;
+
+// ======================================
+// 趋势曲线时序存储
+// 每个变量的采样点先进入内存缓冲，攒满 BLOCK_POINTS 个点后按列编码压缩为一个数据块，
+// 以 (model_id, config_id, variable_id, start_time) 建索引，查询时只解码时间窗口内的数据块，
+// 再按目标点数在服务端降采样。
+// 模型打开磁盘归档后，旧数据块会搬到归档文件，查询时两处的数据块合并读取。
+// 每次写入另记一条写入记录（ID、仿真时间、写入时间），用于还原旧版按行查询的结果
+// ======================================
+
+/// 每个数据块的点数
+const BLOCK_POINTS: usize = 1024;
+
+/// (model_id, config_id, variable_id)
+type SeriesKey = (String, String, String);
+
+#[derive(Default)]
+struct TrendBuffer {
+    /// 尚未写成数据块的采样点
+    points: HashMap<SeriesKey, Vec<TrendPoint>>,
+    /// 已写入变量表的名称，名称不变时不再写库
+    names: HashMap<SeriesKey, String>,
+}
+
+/// 写入缓冲；查询时持有同一把锁，保证缓冲与数据块之间不会漏点或重复
+static TREND_BUFFER: Lazy<Mutex<TrendBuffer>> = Lazy::new(|| Mutex::new(TrendBuffer::default()));
+
+#[napi(string_enum, namespace = "variableCurveHandle")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TrendDownsampleMode {
+    /// 保持曲线形状，适合连续量
+    Lttb,
+    /// 每个桶保留最小值与最大值，不丢尖峰
+    MinMax,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[napi(object, namespace = "variableCurveHandle")]
+pub struct TrendSeries {
+    pub variable_id: String,
+    pub name: String,
+    /// 仿真时间，与 values 一一对应
+    pub times: Vec<f64>,
+    pub values: Vec<f64>,
+    /// 降采样前时间窗口内的点数
+    pub raw_count: u32,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[napi(object, namespace = "variableCurveHandle")]
+pub struct TrendVariable {
+    pub variable_id: String,
+    pub name: String,
+    pub point_count: u32,
+    pub start_time: Option<f64>,
+    pub end_time: Option<f64>,
+}
+
+/// 采样值转为数值，布尔量记为 0 / 1；不是有限数值时返回 None
+pub(crate) fn parse_value(value: &str) -> Option<f64> {
+    match value.trim() {
+        "true" => Some(1.0),
+        "false" => Some(0.0),
+        v => v.parse::<f64>().ok().filter(|v| v.is_finite()),
+    }
+}
+
+fn in_window(time: f64, start_time: Option<f64>, end_time: Option<f64>) -> bool {
+    start_time.is_none_or(|s| time >= s) && end_time.is_none_or(|e| time <= e)
+}
+
+/// 把一段采样点写成一个数据块
+async fn write_block<C: ConnectionTrait>(
+    db: &C,
+    key: &SeriesKey,
+    mut points: Vec<TrendPoint>,
+) -> Result<(), DbErr> {
+    if points.is_empty() {
+        return Ok(());
+    }
+    // 仿真回退后时间可能不再递增，块内按时间排序以便区间过滤
+    points.sort_by(|a, b| a.0.total_cmp(&b.0));
+    let (min_value, max_value) = points.iter().fold((f64::MAX, f64::MIN), |(lo, hi), p| {
+        (lo.min(p.1), hi.max(p.1))
+    });
+    let data =
+        encode_points(&points).map_err(|e| DbErr::Custom(format!("趋势数据编码失败: {}", e)))?;
+    BlockEntity::insert(BlockActiveModel {
+        model_id: Set(key.0.clone()),
+        config_id: Set(key.1.clone()),
+        variable_id: Set(key.2.clone()),
+        start_time: Set(points[0].0),
+        end_time: Set(points[points.len() - 1].0),
+        point_count: Set(points.len() as i32),
+        min_value: Set(min_value),
+        max_value: Set(max_value),
+        create_at: Set(Utc::now().timestamp_millis()),
+        data: Set(data),
+        ..Default::default()
+    })
+    .exec(db)
+    .await?;
+    Ok(())
+}
+
+/// 追加一个时刻的采样值，返回 (写入记录 ID, 入库的点数)。
+/// 趋势存储只保存数值，无法解析为数值的采样（如状态文本）跳过，不计入点数，与实时推送的处理一致
+pub async fn append_trend_samples(
+    datasets: Vec<DatasetItem>,
+    model_id: String,
+    config_id: String,
+    sim_time: f64,
+) -> Result<(i32, u32), DbErr> {
+    let samples: Vec<(DatasetItem, f64)> = datasets
+        .into_iter()
+        .filter_map(|item| parse_value(&item.value).map(|value| (item, value)))
+        .collect();
+
+    let db = get_cache_db().await?;
+    let mut buffer = TREND_BUFFER.lock().await;
+    let record_id = RecordEntity::insert(RecordActiveModel {
+        model_id: Set(model_id.clone()),
+        config_id: Set(config_id.clone()),
+        sim_time: Set(sim_time),
+        create_at: Set(Utc::now().timestamp_millis()),
+        ..Default::default()
+    })
+    .exec(db)
+    .await?
+    .last_insert_id;
+    let mut appended = 0;
+    let mut written = false;
+    for (item, value) in samples {
+        let key = (model_id.clone(), config_id.clone(), item.id);
+        if buffer.names.get(&key) != Some(&item.name) {
+            VariableEntity::insert(VariableActiveModel {
+                model_id: Set(key.0.clone()),
+                config_id: Set(key.1.clone()),
+                variable_id: Set(key.2.clone()),
+                name: Set(item.name.clone()),
+                ..Default::default()
+            })
+            .on_conflict(
+                OnConflict::columns([
+                    VariableColumn::ModelId,
+                    VariableColumn::ConfigId,
+                    VariableColumn::VariableId,
+                ])
+                .update_column(VariableColumn::Name)
+                .to_owned(),
+            )
+            .exec(db)
+            .await?;
+            buffer.names.insert(key.clone(), item.name);
+        }
+
+        let points = buffer.points.entry(key.clone()).or_default();
+        points.push((sim_time, value));
+        appended += 1;
+        if points.len() >= BLOCK_POINTS {
+            let full = std::mem::take(points);
+            write_block(db, &key, full).await?;
+            written = true;
+        }
+    }
+    if written {
+        spill_blocks(db, &model_id, false).await?;
+    }
+    Ok((record_id, appended))
+}
+
+/// 把模型缓冲中的采样点全部写成数据块，返回写入的数据块数；调用方持有缓冲锁
+async fn flush_locked<C: ConnectionTrait>(
+    db: &C,
+    buffer: &mut TrendBuffer,
+    model_id: &str,
+) -> Result<u32, DbErr> {
+    let keys: Vec<SeriesKey> = buffer
+        .points
+        .iter()
+        .filter(|(k, v)| k.0 == model_id && !v.is_empty())
+        .map(|(k, _)| k.clone())
+        .collect();
+    for key in &keys {
+        let points = buffer.points.remove(key).unwrap_or_default();
+        write_block(db, key, points).await?;
+    }
+    Ok(keys.len() as u32)
+}
+
+/// 把模型缓冲中的采样点全部写成数据块，返回写入的数据块数
+pub async fn flush_trend_buffer(model_id: String) -> Result<u32, DbErr> {
+    let db = get_cache_db().await?;
+    let mut buffer = TREND_BUFFER.lock().await;
+    let written = flush_locked(db, &mut buffer, &model_id).await?;
+    if written > 0 {
+        spill_blocks(db, &model_id, false).await?;
+    }
+    Ok(written)
+}
+
+/// 把模型缓冲与缓存中的全部数据块搬到磁盘归档，返回搬迁的数据块数
+pub(crate) async fn archive_trend_buffer(model_id: String) -> Result<u32, DbErr> {
+    let db = get_cache_db().await?;
+    let mut buffer = TREND_BUFFER.lock().await;
+    flush_locked(db, &mut buffer, &model_id).await?;
+    spill_blocks(db, &model_id, true).await
+}
+
+/// 配置下的变量，按首次出现的顺序
+async fn find_variables<C: ConnectionTrait>(
+    db: &C,
+    model_id: &str,
+    config_id: &str,
+) -> Result<Vec<VariableModel>, DbErr> {
+    VariableEntity::find()
+        .filter(VariableColumn::ModelId.eq(model_id))
+        .filter(VariableColumn::ConfigId.eq(config_id))
+        .order_by_asc(VariableColumn::Id)
+        .all(db)
+        .await
+}
+
+/// 归档与缓存中的变量合并，归档中的变量在前，名称以缓存为准
+async fn find_all_variables<C: ConnectionTrait>(
+    db: &C,
+    model_id: &str,
+    config_id: &str,
+) -> Result<Vec<VariableModel>, DbErr> {
+    let mut variables = match archive_db(model_id) {
+        Some(archive) => find_variables(&archive, model_id, config_id).await?,
+        None => Vec::new(),
+    };
+    for v in find_variables(db, model_id, config_id).await? {
+        match variables
+            .iter_mut()
+            .find(|a| a.variable_id == v.variable_id)
+        {
+            Some(a) => a.name = v.name,
+            None => variables.push(v),
+        }
+    }
+    Ok(variables)
+}
+
+/// 与时间窗口相交的数据块，按写入顺序
+async fn find_blocks<C: ConnectionTrait>(
+    db: &C,
+    model_id: &str,
+    config_id: &str,
+    ids: &[String],
+    start_time: Option<f64>,
+    end_time: Option<f64>,
+) -> Result<Vec<BlockModel>, DbErr> {
+    let mut query = BlockEntity::find()
+        .filter(BlockColumn::ModelId.eq(model_id))
+        .filter(BlockColumn::ConfigId.eq(config_id))
+        .filter(BlockColumn::VariableId.is_in(ids.to_vec()));
+    if let Some(start) = start_time {
+        query = query.filter(BlockColumn::EndTime.gte(start));
+    }
+    if let Some(end) = end_time {
+        query = query.filter(BlockColumn::StartTime.lte(end));
+    }
+    // 按写入顺序返回，仿真回退产生的同一时刻的点在排序后仍保持写入顺序
+    query.order_by_asc(BlockColumn::Id).all(db).await
+}
+
+/// 按仿真时间窗口查询变量曲线，max_points 大于 0 时降采样到该点数（默认 LTTB）。
+/// variable_ids 为空时返回该配置下的全部变量
+pub async fn query_trend_series(
+    model_id: String,
+    config_id: String,
+    variable_ids: Vec<String>,
+    start_time: Option<f64>,
+    end_time: Option<f64>,
+    max_points: Option<u32>,
+    mode: Option<TrendDownsampleMode>,
+) -> Result<Vec<TrendSeries>, DbErr> {
+    let db = get_cache_db().await?;
+    let buffer = TREND_BUFFER.lock().await;
+
+    let archive = archive_db(&model_id);
+
+    let variables = find_all_variables(db, &model_id, &config_id).await?;
+    let mut names: HashMap<String, String> = HashMap::new();
+    let mut ids = Vec::new();
+    for v in variables {
+        ids.push(v.variable_id.clone());
+        names.insert(v.variable_id, v.name);
+    }
+    if !variable_ids.is_empty() {
+        ids = variable_ids;
+    }
+
+    let mut blocks = match &archive {
+        Some(archive) => {
+            find_blocks(archive, &model_id, &config_id, &ids, start_time, end_time).await?
+        }
+        None => Vec::new(),
+    };
+    blocks.extend(find_blocks(db, &model_id, &config_id, &ids, start_time, end_time).await?);
+
+    let mut series: HashMap<String, Vec<TrendPoint>> = HashMap::new();
+    for block in blocks {
+        let points = decode_points(&block.data)
+            .map_err(|e| DbErr::Custom(format!("趋势数据块 {} 损坏: {}", block.id, e)))?;
+        series.entry(block.variable_id).or_default().extend(
+            points
+                .into_iter()
+                .filter(|p| in_window(p.0, start_time, end_time)),
+        );
+    }
+
+    let threshold = max_points.unwrap_or(0) as usize;
+    let result = ids
+        .into_iter()
+        .map(|id| {
+            let mut points = series.remove(&id).unwrap_or_default();
+            let key = (model_id.clone(), config_id.clone(), id.clone());
+            if let Some(buffered) = buffer.points.get(&key) {
+                points.extend(
+                    buffered
+                        .iter()
+                        .filter(|p| in_window(p.0, start_time, end_time)),
+                );
+            }
+            if !points.is_sorted_by(|a, b| a.0 <= b.0) {
+                points.sort_by(|a, b| a.0.total_cmp(&b.0));
+            }
+            let raw_count = points.len() as u32;
+            let sampled = match mode.unwrap_or(TrendDownsampleMode::Lttb) {
+                TrendDownsampleMode::Lttb => lttb(&points, threshold),
+                TrendDownsampleMode::MinMax => min_max(&points, threshold),
+            };
+            let (times, values) = sampled.into_iter().unzip();
+            TrendSeries {
+                name: names.remove(&id).unwrap_or_default(),
+                variable_id: id,
+                times,
+                values,
+                raw_count,
+            }
+        })
+        .collect();
+    Ok(result)
+}
+
+/// 各数据块的 (变量 ID, 点数, 起始时间, 结束时间)
+async fn block_ranges<C: ConnectionTrait>(
+    db: &C,
+    model_id: &str,
+    config_id: &str,
+) -> Result<Vec<(String, i32, f64, f64)>, DbErr> {
+    BlockEntity::find()
+        .select_only()
+        .column(BlockColumn::VariableId)
+        .column(BlockColumn::PointCount)
+        .column(BlockColumn::StartTime)
+        .column(BlockColumn::EndTime)
+        .filter(BlockColumn::ModelId.eq(model_id))
+        .filter(BlockColumn::ConfigId.eq(config_id))
+        .into_tuple()
+        .all(db)
+        .await
+}
+
+/// 列出配置下的趋势变量及其数据范围
+pub async fn get_trend_variables(
+    model_id: String,
+    config_id: String,
+) -> Result<Vec<TrendVariable>, DbErr> {
+    let db = get_cache_db().await?;
+    let buffer = TREND_BUFFER.lock().await;
+
+    let variables = find_all_variables(db, &model_id, &config_id).await?;
+    let mut blocks = match archive_db(&model_id) {
+        Some(archive) => block_ranges(&archive, &model_id, &config_id).await?,
+        None => Vec::new(),
+    };
+    blocks.extend(block_ranges(db, &model_id, &config_id).await?);
+
+    let mut ranges: HashMap<String, (u32, Option<f64>, Option<f64>)> = HashMap::new();
+    let mut merge = |id: &str, count: u32, start: f64, end: f64| {
+        let range = ranges.entry(id.to_string()).or_insert((0, None, None));
+        range.0 += count;
+        range.1 = Some(range.1.map_or(start, |s| s.min(start)));
+        range.2 = Some(range.2.map_or(end, |e| e.max(end)));
+    };
+    for (id, count, start, end) in blocks {
+        merge(&id, count as u32, start, end);
+    }
+    for ((m, c, id), points) in &buffer.points {
+        if *m == model_id && *c == config_id {
+            for p in points {
+                merge(id, 1, p.0, p.0);
+            }
+        }
+    }
+
+    Ok(variables
+        .into_iter()
+        .map(|v| {
+            let (point_count, start_time, end_time) =
+                ranges.remove(&v.variable_id).unwrap_or((0, None, None));
+            TrendVariable {
+                variable_id: v.variable_id,
+                name: v.name,
+                point_count,
+                start_time,
+                end_time,
+            }
+        })
+        .collect())
+}
+
+/// 删除一个库中配置下的数据块与变量，返回删除的数据块数
+async fn delete_series<C: ConnectionTrait>(
+    db: &C,
+    model_id: &str,
+    config_id: &str,
+) -> Result<u64, DbErr> {
+    let deleted = BlockEntity::delete_many()
+        .filter(BlockColumn::ModelId.eq(model_id))
+        .filter(BlockColumn::ConfigId.eq(config_id))
+        .exec(db)
+        .await?
+        .rows_affected;
+    VariableEntity::delete_many()
+        .filter(VariableColumn::ModelId.eq(model_id))
+        .filter(VariableColumn::ConfigId.eq(config_id))
+        .exec(db)
+        .await?;
+    Ok(deleted)
+}
+
+/// 淘汰 id 不超过 last_id 的数据块（缓存容量治理使用），返回移出缓存的数据块数。
+/// 打开了磁盘归档的模型把数据块搬到归档，其余模型直接删除
+pub(crate) async fn evict_trend_blocks(last_id: i32) -> Result<u64, DbErr> {
+    let db = get_cache_db().await?;
+    let _buffer = TREND_BUFFER.lock().await;
+    let rows: Vec<(i32, String)> = BlockEntity::find()
+        .select_only()
+        .column(BlockColumn::Id)
+        .column(BlockColumn::ModelId)
+        .filter(BlockColumn::Id.lte(last_id))
+        .order_by_asc(BlockColumn::Id)
+        .into_tuple()
+        .all(db)
+        .await?;
+    let mut by_model: HashMap<String, Vec<i32>> = HashMap::new();
+    for (id, model_id) in rows {
+        by_model.entry(model_id).or_default().push(id);
+    }
+
+    let mut evicted = 0;
+    for (model_id, ids) in by_model {
+        if let Some(moved) = spill_block_ids(db, &model_id, &ids).await? {
+            evicted += moved as u64;
+            continue;
+        }
+        for chunk in ids.chunks(500) {
+            evicted += BlockEntity::delete_many()
+                .filter(BlockColumn::Id.is_in(chunk.to_vec()))
+                .exec(db)
+                .await?
+                .rows_affected;
+        }
+    }
+    Ok(evicted)
+}
+
+/// 清空配置下的全部趋势数据（包括磁盘归档），返回删除的数据块数
+pub async fn clear_trend_data(model_id: String, config_id: String) -> Result<u32, DbErr> {
+    let db = get_cache_db().await?;
+    let mut buffer = TREND_BUFFER.lock().await;
+    buffer
+        .points
+        .retain(|k, _| !(k.0 == model_id && k.1 == config_id));
+    buffer
+        .names
+        .retain(|k, _| !(k.0 == model_id && k.1 == config_id));
+
+    RecordEntity::delete_many()
+        .filter(RecordColumn::ModelId.eq(model_id.clone()))
+        .filter(RecordColumn::ConfigId.eq(config_id.clone()))
+        .exec(db)
+        .await?;
+    let mut deleted = delete_series(db, &model_id, &config_id).await?;
+    if let Some(archive) = archive_db(&model_id) {
+        deleted += delete_series(&archive, &model_id, &config_id).await?;
+    }
+    Ok(deleted as u32)
+}
@@ -0,0 +1,155 @@
+use crate::service_database::database_cache::service::trend_service::parse_value;
+use crate::service_database::database_cache::service::variable_curve_service::DatasetItem;
+use napi::bindgen_prelude::Function;
+use napi::threadsafe_function::{ThreadsafeFunction, ThreadsafeFunctionCallMode};
+use napi::{Result, Status};
+use napi_derive::napi;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::{Arc, Mutex};
+
+// ======================================
+// 趋势实时订阅
+// JS 为趋势配置注册回调后，写入曲线采样时把新数据推送给回调，前端无需轮询。
+// 推送经 ThreadsafeFunction 回到 JS 线程：两次回调之间写入的采样攒成一批，
+// JS 每轮事件循环最多收到一次回调，写入频率高于渲染频率时不会堆积回调
+// ======================================
+
+/// 一个仿真时刻的采样，只包含能解析为数值的变量（与趋势存储一致）
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[napi(object, namespace = "variableCurveHandle")]
+pub struct TrendTick {
+    pub model_id: String,
+    pub config_id: String,
+    pub sim_time: f64,
+    /// 与 names、values 一一对应
+    pub variable_ids: Vec<String>,
+    pub names: Vec<String>,
+    pub values: Vec<f64>,
+}
+
+/// 弱引用的回调，不阻止 Node 进程退出
+type TrendTickCallback = ThreadsafeFunction<(), (), Vec<TrendTick>, Status, false, true>;
+
+#[derive(Default)]
+struct PendingTicks {
+    ticks: Vec<TrendTick>,
+    /// 已向 JS 线程投递回调、尚未执行
+    scheduled: bool,
+}
+
+struct TrendSubscriber {
+    config_id: String,
+    pending: Arc<Mutex<PendingTicks>>,
+    callback: TrendTickCallback,
+}
+
+/// 订阅表，按订阅 ID 索引
+static TREND_SUBSCRIBERS: Lazy<Mutex<HashMap<u32, TrendSubscriber>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+static NEXT_SUBSCRIPTION_ID: AtomicU32 = AtomicU32::new(1);
+
+/// 为趋势配置注册回调，返回订阅 ID
+pub fn subscribe_trend(config_id: String, callback: Function<Vec<TrendTick>, ()>) -> Result<u32> {
+    let pending = Arc::new(Mutex::new(PendingTicks::default()));
+    let drain = pending.clone();
+    let callback: TrendTickCallback = callback
+        .build_threadsafe_function::<()>()
+        .callee_handled::<false>()
+        .weak::<true>()
+        .build_callback(move |_| {
+            // 取走批次与清除标记在同一把锁内完成，之后写入的采样会触发下一次回调
+            let mut pending = drain.lock().unwrap();
+            pending.scheduled = false;
+            Ok(std::mem::take(&mut pending.ticks))
+        })?;
+
+    let id = NEXT_SUBSCRIPTION_ID.fetch_add(1, Ordering::Relaxed);
+    TREND_SUBSCRIBERS.lock().unwrap().insert(
+        id,
+        TrendSubscriber {
+            config_id,
+            pending,
+            callback,
+        },
+    );
+    Ok(id)
+}
+
+/// 取消订阅，订阅不存在时返回 false
+pub fn unsubscribe_trend(subscription_id: u32) -> bool {
+    TREND_SUBSCRIBERS
+        .lock()
+        .unwrap()
+        .remove(&subscription_id)
+        .is_some()
+}
+
+/// 取消全部订阅，关闭数据库时调用
+pub fn unsubscribe_all_trends() {
+    TREND_SUBSCRIBERS.lock().unwrap().clear();
+}
+
+/// 配置是否有订阅者，没有时写入路径不必构造推送数据
+pub(crate) fn has_trend_subscribers(config_id: &str) -> bool {
+    TREND_SUBSCRIBERS
+        .lock()
+        .unwrap()
+        .values()
+        .any(|s| s.config_id == config_id)
+}
+
+/// 由写入的采样构造推送数据
+pub(crate) fn trend_tick(
+    datasets: &[DatasetItem],
+    model_id: &str,
+    config_id: &str,
+    sim_time: f64,
+) -> TrendTick {
+    let mut tick = TrendTick {
+        model_id: model_id.to_string(),
+        config_id: config_id.to_string(),
+        sim_time,
+        variable_ids: Vec::new(),
+        names: Vec::new(),
+        values: Vec::new(),
+    };
+    for item in datasets {
+        if let Some(value) = parse_value(&item.value) {
+            tick.variable_ids.push(item.id.clone());
+            tick.names.push(item.name.clone());
+            tick.values.push(value);
+        }
+    }
+    tick
+}
+
+/// 把采样推送给该配置的全部订阅者
+pub(crate) fn publish_trend_tick(tick: TrendTick) {
+    if tick.values.is_empty() {
+        return;
+    }
+    let subscribers = TREND_SUBSCRIBERS.lock().unwrap();
+    for subscriber in subscribers
+        .values()
+        .filter(|s| s.config_id == tick.config_id)
+    {
+        let mut pending = subscriber.pending.lock().unwrap();
+        pending.ticks.push(tick.clone());
+        if pending.scheduled {
+            continue;
+        }
+        let status = subscriber
+            .callback
+            .call((), ThreadsafeFunctionCallMode::NonBlocking);
+        // 投递失败（JS 环境正在关闭）时丢弃批次，避免无人消费的数据持续堆积
+        if status == Status::Ok {
+            pending.scheduled = true;
+        } else {
+            pending.ticks.clear();
+        }
+    }
+}
@@ -1,11 +1,22 @@
 use chrono::Utc;
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
+use std::sync::Arc;
 
+use crate::service_database::database_business::service::util_handle::model_undo_apply_service::{
+    UndoChange, UndoDirection, apply_undo_changes,
+};
+use crate::service_database::database_business::service::util_handle::model_undo_history_service::{
+    UndoHistoryRow, delete_undo_history_by_ids, delete_undo_history_by_model_id,
+    list_undo_history, load_undo_history_config, replace_undo_history, save_undo_history_config,
+    upsert_undo_history,
+};
 use crate::service_database::database_cache::entity::model_undo_entity_cache::{
-    self, ActiveModel, Column as UndoEntityColumn, Entity as UndoEntity, Model as UndoModel,
+    ActiveModel, Column as UndoEntityColumn, Entity as UndoEntity, Model as UndoModel,
 };
 
-use sea_orm::{ActiveValue::Set, DbErr, EntityTrait, entity::*, query::*};
-use serde::Deserialize;
+use sea_orm::{ActiveValue::Set, DbErr, EntityTrait, entity::*, query::*, sea_query::Expr};
+use serde::{Deserialize, Serialize};
 
 // 导入公共数据库连接函数
 use crate::service_database::database_cache::db_cache_connection::get_cache_db;
@@ -20,6 +31,75 @@ pub struct UndoRedoData {
     pub old_data: String,
     pub new_data: String,
     pub model_id: String,
+    /// 所属撤销步骤 id
+    pub step_id: i32,
+}
+
+/// 撤销历史配置（按模型）
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[napi(object, namespace = "undoRedoHandle")]
+pub struct UndoHistoryConfig {
+    /// 最多保留的撤销步数，0 表示不限制
+    pub max_steps: u32,
+    /// 是否持久化到业务库（软件重启后可恢复）
+    pub persist: bool,
+}
+
+impl Default for UndoHistoryConfig {
+    fn default() -> Self {
+        UndoHistoryConfig {
+            max_steps: 10,
+            persist: false,
+        }
+    }
+}
+
+/// 未结束的操作组
+#[derive(Debug, Default)]
+struct UndoGroup {
+    depth: u32,
+    step_id: Option<i32>,
+}
+
+// 已加载的配置；模型首次访问时从业务库读取，开启持久化的模型同时恢复历史
+static UNDO_CONFIGS: Lazy<tokio::sync::Mutex<HashMap<String, UndoHistoryConfig>>> =
+    Lazy::new(|| tokio::sync::Mutex::new(HashMap::new()));
+// 写入撤销记录时持有，保证同一操作组的记录不会被并发拆成多个步骤
+static UNDO_GROUPS: Lazy<tokio::sync::Mutex<HashMap<String, UndoGroup>>> =
+    Lazy::new(|| tokio::sync::Mutex::new(HashMap::new()));
+// 按模型串行化撤销/重做的执行（查询步骤 → 写业务库 → 更新状态），避免同一步骤被并发执行两次
+static APPLY_LOCKS: Lazy<std::sync::Mutex<HashMap<String, Arc<tokio::sync::Mutex<()>>>>> =
+    Lazy::new(|| std::sync::Mutex::new(HashMap::new()));
+
+fn apply_lock(model_id: &str) -> Arc<tokio::sync::Mutex<()>> {
+    APPLY_LOCKS
+        .lock()
+        .unwrap()
+        .entry(model_id.to_string())
+        .or_default()
+        .clone()
+}
+
+fn to_history_row(m: UndoModel) -> UndoHistoryRow {
+    UndoHistoryRow {
+        id: m.id,
+        model_id: m.model_id,
+        step_id: m.step_id,
+        table_name: m.table_name,
+        op_type: m.op_type,
+        old_data: m.old_data,
+        new_data: m.new_data,
+        status: m.status,
+        operator_at: m.operator_at,
+    }
+}
+
+/// 开启持久化的模型，将修改后的记录同步到业务库
+async fn persist_rows(model_id: &str, rows: Vec<UndoModel>) -> Result<(), DbErr> {
+    if undo_history_config(model_id).await?.persist {
+        upsert_undo_history(model_id, rows.into_iter().map(to_history_row).collect()).await?;
+    }
+    Ok(())
 }
 
 impl From<UndoModel> for UndoRedoData {
@@ -31,11 +111,14 @@ impl From<UndoModel> for UndoRedoData {
             old_data: model.old_data,
             new_data: model.new_data, // 假设字段名匹配
             model_id: model.model_id,
+            step_id: model.step_id,
         }
     }
 }
 
 /// 添加一条操作日志，返回插入ID
+///
+/// 处于操作组内时与组内其他记录合并为同一撤销步骤；否则单独成为一步
 pub async fn add_undo_log_service(
     op_type: String,
     table_name: String,
@@ -44,14 +127,33 @@ pub async fn add_undo_log_service(
     model_id: String,
 ) -> Result<i32, DbErr> {
     let db = get_cache_db().await?;
-    // 删除 status = 1 或 2 的记录
-    model_undo_entity_cache::Entity::delete_many()
-        .filter(model_undo_entity_cache::Column::Status.is_in([1, 2]))
-        .exec(db)
-        .await?;
+    let config = undo_history_config(&model_id).await?;
+    let mut groups = UNDO_GROUPS.lock().await;
+    let group_step = groups.get(&model_id).and_then(|g| g.step_id);
+
+    // 新步骤开始时截断该模型的重做分支（仅删除本模型的已撤销记录，已重做的步骤仍可撤销）
+    let mut removed = Vec::new();
+    if group_step.is_none() {
+        let redo_ids: Vec<i32> = UndoEntity::find()
+            .select_only()
+            .column(UndoEntityColumn::Id)
+            .filter(UndoEntityColumn::ModelId.eq(model_id.clone()))
+            .filter(UndoEntityColumn::Status.eq(1))
+            .into_tuple()
+            .all(db)
+            .await?;
+        if !redo_ids.is_empty() {
+            UndoEntity::delete_many()
+                .filter(UndoEntityColumn::Id.is_in(redo_ids.clone()))
+                .exec(db)
+                .await?;
+        }
+        removed.extend(redo_ids);
+    }
 
     let new_item = ActiveModel {
         model_id: Set(model_id.clone()),
+        step_id: Set(group_step.unwrap_or(0)),
         table_name: Set(table_name),
         op_type: Set(op_type),
         old_data: Set(old_data),
@@ -60,13 +162,161 @@ pub async fn add_undo_log_service(
         operator_at: Set(Utc::now().naive_utc()),
         ..Default::default()
     };
+    let mut inserted = new_item.insert(db).await?;
 
-    let result = model_undo_entity_cache::Entity::insert(new_item)
-        .exec(db)
+    // 新步骤以首条记录的 id 作为步骤 id
+    if group_step.is_none() {
+        let mut active: ActiveModel = inserted.into();
+        active.step_id = Set(active.id.clone().unwrap());
+        inserted = active.update(db).await?;
+        if let Some(group) = groups.get_mut(&model_id) {
+            group.step_id = Some(inserted.step_id);
+        }
+        // 限制步数
+        removed.extend(prune_undo_logs(db, &model_id, config.max_steps as usize).await?);
+    }
+    drop(groups);
+
+    let id = inserted.id;
+    if config.persist {
+        delete_undo_history_by_ids(model_id.clone(), removed).await?;
+        upsert_undo_history(&model_id, vec![to_history_row(inserted)]).await?;
+    }
+    Ok(id)
+}
+
+/// 开始一个操作组，组内的多次 add_undo_log 合并为一个撤销步骤，可嵌套
+pub async fn begin_undo_group_service(model_id: String) -> Result<(), DbErr> {
+    let mut groups = UNDO_GROUPS.lock().await;
+    groups.entry(model_id).or_default().depth += 1;
+    Ok(())
+}
+
+/// 结束一个操作组，最外层结束时返回该组的步骤 id（组内无记录时为 None）
+pub async fn end_undo_group_service(model_id: String) -> Result<Option<i32>, DbErr> {
+    let mut groups = UNDO_GROUPS.lock().await;
+    let Some(group) = groups.get_mut(&model_id) else {
+        return Err(DbErr::Custom(format!(
+            "model_id: {} 没有未结束的操作组",
+            model_id
+        )));
+    };
+    group.depth -= 1;
+    if group.depth > 0 {
+        return Ok(None);
+    }
+    let step_id = group.step_id;
+    groups.remove(&model_id);
+    Ok(step_id)
+}
+
+/// 读取模型的撤销历史配置。
+///
+/// 本次运行中首次访问某个模型时从业务库读取配置（未保存过时为默认配置），
+/// 开启持久化的模型同时把业务库中的历史恢复到缓存库，保证重启后新增的记录
+/// 不会在配置重新下发之前覆盖或清除上次的历史
+async fn undo_history_config(model_id: &str) -> Result<UndoHistoryConfig, DbErr> {
+    let mut configs = UNDO_CONFIGS.lock().await;
+    if let Some(config) = configs.get(model_id) {
+        return Ok(config.clone());
+    }
+    let config = match load_undo_history_config(model_id).await? {
+        Some(saved) => UndoHistoryConfig {
+            max_steps: u32::try_from(saved.max_steps).unwrap_or(0),
+            persist: saved.persist,
+        },
+        None => UndoHistoryConfig::default(),
+    };
+    if config.persist {
+        restore_undo_history(model_id, config.max_steps).await?;
+    }
+    configs.insert(model_id.to_string(), config.clone());
+    Ok(config)
+}
+
+/// 将业务库中的历史恢复到缓存库，并按 max_steps 裁剪
+async fn restore_undo_history(model_id: &str, max_steps: u32) -> Result<(), DbErr> {
+    let history = list_undo_history(model_id.to_string()).await?;
+    if history.is_empty() {
+        return Ok(());
+    }
+    // 缓存库 id 在重启后重新分配，恢复时重新映射 id 与步骤 id
+    let db = get_cache_db().await?;
+    let txn = db.begin().await?;
+    let mut step_map: HashMap<i32, i32> = HashMap::new();
+    let mut restored = Vec::with_capacity(history.len());
+    for row in history {
+        let item = ActiveModel {
+            model_id: Set(row.model_id),
+            step_id: Set(step_map.get(&row.step_id).copied().unwrap_or(0)),
+            table_name: Set(row.table_name),
+            op_type: Set(row.op_type),
+            old_data: Set(row.old_data),
+            new_data: Set(row.new_data),
+            status: Set(row.status),
+            operator_at: Set(row.operator_at),
+            ..Default::default()
+        };
+        let mut inserted = item.insert(&txn).await?;
+        if let std::collections::hash_map::Entry::Vacant(e) = step_map.entry(row.step_id) {
+            e.insert(inserted.id);
+            let mut active: ActiveModel = inserted.into();
+            active.step_id = Set(active.id.clone().unwrap());
+            inserted = active.update(&txn).await?;
+        }
+        restored.push(to_history_row(inserted));
+    }
+    txn.commit().await?;
+
+    let removed = prune_undo_logs(db, model_id, max_steps as usize).await?;
+    restored.retain(|r| !removed.contains(&r.id));
+    // 业务库中的记录全部在 restored 里（仅 id 重新映射、超出步数的被裁剪），整体换成新 id
+    replace_undo_history(model_id.to_string(), restored).await
+}
+
+/// 设置模型的撤销历史配置并保存到业务库
+///
+/// 开启持久化时将缓存中的当前历史按 (id, model_id) 写入业务库；
+/// 由开启改为关闭持久化时清除业务库中的历史
+pub async fn set_undo_history_config_service(
+    model_id: String,
+    config: UndoHistoryConfig,
+) -> Result<(), DbErr> {
+    let db = get_cache_db().await?;
+    let _groups = UNDO_GROUPS.lock().await;
+    // 首次访问时按已保存的配置恢复历史，之后再应用新配置
+    let previous = undo_history_config(&model_id).await?;
+    save_undo_history_config(&model_id, config.max_steps, config.persist).await?;
+    UNDO_CONFIGS
+        .lock()
+        .await
+        .insert(model_id.clone(), config.clone());
+
+    let removed = prune_undo_logs(db, &model_id, config.max_steps as usize).await?;
+    if !config.persist {
+        if previous.persist {
+            delete_undo_history_by_model_id(model_id).await?;
+        }
+        return Ok(());
+    }
+    if previous.persist {
+        // 历史已与业务库同步，只需删除本次裁剪掉的记录
+        delete_undo_history_by_ids(model_id, removed).await?;
+        return Ok(());
+    }
+
+    // 之前未持久化，业务库中没有该模型的历史，按 (id, model_id) 写入缓存中的记录
+    let cached = UndoEntity::find()
+        .filter(UndoEntityColumn::ModelId.eq(model_id.clone()))
+        .order_by_asc(UndoEntityColumn::Id)
+        .all(db)
         .await?;
-    // 4. 限制步数：只保留最近 10 步
-    prune_undo_logs(db, &model_id, 10).await?;
-    Ok(result.last_insert_id)
+    upsert_undo_history(&model_id, cached.into_iter().map(to_history_row).collect()).await
+}
+
+/// 获取模型的撤销历史配置（未设置时为默认配置）
+pub async fn get_undo_history_config_service(model_id: String) -> Result<UndoHistoryConfig, DbErr> {
+    undo_history_config(&model_id).await
 }
 
 /// 查询某个模型的所有日志
@@ -98,7 +348,8 @@ pub async fn update_status_service(id: i32, model_id: String, status: i32) -> Re
     {
         let mut active: ActiveModel = record.into();
         active.status = Set(status);
-        active.update(db).await?;
+        let updated = active.update(db).await?;
+        persist_rows(&updated.model_id.clone(), vec![updated]).await?;
         return Ok(true);
     }
     Ok(false)
@@ -120,7 +371,8 @@ pub async fn update_new_data_service(
     {
         let mut active: ActiveModel = record.into();
         active.new_data = Set(new_data);
-        active.update(db).await?;
+        let updated = active.update(db).await?;
+        persist_rows(&updated.model_id.clone(), vec![updated]).await?;
         return Ok(true);
     }
     Ok(false)
@@ -131,9 +383,12 @@ pub async fn delete_undo_log_service(id: i32, model_id: String) -> Result<bool,
     let db = get_cache_db().await?;
     let result = UndoEntity::delete_many()
         .filter(UndoEntityColumn::Id.eq(id))
-        .filter(UndoEntityColumn::ModelId.eq(model_id))
+        .filter(UndoEntityColumn::ModelId.eq(model_id.clone()))
         .exec(db)
         .await?;
+    if result.rows_affected > 0 && undo_history_config(&model_id).await?.persist {
+        delete_undo_history_by_ids(model_id, vec![id]).await?;
+    }
     Ok(result.rows_affected > 0)
 }
 
@@ -141,9 +396,12 @@ pub async fn delete_undo_log_service(id: i32, model_id: String) -> Result<bool,
 pub async fn delete_undo_log_service_by_model_id(model_id: String) -> Result<bool, DbErr> {
     let db = get_cache_db().await?;
     let result = UndoEntity::delete_many()
-        .filter(UndoEntityColumn::ModelId.eq(model_id))
+        .filter(UndoEntityColumn::ModelId.eq(model_id.clone()))
         .exec(db)
         .await?;
+    if undo_history_config(&model_id).await?.persist {
+        delete_undo_history_by_model_id(model_id).await?;
+    }
     Ok(result.rows_affected > 0)
 }
 
@@ -176,6 +434,118 @@ pub async fn get_latest_redo_log(model_id: String) -> Result<Option<UndoRedoData
     Ok(result_data) // Option<Model>
 }
 
+/// 获取最近一个可撤销步骤的全部记录（按 id 倒序，即撤销时的执行顺序）
+pub async fn get_latest_undo_step(model_id: String) -> Result<Vec<UndoRedoData>, DbErr> {
+    let db = get_cache_db().await?;
+    let step_id: Option<i32> = UndoEntity::find()
+        .select_only()
+        .column_as(UndoEntityColumn::StepId.max(), "step_id")
+        .filter(UndoEntityColumn::ModelId.eq(model_id.clone()))
+        .filter(UndoEntityColumn::Status.is_in([0, 2]))
+        .into_tuple()
+        .one(db)
+        .await?
+        .flatten();
+    let Some(step_id) = step_id else {
+        return Ok(vec![]);
+    };
+    let logs = UndoEntity::find()
+        .filter(UndoEntityColumn::ModelId.eq(model_id))
+        .filter(UndoEntityColumn::StepId.eq(step_id))
+        .order_by_desc(UndoEntityColumn::Id)
+        .all(db)
+        .await?;
+    Ok(logs.into_iter().map(UndoRedoData::from).collect())
+}
+
+/// 获取最近一个可重做步骤的全部记录（按 id 正序，即重做时的执行顺序）
+pub async fn get_latest_redo_step(model_id: String) -> Result<Vec<UndoRedoData>, DbErr> {
+    let db = get_cache_db().await?;
+    // 撤销从新到旧进行，因此已撤销步骤中 id 最小的就是下一个要重做的步骤
+    let step_id: Option<i32> = UndoEntity::find()
+        .select_only()
+        .column_as(UndoEntityColumn::StepId.min(), "step_id")
+        .filter(UndoEntityColumn::ModelId.eq(model_id.clone()))
+        .filter(UndoEntityColumn::Status.eq(1))
+        .into_tuple()
+        .one(db)
+        .await?
+        .flatten();
+    let Some(step_id) = step_id else {
+        return Ok(vec![]);
+    };
+    let logs = UndoEntity::find()
+        .filter(UndoEntityColumn::ModelId.eq(model_id))
+        .filter(UndoEntityColumn::StepId.eq(step_id))
+        .order_by_asc(UndoEntityColumn::Id)
+        .all(db)
+        .await?;
+    Ok(logs.into_iter().map(UndoRedoData::from).collect())
+}
+
+/// 更新整个步骤的状态，返回受影响的记录数
+pub async fn update_step_status_service(
+    step_id: i32,
+    model_id: String,
+    status: i32,
+) -> Result<u64, DbErr> {
+    let db = get_cache_db().await?;
+    let result = UndoEntity::update_many()
+        .col_expr(UndoEntityColumn::Status, Expr::value(status))
+        .filter(UndoEntityColumn::ModelId.eq(model_id.clone()))
+        .filter(UndoEntityColumn::StepId.eq(step_id))
+        .exec(db)
+        .await?;
+    if undo_history_config(&model_id).await?.persist {
+        let rows = UndoEntity::find()
+            .filter(UndoEntityColumn::ModelId.eq(model_id.clone()))
+            .filter(UndoEntityColumn::StepId.eq(step_id))
+            .all(db)
+            .await?;
+        persist_rows(&model_id, rows).await?;
+    }
+    Ok(result.rows_affected)
+}
+
+/// 在业务库中撤销最近一个步骤并将其标记为已撤销，返回执行的记录（无可撤销步骤时为空）
+pub async fn apply_undo_service(model_id: String) -> Result<Vec<UndoRedoData>, DbErr> {
+    let lock = apply_lock(&model_id);
+    let _guard = lock.lock().await;
+    let step = get_latest_undo_step(model_id.clone()).await?;
+    apply_step(model_id, step, UndoDirection::Undo, 1).await
+}
+
+/// 在业务库中重做最近一个已撤销的步骤并将其标记为已重做，返回执行的记录（无可重做步骤时为空）
+pub async fn apply_redo_service(model_id: String) -> Result<Vec<UndoRedoData>, DbErr> {
+    let lock = apply_lock(&model_id);
+    let _guard = lock.lock().await;
+    let step = get_latest_redo_step(model_id.clone()).await?;
+    apply_step(model_id, step, UndoDirection::Redo, 2).await
+}
+
+async fn apply_step(
+    model_id: String,
+    step: Vec<UndoRedoData>,
+    direction: UndoDirection,
+    status: i32,
+) -> Result<Vec<UndoRedoData>, DbErr> {
+    let Some(step_id) = step.first().map(|s| s.step_id) else {
+        return Ok(step);
+    };
+    let changes = step
+        .iter()
+        .map(|s| UndoChange {
+            table_name: s.table_name.clone(),
+            op_type: s.op_type.clone(),
+            old_data: s.old_data.clone(),
+            new_data: s.new_data.clone(),
+        })
+        .collect();
+    apply_undo_changes(changes, direction).await?;
+    update_step_status_service(step_id, model_id, status).await?;
+    Ok(step)
+}
+
 pub async fn bench_insert() -> Result<(), DbErr> {
     let db = get_cache_db().await?;
 
@@ -195,31 +565,237 @@ pub async fn bench_insert() -> Result<(), DbErr> {
     Ok(())
 }
 
-/// 内部函数：限制某个模型的撤销步数
+/// 内部函数：限制某个模型的撤销步数（按步骤计数，max_steps 为 0 时不限制），返回被删除的记录 id
 async fn prune_undo_logs(
     db: &sea_orm::DatabaseConnection,
     model_id: &str,
     max_steps: usize,
-) -> Result<(), DbErr> {
-    // 找到该模型下所有 status=0 的记录，按时间倒序排列
-    let logs = UndoEntity::find()
+) -> Result<Vec<i32>, DbErr> {
+    if max_steps == 0 {
+        return Ok(vec![]);
+    }
+    // 找到该模型下所有未撤销的步骤，按步骤倒序排列
+    let steps: Vec<i32> = UndoEntity::find()
+        .select_only()
+        .column(UndoEntityColumn::StepId)
+        .distinct()
         .filter(UndoEntityColumn::ModelId.eq(model_id))
-        .filter(UndoEntityColumn::Status.eq(0))
-        .order_by_desc(UndoEntityColumn::OperatorAt)
+        .filter(UndoEntityColumn::Status.is_in([0, 2]))
+        .order_by_desc(UndoEntityColumn::StepId)
+        .into_tuple()
         .all(db)
         .await?;
 
-    // 如果记录数超过限制，删除较旧的记录
-    if logs.len() > max_steps {
-        let ids_to_delete: Vec<i32> = logs[max_steps..] // 截取第 11 条之后的所有记录
-            .iter()
-            .map(|m| m.id)
-            .collect();
+    // 如果步数超过限制，删除较旧步骤的全部记录
+    if steps.len() <= max_steps {
+        return Ok(vec![]);
+    }
+    let ids_to_delete: Vec<i32> = UndoEntity::find()
+        .select_only()
+        .column(UndoEntityColumn::Id)
+        .filter(UndoEntityColumn::ModelId.eq(model_id))
+        .filter(UndoEntityColumn::StepId.is_in(steps[max_steps..].to_vec()))
+        .into_tuple()
+        .all(db)
+        .await?;
+    UndoEntity::delete_many()
+        .filter(UndoEntityColumn::Id.is_in(ids_to_delete.clone()))
+        .exec(db)
+        .await?;
+    Ok(ids_to_delete)
+}
 
-        UndoEntity::delete_many()
-            .filter(UndoEntityColumn::Id.is_in(ids_to_delete))
+/// 淘汰步骤 id 不超过 last_step_id 的撤销记录（缓存容量治理使用），返回删除的记录数。
+/// 未结束操作组的步骤不淘汰；只释放缓存库，业务库中持久化的历史保持不变
+pub(crate) async fn evict_undo_steps(last_step_id: i32) -> Result<u64, DbErr> {
+    let db = get_cache_db().await?;
+    let groups = UNDO_GROUPS.lock().await;
+    let open_steps: Vec<i32> = groups.values().filter_map(|g| g.step_id).collect();
+    let ids: Vec<i32> = UndoEntity::find()
+        .select_only()
+        .column(UndoEntityColumn::Id)
+        .filter(UndoEntityColumn::StepId.lte(last_step_id))
+        .filter(UndoEntityColumn::StepId.is_not_in(open_steps))
+        .into_tuple()
+        .all(db)
+        .await?;
+    let mut evicted = 0;
+    for chunk in ids.chunks(500) {
+        evicted += UndoEntity::delete_many()
+            .filter(UndoEntityColumn::Id.is_in(chunk.to_vec()))
             .exec(db)
-            .await?;
+            .await?
+            .rows_affected;
+    }
+    Ok(evicted)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::service_database::database_business::db_business_connection::initialize_business_db;
+
+    // 撤销配置与持久化的历史都在业务库中，测试进程共用一个临时库文件
+    async fn init_business_db() {
+        let path = std::env::temp_dir().join(format!("undo_test_{}.db", std::process::id()));
+        initialize_business_db(path.to_string_lossy().into_owned())
+            .await
+            .unwrap();
+    }
+
+    // 模拟软件重启：清空内存中的配置与缓存库中的记录
+    async fn restart(model_id: &str) {
+        UNDO_CONFIGS.lock().await.remove(model_id);
+        UndoEntity::delete_many()
+            .filter(UndoEntityColumn::ModelId.eq(model_id))
+            .exec(get_cache_db().await.unwrap())
+            .await
+            .unwrap();
+    }
+
+    async fn persisted(model_id: &str) -> Vec<String> {
+        list_undo_history(model_id.to_string())
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|r| r.new_data)
+            .collect()
+    }
+
+    async fn add(model_id: &str, n: i32) -> i32 {
+        init_business_db().await;
+        add_undo_log_service(
+            "UPDATE".to_string(),
+            "t".to_string(),
+            String::new(),
+            n.to_string(),
+            model_id.to_string(),
+        )
+        .await
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_grouped_steps_and_redo_truncation() {
+        let model = "undo_test_model".to_string();
+        let other = "undo_test_other".to_string();
+        init_business_db().await;
+        set_undo_history_config_service(
+            model.clone(),
+            UndoHistoryConfig {
+                max_steps: 2,
+                persist: false,
+            },
+        )
+        .await
+        .unwrap();
+
+        add(&model, 1).await;
+        begin_undo_group_service(model.clone()).await.unwrap();
+        let first = add(&model, 2).await;
+        add(&model, 3).await;
+        assert_eq!(
+            end_undo_group_service(model.clone()).await.unwrap(),
+            Some(first)
+        );
+
+        // 组内两条记录作为一个步骤撤销
+        let step = get_latest_undo_step(model.clone()).await.unwrap();
+        assert_eq!(step.len(), 2);
+        assert!(step.iter().all(|s| s.step_id == first));
+        update_step_status_service(first, model.clone(), 1)
+            .await
+            .unwrap();
+        assert_eq!(get_latest_redo_step(model.clone()).await.unwrap().len(), 2);
+
+        // 其他模型的新操作不能截断本模型的重做分支
+        add(&other, 1).await;
+        assert_eq!(get_latest_redo_step(model.clone()).await.unwrap().len(), 2);
+
+        // 本模型的新操作截断重做分支，并按步数上限裁剪
+        add(&model, 4).await;
+        add(&model, 5).await;
+        assert!(
+            get_latest_redo_step(model.clone())
+                .await
+                .unwrap()
+                .is_empty()
+        );
+        assert_eq!(
+            list_undo_logs_service(model.clone()).await.unwrap().len(),
+            2
+        );
+    }
+
+    #[tokio::test]
+    async fn test_redone_step_survives_new_action() {
+        let model = "undo_test_redo_model".to_string();
+        add(&model, 1).await;
+        let redone = add(&model, 2).await;
+
+        // 撤销后重做，步骤状态变为已重做
+        update_step_status_service(redone, model.clone(), 1)
+            .await
+            .unwrap();
+        update_step_status_service(redone, model.clone(), 2)
+            .await
+            .unwrap();
+
+        // 新操作只截断已撤销的记录，已重做的步骤仍可撤销
+        let latest = add(&model, 3).await;
+        assert_eq!(
+            list_undo_logs_service(model.clone()).await.unwrap().len(),
+            3
+        );
+        let step = get_latest_undo_step(model.clone()).await.unwrap();
+        assert_eq!(step[0].step_id, latest);
+        update_step_status_service(latest, model.clone(), 1)
+            .await
+            .unwrap();
+        let step = get_latest_undo_step(model.clone()).await.unwrap();
+        assert_eq!(step[0].step_id, redone);
+    }
+
+    #[tokio::test]
+    async fn test_persisted_history_survives_restart() {
+        let model = "undo_test_persist_model".to_string();
+        init_business_db().await;
+        set_undo_history_config_service(
+            model.clone(),
+            UndoHistoryConfig {
+                max_steps: 3,
+                persist: true,
+            },
+        )
+        .await
+        .unwrap();
+        for n in 1..=4 {
+            add(&model, n).await;
+        }
+        assert_eq!(persisted(&model).await, vec!["2", "3", "4"]);
+
+        // 重启后前端尚未下发配置就新增记录：先恢复上次的历史，再追加
+        restart(&model).await;
+        add(&model, 5).await;
+        assert_eq!(persisted(&model).await, vec!["3", "4", "5"]);
+        let cached = list_undo_logs_service(model.clone()).await.unwrap();
+        assert_eq!(cached.len(), 3);
+        assert_eq!(cached[0].new_data, "5");
+
+        // 恢复的历史按保存的步数上限裁剪
+        save_undo_history_config(&model, 2, true).await.unwrap();
+        restart(&model).await;
+        assert_eq!(
+            get_undo_history_config_service(model.clone())
+                .await
+                .unwrap()
+                .max_steps,
+            2
+        );
+        assert_eq!(persisted(&model).await, vec!["4", "5"]);
+        assert_eq!(
+            list_undo_logs_service(model.clone()).await.unwrap().len(),
+            2
+        );
     }
-    Ok(())
 }
@@ -1,10 +1,19 @@
-use chrono::Utc;
-use sea_orm::{ActiveValue::Set, DbErr, InsertResult, entity::*, query::*};
+use sea_orm::{DbErr, entity::*, query::*};
 use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
 
 // 假设 Entity 定义位于此路径下
+use crate::service_database::database_cache::entity::model_trend_record_entity_cache::{
+    Column as RecordColumn, Entity as RecordEntity,
+};
 use crate::service_database::database_cache::entity::model_variable_curve_entity_cache::{
-    ActiveModel, Column, Entity, Model,
+    Column, Entity, Model,
+};
+use crate::service_database::database_cache::service::trend_service::{
+    TrendSeries, append_trend_samples, query_trend_series,
+};
+use crate::service_database::database_cache::service::trend_subscription_service::{
+    has_trend_subscribers, publish_trend_tick, trend_tick,
 };
 // 导入公共数据库连接函数
 use crate::service_database::database_cache::db_cache_connection::get_cache_db;
@@ -46,41 +55,32 @@ impl From<Model> for CurveModel {
 // Service Functions
 // =========================================================================
 
-/// 插入变量曲线信息
+/// 插入变量曲线信息，写入趋势时序存储并推送给该配置的订阅者，返回 (写入记录 ID, 入库的点数)
 /// 对应 TypeScript: insertModelVariableCurveEntityCache
 pub async fn insert_model_variable_curve_entity_cache(
     datasets: Vec<DatasetItem>,
     model_id: String,
     config_id: String,
     sim_time: f64,
-) -> Result<InsertResult<ActiveModel>, DbErr> {
-    let db = get_cache_db().await?;
-    // 1. 获取当前时间戳 (毫秒)
-    let current_time = Utc::now().timestamp_millis().to_string();
-
-    // 2. 序列化 datasets 为 JSON 字符串 (对应 checkAnyTypeIsObjectToString)
-    let datasets_str = serde_json::to_string(&datasets)
-        .map_err(|e| DbErr::Custom(format!("Datasets serialization error: {}", e)))?;
-
-    // 3. 创建 ActiveModel
-    let new_entry = ActiveModel {
-        // ID 是自增的，不需要设置
-        datasets: Set(datasets_str),
-        model_id: Set(model_id),
-        config_id: Set(config_id),
-        // 数据库字段 sim_time 和 ceate_at 是 String 类型，需要转换
-        sim_time: Set(sim_time.to_string()),
-        create_at: Set(current_time),
-        ..Default::default()
-    };
-
-    // 4. 执行插入
-    Entity::insert(new_entry).exec(db).await
+) -> Result<(i32, u32), DbErr> {
+    let tick = has_trend_subscribers(&config_id)
+        .then(|| trend_tick(&datasets, &model_id, &config_id, sim_time));
+    let result = append_trend_samples(datasets, model_id, config_id, sim_time).await?;
+    if let Some(tick) = tick {
+        publish_trend_tick(tick);
+    }
+    Ok(result)
 }
 
 // ---
 
-/// 根据模型 ID 和配置 ID 查询所有曲线数据，并按创建时间排序
+/// 根据模型 ID 和配置 ID 查询所有曲线数据：旧版按行存储的数据在前，趋势存储的数据按写入记录还原在后。
+/// 还原的行沿用写入记录的 ID、仿真时间与写入时间，datasets 中：
+/// - 数值按 f64 重新格式化（"1.50" 还原为 "1.5"），布尔量还原为 "1" / "0"
+/// - 变量按首次出现的顺序排列，变量名取最新的名称
+///
+/// 没有写入记录的数据（如上次运行留在磁盘归档中的数据）按仿真时间合并成行，ID 接在最大 ID 之后，create_at 为空。
+/// 数据量大时应改用 get_model_variable_curve_entity_cache_by_filter_count 或 query_trend_series 按时间窗口查询并降采样
 /// 对应 TypeScript: getModelVariableCurveEntityCacheByFilterTime
 pub async fn get_model_variable_curve_entity_cache_by_filter_time(
     model_id: String,
@@ -89,60 +89,304 @@ pub async fn get_model_variable_curve_entity_cache_by_filter_time(
     let db = get_cache_db().await?;
     // 组合查询条件
     let filter = Condition::all()
-        .add(Column::ModelId.eq(model_id))
-        .add(Column::ConfigId.eq(config_id));
+        .add(Column::ModelId.eq(model_id.clone()))
+        .add(Column::ConfigId.eq(config_id.clone()));
 
+    // 旧版按行存储的数据
     let result = Entity::find()
         .filter(filter)
         // 对应 TypeScript 中的 orderBy("ceateAt", "ASC")
         .order_by_asc(Column::CreateAt)
         .all(db)
         .await?;
+    let mut result_data: Vec<CurveModel> = result.into_iter().map(CurveModel::from).collect();
 
-    let result_data: Vec<CurveModel> = result.into_iter().map(CurveModel::from).collect();
+    // 趋势存储中的点按 (仿真时间, 变量) 排队，同一时刻多次写入（仿真回退）时按写入顺序排列
+    let series = query_trend_series(
+        model_id.clone(),
+        config_id.clone(),
+        vec![],
+        None,
+        None,
+        None,
+        None,
+    )
+    .await?;
+    let mut pending: HashMap<(u64, usize), VecDeque<f64>> = HashMap::new();
+    for (index, s) in series.iter().enumerate() {
+        for (t, v) in s.times.iter().zip(&s.values) {
+            pending
+                .entry((t.to_bits(), index))
+                .or_default()
+                .push_back(*v);
+        }
+    }
+
+    // 每条写入记录取走该时刻各变量最早的一个点
+    let records = RecordEntity::find()
+        .filter(RecordColumn::ModelId.eq(model_id.clone()))
+        .filter(RecordColumn::ConfigId.eq(config_id.clone()))
+        .order_by_asc(RecordColumn::Id)
+        .all(db)
+        .await?;
+    let mut recorded = Vec::with_capacity(records.len());
+    for record in records {
+        let items: Vec<(usize, f64)> = (0..series.len())
+            .filter_map(|index| {
+                pending
+                    .get_mut(&(record.sim_time.to_bits(), index))
+                    .and_then(|queue| queue.pop_front())
+                    .map(|value| (index, value))
+            })
+            .collect();
+        // 数据已被淘汰或清空的记录不再返回
+        if items.is_empty() {
+            continue;
+        }
+        recorded.push(CurveModel {
+            id: record.id,
+            sim_time: record.sim_time.to_string(),
+            datasets: series_datasets(&series, items)?,
+            create_at: record.create_at.to_string(),
+            model_id: model_id.clone(),
+            config_id: config_id.clone(),
+        });
+    }
+
+    // 没有写入记录的点按仿真时间合并成行，同一时刻有多个点的变量依次分到后面的行
+    let mut leftover: Vec<(f64, usize, VecDeque<f64>)> = pending
+        .into_iter()
+        .filter(|(_, queue)| !queue.is_empty())
+        .map(|((t, index), queue)| (f64::from_bits(t), index, queue))
+        .collect();
+    leftover.sort_by(|a, b| a.0.total_cmp(&b.0).then(a.1.cmp(&b.1)));
+    let mut id = result_data
+        .iter()
+        .chain(&recorded)
+        .map(|c| c.id)
+        .max()
+        .unwrap_or(0)
+        + 1;
+    for group in leftover.chunk_by_mut(|a, b| a.0 == b.0) {
+        let sim_time = group[0].0;
+        loop {
+            let items: Vec<(usize, f64)> = group
+                .iter_mut()
+                .filter_map(|(_, index, queue)| queue.pop_front().map(|v| (*index, v)))
+                .collect();
+            if items.is_empty() {
+                break;
+            }
+            result_data.push(CurveModel {
+                id,
+                sim_time: sim_time.to_string(),
+                datasets: series_datasets(&series, items)?,
+                create_at: String::new(),
+                model_id: model_id.clone(),
+                config_id: config_id.clone(),
+            });
+            id += 1;
+        }
+    }
+    result_data.extend(recorded);
     Ok(result_data)
 }
 
 // ---
 
-/// 根据模型 ID、配置 ID 和时间间隔进行过滤查询 (包含内存逻辑过滤)
+/// 根据模型 ID、配置 ID 和时间间隔进行过滤查询。
+/// 趋势存储只读取 [start_time, end_time] 窗口内的数据块，max_points 大于 0 时每个变量先在服务端 LTTB 降采样；
+/// 每个变量只保留与上一个保留点的仿真时间间隔不小于 filter_count 的点，再按仿真时间合并成行。
+/// 合并出的行没有对应的写入记录，ID 接在旧版数据之后，create_at 为空；旧版按行存储的数据按同样的窗口与间隔过滤后排在前面
 /// 对应 TypeScript: getModelVariableCurveEntityCacheByFilterCount
 pub async fn get_model_variable_curve_entity_cache_by_filter_count(
     model_id: String,
     config_id: String,
     filter_count: i32, // 时间间隔阈值
+    start_time: Option<f64>,
+    end_time: Option<f64>,
+    max_points: Option<u32>,
 ) -> Result<Vec<CurveModel>, DbErr> {
-    // 1. 获取所有匹配的数据 (复用上面的查询逻辑)
-    let results = get_model_variable_curve_entity_cache_by_filter_time(model_id, config_id).await?;
+    let db = get_cache_db().await?;
+    let gap = filter_count as f64;
+    let in_window = |t: f64| start_time.is_none_or(|s| t >= s) && end_time.is_none_or(|e| t <= e);
 
-    if results.is_empty() {
-        return Ok(vec![]);
+    // 旧版按行存储的数据
+    let legacy = Entity::find()
+        .filter(Column::ModelId.eq(model_id.clone()))
+        .filter(Column::ConfigId.eq(config_id.clone()))
+        .order_by_asc(Column::CreateAt)
+        .all(db)
+        .await?;
+    let mut result: Vec<CurveModel> = Vec::new();
+    let mut last: Option<f64> = None;
+    for row in legacy {
+        // 使用 unwrap_or(0.0) 避免解析失败导致程序崩溃
+        let sim_time: f64 = row.sim_time.parse().unwrap_or(0.0);
+        if in_window(sim_time) && last.is_none_or(|l| sim_time - l >= gap) {
+            last = Some(sim_time);
+            result.push(CurveModel::from(row));
+        }
     }
 
-    // 2. 内存过滤逻辑
-    let mut filtered: Vec<CurveModel> = Vec::new();
-
-    // 保留第一个数据
-    filtered.push(results[0].clone());
+    // 趋势存储：按窗口读取并降采样后，逐变量按时间间隔抽取
+    let series = query_trend_series(
+        model_id.clone(),
+        config_id.clone(),
+        vec![],
+        start_time,
+        end_time,
+        max_points,
+        None,
+    )
+    .await?;
+    let mut points: Vec<(f64, usize, f64)> = Vec::new();
+    for (index, s) in series.iter().enumerate() {
+        let mut last: Option<f64> = None;
+        for (t, v) in s.times.iter().zip(&s.values) {
+            if last.is_none_or(|l| t - l >= gap) {
+                last = Some(*t);
+                points.push((*t, index, *v));
+            }
+        }
+    }
+    // 排序稳定，同一时刻同一变量的多个点（仿真回退）保持写入顺序，依次分到后面的行
+    points.sort_by(|a, b| a.0.total_cmp(&b.0).then(a.1.cmp(&b.1)));
 
-    // 遍历后续数据
-    for current in results.iter().skip(1) {
-        // 获取上一个保留的数据
-        let prev_item = filtered.last().unwrap();
+    let mut id = result.iter().map(|c| c.id).max().unwrap_or(0) + 1;
+    for group in points.chunk_by(|a, b| a.0 == b.0) {
+        let mut rows: Vec<Vec<(usize, f64)>> = Vec::new();
+        let mut occurrence: HashMap<usize, usize> = HashMap::new();
+        for (_, index, value) in group {
+            let n = occurrence.entry(*index).or_default();
+            if rows.len() <= *n {
+                rows.push(Vec::new());
+            }
+            rows[*n].push((*index, *value));
+            *n += 1;
+        }
+        for items in rows {
+            result.push(CurveModel {
+                id,
+                sim_time: group[0].0.to_string(),
+                datasets: series_datasets(&series, items)?,
+                create_at: String::new(),
+                model_id: model_id.clone(),
+                config_id: config_id.clone(),
+            });
+            id += 1;
+        }
+    }
+    Ok(result)
+}
 
-        // 解析 sim_time (数据库存的是 String，业务逻辑需要 f64)
-        // 使用 unwrap_or(0.0) 避免解析失败导致程序崩溃
-        let prev_sim: i32 = prev_item.sim_time.parse().unwrap_or(0);
-        let current_sim: i32 = current.sim_time.parse().unwrap_or(0);
+/// 把 (变量在 series 中的下标, 值) 序列化为 datasets 字段
+fn series_datasets(series: &[TrendSeries], items: Vec<(usize, f64)>) -> Result<String, DbErr> {
+    let datasets: Vec<DatasetItem> = items
+        .into_iter()
+        .map(|(index, value)| DatasetItem {
+            id: series[index].variable_id.clone(),
+            name: series[index].name.clone(),
+            value: value.to_string(),
+        })
+        .collect();
+    serde_json::to_string(&datasets)
+        .map_err(|e| DbErr::Custom(format!("Datasets serialization error: {}", e)))
+}
 
-        // 计算时间差
-        let time_gap = current_sim - prev_sim;
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-        // 如果时间差大于等于阈值，则保留当前数据
-        if time_gap >= filter_count {
-            filtered.push(current.clone());
+    fn item(id: &str, value: &str) -> DatasetItem {
+        DatasetItem {
+            id: id.to_string(),
+            name: id.to_uppercase(),
+            value: value.to_string(),
         }
     }
 
-    Ok(filtered)
+    fn values(row: &CurveModel) -> Vec<(String, String)> {
+        serde_json::from_str::<Vec<DatasetItem>>(&row.datasets)
+            .unwrap()
+            .into_iter()
+            .map(|d| (d.id, d.value))
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_curve_rows_round_trip() {
+        let (model_id, config_id) = ("curve_round_trip_model", "curve_round_trip_config");
+        let insert = |datasets, sim_time| {
+            insert_model_variable_curve_entity_cache(
+                datasets,
+                model_id.to_string(),
+                config_id.to_string(),
+                sim_time,
+            )
+        };
+        let (first, count) = insert(vec![item("a", "1.50"), item("b", "true")], 1.0)
+            .await
+            .unwrap();
+        assert_eq!(count, 2);
+        // 仿真回退后同一仿真时间再次写入
+        let (second, _) = insert(vec![item("a", "2")], 1.0).await.unwrap();
+        // 非数值的采样跳过，其余照常入库
+        let (third, count) = insert(vec![item("a", "3"), item("b", "open")], 2.0)
+            .await
+            .unwrap();
+        assert_eq!(count, 1);
+        insert(vec![item("a", "4"), item("b", "0")], 2.5)
+            .await
+            .unwrap();
+
+        let rows = get_model_variable_curve_entity_cache_by_filter_time(
+            model_id.to_string(),
+            config_id.to_string(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            rows.iter().map(|r| r.id).take(3).collect::<Vec<_>>(),
+            [first, second, third]
+        );
+        assert!(rows.iter().all(|r| !r.create_at.is_empty()));
+        // 数值按 f64 重新格式化，布尔量还原为 1 / 0
+        let pair = |id: &str, value: &str| (id.to_string(), value.to_string());
+        assert_eq!(values(&rows[0]), [pair("a", "1.5"), pair("b", "1")]);
+        assert_eq!(values(&rows[1]), [pair("a", "2")]);
+        assert_eq!(values(&rows[2]), [pair("a", "3")]);
+
+        // 按时间间隔逐变量抽取：a 保留 1 与 2，b 保留 1 与 2.5
+        let rows = get_model_variable_curve_entity_cache_by_filter_count(
+            model_id.to_string(),
+            config_id.to_string(),
+            1,
+            None,
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+        let times: Vec<&str> = rows.iter().map(|r| r.sim_time.as_str()).collect();
+        assert_eq!(times, ["1", "2", "2.5"]);
+        assert_eq!(values(&rows[0]), [pair("a", "1.5"), pair("b", "1")]);
+        assert_eq!(values(&rows[1]), [pair("a", "3")]);
+        assert_eq!(values(&rows[2]), [pair("b", "0")]);
+
+        // 只读取窗口内的数据
+
+        let rows = get_model_variable_curve_entity_cache_by_filter_count(
+            model_id.to_string(),
+            config_id.to_string(),
+            0,
+            Some(2.0),
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+        let times: Vec<&str> = rows.iter().map(|r| r.sim_time.as_str()).collect();
+        assert_eq!(times, ["2", "2.5"]);
+    }
 }
@@ -0,0 +1,29 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// 配置数据库单位换算系数（同一单位类别内 SI 值 = 数值 × factor + offset）
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "conf_unit_conversion_entity")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+
+    /// 单位类别编码，对应 conf_unit_item_category_entity.code
+    #[sea_orm(column_name = "category_code")]
+    pub category_code: String,
+
+    #[sea_orm(column_name = "unit")]
+    pub unit: String,
+
+    #[sea_orm(column_name = "factor")]
+    pub factor: f64,
+
+    #[sea_orm(column_name = "offset")]
+    pub offset: f64,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+/// 为实体模型实现 ActiveModelBehavior
+impl ActiveModelBehavior for ActiveModel {}
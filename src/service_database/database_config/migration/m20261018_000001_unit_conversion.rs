@@ -0,0 +1,82 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // 单位换算系数：SI 值 = 数值 × factor + offset
+        manager
+            .create_table(
+                Table::create()
+                    .table(ConfUnitConversionEntity::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(ConfUnitConversionEntity::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(ConfUnitConversionEntity::CategoryCode)
+                            .string_len(64)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ConfUnitConversionEntity::Unit)
+                            .string_len(64)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ConfUnitConversionEntity::Factor)
+                            .double()
+                            .not_null()
+                            .default(1.0),
+                    )
+                    .col(
+                        ColumnDef::new(ConfUnitConversionEntity::Offset)
+                            .double()
+                            .not_null()
+                            .default(0.0),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-unit_conversion-category_unit")
+                    .table(ConfUnitConversionEntity::Table)
+                    .col(ConfUnitConversionEntity::CategoryCode)
+                    .col(ConfUnitConversionEntity::Unit)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(
+                Table::drop()
+                    .table(ConfUnitConversionEntity::Table)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum ConfUnitConversionEntity {
+    Table,
+    Id,
+    CategoryCode,
+    Unit,
+    Factor,
+    Offset,
+}
@@ -0,0 +1,202 @@
+use crate::calc_handle::unit_conversion::{UnitFactor, UnitRegistry};
+use crate::service_database::database_config::db_config_connection::get_config_db;
+use crate::service_database::database_config::entity::conf_unit_conversion_entity::{
+    ActiveModel as ConfUnitConversionActiveModel, Column as ConfUnitConversionColumn,
+    Entity as ConfUnitConversionEntity, Model as ConfUnitConversionModel,
+};
+use napi_derive::napi;
+use once_cell::sync::Lazy;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DatabaseConnection, DbErr, EntityTrait, IntoActiveModel, NotSet,
+    QueryFilter, QueryOrder, Set, TransactionTrait,
+};
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, RwLock};
+
+// ======================================
+// 单位换算系数配置与换算服务
+// 注册表 = 内置单位 + 配置库中的换算系数（同类别同单位时以配置库为准），写入后失效重建
+// ======================================
+
+#[napi(object, namespace = "confUnit", js_name = "ConfUnitConversionDTO")]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConfUnitConversionDTO {
+    pub category_code: String,
+    pub unit: String,
+    pub factor: f64,
+    pub offset: f64,
+}
+
+impl From<ConfUnitConversionModel> for ConfUnitConversionDTO {
+    fn from(model: ConfUnitConversionModel) -> Self {
+        ConfUnitConversionDTO {
+            category_code: model.category_code,
+            unit: model.unit,
+            factor: model.factor,
+            offset: model.offset,
+        }
+    }
+}
+
+static UNIT_REGISTRY: Lazy<RwLock<Option<Arc<UnitRegistry>>>> = Lazy::new(|| RwLock::new(None));
+
+fn invalidate_unit_registry() {
+    *UNIT_REGISTRY.write().unwrap() = None;
+}
+
+fn unit_err(msg: String) -> DbErr {
+    DbErr::Custom(format!("单位换算错误: {}", msg))
+}
+
+/// 获取单位注册表（首次使用或配置变更后从配置库重建）
+pub async fn get_unit_registry() -> Result<Arc<UnitRegistry>, DbErr> {
+    if let Some(registry) = UNIT_REGISTRY.read().unwrap().as_ref() {
+        return Ok(registry.clone());
+    }
+    let mut registry = UnitRegistry::builtin();
+    for row in select_conf_unit_conversion_all().await? {
+        registry.insert(
+            &row.category_code,
+            &row.unit,
+            UnitFactor {
+                factor: row.factor,
+                offset: row.offset,
+            },
+        );
+    }
+    let registry = Arc::new(registry);
+    *UNIT_REGISTRY.write().unwrap() = Some(registry.clone());
+    Ok(registry)
+}
+
+pub async fn select_conf_unit_conversion_all() -> Result<Vec<ConfUnitConversionDTO>, DbErr> {
+    let db = get_config_db().await?;
+
+    let models = ConfUnitConversionEntity::find()
+        .order_by_asc(ConfUnitConversionColumn::CategoryCode)
+        .order_by_asc(ConfUnitConversionColumn::Id)
+        .all(db)
+        .await?;
+
+    Ok(models
+        .into_iter()
+        .map(ConfUnitConversionDTO::from)
+        .collect())
+}
+
+pub async fn select_conf_unit_conversion_by_category(
+    category_code: String,
+) -> Result<Vec<ConfUnitConversionDTO>, DbErr> {
+    let db = get_config_db().await?;
+
+    let models = ConfUnitConversionEntity::find()
+        .filter(ConfUnitConversionColumn::CategoryCode.eq(category_code))
+        .order_by_asc(ConfUnitConversionColumn::Id)
+        .all(db)
+        .await?;
+
+    Ok(models
+        .into_iter()
+        .map(ConfUnitConversionDTO::from)
+        .collect())
+}
+
+pub async fn upsert_and_insert_conf_unit_conversion(
+    data_list: Vec<ConfUnitConversionDTO>,
+) -> Result<i32, DbErr> {
+    // 先校验全部记录，有一条无效时不写入任何数据
+    for config in &data_list {
+        if config.factor == 0.0 || !config.factor.is_finite() || !config.offset.is_finite() {
+            return Err(unit_err(format!(
+                "单位 {} 的换算系数无效: factor={}, offset={}",
+                config.unit, config.factor, config.offset
+            )));
+        }
+    }
+    let db = get_config_db().await?;
+    let result = upsert_conf_unit_conversions(db, data_list).await;
+    // 事务无论成功与否都刷新换算表缓存，缓存不会与数据库不一致
+    invalidate_unit_registry();
+    result
+}
+
+async fn upsert_conf_unit_conversions(
+    db: &DatabaseConnection,
+    data_list: Vec<ConfUnitConversionDTO>,
+) -> Result<i32, DbErr> {
+    let txn = db.begin().await?;
+    let existing_models = ConfUnitConversionEntity::find().all(&txn).await?;
+    let existing_map: std::collections::HashMap<_, _> = existing_models
+        .into_iter()
+        .map(|m| ((m.category_code.clone(), m.unit.clone()), m))
+        .collect();
+    let mut success_count: i32 = 0;
+    // 根据 (category_code, unit)，本身数据库有数据的进行更新，没有的进行插入
+    for config in data_list {
+        if let Some(model) = existing_map.get(&(config.category_code.clone(), config.unit.clone()))
+        {
+            let mut active_model: ConfUnitConversionActiveModel = model.clone().into_active_model();
+            active_model.factor = Set(config.factor);
+            active_model.offset = Set(config.offset);
+            active_model.update(&txn).await?;
+        } else {
+            let active_model = ConfUnitConversionActiveModel {
+                id: NotSet,
+                category_code: Set(config.category_code),
+                unit: Set(config.unit),
+                factor: Set(config.factor),
+                offset: Set(config.offset),
+            };
+            active_model.insert(&txn).await?;
+        }
+
+        success_count += 1;
+    }
+    txn.commit().await?;
+    Ok(success_count)
+}
+
+pub async fn delete_conf_unit_conversion(
+    category_code: String,
+    unit: String,
+) -> Result<u64, DbErr> {
+    let db = get_config_db().await?;
+    let res = ConfUnitConversionEntity::delete_many()
+        .filter(ConfUnitConversionColumn::CategoryCode.eq(category_code))
+        .filter(ConfUnitConversionColumn::Unit.eq(unit))
+        .exec(db)
+        .await?;
+    invalidate_unit_registry();
+    Ok(res.rows_affected)
+}
+
+/// 单位换算；未指定类别时按两个单位共同所属的类别换算，量纲不一致时报错
+pub async fn convert_unit(
+    value: f64,
+    from_unit: String,
+    to_unit: String,
+    category_code: Option<String>,
+) -> Result<f64, DbErr> {
+    let registry = get_unit_registry().await?;
+    registry
+        .convert(value, &from_unit, &to_unit, category_code.as_deref())
+        .map_err(unit_err)
+}
+
+/// 批量换算同一对单位
+pub async fn convert_unit_batch(
+    values: Vec<f64>,
+    from_unit: String,
+    to_unit: String,
+    category_code: Option<String>,
+) -> Result<Vec<f64>, DbErr> {
+    let registry = get_unit_registry().await?;
+    values
+        .into_iter()
+        .map(|v| {
+            registry
+                .convert(v, &from_unit, &to_unit, category_code.as_deref())
+                .map_err(unit_err)
+        })
+        .collect()
+}
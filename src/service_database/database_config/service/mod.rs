@@ -4,4 +4,5 @@ pub mod conf_graphic_custom_service;
 pub mod conf_model_service;
 pub mod conf_pf_model_params_service;
 pub mod conf_system_variable_service;
+pub mod conf_unit_conversion_service;
 pub mod conf_unit_service;
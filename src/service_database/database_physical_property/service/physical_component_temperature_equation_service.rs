@@ -1,8 +1,13 @@
+use crate::calc_handle::property_equation::{EquationCoefficients, evaluate_equation};
+use crate::calc_handle::unit_conversion::UnitRegistry;
+use crate::service_database::database_config::service::conf_unit_conversion_service::get_unit_registry;
 use crate::service_database::database_physical_property::db_physical_property_connection::get_physical_property_db;
 use crate::service_database::database_physical_property::entity::physical_component_temperature_equation_entity::{
     ActiveModel as TemperatureEquationActiveModel, Column as TemperatureEquationColumn,
     Entity as TemperatureEquationEntity, Model as TemperatureEquationModel,
 };
+use crate::service_database::database_physical_property::service::physical_component_information_service::get_physical_information_list_by_cas_no_list;
+use crate::service_database::database_physical_property::service::physical_enum::ExtrapolationPolicy;
 use napi_derive::napi;
 use sea_orm::Statement;
 use sea_orm::{
@@ -229,3 +234,239 @@ pub async fn init_physical_temperature_equation_data_fast(
     txn.commit().await?;
     Ok(())
 }
+
+// ======================================
+// 温度关联方程求值：按组分 ID 或 CAS 号、性质编码与温度计算性质曲线
+// ======================================
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[napi(
+    object,
+    namespace = "physicalComponent",
+    js_name = "TemperatureEquationEvalQuery"
+)]
+pub struct TemperatureEquationEvalQuery {
+    /// 组分 ID，与 cas_no 二选一
+    #[napi(js_name = "componentId")]
+    pub component_id: Option<i32>,
+    #[napi(js_name = "casNo")]
+    pub cas_no: Option<String>,
+    /// 性质编码（如饱和蒸气压、液体热容）
+    pub code: String,
+    pub temperatures: Vec<f64>,
+    /// 输入温度单位，默认 K
+    #[napi(js_name = "temperatureUnit")]
+    pub temperature_unit: Option<String>,
+    /// 结果单位，默认为方程自身单位
+    pub unit: Option<String>,
+    /// 超出适用温度范围时的处理方式，默认外推
+    pub policy: Option<ExtrapolationPolicy>,
+    /// 指定方程记录，默认取该性质的默认方程
+    #[napi(js_name = "equationId")]
+    pub equation_id: Option<i32>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[napi(
+    object,
+    namespace = "physicalComponent",
+    js_name = "TemperatureEquationPoint"
+)]
+pub struct TemperatureEquationPoint {
+    /// 输入温度（输入单位）
+    pub temperature: f64,
+    /// 计算结果，被拒绝或无法计算时为空
+    pub value: Option<f64>,
+    /// 是否超出适用温度范围
+    #[napi(js_name = "outOfRange")]
+    pub out_of_range: bool,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[napi(
+    object,
+    namespace = "physicalComponent",
+    js_name = "TemperatureEquationCurve"
+)]
+pub struct TemperatureEquationCurve {
+    #[napi(js_name = "equationId")]
+    pub equation_id: i32,
+    #[napi(js_name = "componentId")]
+    pub component_id: i32,
+    pub code: String,
+    pub name: String,
+    #[napi(js_name = "functionCode")]
+    pub function_code: i32,
+    /// 结果单位
+    pub unit: String,
+    /// 适用温度范围（输入单位）
+    #[napi(js_name = "minTemperature")]
+    pub min_temperature: f64,
+    #[napi(js_name = "maxTemperature")]
+    pub max_temperature: f64,
+    pub points: Vec<TemperatureEquationPoint>,
+    pub warnings: Vec<String>,
+}
+
+fn equation_err(msg: impl std::fmt::Display) -> DbErr {
+    DbErr::Custom(format!("温度关联方程计算错误: {}", msg))
+}
+
+/// 温度换算为 K，单位为空时视为 K
+fn temperature_to_kelvin(registry: &UnitRegistry, value: f64, unit: &str) -> Result<f64, DbErr> {
+    if unit.trim().is_empty() {
+        return Ok(value);
+    }
+    registry
+        .convert(value, unit, "K", Some("temperature"))
+        .map_err(equation_err)
+}
+
+fn kelvin_to_temperature(registry: &UnitRegistry, value: f64, unit: &str) -> Result<f64, DbErr> {
+    if unit.trim().is_empty() {
+        return Ok(value);
+    }
+    registry
+        .convert(value, "K", unit, Some("temperature"))
+        .map_err(equation_err)
+}
+
+/// 选择要计算的方程：指定 ID 优先，其次默认方程，再次第一条
+async fn find_equation(
+    query: &TemperatureEquationEvalQuery,
+) -> Result<TemperatureEquationDTO, DbErr> {
+    let component_id = match (query.component_id, query.cas_no.as_deref()) {
+        (Some(id), _) => id,
+        (None, Some(cas_no)) => {
+            get_physical_information_list_by_cas_no_list(vec![cas_no.to_string()])
+                .await?
+                .first()
+                .map(|c| c.id)
+                .ok_or_else(|| {
+                    DbErr::RecordNotFound(format!("未找到 CAS 号为 {} 的组分", cas_no))
+                })?
+        }
+        (None, None) => return Err(equation_err("需要提供组分 ID 或 CAS 号")),
+    };
+    let mut equations: Vec<TemperatureEquationDTO> =
+        get_physical_temperature_equation_by_compound_id(component_id)
+            .await?
+            .into_iter()
+            .filter(|e| match query.equation_id {
+                Some(id) => e.id == id,
+                None => e.code == query.code,
+            })
+            .collect();
+    equations.sort_by_key(|e| (e.is_default != 1, e.id));
+    equations.into_iter().next().ok_or_else(|| {
+        DbErr::RecordNotFound(format!(
+            "组分 {} 没有性质 {} 的温度关联方程",
+            component_id, query.code
+        ))
+    })
+}
+
+/// 计算组分性质随温度的变化曲线
+pub async fn evaluate_physical_temperature_equation(
+    query: TemperatureEquationEvalQuery,
+) -> Result<TemperatureEquationCurve, DbErr> {
+    let equation = find_equation(&query).await?;
+    let registry = get_unit_registry().await?;
+    let policy = query.policy.unwrap_or_default();
+    let temperature_unit = query.temperature_unit.clone().unwrap_or_default();
+    let result_unit = query
+        .unit
+        .clone()
+        .filter(|u| !u.trim().is_empty())
+        .unwrap_or_else(|| equation.unit.clone());
+
+    let coefficients = EquationCoefficients([
+        equation.coefficient_a,
+        equation.coefficient_b,
+        equation.coefficient_c,
+        equation.coefficient_d,
+        equation.coefficient_e,
+        equation.coefficient_f,
+        equation.coefficient_g,
+        equation.coefficient_h,
+        equation.coefficient_k,
+        equation.coefficient_l,
+    ]);
+    let t_min = temperature_to_kelvin(
+        &registry,
+        equation.min_applicable_temperature,
+        &equation.min_applicable_temperature_unit,
+    )?;
+    let t_max = temperature_to_kelvin(
+        &registry,
+        equation.max_applicable_temperature,
+        &equation.max_applicable_temperature_unit,
+    )?;
+    // 上下限均为 0 视为未给出适用范围
+    let has_range = t_max > t_min;
+
+    let mut warnings = Vec::new();
+    let mut points = Vec::with_capacity(query.temperatures.len());
+    let mut out_of_range_count = 0;
+    for &temperature in &query.temperatures {
+        let t = temperature_to_kelvin(&registry, temperature, &temperature_unit)?;
+        let out_of_range = has_range && (t < t_min || t > t_max);
+        if out_of_range {
+            out_of_range_count += 1;
+        }
+        let t_eval = match (out_of_range, policy) {
+            (true, ExtrapolationPolicy::Reject) => None,
+            (true, ExtrapolationPolicy::Clamp) => Some(t.clamp(t_min, t_max)),
+            _ => Some(t),
+        };
+        let value = match t_eval {
+            Some(t_eval) => {
+                match evaluate_equation(equation.function_code, &coefficients, t_eval) {
+                    Ok(v) if result_unit == equation.unit => Some(v),
+                    Ok(v) => Some(
+                        registry
+                            .convert(v, &equation.unit, &result_unit, None)
+                            .map_err(equation_err)?,
+                    ),
+                    Err(e) => {
+                        warnings.push(format!("T = {} {}: {}", temperature, temperature_unit, e));
+                        None
+                    }
+                }
+            }
+            None => None,
+        };
+        points.push(TemperatureEquationPoint {
+            temperature,
+            value,
+            out_of_range,
+        });
+    }
+    if out_of_range_count > 0 {
+        let action = match policy {
+            ExtrapolationPolicy::Extrapolate => "已外推",
+            ExtrapolationPolicy::Clamp => "已取边界值",
+            ExtrapolationPolicy::Reject => "未计算",
+        };
+        warnings.insert(
+            0,
+            format!(
+                "{} 个温度点超出方程适用范围 [{}, {}] K，{}",
+                out_of_range_count, t_min, t_max, action
+            ),
+        );
+    }
+
+    Ok(TemperatureEquationCurve {
+        equation_id: equation.id,
+        component_id: equation.component_id,
+        code: equation.code,
+        name: equation.name,
+        function_code: equation.function_code,
+        unit: result_unit,
+        min_temperature: kelvin_to_temperature(&registry, t_min, &temperature_unit)?,
+        max_temperature: kelvin_to_temperature(&registry, t_max, &temperature_unit)?,
+        points,
+        warnings,
+    })
+}
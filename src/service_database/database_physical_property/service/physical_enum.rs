@@ -2,7 +2,7 @@ use napi_derive::napi;
 use serde::{Deserialize, Serialize};
 // 映射二元交互参数
 #[napi(string_enum)]
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum BinaryFuncCode {
     PR,
     RK,
@@ -13,3 +13,16 @@ pub enum BinaryFuncCode {
     UNIQUAC,
     PSRK,
 }
+
+// 温度关联方程超出适用温度范围时的处理方式
+#[napi(string_enum)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum ExtrapolationPolicy {
+    /// 按方程外推并给出警告
+    #[default]
+    Extrapolate,
+    /// 取适用范围边界处的值并给出警告
+    Clamp,
+    /// 不计算，结果为空并给出警告
+    Reject,
+}
@@ -1,4 +1,6 @@
+use crate::service_database::shutter_chunk::snapshot_table::SnapshotDataTable;
 use sea_orm::entity::prelude::*;
+use sea_orm::{IntoActiveModel, Set};
 
 #[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
 #[sea_orm(table_name = "model_shutter_data_entity")]
@@ -9,6 +11,11 @@ pub struct Model {
     pub objects: Vec<u8>, // 存储 zstd 压缩后的二进制
     #[sea_orm(column_type = "Blob")]
     pub sysvars: Vec<u8>, // 存储 zstd 压缩后的二进制
+    /// objects 存储格式：0 整体压缩，1 数据块清单（见 shutter_chunk::chunk_store）
+    pub format: i32,
+    /// objects、sysvars 压缩数据的校验和（见 chunk_store::blob_checksum），为空表示未记录
+    #[sea_orm(default = "")]
+    pub checksum: String,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -28,3 +35,22 @@ impl Related<super::model_shutter_entity::Entity> for Entity {
 }
 
 impl ActiveModelBehavior for ActiveModel {}
+
+impl SnapshotDataTable for Entity {
+    type Main = super::model_shutter_entity::Entity;
+    const MAIN_MODEL_ID: super::model_shutter_entity::Column =
+        super::model_shutter_entity::Column::ModelId;
+    const FORMAT: Column = Column::Format;
+
+    fn stored(row: &Model) -> (i32, &[u8], &[u8], &str) {
+        (row.format, &row.objects, &row.sysvars, &row.checksum)
+    }
+
+    fn with_objects(row: Model, objects: Vec<u8>, format: i32, checksum: String) -> ActiveModel {
+        let mut active = row.into_active_model();
+        active.objects = Set(objects);
+        active.format = Set(format);
+        active.checksum = Set(checksum);
+        active
+    }
+}
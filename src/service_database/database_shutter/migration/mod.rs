@@ -1,12 +1,18 @@
 pub use sea_orm_migration::prelude::*;
 
 mod m20251203_000001_create_tables;
+mod m20261018_000001_shutter_chunks;
+mod m20261018_000002_shutter_checksum;
 
 pub struct Migrator;
 
 #[async_trait::async_trait]
 impl MigratorTrait for Migrator {
     fn migrations() -> Vec<Box<dyn MigrationTrait>> {
-        vec![Box::new(m20251203_000001_create_tables::Migration)]
+        vec![
+            Box::new(m20251203_000001_create_tables::Migration),
+            Box::new(m20261018_000001_shutter_chunks::Migration),
+            Box::new(m20261018_000002_shutter_checksum::Migration),
+        ]
     }
 }
@@ -1 +1,4 @@
+pub mod shutter_diff_service;
+pub mod shutter_integrity_service;
 pub mod shutter_service;
+pub mod shutter_transfer_service;
@@ -0,0 +1,429 @@
+use crate::service_database::database_auto_shutter::service::auto_shutter_service::get_model_auto_shutter_entity_by_id_cache;
+use crate::service_database::database_business::service::util_handle::model_status_handle_service::{
+    update_dynamic_objects_ex, update_info_update_at,
+};
+use crate::service_database::database_shutter::service::shutter_service::get_model_shutter_entity_by_id;
+use crate::tool_handle::json_diff_tool::{
+    ValueChange, diff_values, index_units, parse_blob, unit_str,
+};
+use napi_derive::napi;
+use sea_orm::DbErr;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+
+// ======================================
+// 快照比较与部分恢复
+// 比较：逐图元、逐变量列出两个快照（手动或自动）的差异，以及系统变量的差异
+// 部分恢复：把快照（手动或自动）中选定图元的数据写入指定状态，其余图元保持不变
+// ======================================
+
+#[napi(string_enum, namespace = "shutterHandle")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ShutterDiffKind {
+    /// 只在 B 中存在
+    Added,
+    /// 只在 A 中存在
+    Removed,
+    Changed,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[napi(object, namespace = "shutterHandle")]
+pub struct ShutterVariableChange {
+    /// 变量路径，嵌套字段以 . 连接（params.pressure）
+    pub path: String,
+    /// A 中的值，不存在时为空
+    pub before: Option<Value>,
+    /// B 中的值，不存在时为空
+    pub after: Option<Value>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[napi(object, namespace = "shutterHandle")]
+pub struct ShutterUnitDiff {
+    pub graphic_id: String,
+    pub name: Option<String>,
+    pub unit_type: Option<String>,
+    pub kind: ShutterDiffKind,
+    /// 新增/删除的图元为空
+    pub changes: Vec<ShutterVariableChange>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[napi(object, namespace = "shutterHandle")]
+pub struct ShutterDiffResult {
+    pub id_a: String,
+    pub id_b: String,
+    /// 有差异的图元
+    pub units: Vec<ShutterUnitDiff>,
+    /// 系统变量差异
+    pub sysvars: Vec<ShutterVariableChange>,
+    /// 两边都有且没有差异的图元数
+    pub unchanged_units: u32,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[napi(object, namespace = "shutterHandle")]
+pub struct ShutterRestoreResult {
+    /// 写入的状态编码
+    pub state_code: String,
+    /// 快照所基于的状态编码
+    pub base_state_code: String,
+    /// 已恢复的图元
+    pub restored: Vec<String>,
+    /// 快照中不存在的图元
+    pub missing: Vec<String>,
+    /// 已恢复图元的快照数据，供前端同步到运行中的模型
+    pub objects: Vec<Value>,
+}
+
+fn shutter_err(msg: impl std::fmt::Display) -> DbErr {
+    DbErr::Custom(format!("快照数据错误: {}", msg))
+}
+
+fn to_change(c: ValueChange) -> ShutterVariableChange {
+    ShutterVariableChange {
+        path: c.path,
+        before: c.before,
+        after: c.after,
+    }
+}
+
+fn diff_blobs(
+    id_a: String,
+    id_b: String,
+    (objects_a, sysvars_a): (&str, &str),
+    (objects_b, sysvars_b): (&str, &str),
+) -> Result<ShutterDiffResult, DbErr> {
+    let units_a = index_units(&parse_blob(objects_a).map_err(shutter_err)?).map_err(shutter_err)?;
+    let units_b = index_units(&parse_blob(objects_b).map_err(shutter_err)?).map_err(shutter_err)?;
+
+    let mut units = Vec::new();
+    let mut unchanged_units = 0;
+    for (id, a) in &units_a {
+        match units_b.get(id) {
+            Some(b) => {
+                let changes = diff_values(a, b);
+                if changes.is_empty() {
+                    unchanged_units += 1;
+                    continue;
+                }
+                units.push(ShutterUnitDiff {
+                    graphic_id: id.clone(),
+                    name: unit_str(b, "name"),
+                    unit_type: unit_str(b, "type"),
+                    kind: ShutterDiffKind::Changed,
+                    changes: changes.into_iter().map(to_change).collect(),
+                });
+            }
+            None => units.push(ShutterUnitDiff {
+                graphic_id: id.clone(),
+                name: unit_str(a, "name"),
+                unit_type: unit_str(a, "type"),
+                kind: ShutterDiffKind::Removed,
+                changes: vec![],
+            }),
+        }
+    }
+    for (id, b) in units_b.iter().filter(|(id, _)| !units_a.contains_key(*id)) {
+        units.push(ShutterUnitDiff {
+            graphic_id: id.clone(),
+            name: unit_str(b, "name"),
+            unit_type: unit_str(b, "type"),
+            kind: ShutterDiffKind::Added,
+            changes: vec![],
+        });
+    }
+    units.sort_by(|x, y| x.graphic_id.cmp(&y.graphic_id));
+
+    let sysvars = diff_values(
+        &parse_blob(sysvars_a).map_err(shutter_err)?,
+        &parse_blob(sysvars_b).map_err(shutter_err)?,
+    );
+    Ok(ShutterDiffResult {
+        id_a,
+        id_b,
+        units,
+        sysvars: sysvars.into_iter().map(to_change).collect(),
+        unchanged_units,
+    })
+}
+
+/// 比较两个手动快照
+pub async fn diff_shutters(
+    id_a: String,
+    id_b: String,
+    model_id: String,
+) -> Result<ShutterDiffResult, DbErr> {
+    let not_found = |id: &str| DbErr::RecordNotFound(format!("快照 {} 不存在", id));
+    let a = get_model_shutter_entity_by_id(id_a.clone(), model_id.clone())
+        .await?
+        .ok_or_else(|| not_found(&id_a))?;
+    let b = get_model_shutter_entity_by_id(id_b.clone(), model_id)
+        .await?
+        .ok_or_else(|| not_found(&id_b))?;
+    diff_blobs(
+        id_a,
+        id_b,
+        (&a.objects, &a.sysvars),
+        (&b.objects, &b.sysvars),
+    )
+}
+
+/// 比较两个自动快照
+pub async fn diff_auto_shutters(
+    id_a: i32,
+    id_b: i32,
+    model_id: String,
+) -> Result<ShutterDiffResult, DbErr> {
+    let a = get_model_auto_shutter_entity_by_id_cache(id_a, model_id.clone()).await?;
+    let b = get_model_auto_shutter_entity_by_id_cache(id_b, model_id).await?;
+    diff_blobs(
+        id_a.to_string(),
+        id_b.to_string(),
+        (&a.objects, &a.sysvars),
+        (&b.objects, &b.sysvars),
+    )
+}
+
+/// 把手动快照中选定图元的数据写入状态 state_code，其余图元保持不变
+pub async fn restore_shutter_partial(
+    shutter_id: String,
+    model_id: String,
+    graphic_ids: Vec<String>,
+    state_code: String,
+) -> Result<ShutterRestoreResult, DbErr> {
+    let shutter = get_model_shutter_entity_by_id(shutter_id.clone(), model_id.clone())
+        .await?
+        .ok_or_else(|| DbErr::RecordNotFound(format!("快照 {} 不存在", shutter_id)))?;
+    restore_units(
+        model_id,
+        state_code,
+        shutter.base_state_code,
+        &shutter.objects,
+        graphic_ids,
+    )
+    .await
+}
+
+/// 把自动快照中选定图元的数据写入状态 state_code，其余图元保持不变
+pub async fn restore_auto_shutter_partial(
+    id: i32,
+    model_id: String,
+    graphic_ids: Vec<String>,
+    state_code: String,
+) -> Result<ShutterRestoreResult, DbErr> {
+    let shutter = get_model_auto_shutter_entity_by_id_cache(id, model_id.clone()).await?;
+    restore_units(
+        model_id,
+        state_code,
+        shutter.base_state_code,
+        &shutter.objects,
+        graphic_ids,
+    )
+    .await
+}
+
+async fn restore_units(
+    model_id: String,
+    state_code: String,
+    base_state_code: String,
+    objects_blob: &str,
+    graphic_ids: Vec<String>,
+) -> Result<ShutterRestoreResult, DbErr> {
+    let units =
+        index_units(&parse_blob(objects_blob).map_err(shutter_err)?).map_err(shutter_err)?;
+
+    let mut restored = Vec::new();
+    let mut missing = Vec::new();
+    let mut objects = Vec::new();
+    for id in graphic_ids {
+        match units.get(&id) {
+            Some(unit) => {
+                // 以对象形式保存的快照图元可能不带 id，写回时补上
+                let mut unit = unit.clone();
+                if let Some(map) = unit.as_object_mut() {
+                    map.insert("id".to_string(), json!(id));
+                }
+                objects.push(unit);
+                restored.push(id);
+            }
+            None => missing.push(id),
+        }
+    }
+    if !objects.is_empty() {
+        if update_info_update_at(model_id.clone(), state_code.clone()).await? == 0 {
+            return Err(shutter_err(format!(
+                "模型 {} 没有状态 {}，无法恢复",
+                model_id, state_code
+            )));
+        }
+        update_dynamic_objects_ex(model_id, state_code.clone(), objects.clone()).await?;
+    }
+    Ok(ShutterRestoreResult {
+        state_code,
+        base_state_code,
+        restored,
+        missing,
+        objects,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::service_database::database_auto_shutter::db_auto_shutter_connection::initialize_auto_shutter_db;
+    use crate::service_database::database_auto_shutter::service::auto_shutter_service::{
+        AutoShutterData, read_one_model_auto_shutter_entity_cache,
+    };
+    use crate::service_database::database_business::db_business_connection::initialize_business_db;
+    use crate::service_database::database_business::service::util_handle::model_status_handle_service::{
+        ModelStatusInformationDTO, get_params_by_ids_and_code,
+        update_or_creat_status_by_infor_and_params,
+    };
+    use crate::service_database::database_shutter::db_shutter_connection::initialize_shutter_db;
+    use crate::service_database::database_shutter::service::shutter_service::{
+        FullShutterModel, insert_model_shutter_entity_only,
+    };
+
+    const MODEL: &str = "restore_test_model";
+    const OBJECTS: &str = r#"[{"id":"V1","name":"V1","type":"Valve","params":{"opening":0.4}},{"id":"V2","name":"V2","type":"Valve","params":{"opening":0.9}}]"#;
+
+    // 快照、自动快照与状态分别在三个库中，测试进程共用临时库文件
+    async fn init_dbs() {
+        let dir = std::env::temp_dir();
+        let path = |name: &str| {
+            dir.join(format!("restore_test_{}_{}.db", name, std::process::id()))
+                .to_string_lossy()
+                .into_owned()
+        };
+        initialize_business_db(path("business")).await.unwrap();
+        initialize_shutter_db(path("shutter")).await.unwrap();
+        initialize_auto_shutter_db(path("auto")).await.unwrap();
+        update_or_creat_status_by_infor_and_params(
+            ModelStatusInformationDTO {
+                id: format!("{}_T1", MODEL),
+                model_id: MODEL.to_string(),
+                name: "T1".to_string(),
+                code: "T1".to_string(),
+                update_at: 0,
+            },
+            vec![],
+        )
+        .await
+        .unwrap();
+    }
+
+    async fn restored_opening(code: &str) -> Vec<Value> {
+        get_params_by_ids_and_code(
+            vec!["V1".to_string(), "V2".to_string()],
+            MODEL.to_string(),
+            Some(code.to_string()),
+        )
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|v| v["params"]["opening"].clone())
+        .collect()
+    }
+
+    #[tokio::test]
+    async fn test_restore_partial_into_target_state() {
+        init_dbs().await;
+        let id = format!("restore_test_{}", std::process::id());
+        insert_model_shutter_entity_only(FullShutterModel {
+            id: id.clone(),
+            name: "A".to_string(),
+            index_num: 1,
+            update_at: "0".to_string(),
+            objects: OBJECTS.to_string(),
+            sysvars: "{}".to_string(),
+            model_id: MODEL.to_string(),
+            user_name: None,
+            type_num: None,
+            state_index: None,
+            state_desc: None,
+            base_state_code: "S0".to_string(),
+        })
+        .await
+        .unwrap();
+
+        let result = restore_shutter_partial(
+            id.clone(),
+            MODEL.to_string(),
+            vec!["V1".to_string(), "X".to_string()],
+            "T1".to_string(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            (result.state_code.as_str(), result.base_state_code.as_str()),
+            ("T1", "S0")
+        );
+        assert_eq!(
+            (result.restored, result.missing),
+            (vec!["V1".to_string()], vec!["X".to_string()])
+        );
+        // 只写入目标状态，快照所基于的状态不受影响
+        assert_eq!(restored_opening("T1").await, vec![json!(0.4)]);
+        assert!(restored_opening("S0").await.is_empty());
+
+        let err = restore_shutter_partial(
+            id,
+            MODEL.to_string(),
+            vec!["V1".to_string()],
+            "NONE".to_string(),
+        )
+        .await
+        .unwrap_err();
+        assert!(err.to_string().contains("没有状态 NONE"), "{}", err);
+    }
+
+    #[tokio::test]
+    async fn test_restore_auto_partial_into_target_state() {
+        init_dbs().await;
+        let id = read_one_model_auto_shutter_entity_cache(
+            AutoShutterData {
+                objects: OBJECTS.replace("0.9", "0.7"),
+                sysvars: "{}".to_string(),
+                sim_time: "0".to_string(),
+                base_state_code: "S0".to_string(),
+            },
+            MODEL.to_string(),
+        )
+        .await
+        .unwrap();
+
+        let result = restore_auto_shutter_partial(
+            id,
+            MODEL.to_string(),
+            vec!["V2".to_string()],
+            "T2".to_string(),
+        )
+        .await;
+        assert!(result.is_err());
+
+        update_or_creat_status_by_infor_and_params(
+            ModelStatusInformationDTO {
+                id: format!("{}_T2", MODEL),
+                model_id: MODEL.to_string(),
+                name: "T2".to_string(),
+                code: "T2".to_string(),
+                update_at: 0,
+            },
+            vec![],
+        )
+        .await
+        .unwrap();
+        let result = restore_auto_shutter_partial(
+            id,
+            MODEL.to_string(),
+            vec!["V2".to_string()],
+            "T2".to_string(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(result.restored, vec!["V2".to_string()]);
+        assert_eq!(restored_opening("T2").await, vec![json!(0.7)]);
+    }
+}
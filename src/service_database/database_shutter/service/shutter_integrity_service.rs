@@ -0,0 +1,254 @@
+use crate::service_database::database_auto_shutter::service::auto_shutter_service::{
+    FullCacheData, find_nearest_good_auto_shutter, recover_model_auto_shutter_entity,
+    verify_model_auto_shutter_entities,
+};
+use crate::service_database::database_shutter::db_shutter_connection::get_shutter_db;
+use crate::service_database::database_shutter::entity::model_shutter_data_entity::{
+    ActiveModel as DataActiveModel, Column as DataColumn, Entity as DataEntity,
+};
+use crate::service_database::database_shutter::entity::model_shutter_entity::{
+    Column as MainColumn, Entity as MainEntity,
+};
+use crate::service_database::shutter_chunk::chunk_store::{
+    FORMAT_MANIFEST, heal_chunks, release_objects, store_snapshot,
+};
+use crate::service_database::shutter_chunk::snapshot_table::{
+    check_snapshot_row, verify_snapshot_rows,
+};
+use crate::tool_handle::time_tool::string_to_integer;
+use napi_derive::napi;
+use sea_orm::{
+    ColumnTrait, DbErr, EntityTrait, QueryFilter, QueryOrder, QuerySelect, Set, TransactionError,
+    TransactionTrait,
+};
+use serde::{Deserialize, Serialize};
+
+// ======================================
+// 快照完整性校验与损坏恢复
+// 校验：逐条核对校验和、解压并还原数据块，确认 objects / sysvars 为合法 JSON
+// 恢复：快照损坏时取最近的完好自动快照（优先同一状态，其次时间最接近）
+// ======================================
+
+#[napi(string_enum, namespace = "shutterHandle")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ShutterSource {
+    /// 手动快照
+    Manual,
+    /// 自动快照
+    Auto,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[napi(object, namespace = "shutterHandle")]
+pub struct ShutterDamage {
+    pub source: ShutterSource,
+    pub id: String,
+    /// 手动快照名称
+    pub name: Option<String>,
+    pub update_at: String,
+    pub reason: String,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[napi(object, namespace = "shutterHandle")]
+pub struct ShutterVerifyReport {
+    pub model_id: String,
+    /// 已检查的快照数（手动 + 自动）
+    pub checked: u32,
+    /// 没有记录校验和（旧数据）、只做了解压检查的快照数
+    pub unverified: u32,
+    pub damaged: Vec<ShutterDamage>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[napi(object, namespace = "shutterHandle")]
+pub struct ShutterRecoveryResult {
+    /// 请求的快照是否损坏
+    pub damaged: bool,
+    /// 损坏原因
+    pub reason: Option<String>,
+    /// 数据来源的自动快照 ID，快照完好时为空
+    pub source_auto_id: Option<i32>,
+    /// 数据来源快照的保存时间
+    pub update_at: String,
+    pub base_state_code: String,
+    pub objects: String,
+    pub sysvars: String,
+    /// 是否已用恢复的数据重写损坏的手动快照
+    pub repaired: bool,
+    /// 重写时修复的数据块数
+    pub healed_chunks: u32,
+}
+
+impl ShutterRecoveryResult {
+    /// 快照完好，直接返回其数据
+    pub fn intact(data: FullCacheData) -> Self {
+        Self {
+            damaged: false,
+            reason: None,
+            source_auto_id: None,
+            update_at: data.update_at,
+            base_state_code: data.base_state_code,
+            objects: data.objects,
+            sysvars: data.sysvars,
+            repaired: false,
+            healed_chunks: 0,
+        }
+    }
+
+    /// 快照损坏，数据取自自动快照 source
+    pub fn recovered(reason: String, source: FullCacheData) -> Self {
+        Self {
+            damaged: true,
+            reason: Some(reason),
+            source_auto_id: Some(source.id),
+            update_at: source.update_at,
+            base_state_code: source.base_state_code,
+            objects: source.objects,
+            sysvars: source.sysvars,
+            repaired: false,
+            healed_chunks: 0,
+        }
+    }
+}
+
+/// 扫描模型下全部手动快照与自动快照，列出损坏的条目
+pub async fn verify_shutters(model_id: String) -> Result<ShutterVerifyReport, DbErr> {
+    let db = get_shutter_db().await?;
+    let rows = MainEntity::find()
+        .filter(MainColumn::ModelId.eq(model_id.clone()))
+        .order_by_asc(MainColumn::IndexNum)
+        .find_also_related(DataEntity)
+        .all(db)
+        .await?;
+
+    let (checked, unverified, damaged) =
+        verify_snapshot_rows::<DataEntity, _, _>(db, &model_id, rows).await?;
+    let mut damaged: Vec<ShutterDamage> = damaged
+        .into_iter()
+        .map(|(main, reason)| ShutterDamage {
+            source: ShutterSource::Manual,
+            id: main.id,
+            name: Some(main.name),
+            update_at: main.update_at,
+            reason,
+        })
+        .collect();
+
+    let (auto_checked, auto_unverified, auto_damaged) =
+        verify_model_auto_shutter_entities(model_id.clone()).await?;
+    damaged.extend(auto_damaged);
+    Ok(ShutterVerifyReport {
+        model_id,
+        checked: checked + auto_checked,
+        unverified: unverified + auto_unverified,
+        damaged,
+    })
+}
+
+/// 读取手动快照，损坏时回退到最近的完好自动快照。
+/// repair 为 true 时用恢复的数据重写该手动快照，并修复其中可修复的数据块
+pub async fn recover_shutter(
+    shutter_id: String,
+    model_id: String,
+    repair: bool,
+) -> Result<ShutterRecoveryResult, DbErr> {
+    let db = get_shutter_db().await?;
+    let (main, data) = MainEntity::find_by_id(shutter_id.clone())
+        .filter(MainColumn::ModelId.eq(model_id.clone()))
+        .find_also_related(DataEntity)
+        .one(db)
+        .await?
+        .ok_or_else(|| DbErr::RecordNotFound(format!("快照 {} 不存在", shutter_id)))?;
+
+    let result = check_snapshot_row::<DataEntity, _>(db, &model_id, data.as_ref()).await?;
+    let reason = match result {
+        Ok((objects, sysvars)) => {
+            return Ok(ShutterRecoveryResult {
+                damaged: false,
+                reason: None,
+                source_auto_id: None,
+                update_at: main.update_at,
+                base_state_code: main.base_state_code,
+                objects,
+                sysvars,
+                repaired: false,
+                healed_chunks: 0,
+            });
+        }
+        Err(reason) => reason,
+    };
+
+    let source = find_nearest_good_auto_shutter(
+        &model_id,
+        string_to_integer(&main.update_at),
+        &main.base_state_code,
+        None,
+    )
+    .await?
+    .ok_or_else(|| {
+        DbErr::Custom(format!(
+            "快照 {} 已损坏（{}），且没有可用于恢复的自动快照",
+            shutter_id, reason
+        ))
+    })?;
+    let mut recovered = ShutterRecoveryResult::recovered(reason, source);
+    if !repair {
+        return Ok(recovered);
+    }
+
+    let (objects, sysvars) = (recovered.objects.clone(), recovered.sysvars.clone());
+    recovered.healed_chunks = db
+        .transaction::<_, u32, DbErr>(|txn| {
+            Box::pin(async move {
+                // 先修复同内容的数据块，否则写入时会直接引用仍然损坏的数据块
+                let manifests: Vec<Vec<u8>> = DataEntity::find()
+                    .select_only()
+                    .column(DataColumn::Objects)
+                    .inner_join(MainEntity)
+                    .filter(MainColumn::ModelId.eq(model_id.clone()))
+                    .filter(DataColumn::Format.eq(FORMAT_MANIFEST))
+                    .into_tuple()
+                    .all(txn)
+                    .await?;
+                let healed = heal_chunks(txn, &model_id, &objects, &manifests).await?;
+                let stored = store_snapshot(txn, &model_id, &objects, &sysvars).await?;
+                let active_data = DataActiveModel {
+                    id: Set(shutter_id),
+                    objects: Set(stored.objects),
+                    sysvars: Set(stored.sysvars),
+                    format: Set(stored.format),
+                    checksum: Set(stored.checksum),
+                };
+                match data {
+                    Some(old) => {
+                        DataEntity::update(active_data).exec(txn).await?;
+                        match release_objects(txn, &model_id, old.format, &old.objects).await {
+                            // 清单本身已损坏时无法释放旧引用，对应数据块不再回收
+                            Err(e) if e.is_corruption() => {}
+                            other => other?,
+                        }
+                    }
+                    None => {
+                        DataEntity::insert(active_data).exec(txn).await?;
+                    }
+                }
+                Ok(healed)
+            })
+        })
+        .await
+        .map_err(|e| match e {
+            TransactionError::Connection(e) => e,
+            TransactionError::Transaction(e) => e,
+        })?;
+    recovered.repaired = true;
+    Ok(recovered)
+}
+
+/// 读取自动快照，损坏时回退到时间最接近的其他完好自动快照
+pub async fn recover_auto_shutter(
+    id: i32,
+    model_id: String,
+) -> Result<ShutterRecoveryResult, DbErr> {
+    recover_model_auto_shutter_entity(id, model_id).await
+}
@@ -1,13 +1,19 @@
+use crate::error_handle::err_handle::ShutterDataError;
 use crate::service_database::database_shutter::db_shutter_connection::get_shutter_db;
+use crate::service_database::shutter_chunk::chunk_store::{
+    ShutterChunkStats, ShutterDictInfo, chunk_stats, load_snapshot, release_objects,
+    store_snapshot, train_dictionary,
+};
+use crate::service_database::shutter_chunk::snapshot_table::compact_snapshot_storage;
 use crate::tool_handle::time_tool::integer_to_string;
 use chrono::Utc;
 use napi_derive::napi;
 use sea_orm::{
-    ActiveModelTrait, ColumnTrait, DbErr, EntityTrait, FromQueryResult, IntoActiveModel,
-    QueryFilter, QuerySelect, Set, TransactionError, TransactionTrait, entity::prelude::*,
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, DbErr, EntityTrait, FromQueryResult,
+    IntoActiveModel, QueryFilter, QuerySelect, Set, TransactionError, TransactionTrait,
+    entity::prelude::*,
 };
 use serde::{Deserialize, Serialize};
-use zstd::{decode_all, encode_all};
 // 引入两个实体
 use crate::service_database::database_shutter::entity::model_shutter_data_entity::{
     ActiveModel as DataActiveModel, Column as DataColumn, Entity as DataEntity,
@@ -16,18 +22,6 @@ use crate::service_database::database_shutter::entity::model_shutter_entity::{
     ActiveModel as MainActiveModel, Column as MainColumn, Entity as MainEntity,
 };
 
-// ======================================
-// 工具函数：压缩
-// ======================================
-fn compress_data(data: &str) -> Vec<u8> {
-    encode_all(data.as_bytes(), 3).unwrap_or_default()
-}
-
-fn decompress_data(data: &[u8]) -> String {
-    let decoded = decode_all(data).unwrap_or_default();
-    String::from_utf8(decoded).unwrap_or_default()
-}
-
 // ======================================
 // DTO 定义 (保持与 NAPI 兼容)
 // ======================================
@@ -97,13 +91,22 @@ pub async fn get_model_shutter_entity_by_id(
         .await?;
 
     if let Some((main, Some(data))) = result {
+        let (objects, sysvars) = load_snapshot(
+            db,
+            &main.model_id,
+            data.format,
+            &data.objects,
+            &data.sysvars,
+            &data.checksum,
+        )
+        .await?;
         Ok(Some(FullShutterModel {
             id: main.id,
             name: main.name,
             index_num: main.index_num,
             model_id: main.model_id,
-            objects: decompress_data(&data.objects), // 解压
-            sysvars: decompress_data(&data.sysvars), // 解压
+            objects,
+            sysvars,
             update_at: main.update_at,
             base_state_code: main.base_state_code,
             user_name: main.user_name,
@@ -132,13 +135,22 @@ pub async fn get_model_shutter_entity_by_index_num_model_id_only(
         .await?;
 
     if let Some((main, Some(data))) = result {
+        let (objects, sysvars) = load_snapshot(
+            db,
+            &main.model_id,
+            data.format,
+            &data.objects,
+            &data.sysvars,
+            &data.checksum,
+        )
+        .await?;
         Ok(Some(FullShutterModel {
             id: main.id,
             name: main.name,
             index_num: main.index_num,
             model_id: main.model_id,
-            objects: decompress_data(&data.objects), // 解压
-            sysvars: decompress_data(&data.sysvars), // 解压
+            objects,
+            sysvars,
             update_at: main.update_at,
             base_state_code: main.base_state_code,
             user_name: main.user_name,
@@ -161,7 +173,7 @@ pub async fn insert_model_shutter_entity(
     db.transaction::<_, i32, DbErr>(|txn| {
         Box::pin(async move {
             let existing = MainEntity::find()
-                .filter(MainColumn::ModelId.eq(model_id))
+                .filter(MainColumn::ModelId.eq(model_id.clone()))
                 .filter(MainColumn::IndexNum.eq(data.index_num))
                 .one(txn)
                 .await?;
@@ -171,18 +183,28 @@ pub async fn insert_model_shutter_entity(
                 let mut active_main: MainActiveModel = record.into_active_model();
                 active_main.base_state_code = Set(data.base_state_code);
                 active_main.update_at = Set(data.update_at);
-                let active_main_id = active_main.id.clone();
+                let active_main_id = active_main.id.clone().unwrap();
                 active_main.update(txn).await?;
 
-                // 2. 更新数据表
+                // 2. 更新数据表：先写入新数据块再释放旧引用，未变化的图元不会被删除后重写
+                let old = DataEntity::find_by_id(active_main_id.clone())
+                    .one(txn)
+                    .await?;
+                let stored = store_snapshot(txn, &model_id, &data.objects, &data.sysvars).await?;
                 let active_data = DataActiveModel {
-                    id: Set(active_main_id.unwrap()),
-                    objects: Set(compress_data(&data.objects)),
-                    sysvars: Set(compress_data(&data.sysvars)),
+                    id: Set(active_main_id),
+                    objects: Set(stored.objects),
+                    sysvars: Set(stored.sysvars),
+                    format: Set(stored.format),
+                    checksum: Set(stored.checksum),
                 };
                 DataEntity::update(active_data).exec(txn).await?;
+                if let Some(old) = old {
+                    release_objects(txn, &model_id, old.format, &old.objects).await?;
+                }
                 Ok(data.index_num)
             } else {
+                let stored = store_snapshot(txn, &model_id, &data.objects, &data.sysvars).await?;
                 // 1. 插入主表
                 let active_main = MainActiveModel {
                     id: Set(data.id.clone()),
@@ -201,8 +223,10 @@ pub async fn insert_model_shutter_entity(
                 // 2. 插入数据表
                 let active_data = DataActiveModel {
                     id: Set(data.id),
-                    objects: Set(compress_data(&data.objects)),
-                    sysvars: Set(compress_data(&data.sysvars)),
+                    objects: Set(stored.objects),
+                    sysvars: Set(stored.sysvars),
+                    format: Set(stored.format),
+                    checksum: Set(stored.checksum),
                 };
                 DataEntity::insert(active_data).exec(txn).await?;
                 Ok(data.index_num)
@@ -222,6 +246,7 @@ pub async fn insert_model_shutter_entity_only(data: FullShutterModel) -> Result<
 
     db.transaction::<_, (), DbErr>(|txn| {
         Box::pin(async move {
+            let stored = store_snapshot(txn, &data.model_id, &data.objects, &data.sysvars).await?;
             let active_main = MainActiveModel {
                 id: Set(data.id.clone()),
                 model_id: Set(data.model_id),
@@ -238,8 +263,10 @@ pub async fn insert_model_shutter_entity_only(data: FullShutterModel) -> Result<
 
             let active_data = DataActiveModel {
                 id: Set(data.id),
-                objects: Set(compress_data(&data.objects)),
-                sysvars: Set(compress_data(&data.sysvars)),
+                objects: Set(stored.objects),
+                sysvars: Set(stored.sysvars),
+                format: Set(stored.format),
+                checksum: Set(stored.checksum),
             };
             DataEntity::insert(active_data).exec(txn).await?;
             Ok(())
@@ -256,18 +283,32 @@ pub async fn insert_model_shutter_entity_only(data: FullShutterModel) -> Result<
 pub async fn delete_model_shutter_entity(id: String, model_id: String) -> Result<u32, DbErr> {
     let db = get_shutter_db().await?;
 
-    // 注意：如果在数据库设置了级联删除（ON DELETE CASCADE），只需删除主表。
-    // 否则需要在事务中删除两表。
-    let result = MainEntity::delete_many()
-        .filter(MainColumn::Id.eq(id.clone()))
-        .filter(MainColumn::ModelId.eq(model_id))
-        .exec(db)
-        .await?;
+    db.transaction::<_, u32, DbErr>(|txn| {
+        Box::pin(async move {
+            // 数据表设置了级联删除，需在删除主表前取出清单以释放数据块引用
+            let data = DataEntity::find_by_id(id.clone()).one(txn).await?;
+            let result = MainEntity::delete_many()
+                .filter(MainColumn::Id.eq(id.clone()))
+                .filter(MainColumn::ModelId.eq(model_id.clone()))
+                .exec(txn)
+                .await?;
+            if result.rows_affected == 0 {
+                return Ok(0);
+            }
 
-    // 手动删除数据表内容
-    DataEntity::delete_by_id(id).exec(db).await?;
+            if let Some(data) = data {
+                release_objects(txn, &model_id, data.format, &data.objects).await?;
+            }
+            DataEntity::delete_by_id(id).exec(txn).await?;
 
-    Ok(result.rows_affected as u32)
+            Ok(result.rows_affected as u32)
+        })
+    })
+    .await
+    .map_err(|e| match e {
+        TransactionError::Connection(e) => e,
+        TransactionError::Transaction(e) => e,
+    })
 }
 
 /// 根据 ID 更新部分信息 (适配分表+压缩架构)
@@ -282,7 +323,7 @@ pub async fn update_model_shutter_entity_by_id_only(
 
     // 1. 先查出该 model_id 和 index_num 对应的唯一主键 ID
     let target = MainEntity::find()
-        .filter(MainColumn::ModelId.eq(model_id))
+        .filter(MainColumn::ModelId.eq(model_id.clone()))
         .filter(MainColumn::IndexNum.eq(index_num))
         .one(db)
         .await?
@@ -313,12 +354,21 @@ pub async fn update_model_shutter_entity_by_id_only(
                 }
 
                 // 更新数据表
+                let old = DataEntity::find_by_id(primary_id_clone.clone())
+                    .one(txn)
+                    .await?;
+                let stored = store_snapshot(txn, &model_id, &objects, &sysvars).await?;
                 DataEntity::update_many()
-                    .col_expr(DataColumn::Objects, Expr::value(compress_data(&objects)))
-                    .col_expr(DataColumn::Sysvars, Expr::value(compress_data(&sysvars)))
+                    .col_expr(DataColumn::Objects, Expr::value(stored.objects))
+                    .col_expr(DataColumn::Sysvars, Expr::value(stored.sysvars))
+                    .col_expr(DataColumn::Format, Expr::value(stored.format))
+                    .col_expr(DataColumn::Checksum, Expr::value(stored.checksum))
                     .filter(DataColumn::Id.eq(primary_id_clone))
                     .exec(txn)
                     .await?;
+                if let Some(old) = old {
+                    release_objects(txn, &model_id, old.format, &old.objects).await?;
+                }
 
                 Ok(main_res.rows_affected)
             })
@@ -346,3 +396,235 @@ pub async fn update_model_shutter_entity_name_by_id_and_model_id_only(
         .await?;
     Ok(true)
 }
+
+/// 读取模型下全部快照（含解压后的数据，用于工程导出）
+pub async fn get_all_model_shutter_entity_full(
+    model_id: String,
+) -> Result<Vec<FullShutterModel>, DbErr> {
+    let db = get_shutter_db().await?;
+    load_model_shutters_full(db, &model_id).await
+}
+
+/// 读取模型下全部快照并还原数据，任一快照损坏时返回错误并指明是哪一条
+pub async fn load_model_shutters_full<C: ConnectionTrait>(
+    db: &C,
+    model_id: &str,
+) -> Result<Vec<FullShutterModel>, DbErr> {
+    let results = MainEntity::find()
+        .filter(MainColumn::ModelId.eq(model_id))
+        .find_also_related(DataEntity)
+        .all(db)
+        .await?;
+
+    let mut list = Vec::with_capacity(results.len());
+    for (main, data) in results {
+        let Some(data) = data else { continue };
+        let (objects, sysvars) = load_snapshot(
+            db,
+            &main.model_id,
+            data.format,
+            &data.objects,
+            &data.sysvars,
+            &data.checksum,
+        )
+        .await
+        .map_err(|e| match e {
+            ShutterDataError::Db(e) => e,
+            e => DbErr::Custom(format!("快照 {}（{}）数据损坏：{}", main.name, main.id, e)),
+        })?;
+        list.push(FullShutterModel {
+            id: main.id,
+            name: main.name,
+            index_num: main.index_num,
+            model_id: main.model_id,
+            objects,
+            sysvars,
+            update_at: main.update_at,
+            base_state_code: main.base_state_code,
+            user_name: main.user_name,
+            state_index: main.state_index,
+            state_desc: main.state_desc,
+            type_num: main.type_num,
+        });
+    }
+    Ok(list)
+}
+
+/// 批量导入快照（工程导入），主键重新生成并挂到新的 model_id 下
+pub async fn import_model_shutter_entities(
+    datas: Vec<FullShutterModel>,
+    model_id: String,
+) -> Result<u32, DbErr> {
+    let db = get_shutter_db().await?;
+
+    db.transaction::<_, u32, DbErr>(|txn| {
+        Box::pin(async move { insert_model_shutters(txn, datas, &model_id).await })
+    })
+    .await
+    .map_err(|e| match e {
+        TransactionError::Connection(e) => e,
+        TransactionError::Transaction(e) => e,
+    })
+}
+
+/// 写入一批快照，主键重新生成并挂到 model_id 下；由调用方控制事务
+pub async fn insert_model_shutters<C: ConnectionTrait>(
+    db: &C,
+    datas: Vec<FullShutterModel>,
+    model_id: &str,
+) -> Result<u32, DbErr> {
+    let mut count = 0;
+    for data in datas {
+        let new_id = crate::tool_handle::id_tool::new_id();
+        let stored = store_snapshot(db, model_id, &data.objects, &data.sysvars).await?;
+        let active_main = MainActiveModel {
+            id: Set(new_id.clone()),
+            model_id: Set(model_id.to_string()),
+            name: Set(data.name),
+            index_num: Set(data.index_num),
+            update_at: Set(data.update_at),
+            base_state_code: Set(data.base_state_code),
+            user_name: Set(data.user_name),
+            state_index: Set(data.state_index),
+            state_desc: Set(data.state_desc),
+            type_num: Set(data.type_num),
+        };
+        active_main.insert(db).await?;
+
+        let active_data = DataActiveModel {
+            id: Set(new_id),
+            objects: Set(stored.objects),
+            sysvars: Set(stored.sysvars),
+            format: Set(stored.format),
+            checksum: Set(stored.checksum),
+        };
+        DataEntity::insert(active_data).exec(db).await?;
+        count += 1;
+    }
+    Ok(count)
+}
+
+/// 把模型下旧格式（整体压缩）的快照转存为数据块清单，返回转换的快照数
+pub async fn compact_model_shutter_storage(model_id: String) -> Result<u32, DbErr> {
+    let db = get_shutter_db().await?;
+    compact_snapshot_storage::<DataEntity, _>(db, &model_id).await
+}
+
+/// 以模型已有快照数据训练 zstd 字典
+pub async fn train_model_shutter_dictionary(
+    model_id: String,
+    max_size: Option<u32>,
+) -> Result<ShutterDictInfo, DbErr> {
+    let db = get_shutter_db().await?;
+    train_dictionary(db, &model_id, max_size).await
+}
+
+/// 模型快照数据块统计
+pub async fn get_model_shutter_chunk_stats(model_id: String) -> Result<ShutterChunkStats, DbErr> {
+    let db = get_shutter_db().await?;
+    chunk_stats(db, &model_id).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::service_database::database_shutter::migration::Migrator;
+    use sea_orm::{Database, DatabaseConnection};
+    use sea_orm_migration::MigratorTrait;
+
+    async fn memory_db() -> DatabaseConnection {
+        let db = Database::connect("sqlite::memory:").await.unwrap();
+        Migrator::up(&db, None).await.unwrap();
+        db
+    }
+
+    fn shutter(name: &str) -> FullShutterModel {
+        FullShutterModel {
+            id: String::new(),
+            name: name.to_string(),
+            index_num: 1,
+            update_at: "0".to_string(),
+            objects: r#"[{"id":"V1","p":1.5},{"id":"V2","p":2}]"#.to_string(),
+            sysvars: r#"{"t":0}"#.to_string(),
+            model_id: String::new(),
+            user_name: None,
+            type_num: None,
+            state_index: None,
+            state_desc: None,
+            base_state_code: "S0".to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_export_fails_on_corrupt_shutter() {
+        let db = memory_db().await;
+        insert_model_shutters(&db, vec![shutter("A"), shutter("B")], "M1")
+            .await
+            .unwrap();
+
+        let list = load_model_shutters_full(&db, "M1").await.unwrap();
+        assert_eq!(list.len(), 2);
+        assert!(list.iter().all(|s| s.objects == shutter("A").objects));
+
+        // 损坏其中一条：校验和不再匹配，导出必须报错而不是静默跳过或返回默认值
+        let id = list.iter().find(|s| s.name == "B").unwrap().id.clone();
+        DataEntity::update_many()
+            .col_expr(DataColumn::Checksum, Expr::value("bad"))
+            .filter(DataColumn::Id.eq(id))
+            .exec(&db)
+            .await
+            .unwrap();
+
+        let err = load_model_shutters_full(&db, "M1").await.unwrap_err();
+        assert!(err.to_string().contains("快照 B"), "{}", err);
+    }
+
+    #[tokio::test]
+    async fn test_compact_and_verify_legacy_rows() {
+        use crate::service_database::shutter_chunk::chunk_store::{FORMAT_FULL, blob_checksum};
+        use crate::service_database::shutter_chunk::snapshot_table::verify_snapshot_rows;
+
+        let db = memory_db().await;
+        insert_model_shutters(&db, vec![shutter("A"), shutter("B")], "M1")
+            .await
+            .unwrap();
+
+        // 把两条都改写成旧格式（objects 整体压缩）
+        for row in DataEntity::find().all(&db).await.unwrap() {
+            let blob = zstd::encode_all(shutter("A").objects.as_bytes(), 3).unwrap();
+            let checksum = blob_checksum(&blob, &row.sysvars);
+            let mut active = row.into_active_model();
+            active.objects = Set(blob);
+            active.format = Set(FORMAT_FULL);
+            active.checksum = Set(checksum);
+            active.update(&db).await.unwrap();
+        }
+
+        assert_eq!(
+            compact_snapshot_storage::<DataEntity, _>(&db, "M1")
+                .await
+                .unwrap(),
+            2
+        );
+        assert_eq!(
+            compact_snapshot_storage::<DataEntity, _>(&db, "M1")
+                .await
+                .unwrap(),
+            0
+        );
+        let list = load_model_shutters_full(&db, "M1").await.unwrap();
+        assert!(list.iter().all(|s| s.objects == shutter("A").objects));
+
+        let rows = MainEntity::find()
+            .filter(MainColumn::ModelId.eq("M1"))
+            .find_also_related(DataEntity)
+            .all(&db)
+            .await
+            .unwrap();
+        let (checked, unverified, damaged) =
+            verify_snapshot_rows::<DataEntity, _, _>(&db, "M1", rows)
+                .await
+                .unwrap();
+        assert_eq!((checked, unverified, damaged.len()), (2, 0, 0));
+    }
+}
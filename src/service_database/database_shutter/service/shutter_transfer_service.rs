@@ -0,0 +1,158 @@
+use crate::service_database::database_auto_shutter::service::auto_shutter_service::{
+    export_model_auto_shutter_raw, import_model_auto_shutter_raw,
+};
+use crate::service_database::database_shutter::db_shutter_connection::get_shutter_db;
+use crate::service_database::database_shutter::entity::model_shutter_data_entity::{
+    ActiveModel as DataActiveModel, Entity as DataEntity,
+};
+use crate::service_database::database_shutter::entity::model_shutter_entity::{
+    ActiveModel as MainActiveModel, Column as MainColumn, Entity as MainEntity,
+};
+use crate::service_database::shutter_chunk::chunk_store::{
+    export_raw_snapshot, import_raw_chunks, release_objects,
+};
+use crate::tool_handle::id_tool::new_id;
+use crate::tool_handle::time_tool::{integer_to_string, string_to_integer};
+use chrono::Utc;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DbErr, EntityTrait, IntoActiveModel, QueryFilter, Set,
+    TransactionError, TransactionTrait,
+};
+
+// ======================================
+// 手动快照与自动快照互相复制
+// 两个库的数据表结构相同，objects / sysvars 的压缩数据、校验和原样复制，不经过解压和重新压缩；
+// 清单引用的数据块与字典一并复制到目标库。源快照保持不变，目标库的写入在一个事务内完成
+// ======================================
+
+/// 两个快照库共有的元数据
+pub struct ShutterTransferMeta {
+    /// 保存时间（毫秒）
+    pub update_at: i64,
+    pub base_state_code: String,
+    pub user_name: Option<String>,
+    pub state_index: Option<i32>,
+    pub state_desc: Option<String>,
+}
+
+/// 把自动快照复制为手动快照：index_num 已存在时覆盖该手动快照，否则新建。返回手动快照 ID
+pub async fn promote_auto_shutter(
+    auto_id: i32,
+    index_num: i32,
+    name: String,
+    model_id: String,
+) -> Result<String, DbErr> {
+    let (meta, raw) = export_model_auto_shutter_raw(auto_id, model_id.clone()).await?;
+    let db = get_shutter_db().await?;
+
+    db.transaction::<_, String, DbErr>(|txn| {
+        Box::pin(async move {
+            import_raw_chunks(txn, &raw).await?;
+            let update_at = integer_to_string(meta.update_at);
+            let existing = MainEntity::find()
+                .filter(MainColumn::ModelId.eq(model_id.clone()))
+                .filter(MainColumn::IndexNum.eq(index_num))
+                .one(txn)
+                .await?;
+
+            let active_data = |id: String| DataActiveModel {
+                id: Set(id),
+                objects: Set(raw.objects.clone()),
+                sysvars: Set(raw.sysvars.clone()),
+                format: Set(raw.format),
+                checksum: Set(raw.checksum.clone()),
+            };
+            match existing {
+                Some(record) => {
+                    let id = record.id.clone();
+                    let mut active_main = record.into_active_model();
+                    active_main.name = Set(name);
+                    active_main.update_at = Set(update_at);
+                    active_main.base_state_code = Set(meta.base_state_code);
+                    active_main.user_name = Set(meta.user_name);
+                    active_main.state_index = Set(meta.state_index);
+                    active_main.state_desc = Set(meta.state_desc);
+                    active_main.update(txn).await?;
+
+                    let old = DataEntity::find_by_id(id.clone()).one(txn).await?;
+                    match &old {
+                        Some(_) => DataEntity::update(active_data(id.clone()))
+                            .exec(txn)
+                            .await
+                            .map(|_| ())?,
+                        None => DataEntity::insert(active_data(id.clone()))
+                            .exec(txn)
+                            .await
+                            .map(|_| ())?,
+                    }
+                    if let Some(old) = old {
+                        match release_objects(txn, &model_id, old.format, &old.objects).await {
+                            // 被覆盖的快照清单已损坏时无法释放引用，不影响复制
+                            Err(e) if e.is_corruption() => {}
+                            other => other?,
+                        }
+                    }
+                    Ok(id)
+                }
+                None => {
+                    let id = new_id();
+                    MainActiveModel {
+                        id: Set(id.clone()),
+                        model_id: Set(model_id),
+                        name: Set(name),
+                        index_num: Set(index_num),
+                        update_at: Set(update_at),
+                        base_state_code: Set(meta.base_state_code),
+                        user_name: Set(meta.user_name),
+                        state_index: Set(meta.state_index),
+                        state_desc: Set(meta.state_desc),
+                        type_num: Set(None),
+                    }
+                    .insert(txn)
+                    .await?;
+                    DataEntity::insert(active_data(id.clone()))
+                        .exec(txn)
+                        .await?;
+                    Ok(id)
+                }
+            }
+        })
+    })
+    .await
+    .map_err(|e| match e {
+        TransactionError::Connection(e) => e,
+        TransactionError::Transaction(e) => e,
+    })
+}
+
+/// 把手动快照复制为一条自动快照，返回自动快照 ID。
+/// 复制出的自动快照保留原保存时间并设为固定，避免被保留策略按时间直接删除
+pub async fn demote_shutter(id: String, model_id: String) -> Result<i32, DbErr> {
+    let db = get_shutter_db().await?;
+    let (main, data) = MainEntity::find_by_id(id.clone())
+        .filter(MainColumn::ModelId.eq(model_id.clone()))
+        .find_also_related(DataEntity)
+        .one(db)
+        .await?
+        .ok_or_else(|| DbErr::RecordNotFound(format!("快照 {} 不存在", id)))?;
+    let data = data.ok_or_else(|| DbErr::RecordNotFound(format!("快照 {} 缺少数据", id)))?;
+    let raw = export_raw_snapshot(
+        db,
+        &main.model_id,
+        data.format,
+        data.objects,
+        data.sysvars,
+        data.checksum,
+    )
+    .await?;
+
+    let meta = ShutterTransferMeta {
+        update_at: string_to_integer(&main.update_at)
+            .unwrap_or_else(|| Utc::now().timestamp_millis()),
+        base_state_code: main.base_state_code,
+        user_name: main.user_name,
+        state_index: main.state_index,
+        state_desc: main.state_desc,
+    };
+    import_model_auto_shutter_raw(model_id, meta, raw, String::new(), true).await
+}
@@ -5,4 +5,7 @@ pub mod database_config;
 pub mod database_physical_property;
 pub mod database_shutter;
 pub mod interface_trait;
+pub mod model_archive_service;
+pub mod shutter_chunk;
+pub mod trend_export_service;
 pub mod until_handle;
@@ -0,0 +1,236 @@
+use std::collections::BTreeMap;
+use std::io::{Read, Write};
+
+use crate::service_database::database_auto_shutter::db_auto_shutter_connection::get_auto_shutter_db;
+use crate::service_database::database_auto_shutter::service::auto_shutter_service::{
+    AutoShutterArchiveItem, get_all_model_auto_shutter_entity_full, insert_model_auto_shutters,
+};
+use crate::service_database::database_business::db_business_connection::get_business_db;
+use crate::service_database::database_business::service::model_config::model_bundle_service::{
+    ModelBundle, insert_model_bundle, load_model_bundle, model_name_taken, unique_model_name,
+};
+use crate::service_database::database_shutter::db_shutter_connection::get_shutter_db;
+use crate::service_database::database_shutter::service::shutter_service::{
+    FullShutterModel, get_all_model_shutter_entity_full, insert_model_shutters,
+};
+use crate::tool_handle::id_tool::new_id;
+use crate::tool_handle::time_tool::integer_to_string;
+use chrono::Utc;
+use napi_derive::napi;
+use sea_orm::{DbErr, TransactionTrait};
+use serde::{Deserialize, Serialize};
+
+// ======================================
+// 工程归档：单个模型在 business / shutter / auto_shutter 三个库中的全部数据
+// 打包为一个 zstd 压缩文件，便于在不同工作区之间传递
+// config / physical_property 库为全局字典数据，不按模型拆分，不进入归档
+// ======================================
+
+/// 归档文件头
+const ARCHIVE_MAGIC: &[u8; 8] = b"DTSPMARC";
+/// 归档格式版本，结构发生不兼容变化时递增
+pub const ARCHIVE_FORMAT_VERSION: u32 = 1;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[napi(object, namespace = "modelArchive")]
+pub struct ModelArchiveManifest {
+    pub format_version: u32,
+    pub app_version: String,
+    pub model_id: String,
+    pub model_name: String,
+    pub exported_at: String,
+    /// 各业务表行数
+    pub table_counts: BTreeMap<String, u32>,
+    pub shutter_count: u32,
+    pub auto_shutter_count: u32,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct ModelArchive {
+    manifest: ModelArchiveManifest,
+    business: ModelBundle,
+    shutters: Vec<FullShutterModel>,
+    auto_shutters: Vec<AutoShutterArchiveItem>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+#[napi(object, namespace = "modelArchive")]
+pub struct ModelArchiveImportResult {
+    /// 导入后分配的新模型 ID
+    pub model_id: String,
+    /// 导入后的模型名称
+    pub model_name: String,
+    pub manifest: ModelArchiveManifest,
+}
+
+fn archive_err(msg: impl std::fmt::Display) -> DbErr {
+    DbErr::Custom(format!("工程归档错误: {}", msg))
+}
+
+fn encode_archive(archive: &ModelArchive) -> Result<Vec<u8>, DbErr> {
+    let json = serde_json::to_vec(archive).map_err(archive_err)?;
+    let mut out = ARCHIVE_MAGIC.to_vec();
+    let mut encoder = zstd::Encoder::new(&mut out, 3).map_err(archive_err)?;
+    encoder.write_all(&json).map_err(archive_err)?;
+    encoder.finish().map_err(archive_err)?;
+    Ok(out)
+}
+
+fn decode_archive(bytes: &[u8]) -> Result<ModelArchive, DbErr> {
+    let payload = bytes
+        .strip_prefix(ARCHIVE_MAGIC.as_slice())
+        .ok_or_else(|| archive_err("不是有效的模型归档文件"))?;
+    let mut json = Vec::new();
+    zstd::Decoder::new(payload)
+        .map_err(archive_err)?
+        .read_to_end(&mut json)
+        .map_err(archive_err)?;
+
+    // 先只解析清单检查版本，避免新版本格式在字段层面报出难以理解的错误
+    #[derive(Deserialize)]
+    struct ManifestOnly {
+        manifest: ModelArchiveManifest,
+    }
+    let head: ManifestOnly = serde_json::from_slice(&json).map_err(archive_err)?;
+    if head.manifest.format_version > ARCHIVE_FORMAT_VERSION {
+        return Err(archive_err(format!(
+            "归档版本 {} 高于当前支持的版本 {}，请升级软件",
+            head.manifest.format_version, ARCHIVE_FORMAT_VERSION
+        )));
+    }
+    serde_json::from_slice(&json).map_err(archive_err)
+}
+
+fn read_archive_file(path: &str) -> Result<ModelArchive, DbErr> {
+    let bytes =
+        std::fs::read(path).map_err(|e| archive_err(format!("无法读取 {}: {}", path, e)))?;
+    decode_archive(&bytes)
+}
+
+/// 导出模型为归档文件，返回清单
+pub async fn export_model_archive(
+    model_id: String,
+    path: String,
+) -> Result<ModelArchiveManifest, DbErr> {
+    let db = get_business_db().await?;
+    let business = load_model_bundle(db, &model_id).await?;
+    let shutters = get_all_model_shutter_entity_full(model_id.clone()).await?;
+    let auto_shutters = get_all_model_auto_shutter_entity_full(model_id.clone()).await?;
+
+    let manifest = ModelArchiveManifest {
+        format_version: ARCHIVE_FORMAT_VERSION,
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        model_name: business
+            .model
+            .as_ref()
+            .map(|m| m.model_name.clone())
+            .unwrap_or_default(),
+        model_id,
+        exported_at: integer_to_string(Utc::now().timestamp_millis()),
+        table_counts: business.table_counts(),
+        shutter_count: shutters.len() as u32,
+        auto_shutter_count: auto_shutters.len() as u32,
+    };
+    let archive = ModelArchive {
+        manifest: manifest.clone(),
+        business,
+        shutters,
+        auto_shutters,
+    };
+    let bytes = encode_archive(&archive)?;
+
+    let target = std::path::Path::new(&path);
+    if let Some(parent) = target.parent()
+        && !parent.as_os_str().is_empty()
+        && !parent.exists()
+    {
+        std::fs::create_dir_all(parent).map_err(archive_err)?;
+    }
+    std::fs::write(target, bytes).map_err(|e| archive_err(format!("无法写入 {}: {}", path, e)))?;
+
+    eprintln!("✅ [ModelArchive] 模型已导出至: {}", path);
+    Ok(manifest)
+}
+
+/// 仅读取归档清单（导入前预览）
+pub async fn read_model_archive_manifest(path: String) -> Result<ModelArchiveManifest, DbErr> {
+    Ok(read_archive_file(&path)?.manifest)
+}
+
+/// 导入归档文件，所有主键重新生成，返回新的模型 ID。
+/// 指定 new_name 时该名称不能与现有模型重复；未指定时沿用归档中的名称，重名则追加序号
+pub async fn import_model_archive(
+    path: String,
+    new_name: Option<String>,
+) -> Result<ModelArchiveImportResult, DbErr> {
+    let archive = read_archive_file(&path)?;
+    let new_model_id = new_id();
+    let (mut business, _) = archive.business.remap(&new_model_id);
+
+    // 业务数据与两类快照分属三个库文件，各开一个事务：全部写入成功后才提交，
+    // 任一步失败时未提交的事务随 drop 回滚，不会留下半个模型。
+    // 提交顺序为自动快照 → 快照 → 业务数据，业务数据最后提交，
+    // 即使中途提交失败，也只会残留没有模型引用的快照，不会出现缺快照的模型。
+    let business_txn = get_business_db().await?.begin().await?;
+    let shutter_txn = get_shutter_db().await?.begin().await?;
+    let auto_shutter_txn = get_auto_shutter_db().await?.begin().await?;
+
+    let model_name = match new_name {
+        Some(name) if model_name_taken(&business_txn, &name).await? => {
+            return Err(DbErr::Custom(format!("模型名称已存在: {}", name)));
+        }
+        Some(name) => name,
+        None => unique_model_name(&business_txn, &archive.manifest.model_name).await?,
+    };
+    if let Some(m) = business.model.as_mut() {
+        m.model_name = model_name.clone();
+    }
+    insert_model_bundle(&business_txn, business).await?;
+    insert_model_shutters(&shutter_txn, archive.shutters, &new_model_id).await?;
+    insert_model_auto_shutters(&auto_shutter_txn, archive.auto_shutters, &new_model_id).await?;
+
+    auto_shutter_txn.commit().await?;
+    shutter_txn.commit().await?;
+    business_txn.commit().await?;
+
+    eprintln!("✅ [ModelArchive] 模型已导入: {}", new_model_id);
+    Ok(ModelArchiveImportResult {
+        model_id: new_model_id,
+        model_name,
+        manifest: archive.manifest,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_archive_round_trip() {
+        let archive = ModelArchive {
+            manifest: ModelArchiveManifest {
+                format_version: ARCHIVE_FORMAT_VERSION,
+                app_version: "test".to_string(),
+                model_id: "m1".to_string(),
+                model_name: "demo".to_string(),
+                exported_at: String::new(),
+                table_counts: BTreeMap::new(),
+                shutter_count: 0,
+                auto_shutter_count: 0,
+            },
+            business: ModelBundle::default(),
+            shutters: vec![],
+            auto_shutters: vec![],
+        };
+        let bytes = encode_archive(&archive).unwrap();
+        assert!(bytes.starts_with(ARCHIVE_MAGIC));
+        let decoded = decode_archive(&bytes).unwrap();
+        assert_eq!(decoded.manifest.model_name, "demo");
+
+        // 非归档文件与高版本归档均应拒绝
+        assert!(decode_archive(b"not an archive").is_err());
+        let mut newer = archive.clone();
+        newer.manifest.format_version = ARCHIVE_FORMAT_VERSION + 1;
+        assert!(decode_archive(&encode_archive(&newer).unwrap()).is_err());
+    }
+}
@@ -0,0 +1,899 @@
+use crate::error_handle::err_handle::ShutterDataError;
+use crate::service_database::shutter_chunk::entity::model_shutter_chunk_entity::{
+    ActiveModel as ChunkActiveModel, Column as ChunkColumn, Entity as ChunkEntity,
+    Model as ChunkModel,
+};
+use crate::service_database::shutter_chunk::entity::model_shutter_dict_entity::{
+    ActiveModel as DictActiveModel, Column as DictColumn, Entity as DictEntity, Model as DictModel,
+};
+use crate::tool_handle::id_tool::new_id;
+use chrono::Utc;
+use napi_derive::napi;
+use sea_orm::{
+    ColumnTrait, ConnectionTrait, DbErr, EntityTrait, FromQueryResult, QueryFilter, QueryOrder,
+    QuerySelect, Set, sea_query::Expr,
+};
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::value::RawValue;
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashMap};
+
+// ======================================
+// 快照内容寻址存储
+// objects 按图元拆分，每个图元的原文以 SHA-256 为键存入数据块表（同一模型内同内容只存一份），
+// 快照数据表只保存压缩后的清单（图元之间的原文 + 哈希），还原结果与保存时逐字节一致。
+// 数据块按 (model_id, hash) 归属于模型并按引用计数回收。
+// 模型训练过字典后，新数据块使用该模型最新的字典压缩。
+// ======================================
+
+/// 数据表 format：整体压缩的 objects（旧格式）
+pub const FORMAT_FULL: i32 = 0;
+/// 数据表 format：objects 为数据块清单
+pub const FORMAT_MANIFEST: i32 = 1;
+
+const COMPRESSION_LEVEL: i32 = 3;
+/// 单条 SQL 中 IN 列表的长度上限，避免超过 SQLite 变量个数限制
+const BATCH_SIZE: usize = 500;
+/// 训练字典时最多取样的数据块数
+const DICT_MAX_SAMPLES: u64 = 4000;
+const DEFAULT_DICT_SIZE: u32 = 112 * 1024;
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+enum ManifestLayout {
+    /// objects 为图元数组
+    Array,
+    /// objects 为 { 图元ID: 图元 }
+    Object,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+struct ManifestEntry {
+    /// 图元之前的原文：括号、分隔符、空白与对象键
+    prefix: String,
+    hash: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+struct ShutterManifest {
+    layout: ManifestLayout,
+    entries: Vec<ManifestEntry>,
+    /// 最后一个图元之后的原文
+    suffix: String,
+}
+
+/// 写入数据表的 objects 列
+pub struct StoredObjects {
+    pub format: i32,
+    pub blob: Vec<u8>,
+}
+
+/// 写入数据表的一行快照数据
+pub struct StoredSnapshot {
+    pub format: i32,
+    pub objects: Vec<u8>,
+    pub sysvars: Vec<u8>,
+    pub checksum: String,
+}
+
+/// 在手动快照库与自动快照库之间原样复制的一行快照数据（不解压），
+/// 连同清单引用的数据块及其压缩字典
+pub struct RawSnapshot {
+    pub model_id: String,
+    pub format: i32,
+    pub objects: Vec<u8>,
+    pub sysvars: Vec<u8>,
+    pub checksum: String,
+    counts: BTreeMap<String, i64>,
+    chunks: Vec<ChunkModel>,
+    dicts: Vec<DictModel>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[napi(object, namespace = "shutterChunk")]
+pub struct ShutterDictInfo {
+    pub id: String,
+    pub model_id: String,
+    pub size: u32,
+    pub sample_count: u32,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, FromQueryResult)]
+#[napi(object, namespace = "shutterChunk")]
+pub struct ShutterChunkStats {
+    /// 模型的数据块数
+    pub chunk_count: i64,
+    /// 压缩后字节数
+    pub stored_bytes: i64,
+    /// 未压缩字节数
+    pub raw_bytes: i64,
+    /// 所有快照对数据块的引用总数，与 chunk_count 之比即去重倍数
+    pub ref_count: i64,
+}
+
+fn chunk_err(msg: impl std::fmt::Display) -> DbErr {
+    DbErr::Custom(format!("快照数据块错误: {}", msg))
+}
+
+fn compress(data: &[u8]) -> Result<Vec<u8>, DbErr> {
+    zstd::bulk::compress(data, COMPRESSION_LEVEL).map_err(chunk_err)
+}
+
+/// 解压为文本，损坏时返回明确的错误
+pub fn decompress_text(data: &[u8]) -> Result<String, ShutterDataError> {
+    let decoded =
+        zstd::decode_all(data).map_err(|e| ShutterDataError::Decompress(e.to_string()))?;
+    String::from_utf8(decoded).map_err(|_| ShutterDataError::InvalidUtf8)
+}
+
+pub fn hash_bytes(data: &[u8]) -> String {
+    hex::encode(Sha256::digest(data))
+}
+
+/// 数据表中 objects、sysvars 两个压缩数据的校验和，与数据一同写入
+pub fn blob_checksum(objects: &[u8], sysvars: &[u8]) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update((objects.len() as u64).to_le_bytes());
+    hasher.update(objects);
+    hasher.update(sysvars);
+    hex::encode(hasher.finalize())
+}
+
+/// 拆分后的 objects，均为原文切片：依次拼接 (前缀, 图元) 与 suffix 即得到原文
+struct SplitObjects<'a> {
+    layout: ManifestLayout,
+    units: Vec<(&'a str, &'a str)>,
+    suffix: &'a str,
+}
+
+impl<'a> SplitObjects<'a> {
+    /// 生成清单及各数据块的原文（按哈希去重）
+    fn into_manifest(self) -> (ShutterManifest, HashMap<String, &'a str>) {
+        let mut texts = HashMap::new();
+        let mut entries = Vec::with_capacity(self.units.len());
+        for (prefix, text) in self.units {
+            let hash = hash_bytes(text.as_bytes());
+            entries.push(ManifestEntry {
+                prefix: prefix.to_string(),
+                hash: hash.clone(),
+            });
+            texts.entry(hash).or_insert(text);
+        }
+        let manifest = ShutterManifest {
+            layout: self.layout,
+            entries,
+            suffix: self.suffix.to_string(),
+        };
+        (manifest, texts)
+    }
+}
+
+/// 顶层数组的元素或对象的值，借用原文
+struct RawUnits<'a>(ManifestLayout, Vec<&'a RawValue>);
+
+impl<'de> Deserialize<'de> for RawUnits<'de> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct UnitsVisitor;
+
+        impl<'de> serde::de::Visitor<'de> for UnitsVisitor {
+            type Value = RawUnits<'de>;
+
+            fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                f.write_str("JSON 数组或对象")
+            }
+
+            fn visit_seq<A: serde::de::SeqAccess<'de>>(
+                self,
+                mut seq: A,
+            ) -> Result<Self::Value, A::Error> {
+                let mut units = Vec::new();
+                while let Some(value) = seq.next_element::<&RawValue>()? {
+                    units.push(value);
+                }
+                Ok(RawUnits(ManifestLayout::Array, units))
+            }
+
+            fn visit_map<A: serde::de::MapAccess<'de>>(
+                self,
+                mut map: A,
+            ) -> Result<Self::Value, A::Error> {
+                let mut units = Vec::new();
+                while let Some((_, value)) = map.next_entry::<serde::de::IgnoredAny, &RawValue>()? {
+                    units.push(value);
+                }
+                Ok(RawUnits(ManifestLayout::Object, units))
+            }
+        }
+
+        deserializer.deserialize_any(UnitsVisitor)
+    }
+}
+
+/// 拆分 objects：数组按元素、对象按值拆成图元；其他内容（空、标量）不拆分
+fn split_objects(objects: &str) -> Option<SplitObjects<'_>> {
+    let RawUnits(layout, values) = serde_json::from_str(objects).ok()?;
+    if layout == ManifestLayout::Object && values.is_empty() {
+        return None;
+    }
+    let base = objects.as_ptr() as usize;
+    let mut pos = 0;
+    let mut units = Vec::with_capacity(values.len());
+    for value in values {
+        let text = value.get();
+        let start = text.as_ptr() as usize - base;
+        units.push((&objects[pos..start], text));
+        pos = start + text.len();
+    }
+    Some(SplitObjects {
+        layout,
+        units,
+        suffix: &objects[pos..],
+    })
+}
+
+/// 按清单顺序拼回 objects 文本
+fn assemble(
+    manifest: &ShutterManifest,
+    chunks: &HashMap<String, String>,
+) -> Result<String, ShutterDataError> {
+    let chunk = |hash: &String| {
+        chunks
+            .get(hash)
+            .ok_or_else(|| ShutterDataError::MissingChunk(hash.clone()))
+    };
+    let mut text = String::new();
+    for entry in &manifest.entries {
+        text.push_str(&entry.prefix);
+        text.push_str(chunk(&entry.hash)?);
+    }
+    text.push_str(&manifest.suffix);
+    Ok(text)
+}
+
+fn decode_manifest(blob: &[u8]) -> Result<ShutterManifest, ShutterDataError> {
+    let raw = zstd::decode_all(blob).map_err(|e| ShutterDataError::Manifest(e.to_string()))?;
+    serde_json::from_slice(&raw).map_err(|e| ShutterDataError::Manifest(e.to_string()))
+}
+
+/// 清单中每个哈希被引用的次数
+fn count_refs(manifest: &ShutterManifest) -> BTreeMap<String, i64> {
+    let mut counts = BTreeMap::new();
+    for entry in &manifest.entries {
+        *counts.entry(entry.hash.clone()).or_insert(0) += 1;
+    }
+    counts
+}
+
+/// 按引用次数分组批量调整 ref_count
+async fn adjust_refs<C: ConnectionTrait>(
+    db: &C,
+    model_id: &str,
+    counts: &BTreeMap<String, i64>,
+    sign: i64,
+) -> Result<(), DbErr> {
+    use sea_orm::sea_query::ExprTrait;
+    let mut by_count: BTreeMap<i64, Vec<String>> = BTreeMap::new();
+    for (hash, n) in counts {
+        by_count.entry(*n).or_default().push(hash.clone());
+    }
+    for (n, hashes) in by_count {
+        for batch in hashes.chunks(BATCH_SIZE) {
+            ChunkEntity::update_many()
+                .col_expr(
+                    ChunkColumn::RefCount,
+                    Expr::col(ChunkColumn::RefCount).add(n * sign),
+                )
+                .filter(ChunkColumn::ModelId.eq(model_id))
+                .filter(ChunkColumn::Hash.is_in(batch.to_vec()))
+                .exec(db)
+                .await?;
+        }
+    }
+    Ok(())
+}
+
+/// 模型中已存在的数据块哈希
+async fn existing_hashes<C: ConnectionTrait>(
+    db: &C,
+    model_id: &str,
+    hashes: &[String],
+) -> Result<std::collections::HashSet<String>, DbErr> {
+    let mut existing = std::collections::HashSet::new();
+    for batch in hashes.chunks(BATCH_SIZE) {
+        let found: Vec<String> = ChunkEntity::find()
+            .select_only()
+            .column(ChunkColumn::Hash)
+            .filter(ChunkColumn::ModelId.eq(model_id))
+            .filter(ChunkColumn::Hash.is_in(batch.to_vec()))
+            .into_tuple()
+            .all(db)
+            .await?;
+        existing.extend(found);
+    }
+    Ok(existing)
+}
+
+async fn latest_dict<C: ConnectionTrait>(
+    db: &C,
+    model_id: &str,
+) -> Result<Option<(String, Vec<u8>)>, DbErr> {
+    Ok(DictEntity::find()
+        .filter(DictColumn::ModelId.eq(model_id))
+        .order_by_desc(DictColumn::CreatedAt)
+        .one(db)
+        .await?
+        .map(|d| (d.id, d.data)))
+}
+
+/// 保存 objects：可拆分时写入数据块并返回压缩清单，否则整体压缩
+pub async fn store_objects<C: ConnectionTrait>(
+    db: &C,
+    model_id: &str,
+    objects: &str,
+) -> Result<StoredObjects, DbErr> {
+    let Some(split) = split_objects(objects) else {
+        return Ok(StoredObjects {
+            format: FORMAT_FULL,
+            blob: compress(objects.as_bytes())?,
+        });
+    };
+
+    let (manifest, texts) = split.into_manifest();
+    let counts = count_refs(&manifest);
+
+    // 模型中已存在的数据块只增加引用
+    let hashes: Vec<String> = counts.keys().cloned().collect();
+    let existing = existing_hashes(db, model_id, &hashes).await?;
+    let (old, new): (BTreeMap<String, i64>, BTreeMap<String, i64>) = counts
+        .into_iter()
+        .partition(|(hash, _)| existing.contains(hash));
+    adjust_refs(db, model_id, &old, 1).await?;
+
+    if !new.is_empty() {
+        let dict = latest_dict(db, model_id).await?;
+        let mut compressor = match &dict {
+            Some((_, data)) => zstd::bulk::Compressor::with_dictionary(COMPRESSION_LEVEL, data),
+            None => zstd::bulk::Compressor::new(COMPRESSION_LEVEL),
+        }
+        .map_err(chunk_err)?;
+        let mut rows = Vec::with_capacity(new.len());
+        for (hash, n) in new {
+            let text = texts[&hash];
+            rows.push(ChunkActiveModel {
+                hash: Set(hash.clone()),
+                model_id: Set(model_id.to_string()),
+                dict_id: Set(dict.as_ref().map(|(id, _)| id.clone())),
+                raw_size: Set(text.len() as i64),
+                ref_count: Set(n),
+                data: Set(compressor.compress(text.as_bytes()).map_err(chunk_err)?),
+            });
+        }
+        while !rows.is_empty() {
+            let rest = rows.split_off(rows.len().min(BATCH_SIZE));
+            ChunkEntity::insert_many(std::mem::replace(&mut rows, rest))
+                .exec(db)
+                .await?;
+        }
+    }
+
+    let manifest = serde_json::to_vec(&manifest).map_err(chunk_err)?;
+    Ok(StoredObjects {
+        format: FORMAT_MANIFEST,
+        blob: compress(&manifest)?,
+    })
+}
+
+/// 保存一行快照：objects 按数据块存储，sysvars 整体压缩，并计算两者的校验和
+pub async fn store_snapshot<C: ConnectionTrait>(
+    db: &C,
+    model_id: &str,
+    objects: &str,
+    sysvars: &str,
+) -> Result<StoredSnapshot, DbErr> {
+    let stored = store_objects(db, model_id, objects).await?;
+    let sysvars = compress(sysvars.as_bytes())?;
+    Ok(StoredSnapshot {
+        format: stored.format,
+        checksum: blob_checksum(&stored.blob, &sysvars),
+        objects: stored.blob,
+        sysvars,
+    })
+}
+
+/// 读取数据块并逐个解压、校验，单个数据块损坏不影响其他数据块的结果
+async fn read_chunks<C: ConnectionTrait>(
+    db: &C,
+    model_id: &str,
+    hashes: &[String],
+) -> Result<HashMap<String, Result<String, ShutterDataError>>, DbErr> {
+    let mut rows = Vec::with_capacity(hashes.len());
+    for batch in hashes.chunks(BATCH_SIZE) {
+        rows.extend(
+            ChunkEntity::find()
+                .filter(ChunkColumn::ModelId.eq(model_id))
+                .filter(ChunkColumn::Hash.is_in(batch.to_vec()))
+                .all(db)
+                .await?,
+        );
+    }
+    let dict_ids: Vec<String> = rows
+        .iter()
+        .filter_map(|r| r.dict_id.clone())
+        .collect::<std::collections::BTreeSet<_>>()
+        .into_iter()
+        .collect();
+    let dicts: HashMap<String, Vec<u8>> = if dict_ids.is_empty() {
+        HashMap::new()
+    } else {
+        DictEntity::find()
+            .filter(DictColumn::Id.is_in(dict_ids))
+            .all(db)
+            .await?
+            .into_iter()
+            .map(|d| (d.id, d.data))
+            .collect()
+    };
+
+    let mut decompressors = HashMap::new();
+    let mut chunks = HashMap::with_capacity(rows.len());
+    for row in rows {
+        let key = row.dict_id.clone().unwrap_or_default();
+        if !decompressors.contains_key(&key) {
+            let decompressor = match &row.dict_id {
+                Some(id) => match dicts.get(id) {
+                    Some(dict) => zstd::bulk::Decompressor::with_dictionary(dict).ok(),
+                    None => None,
+                },
+                None => zstd::bulk::Decompressor::new().ok(),
+            };
+            decompressors.insert(key.clone(), decompressor);
+        }
+        let text = match decompressors.get_mut(&key).and_then(Option::as_mut) {
+            None => Err(ShutterDataError::MissingDictionary(key)),
+            Some(d) => d
+                .decompress(&row.data, row.raw_size.max(0) as usize)
+                .map_err(|e| ShutterDataError::Decompress(e.to_string()))
+                .and_then(|raw| String::from_utf8(raw).map_err(|_| ShutterDataError::InvalidUtf8))
+                .and_then(|text| {
+                    // 内容寻址：解压后的哈希必须与键一致
+                    if hash_bytes(text.as_bytes()) == row.hash {
+                        Ok(text)
+                    } else {
+                        Err(ShutterDataError::CorruptedChunk(row.hash.clone()))
+                    }
+                }),
+        };
+        chunks.insert(row.hash, text);
+    }
+    Ok(chunks)
+}
+
+/// 读取数据块，任一数据块损坏即返回错误
+async fn load_chunks<C: ConnectionTrait>(
+    db: &C,
+    model_id: &str,
+    hashes: Vec<String>,
+) -> Result<HashMap<String, String>, ShutterDataError> {
+    let mut chunks = HashMap::with_capacity(hashes.len());
+    for (hash, text) in read_chunks(db, model_id, &hashes).await? {
+        chunks.insert(hash, text?);
+    }
+    Ok(chunks)
+}
+
+/// 还原 objects 文本
+pub async fn load_objects<C: ConnectionTrait>(
+    db: &C,
+    model_id: &str,
+    format: i32,
+    blob: &[u8],
+) -> Result<String, ShutterDataError> {
+    if format != FORMAT_MANIFEST {
+        return decompress_text(blob);
+    }
+    let manifest = decode_manifest(blob)?;
+    let chunks = load_chunks(db, model_id, count_refs(&manifest).into_keys().collect()).await?;
+    assemble(&manifest, &chunks)
+}
+
+/// 校验并还原一行快照数据，返回 (objects, sysvars)。
+/// 校验和为空表示该行写入时尚未记录校验和，只做解压检查
+pub async fn load_snapshot<C: ConnectionTrait>(
+    db: &C,
+    model_id: &str,
+    format: i32,
+    objects: &[u8],
+    sysvars: &[u8],
+    checksum: &str,
+) -> Result<(String, String), ShutterDataError> {
+    if !checksum.is_empty() && blob_checksum(objects, sysvars) != checksum {
+        return Err(ShutterDataError::ChecksumMismatch);
+    }
+    Ok((
+        load_objects(db, model_id, format, objects).await?,
+        decompress_text(sysvars)?,
+    ))
+}
+
+/// 完整性检查：还原一行快照并确认 objects / sysvars 为合法 JSON。
+/// 外层为数据库错误，内层 Err 为损坏原因
+pub async fn check_snapshot<C: ConnectionTrait>(
+    db: &C,
+    model_id: &str,
+    format: i32,
+    objects: &[u8],
+    sysvars: &[u8],
+    checksum: &str,
+) -> Result<Result<(String, String), String>, DbErr> {
+    let (objects, sysvars) =
+        match load_snapshot(db, model_id, format, objects, sysvars, checksum).await {
+            Ok(v) => v,
+            Err(ShutterDataError::Db(e)) => return Err(e),
+            Err(e) => return Ok(Err(e.to_string())),
+        };
+    for (label, text) in [("objects", &objects), ("sysvars", &sysvars)] {
+        if !text.trim().is_empty() && serde_json::from_str::<serde::de::IgnoredAny>(text).is_err() {
+            return Ok(Err(format!("{} 不是有效的 JSON", label)));
+        }
+    }
+    Ok(Ok((objects, sysvars)))
+}
+
+/// 用完好的 objects 修复损坏或缺失的数据块（内容寻址，同内容的数据块哈希相同），返回修复数
+///
+/// manifests 为模型在本库中仍引用数据块的全部清单，缺失的数据块按其中的引用次数重建 ref_count；
+/// 无法解析的清单不计入（释放时同样跳过）。没有清单引用的数据块由随后的写入创建
+pub async fn heal_chunks<C: ConnectionTrait>(
+    db: &C,
+    model_id: &str,
+    objects: &str,
+    manifests: &[Vec<u8>],
+) -> Result<u32, DbErr> {
+    let Some(split) = split_objects(objects) else {
+        return Ok(0);
+    };
+    let (_, texts) = split.into_manifest();
+    let hashes: Vec<String> = texts.keys().cloned().collect();
+    let found = read_chunks(db, model_id, &hashes).await?;
+    let mut healed = 0;
+    for (hash, text) in &found {
+        if text.is_ok() {
+            continue;
+        }
+        let raw = texts[hash];
+        ChunkEntity::update_many()
+            .col_expr(ChunkColumn::Data, Expr::value(compress(raw.as_bytes())?))
+            .col_expr(ChunkColumn::DictId, Expr::value(Option::<String>::None))
+            .col_expr(ChunkColumn::RawSize, Expr::value(raw.len() as i64))
+            .filter(ChunkColumn::ModelId.eq(model_id))
+            .filter(ChunkColumn::Hash.eq(hash))
+            .exec(db)
+            .await?;
+        healed += 1;
+    }
+
+    // 缺失的数据块：按清单引用次数重新插入
+    let mut refs: BTreeMap<String, i64> = BTreeMap::new();
+    for blob in manifests {
+        let Ok(manifest) = decode_manifest(blob) else {
+            continue;
+        };
+        for (hash, n) in count_refs(&manifest) {
+            if texts.contains_key(&hash) && !found.contains_key(&hash) {
+                *refs.entry(hash).or_insert(0) += n;
+            }
+        }
+    }
+    let mut rows = Vec::with_capacity(refs.len());
+    for (hash, n) in refs {
+        let raw = texts[&hash];
+        rows.push(ChunkActiveModel {
+            hash: Set(hash),
+            model_id: Set(model_id.to_string()),
+            dict_id: Set(None),
+            raw_size: Set(raw.len() as i64),
+            ref_count: Set(n),
+            data: Set(compress(raw.as_bytes())?),
+        });
+    }
+    healed += rows.len() as u32;
+    while !rows.is_empty() {
+        let rest = rows.split_off(rows.len().min(BATCH_SIZE));
+        ChunkEntity::insert_many(std::mem::replace(&mut rows, rest))
+            .exec(db)
+            .await?;
+    }
+    Ok(healed)
+}
+
+/// 释放快照对数据块的引用，无引用的数据块随即删除
+pub async fn release_objects<C: ConnectionTrait>(
+    db: &C,
+    model_id: &str,
+    format: i32,
+    blob: &[u8],
+) -> Result<(), ShutterDataError> {
+    if format != FORMAT_MANIFEST {
+        return Ok(());
+    }
+    let counts = count_refs(&decode_manifest(blob)?);
+    adjust_refs(db, model_id, &counts, -1).await?;
+    let hashes: Vec<String> = counts.into_keys().collect();
+    for batch in hashes.chunks(BATCH_SIZE) {
+        ChunkEntity::delete_many()
+            .filter(ChunkColumn::ModelId.eq(model_id))
+            .filter(ChunkColumn::Hash.is_in(batch.to_vec()))
+            .filter(ChunkColumn::RefCount.lte(0))
+            .exec(db)
+            .await?;
+    }
+    Ok(())
+}
+
+/// 读出一行快照引用的数据块与字典用于复制。校验和不一致或缺少数据块时返回错误，避免复制损坏的数据
+pub async fn export_raw_snapshot<C: ConnectionTrait>(
+    db: &C,
+    model_id: &str,
+    format: i32,
+    objects: Vec<u8>,
+    sysvars: Vec<u8>,
+    checksum: String,
+) -> Result<RawSnapshot, ShutterDataError> {
+    if !checksum.is_empty() && blob_checksum(&objects, &sysvars) != checksum {
+        return Err(ShutterDataError::ChecksumMismatch);
+    }
+    let mut raw = RawSnapshot {
+        model_id: model_id.to_string(),
+        format,
+        objects,
+        sysvars,
+        checksum,
+        counts: BTreeMap::new(),
+        chunks: Vec::new(),
+        dicts: Vec::new(),
+    };
+    if format != FORMAT_MANIFEST {
+        return Ok(raw);
+    }
+
+    raw.counts = count_refs(&decode_manifest(&raw.objects)?);
+    let hashes: Vec<String> = raw.counts.keys().cloned().collect();
+    for batch in hashes.chunks(BATCH_SIZE) {
+        raw.chunks.extend(
+            ChunkEntity::find()
+                .filter(ChunkColumn::ModelId.eq(model_id))
+                .filter(ChunkColumn::Hash.is_in(batch.to_vec()))
+                .all(db)
+                .await?,
+        );
+    }
+    if raw.chunks.len() < hashes.len() {
+        let found: std::collections::HashSet<&String> =
+            raw.chunks.iter().map(|c| &c.hash).collect();
+        let missing = hashes.iter().find(|h| !found.contains(h)).cloned();
+        return Err(ShutterDataError::MissingChunk(missing.unwrap_or_default()));
+    }
+    let dict_ids: std::collections::BTreeSet<String> = raw
+        .chunks
+        .iter()
+        .filter_map(|c| c.dict_id.clone())
+        .collect();
+    if !dict_ids.is_empty() {
+        raw.dicts = DictEntity::find()
+            .filter(DictColumn::Id.is_in(dict_ids))
+            .all(db)
+            .await?;
+    }
+    Ok(raw)
+}
+
+/// 在目标库写入快照数据表前导入数据块与字典：已存在的数据块只增加引用，其余原样插入
+pub async fn import_raw_chunks<C: ConnectionTrait>(db: &C, raw: &RawSnapshot) -> Result<(), DbErr> {
+    for dict in &raw.dicts {
+        if DictEntity::find_by_id(dict.id.clone())
+            .one(db)
+            .await?
+            .is_none()
+        {
+            DictEntity::insert(DictActiveModel::from(dict.clone()))
+                .exec(db)
+                .await?;
+        }
+    }
+
+    let hashes: Vec<String> = raw.counts.keys().cloned().collect();
+    let existing = existing_hashes(db, &raw.model_id, &hashes).await?;
+    let old: BTreeMap<String, i64> = raw
+        .counts
+        .iter()
+        .filter(|(hash, _)| existing.contains(*hash))
+        .map(|(hash, n)| (hash.clone(), *n))
+        .collect();
+    adjust_refs(db, &raw.model_id, &old, 1).await?;
+
+    let mut rows: Vec<ChunkActiveModel> = raw
+        .chunks
+        .iter()
+        .filter(|c| !existing.contains(&c.hash))
+        .map(|c| {
+            let mut row = ChunkActiveModel::from(c.clone());
+            row.ref_count = Set(raw.counts.get(&c.hash).copied().unwrap_or(1));
+            row
+        })
+        .collect();
+    while !rows.is_empty() {
+        let rest = rows.split_off(rows.len().min(BATCH_SIZE));
+        ChunkEntity::insert_many(std::mem::replace(&mut rows, rest))
+            .exec(db)
+            .await?;
+    }
+    Ok(())
+}
+
+/// 以模型已有数据块为样本训练 zstd 字典，之后写入的新数据块使用该字典
+pub async fn train_dictionary<C: ConnectionTrait>(
+    db: &C,
+    model_id: &str,
+    max_size: Option<u32>,
+) -> Result<ShutterDictInfo, DbErr> {
+    let hashes: Vec<String> = ChunkEntity::find()
+        .select_only()
+        .column(ChunkColumn::Hash)
+        .filter(ChunkColumn::ModelId.eq(model_id))
+        .limit(DICT_MAX_SAMPLES)
+        .into_tuple()
+        .all(db)
+        .await?;
+    let samples: Vec<Vec<u8>> = load_chunks(db, model_id, hashes)
+        .await?
+        .into_values()
+        .map(String::into_bytes)
+        .collect();
+    let size = max_size.unwrap_or(DEFAULT_DICT_SIZE) as usize;
+    let dict = zstd::dict::from_samples(&samples, size).map_err(|e| {
+        chunk_err(format!(
+            "样本不足，无法训练字典（{} 个数据块）: {}",
+            samples.len(),
+            e
+        ))
+    })?;
+    let info = ShutterDictInfo {
+        id: new_id(),
+        model_id: model_id.to_string(),
+        size: dict.len() as u32,
+        sample_count: samples.len() as u32,
+    };
+    DictEntity::insert(DictActiveModel {
+        id: Set(info.id.clone()),
+        model_id: Set(info.model_id.clone()),
+        created_at: Set(Utc::now().timestamp_millis()),
+        sample_count: Set(info.sample_count as i32),
+        data: Set(dict),
+    })
+    .exec(db)
+    .await?;
+    Ok(info)
+}
+
+/// 模型的数据块统计
+pub async fn chunk_stats<C: ConnectionTrait>(
+    db: &C,
+    model_id: &str,
+) -> Result<ShutterChunkStats, DbErr> {
+    let stats = ChunkEntity::find()
+        .select_only()
+        .column_as(Expr::cust("COUNT(*)"), "chunk_count")
+        .column_as(Expr::cust("COALESCE(SUM(LENGTH(data)), 0)"), "stored_bytes")
+        .column_as(Expr::cust("COALESCE(SUM(raw_size), 0)"), "raw_bytes")
+        .column_as(Expr::cust("COALESCE(SUM(ref_count), 0)"), "ref_count")
+        .filter(ChunkColumn::ModelId.eq(model_id))
+        .into_model::<ShutterChunkStats>()
+        .one(db)
+        .await?;
+    Ok(stats.unwrap_or(ShutterChunkStats {
+        chunk_count: 0,
+        stored_bytes: 0,
+        raw_bytes: 0,
+        ref_count: 0,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 拆分后按清单拼回，返回清单与拼回的文本
+    fn round_trip(objects: &str) -> (ShutterManifest, String) {
+        let (manifest, texts) = split_objects(objects).unwrap().into_manifest();
+        let chunks = texts
+            .into_iter()
+            .map(|(hash, text)| (hash, text.to_string()))
+            .collect();
+        let text = assemble(&manifest, &chunks).unwrap();
+        (manifest, text)
+    }
+
+    #[test]
+    fn test_split_and_assemble() {
+        let objects = r#"[{"id":"V1","p":1.5},{"id":"V2","p":2},{"id":"V1","p":1.5}]"#;
+        let (manifest, back) = round_trip(objects);
+        assert_eq!(manifest.layout, ManifestLayout::Array);
+        // 相同图元只保留一个数据块
+        let counts = count_refs(&manifest);
+        assert_eq!(counts.len(), 2);
+        assert_eq!(counts.values().max(), Some(&2));
+        assert_eq!(back, objects);
+
+        // 键顺序、空白、转义与数字写法都按原文保留
+        let keyed =
+            " {\"z\" : {\"p\":1.50, \"a\":1e3},\n  \"a \\\"x\\\"\": [1, 2] ,\"m\":\"\\u00e9\"}\n";
+        let (manifest, back) = round_trip(keyed);
+        assert_eq!(manifest.layout, ManifestLayout::Object);
+        assert_eq!(back, keyed);
+
+        assert!(split_objects("").is_none());
+        assert!(split_objects("{}").is_none());
+        assert!(split_objects("1").is_none());
+        let manifest_bytes = serde_json::to_vec(&manifest).unwrap();
+        assert_eq!(
+            decode_manifest(&compress(&manifest_bytes).unwrap()).unwrap(),
+            manifest
+        );
+    }
+
+    #[tokio::test]
+    async fn test_heal_missing_chunk() {
+        let db = sea_orm::Database::connect("sqlite::memory:").await.unwrap();
+        let manager = sea_orm_migration::SchemaManager::new(&db);
+        crate::service_database::shutter_chunk::migration::create_chunk_tables(&manager)
+            .await
+            .unwrap();
+
+        let model_id = "heal_model";
+        let objects = r#"[{"id":"V1"},{"id":"V2"},{"id":"V1"}]"#;
+        let first = store_objects(&db, model_id, objects).await.unwrap();
+        let second = store_objects(&db, model_id, objects).await.unwrap();
+        let lost = hash_bytes(br#"{"id":"V1"}"#);
+        ChunkEntity::delete_many()
+            .filter(ChunkColumn::Hash.eq(lost.clone()))
+            .exec(&db)
+            .await
+            .unwrap();
+        assert!(
+            load_objects(&db, model_id, first.format, &first.blob)
+                .await
+                .is_err()
+        );
+
+        let manifests = vec![first.blob.clone(), second.blob.clone()];
+        assert_eq!(
+            heal_chunks(&db, model_id, objects, &manifests)
+                .await
+                .unwrap(),
+            1
+        );
+        assert_eq!(
+            load_objects(&db, model_id, first.format, &first.blob)
+                .await
+                .unwrap(),
+            objects
+        );
+        let row = ChunkEntity::find()
+            .filter(ChunkColumn::ModelId.eq(model_id))
+            .filter(ChunkColumn::Hash.eq(lost))
+            .one(&db)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(row.ref_count, 4);
+
+        // 两份快照都释放后数据块随之回收
+        release_objects(&db, model_id, first.format, &first.blob)
+            .await
+            .unwrap();
+        release_objects(&db, model_id, second.format, &second.blob)
+            .await
+            .unwrap();
+        assert_eq!(ChunkEntity::find().all(&db).await.unwrap().len(), 0);
+    }
+}
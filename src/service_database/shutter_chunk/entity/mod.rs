@@ -0,0 +1,2 @@
+pub mod model_shutter_chunk_entity;
+pub mod model_shutter_dict_entity;
@@ -0,0 +1,27 @@
+use sea_orm::entity::prelude::*;
+
+/// 快照图元数据块：同一模型内同一内容只存一份，由该模型的快照清单按哈希引用。
+/// 主键为 (model_id, hash)，数据块与压缩字典都只属于一个模型
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "model_shutter_chunk_entity")]
+pub struct Model {
+    /// 未压缩内容的 SHA-256（十六进制）
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub hash: String,
+    /// 所属模型
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub model_id: String,
+    /// 压缩使用的字典，为空表示未使用字典
+    pub dict_id: Option<String>,
+    /// 未压缩字节数
+    pub raw_size: i64,
+    /// 被快照清单引用的次数，为 0 时删除
+    pub ref_count: i64,
+    #[sea_orm(column_type = "Blob")]
+    pub data: Vec<u8>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,19 @@
+use sea_orm::entity::prelude::*;
+
+/// 按模型训练的 zstd 字典，数据块记录压缩时使用的字典 ID
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "model_shutter_dict_entity")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: String,
+    pub model_id: String,
+    pub created_at: i64,
+    pub sample_count: i32,
+    #[sea_orm(column_type = "Blob")]
+    pub data: Vec<u8>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,201 @@
+use sea_orm_migration::prelude::*;
+
+// 手动快照库与自动快照库共用的数据块表 / 字典表，以及数据表的存储格式列
+
+pub async fn create_chunk_tables(manager: &SchemaManager<'_>) -> Result<(), DbErr> {
+    manager
+        .create_table(
+            Table::create()
+                .table(ModelShutterChunkEntity::Table)
+                .if_not_exists()
+                .col(
+                    ColumnDef::new(ModelShutterChunkEntity::Hash)
+                        .string_len(64)
+                        .not_null(),
+                )
+                .col(
+                    ColumnDef::new(ModelShutterChunkEntity::ModelId)
+                        .string_len(64)
+                        .not_null(),
+                )
+                .col(
+                    ColumnDef::new(ModelShutterChunkEntity::DictId)
+                        .string_len(36)
+                        .null(),
+                )
+                .col(
+                    ColumnDef::new(ModelShutterChunkEntity::RawSize)
+                        .big_integer()
+                        .not_null()
+                        .default(0),
+                )
+                .col(
+                    ColumnDef::new(ModelShutterChunkEntity::RefCount)
+                        .big_integer()
+                        .not_null()
+                        .default(0),
+                )
+                .col(
+                    ColumnDef::new(ModelShutterChunkEntity::Data)
+                        .blob()
+                        .not_null(),
+                )
+                // 数据块按模型去重，模型之间不共享数据块与字典
+                .primary_key(
+                    Index::create()
+                        .col(ModelShutterChunkEntity::ModelId)
+                        .col(ModelShutterChunkEntity::Hash),
+                )
+                .to_owned(),
+        )
+        .await?;
+    manager
+        .create_table(
+            Table::create()
+                .table(ModelShutterDictEntity::Table)
+                .if_not_exists()
+                .col(
+                    ColumnDef::new(ModelShutterDictEntity::Id)
+                        .string_len(36)
+                        .not_null()
+                        .primary_key(),
+                )
+                .col(
+                    ColumnDef::new(ModelShutterDictEntity::ModelId)
+                        .string_len(64)
+                        .not_null(),
+                )
+                .col(
+                    ColumnDef::new(ModelShutterDictEntity::CreatedAt)
+                        .big_integer()
+                        .not_null(),
+                )
+                .col(
+                    ColumnDef::new(ModelShutterDictEntity::SampleCount)
+                        .integer()
+                        .not_null()
+                        .default(0),
+                )
+                .col(
+                    ColumnDef::new(ModelShutterDictEntity::Data)
+                        .blob()
+                        .not_null(),
+                )
+                .to_owned(),
+        )
+        .await?;
+    Ok(())
+}
+
+pub async fn drop_chunk_tables(manager: &SchemaManager<'_>) -> Result<(), DbErr> {
+    manager
+        .drop_table(
+            Table::drop()
+                .table(ModelShutterChunkEntity::Table)
+                .to_owned(),
+        )
+        .await?;
+    manager
+        .drop_table(
+            Table::drop()
+                .table(ModelShutterDictEntity::Table)
+                .to_owned(),
+        )
+        .await?;
+    Ok(())
+}
+
+/// 数据表增加 format 列：0 为整体压缩（旧格式），1 为数据块清单
+pub async fn add_format_column<T: IntoIden + 'static>(
+    manager: &SchemaManager<'_>,
+    table: T,
+) -> Result<(), DbErr> {
+    manager
+        .alter_table(
+            Table::alter()
+                .table(table)
+                .add_column(
+                    ColumnDef::new(ShutterDataFormat::Format)
+                        .integer()
+                        .not_null()
+                        .default(0),
+                )
+                .to_owned(),
+        )
+        .await
+}
+
+pub async fn drop_format_column<T: IntoIden + 'static>(
+    manager: &SchemaManager<'_>,
+    table: T,
+) -> Result<(), DbErr> {
+    manager
+        .alter_table(
+            Table::alter()
+                .table(table)
+                .drop_column(ShutterDataFormat::Format)
+                .to_owned(),
+        )
+        .await
+}
+
+/// 数据表增加 checksum 列：objects、sysvars 压缩数据的 SHA-256，旧数据为空
+pub async fn add_checksum_column<T: IntoIden + 'static>(
+    manager: &SchemaManager<'_>,
+    table: T,
+) -> Result<(), DbErr> {
+    manager
+        .alter_table(
+            Table::alter()
+                .table(table)
+                .add_column(
+                    ColumnDef::new(ShutterDataFormat::Checksum)
+                        .string_len(64)
+                        .not_null()
+                        .default(""),
+                )
+                .to_owned(),
+        )
+        .await
+}
+
+pub async fn drop_checksum_column<T: IntoIden + 'static>(
+    manager: &SchemaManager<'_>,
+    table: T,
+) -> Result<(), DbErr> {
+    manager
+        .alter_table(
+            Table::alter()
+                .table(table)
+                .drop_column(ShutterDataFormat::Checksum)
+                .to_owned(),
+        )
+        .await
+}
+
+#[derive(Iden)]
+enum ModelShutterChunkEntity {
+    Table,
+    Hash,
+    ModelId,
+    DictId,
+    RawSize,
+    RefCount,
+    Data,
+}
+
+#[derive(Iden)]
+enum ModelShutterDictEntity {
+    Table,
+    Id,
+    ModelId,
+    CreatedAt,
+    SampleCount,
+    Data,
+}
+
+#[derive(Iden)]
+enum ShutterDataFormat {
+    Format,
+    Checksum,
+}
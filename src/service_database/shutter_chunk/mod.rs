@@ -0,0 +1,4 @@
+pub mod chunk_store;
+pub mod entity;
+pub mod migration;
+pub mod snapshot_table;
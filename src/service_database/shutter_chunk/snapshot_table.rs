@@ -0,0 +1,100 @@
+use crate::service_database::shutter_chunk::chunk_store::{
+    FORMAT_FULL, blob_checksum, check_snapshot, load_snapshot, store_objects,
+};
+use sea_orm::{
+    ColumnTrait, ConnectionTrait, DbErr, EntityTrait, QueryFilter, Related, TransactionSession,
+    TransactionTrait,
+};
+
+// ======================================
+// 手动快照、自动快照两张数据表的公共逻辑
+// 两张表列相同、主键类型不同，按数据表实体泛型实现存储整理与完整性校验
+// ======================================
+
+/// 快照数据表：objects / sysvars / format / checksum 四列，与主表一对一
+pub trait SnapshotDataTable: EntityTrait + Related<Self::Main> {
+    /// 快照主表
+    type Main: EntityTrait;
+    /// 主表的模型 ID 列
+    const MAIN_MODEL_ID: <Self::Main as EntityTrait>::Column;
+    /// 数据表的 format 列
+    const FORMAT: Self::Column;
+
+    /// 一行数据的 (format, objects, sysvars, checksum)
+    fn stored(row: &Self::Model) -> (i32, &[u8], &[u8], &str);
+
+    /// 用新的 objects 存储更新一行数据
+    fn with_objects(
+        row: Self::Model,
+        objects: Vec<u8>,
+        format: i32,
+        checksum: String,
+    ) -> Self::ActiveModel;
+}
+
+/// 校验一行快照数据，缺少数据行视为损坏。外层为数据库错误，内层 Err 为损坏原因
+pub async fn check_snapshot_row<D: SnapshotDataTable, C: ConnectionTrait>(
+    db: &C,
+    model_id: &str,
+    data: Option<&D::Model>,
+) -> Result<Result<(String, String), String>, DbErr> {
+    let Some(data) = data else {
+        return Ok(Err("缺少快照数据".to_string()));
+    };
+    let (format, objects, sysvars, checksum) = D::stored(data);
+    check_snapshot(db, model_id, format, objects, sysvars, checksum).await
+}
+
+/// 逐条校验模型的快照，返回 (检查数, 未记录校验和数, 损坏的主表条目及原因)
+pub async fn verify_snapshot_rows<D: SnapshotDataTable, M, C: ConnectionTrait>(
+    db: &C,
+    model_id: &str,
+    rows: Vec<(M, Option<D::Model>)>,
+) -> Result<(u32, u32, Vec<(M, String)>), DbErr> {
+    let (mut checked, mut unverified, mut damaged) = (0, 0, Vec::new());
+    for (main, data) in rows {
+        checked += 1;
+        if data.as_ref().is_some_and(|d| D::stored(d).3.is_empty()) {
+            unverified += 1;
+        }
+        if let Err(reason) = check_snapshot_row::<D, _>(db, model_id, data.as_ref()).await? {
+            damaged.push((main, reason));
+        }
+    }
+    Ok((checked, unverified, damaged))
+}
+
+/// 把模型下旧格式（整体压缩）的快照转存为数据块清单，返回转换的快照数。
+/// 已损坏的快照保持原样，由完整性校验报告
+pub async fn compact_snapshot_storage<D: SnapshotDataTable, C: TransactionTrait>(
+    db: &C,
+    model_id: &str,
+) -> Result<u32, DbErr> {
+    let txn = db.begin().await?;
+    let rows = D::find()
+        .inner_join(D::Main::default())
+        .filter(D::MAIN_MODEL_ID.eq(model_id))
+        .filter(D::FORMAT.eq(FORMAT_FULL))
+        .all(&txn)
+        .await?;
+    let mut count = 0;
+    for row in rows {
+        let (format, objects, sysvars, checksum) = D::stored(&row);
+        let objects = match load_snapshot(&txn, model_id, format, objects, sysvars, checksum).await
+        {
+            Ok((objects, _)) => objects,
+            Err(e) if e.is_corruption() => continue,
+            Err(e) => return Err(e.into()),
+        };
+        let stored = store_objects(&txn, model_id, &objects).await?;
+        if stored.format == FORMAT_FULL {
+            continue;
+        }
+        let checksum = blob_checksum(&stored.blob, sysvars);
+        let active = D::with_objects(row, stored.blob, stored.format, checksum);
+        D::update(active).exec_without_returning(&txn).await?;
+        count += 1;
+    }
+    txn.commit().await?;
+    Ok(count)
+}
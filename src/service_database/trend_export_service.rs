@@ -0,0 +1,118 @@
+use crate::service_database::database_business::db_business_connection::get_business_db;
+use crate::service_database::database_business::service::util_handle::model_variable_choose_service::get_by_ids_and_model;
+use crate::service_database::database_cache::service::trend_service::query_trend_series;
+use crate::tool_handle::trend_export_tool::{TrendTable, write_csv, write_parquet};
+use napi_derive::napi;
+use sea_orm::DbErr;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::io::BufWriter;
+
+// ======================================
+// 趋势数据导出：缓存与磁盘归档中的原始采样点（不降采样）按仿真时间对齐成宽表，
+// 表头取变量选择表中的显示名称与单位，供教员把培训过程数据交给学员
+// ======================================
+
+#[napi(string_enum, namespace = "variableCurveHandle")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TrendExportFormat {
+    Csv,
+    Parquet,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[napi(object, namespace = "variableCurveHandle")]
+pub struct TrendExportResult {
+    pub file_path: String,
+    /// 数据行数（不含表头）
+    pub row_count: u32,
+    /// 变量列数（不含时间列）
+    pub column_count: u32,
+}
+
+fn export_err(msg: impl std::fmt::Display) -> DbErr {
+    DbErr::Custom(format!("趋势导出错误: {}", msg))
+}
+
+/// 变量列表头：显示名称 (单位)。
+/// 业务库未打开或变量不在选择表中时，退回趋势记录中的名称
+async fn column_headers(
+    model_id: &str,
+    variables: &[(String, String)],
+) -> Result<Vec<String>, DbErr> {
+    let mut chosen: HashMap<String, String> = HashMap::new();
+    if get_business_db().await.is_ok() {
+        let ids: Vec<String> = variables.iter().map(|v| v.0.clone()).collect();
+        for m in get_by_ids_and_model(ids, model_id.to_string()).await? {
+            let header = if m.unit_code.is_empty() {
+                m.show_name
+            } else {
+                format!("{} ({})", m.show_name, m.unit_code)
+            };
+            chosen.insert(m.id, header);
+        }
+    }
+    Ok(variables
+        .iter()
+        .map(|(id, name)| {
+            chosen.remove(id).unwrap_or_else(|| {
+                if name.is_empty() {
+                    id.clone()
+                } else {
+                    name.clone()
+                }
+            })
+        })
+        .collect())
+}
+
+/// 导出趋势配置在仿真时间窗口内的数据到文件，窗口端点为空时不限制
+pub async fn export_trend(
+    model_id: String,
+    config_id: String,
+    start_time: Option<f64>,
+    end_time: Option<f64>,
+    format: TrendExportFormat,
+    file_path: String,
+) -> Result<TrendExportResult, DbErr> {
+    let series = query_trend_series(
+        model_id.clone(),
+        config_id,
+        vec![],
+        start_time,
+        end_time,
+        None,
+        None,
+    )
+    .await?;
+    let variables: Vec<(String, String)> = series
+        .iter()
+        .map(|s| (s.variable_id.clone(), s.name.clone()))
+        .collect();
+    let headers = column_headers(&model_id, &variables).await?;
+    let points: Vec<Vec<(f64, f64)>> = series
+        .into_iter()
+        .map(|s| s.times.into_iter().zip(s.values).collect())
+        .collect();
+    let table = TrendTable::from_series(headers, &points);
+
+    let path = std::path::Path::new(&file_path);
+    if let Some(parent) = path.parent()
+        && !parent.as_os_str().is_empty()
+    {
+        std::fs::create_dir_all(parent).map_err(export_err)?;
+    }
+    let file = std::fs::File::create(path)
+        .map_err(|e| export_err(format!("无法写入 {}: {}", file_path, e)))?;
+    match format {
+        TrendExportFormat::Csv => write_csv(&table, BufWriter::new(file)),
+        TrendExportFormat::Parquet => write_parquet(&table, file),
+    }
+    .map_err(export_err)?;
+
+    Ok(TrendExportResult {
+        file_path,
+        row_count: table.row_count() as u32,
+        column_count: table.headers.len() as u32,
+    })
+}
@@ -0,0 +1,79 @@
+// ======================================
+// 缓存表容量上限
+// 数据按写入顺序切成淘汰单元（撤销步骤、曲线记录或趋势数据块），从最旧的单元开始淘汰，
+// 直到剩余的行数与字节数都不超过上限；上限为空时不限制该项
+// ======================================
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct CacheLimit {
+    pub max_rows: Option<i64>,
+    pub max_bytes: Option<i64>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EvictionUnit {
+    pub rows: i64,
+    pub bytes: i64,
+}
+
+/// 应淘汰的最旧单元数；units 按从旧到新排列
+pub fn eviction_count(units: &[EvictionUnit], limit: CacheLimit) -> usize {
+    let mut rows: i64 = units.iter().map(|u| u.rows).sum();
+    let mut bytes: i64 = units.iter().map(|u| u.bytes).sum();
+    let over = |rows: i64, bytes: i64| {
+        limit.max_rows.is_some_and(|m| rows > m) || limit.max_bytes.is_some_and(|m| bytes > m)
+    };
+    let mut count = 0;
+    for unit in units {
+        if !over(rows, bytes) {
+            break;
+        }
+        rows -= unit.rows;
+        bytes -= unit.bytes;
+        count += 1;
+    }
+    count
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn units() -> Vec<EvictionUnit> {
+        vec![
+            EvictionUnit {
+                rows: 2,
+                bytes: 100,
+            },
+            EvictionUnit {
+                rows: 1,
+                bytes: 500,
+            },
+            EvictionUnit { rows: 3, bytes: 50 },
+        ]
+    }
+
+    #[test]
+    fn test_eviction_count() {
+        // 不限制时不淘汰
+        assert_eq!(eviction_count(&units(), CacheLimit::default()), 0);
+        // 行数上限：6 行 -> 不超过 4 行需淘汰最旧的一个单元
+        let rows = CacheLimit {
+            max_rows: Some(4),
+            max_bytes: None,
+        };
+        assert_eq!(eviction_count(&units(), rows), 1);
+        // 字节上限：650 字节 -> 不超过 100 字节需淘汰前两个单元
+        let bytes = CacheLimit {
+            max_rows: None,
+            max_bytes: Some(100),
+        };
+        assert_eq!(eviction_count(&units(), bytes), 2);
+        // 两项同时限制时取淘汰更多的一项
+        let both = CacheLimit {
+            max_rows: Some(5),
+            max_bytes: Some(0),
+        };
+        assert_eq!(eviction_count(&units(), both), 3);
+    }
+}
@@ -0,0 +1,718 @@
+use calamine::{Data, Range, Reader, open_workbook_auto};
+use std::collections::HashMap;
+
+// ======================================
+// DCS 位号表导入
+// 解析各厂商组态软件导出的位号清单：分隔文本（CSV、制表符分隔的 Excel 复制内容、分号分隔的 CSV）
+// 与电子表格文件（xlsx / xlsm / xlsb / xls / ods，读取第一个工作表）。
+// 表头按别名识别：英文 / 中文列名与常见组态参数名（如 HI_HI_LIM、PVHHTP、EU100、量程上限），
+// 位号列之前的标题行、空行与 # 开头的注释行跳过；调用方也可以显式指定列名
+// ======================================
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum DcsTagField {
+    TagName,
+    Description,
+    TypeNum,
+    TypeName,
+    Unit,
+    RangeLow,
+    RangeHigh,
+    AlarmHh,
+    AlarmH,
+    AlarmL,
+    AlarmLl,
+    GraphicId,
+    VariablePath,
+}
+
+impl DcsTagField {
+    pub const ALL: [DcsTagField; 13] = [
+        DcsTagField::TagName,
+        DcsTagField::Description,
+        DcsTagField::TypeNum,
+        DcsTagField::TypeName,
+        DcsTagField::Unit,
+        DcsTagField::RangeLow,
+        DcsTagField::RangeHigh,
+        DcsTagField::AlarmHh,
+        DcsTagField::AlarmH,
+        DcsTagField::AlarmL,
+        DcsTagField::AlarmLl,
+        DcsTagField::GraphicId,
+        DcsTagField::VariablePath,
+    ];
+
+    /// 字段键（与 DTO 字段名一致，snake_case 或 camelCase 均可识别）
+    pub fn key(&self) -> &'static str {
+        match self {
+            DcsTagField::TagName => "tag_name",
+            DcsTagField::Description => "description",
+            DcsTagField::TypeNum => "type_num",
+            DcsTagField::TypeName => "type_name",
+            DcsTagField::Unit => "unit",
+            DcsTagField::RangeLow => "range_low",
+            DcsTagField::RangeHigh => "range_high",
+            DcsTagField::AlarmHh => "alarm_hh",
+            DcsTagField::AlarmH => "alarm_h",
+            DcsTagField::AlarmL => "alarm_l",
+            DcsTagField::AlarmLl => "alarm_ll",
+            DcsTagField::GraphicId => "graphic_id",
+            DcsTagField::VariablePath => "variable_path",
+        }
+    }
+
+    /// 中文名称（用于错误提示）
+    pub fn label(&self) -> &'static str {
+        match self {
+            DcsTagField::TagName => "位号",
+            DcsTagField::Description => "描述",
+            DcsTagField::TypeNum => "类型编号",
+            DcsTagField::TypeName => "类型",
+            DcsTagField::Unit => "工程单位",
+            DcsTagField::RangeLow => "量程下限",
+            DcsTagField::RangeHigh => "量程上限",
+            DcsTagField::AlarmHh => "高高限",
+            DcsTagField::AlarmH => "高限",
+            DcsTagField::AlarmL => "低限",
+            DcsTagField::AlarmLl => "低低限",
+            DcsTagField::GraphicId => "图元",
+            DcsTagField::VariablePath => "变量路径",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<DcsTagField> {
+        let key = normalize_header(key);
+        DcsTagField::ALL
+            .into_iter()
+            .find(|f| normalize_header(f.key()) == key)
+    }
+
+    /// 表头别名（已按 normalize_header 规整）
+    fn aliases(&self) -> &'static [&'static str] {
+        match self {
+            DcsTagField::TagName => &[
+                "tagname",
+                "tag",
+                "tagno",
+                "name",
+                "pointname",
+                "point",
+                "modulename",
+                "module",
+                "位号",
+                "位号名",
+                "位号名称",
+                "点名",
+                "点名称",
+            ],
+            DcsTagField::Description => &[
+                "description",
+                "desc",
+                "descriptor",
+                "comment",
+                "tagcomment",
+                "描述",
+                "位号描述",
+                "说明",
+                "注释",
+            ],
+            DcsTagField::TypeNum => &["typenum", "typeno", "typecode", "类型编号", "类型代码"],
+            DcsTagField::TypeName => &[
+                "typename",
+                "type",
+                "pointtype",
+                "blocktype",
+                "iotype",
+                "signaltype",
+                "类型",
+                "点类型",
+                "位号类型",
+                "信号类型",
+            ],
+            DcsTagField::Unit => &[
+                "unit",
+                "units",
+                "engunit",
+                "engunits",
+                "engineeringunit",
+                "engineeringunits",
+                "eu",
+                "egu",
+                "pveudesc",
+                "单位",
+                "工程单位",
+            ],
+            DcsTagField::RangeLow => &[
+                "rangelow",
+                "lowrange",
+                "rangemin",
+                "scalelow",
+                "sl",
+                "lrv",
+                "eu0",
+                "eulo",
+                "pveulo",
+                "min",
+                "量程下限",
+                "量程低限",
+                "下限",
+            ],
+            DcsTagField::RangeHigh => &[
+                "rangehigh",
+                "highrange",
+                "rangemax",
+                "scalehigh",
+                "sh",
+                "urv",
+                "eu100",
+                "euhi",
+                "pveuhi",
+                "max",
+                "量程上限",
+                "量程高限",
+                "上限",
+            ],
+            DcsTagField::AlarmHh => &[
+                "alarmhh",
+                "hh",
+                "hihi",
+                "hhlim",
+                "hihilim",
+                "hhalarm",
+                "pvhhtp",
+                "高高限",
+                "高高报",
+                "高高报警",
+                "高高报警限",
+            ],
+            DcsTagField::AlarmH => &[
+                "alarmh",
+                "h",
+                "hi",
+                "ph",
+                "hlim",
+                "hilim",
+                "halarm",
+                "hialarm",
+                "pvhitp",
+                "高限",
+                "高报",
+                "高报警",
+                "高报警限",
+            ],
+            DcsTagField::AlarmL => &[
+                "alarml",
+                "l",
+                "lo",
+                "pl",
+                "llim",
+                "lolim",
+                "lalarm",
+                "loalarm",
+                "pvlotp",
+                "低限",
+                "低报",
+                "低报警",
+                "低报警限",
+            ],
+            DcsTagField::AlarmLl => &[
+                "alarmll",
+                "ll",
+                "lolo",
+                "lllim",
+                "lololim",
+                "llalarm",
+                "pvlltp",
+                "低低限",
+                "低低报",
+                "低低报警",
+                "低低报警限",
+            ],
+            DcsTagField::GraphicId => &["graphicid", "graphic", "图元", "图元id", "图元编号"],
+            DcsTagField::VariablePath => &[
+                "variablepath",
+                "variable",
+                "varpath",
+                "path",
+                "变量",
+                "变量路径",
+                "关联变量",
+            ],
+        }
+    }
+
+    fn is_numeric(&self) -> bool {
+        matches!(
+            self,
+            DcsTagField::RangeLow
+                | DcsTagField::RangeHigh
+                | DcsTagField::AlarmHh
+                | DcsTagField::AlarmH
+                | DcsTagField::AlarmL
+                | DcsTagField::AlarmLl
+        )
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct DcsTagRecord {
+    pub tag_name: String,
+    pub description: String,
+    pub type_num: i32,
+    pub type_name: String,
+    pub unit: String,
+    pub range_low: Option<f64>,
+    pub range_high: Option<f64>,
+    pub alarm_hh: Option<f64>,
+    pub alarm_h: Option<f64>,
+    pub alarm_l: Option<f64>,
+    pub alarm_ll: Option<f64>,
+    pub graphic_id: String,
+    pub variable_path: String,
+}
+
+impl DcsTagRecord {
+    fn number_mut(&mut self, field: DcsTagField) -> Option<&mut Option<f64>> {
+        match field {
+            DcsTagField::RangeLow => Some(&mut self.range_low),
+            DcsTagField::RangeHigh => Some(&mut self.range_high),
+            DcsTagField::AlarmHh => Some(&mut self.alarm_hh),
+            DcsTagField::AlarmH => Some(&mut self.alarm_h),
+            DcsTagField::AlarmL => Some(&mut self.alarm_l),
+            DcsTagField::AlarmLl => Some(&mut self.alarm_ll),
+            _ => None,
+        }
+    }
+
+    fn set_text(&mut self, field: DcsTagField, value: String) {
+        match field {
+            DcsTagField::TagName => self.tag_name = value,
+            DcsTagField::Description => self.description = value,
+            DcsTagField::TypeName => self.type_name = value,
+            DcsTagField::Unit => self.unit = value,
+            DcsTagField::GraphicId => self.graphic_id = value,
+            DcsTagField::VariablePath => self.variable_path = value,
+            _ => {}
+        }
+    }
+}
+
+/// 校验位号：位号非空、量程下限小于上限、报警限满足 LL ≤ L ≤ H ≤ HH（未设置的限值跳过）
+pub fn validate_tag(tag: &DcsTagRecord) -> Result<(), String> {
+    if tag.tag_name.trim().is_empty() {
+        return Err("位号不能为空".to_string());
+    }
+    if let (Some(low), Some(high)) = (tag.range_low, tag.range_high)
+        && low >= high
+    {
+        return Err(format!(
+            "位号 {} 的量程下限 {} 不小于上限 {}",
+            tag.tag_name, low, high
+        ));
+    }
+    let limits = [
+        (DcsTagField::AlarmLl, tag.alarm_ll),
+        (DcsTagField::AlarmL, tag.alarm_l),
+        (DcsTagField::AlarmH, tag.alarm_h),
+        (DcsTagField::AlarmHh, tag.alarm_hh),
+    ];
+    let set: Vec<(DcsTagField, f64)> = limits
+        .into_iter()
+        .filter_map(|(field, value)| value.map(|v| (field, v)))
+        .collect();
+    for pair in set.windows(2) {
+        if pair[0].1 > pair[1].1 {
+            return Err(format!(
+                "位号 {} 的{} {} 大于{} {}",
+                tag.tag_name,
+                pair[0].0.label(),
+                pair[0].1,
+                pair[1].0.label(),
+                pair[1].1
+            ));
+        }
+    }
+    Ok(())
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct DcsTagIssue {
+    /// 源文件中的行号（从 1 开始）
+    pub line: usize,
+    pub message: String,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct DcsTagTable {
+    /// 文件中出现的字段，导入时只覆盖这些字段
+    pub columns: Vec<DcsTagField>,
+    pub tags: Vec<DcsTagRecord>,
+    /// tags 中各位号所在的行号
+    pub lines: Vec<usize>,
+    /// 被跳过的数据行数
+    pub skipped: usize,
+    /// 被跳过的行中填写了的位号；整表替换时这些位号不能当作已从文件中移除
+    pub skipped_names: Vec<String>,
+    pub issues: Vec<DcsTagIssue>,
+}
+
+impl DcsTagTable {
+    /// 记录一条被跳过的数据行
+    fn skip(&mut self, name: &str) {
+        self.skipped += 1;
+        if !name.is_empty() {
+            self.skipped_names.push(name.to_string());
+        }
+    }
+}
+
+/// 表头规整：转小写，只保留字母、数字与汉字
+pub fn normalize_header(header: &str) -> String {
+    header
+        .chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// 按首个有内容的行猜测分隔符：制表符、分号或逗号中出现次数最多的一个
+pub fn detect_delimiter(text: &str) -> char {
+    let line = text
+        .lines()
+        .find(|l| !l.trim().is_empty() && !l.trim_start().starts_with('#'))
+        .unwrap_or("");
+    ['\t', ';', ',']
+        .into_iter()
+        .max_by_key(|d| line.matches(*d).count())
+        .filter(|d| line.contains(*d))
+        .unwrap_or(',')
+}
+
+/// 拆分带引号的分隔文本，返回 (起始行号, 单元格)；引号内可包含分隔符与换行，"" 表示一个引号
+pub fn split_records(text: &str, delimiter: char) -> Vec<(usize, Vec<String>)> {
+    let text = text.strip_prefix('\u{feff}').unwrap_or(text);
+    let mut records = Vec::new();
+    let mut record: Vec<String> = Vec::new();
+    let mut cell = String::new();
+    let mut in_quotes = false;
+    let mut line = 1;
+    let mut start_line = 1;
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        if in_quotes {
+            match c {
+                '"' if chars.peek() == Some(&'"') => {
+                    cell.push('"');
+                    chars.next();
+                }
+                '"' => in_quotes = false,
+                '\n' => {
+                    line += 1;
+                    cell.push(c);
+                }
+                _ => cell.push(c),
+            }
+            continue;
+        }
+        match c {
+            '"' if cell.trim().is_empty() => {
+                cell.clear();
+                in_quotes = true;
+            }
+            '\r' => {}
+            '\n' => {
+                record.push(std::mem::take(&mut cell));
+                records.push((start_line, std::mem::take(&mut record)));
+                line += 1;
+                start_line = line;
+            }
+            c if c == delimiter => record.push(std::mem::take(&mut cell)),
+            _ => cell.push(c),
+        }
+    }
+    if !cell.is_empty() || !record.is_empty() {
+        record.push(cell);
+        records.push((start_line, record));
+    }
+    records
+}
+
+/// 解析数值单元格：空值为 None；分号 / 制表符分隔的文件允许逗号作小数点
+fn parse_number(text: &str, delimiter: char) -> Result<Option<f64>, ()> {
+    let text = text.trim();
+    if text.is_empty() || text == "-" {
+        return Ok(None);
+    }
+    if let Ok(value) = text.parse::<f64>() {
+        return Ok(value.is_finite().then_some(value));
+    }
+    if delimiter == ',' || text.matches(',').count() != 1 || text.contains('.') {
+        return Err(());
+    }
+    match text.replace(',', ".").parse::<f64>() {
+        Ok(value) => Ok(value.is_finite().then_some(value)),
+        Err(_) => Err(()),
+    }
+}
+
+/// 根据表头确定各列对应的字段；column_map 为 (字段, 表头) 的显式映射，优先于别名
+fn map_columns(
+    headers: &[String],
+    column_map: &[(DcsTagField, String)],
+) -> Vec<Option<DcsTagField>> {
+    let normalized: Vec<String> = headers.iter().map(|h| normalize_header(h)).collect();
+    let mut columns: Vec<Option<DcsTagField>> = vec![None; headers.len()];
+    for (field, header) in column_map {
+        let header = normalize_header(header);
+        if let Some(index) = normalized.iter().position(|h| *h == header) {
+            columns[index] = Some(*field);
+        }
+    }
+    for field in DcsTagField::ALL {
+        if columns.contains(&Some(field)) || column_map.iter().any(|(f, _)| *f == field) {
+            continue;
+        }
+        let index = field.aliases().iter().find_map(|alias| {
+            (0..normalized.len()).find(|&i| columns[i].is_none() && normalized[i] == *alias)
+        });
+        if let Some(index) = index {
+            columns[index] = Some(field);
+        }
+    }
+    columns
+}
+
+/// 解析位号表；delimiter 为空时自动识别
+pub fn parse_dcs_tag_table(
+    text: &str,
+    delimiter: Option<char>,
+    column_map: &[(DcsTagField, String)],
+) -> Result<DcsTagTable, String> {
+    let delimiter = delimiter.unwrap_or_else(|| detect_delimiter(text));
+    parse_dcs_tag_records(split_records(text, delimiter), delimiter, column_map)
+}
+
+/// 电子表格文件的扩展名
+pub const WORKBOOK_EXTENSIONS: [&str; 5] = ["xlsx", "xlsm", "xlsb", "xls", "ods"];
+
+/// 工作表转为 (行号, 单元格)，行号与电子表格中显示的一致；数值单元格按最短的十进制写法输出
+pub fn sheet_records(range: &Range<Data>) -> Vec<(usize, Vec<String>)> {
+    let first_row = range.start().map_or(0, |(row, _)| row as usize);
+    range
+        .rows()
+        .enumerate()
+        .map(|(i, cells)| {
+            (
+                first_row + i + 1,
+                cells.iter().map(|c| c.to_string()).collect(),
+            )
+        })
+        .collect()
+}
+
+/// 读取电子表格文件第一个工作表中的位号表
+pub fn parse_dcs_tag_workbook(
+    path: &str,
+    column_map: &[(DcsTagField, String)],
+) -> Result<DcsTagTable, String> {
+    let mut workbook = open_workbook_auto(path).map_err(|e| format!("无法打开 {}: {}", path, e))?;
+    let range = workbook
+        .worksheet_range_at(0)
+        .ok_or_else(|| format!("{} 中没有工作表", path))?
+        .map_err(|e| format!("无法读取 {} 的工作表: {}", path, e))?;
+    // 单元格中的文本数值允许逗号作小数点
+    parse_dcs_tag_records(sheet_records(&range), '\t', column_map)
+}
+
+/// 解析已拆分好的行；delimiter 为原文件的分隔符，决定数值能否用逗号作小数点
+pub fn parse_dcs_tag_records(
+    records: Vec<(usize, Vec<String>)>,
+    delimiter: char,
+    column_map: &[(DcsTagField, String)],
+) -> Result<DcsTagTable, String> {
+    let records: Vec<(usize, Vec<String>)> = records
+        .into_iter()
+        .filter(|(_, cells)| {
+            cells.iter().any(|c| !c.trim().is_empty()) && !cells[0].trim_start().starts_with('#')
+        })
+        .collect();
+
+    // 找到能识别出位号列的第一行作为表头
+    let header_index = records
+        .iter()
+        .position(|(_, cells)| map_columns(cells, column_map).contains(&Some(DcsTagField::TagName)))
+        .ok_or_else(|| "未找到位号列，请检查表头或指定列映射".to_string())?;
+    let columns = map_columns(&records[header_index].1, column_map);
+
+    let mut table = DcsTagTable {
+        columns: columns.iter().flatten().copied().collect(),
+        ..Default::default()
+    };
+    let name_column = columns
+        .iter()
+        .position(|c| *c == Some(DcsTagField::TagName));
+    let mut positions: HashMap<String, (usize, usize)> = HashMap::new();
+    'rows: for (line, cells) in records.into_iter().skip(header_index + 1) {
+        let name = name_column
+            .and_then(|i| cells.get(i))
+            .map(|c| c.trim().to_string())
+            .unwrap_or_default();
+        let mut tag = DcsTagRecord::default();
+        for (cell, field) in cells.into_iter().zip(&columns) {
+            let Some(field) = field else { continue };
+            let value = cell.trim().to_string();
+            if field.is_numeric() {
+                match parse_number(&value, delimiter) {
+                    Ok(number) => *tag.number_mut(*field).unwrap() = number,
+                    Err(()) => {
+                        table.skip(&name);
+                        table.issues.push(DcsTagIssue {
+                            line,
+                            message: format!("{} 的值 {} 不是数字", field.label(), value),
+                        });
+                        continue 'rows;
+                    }
+                }
+            } else if *field == DcsTagField::TypeNum {
+                match value.parse::<i32>() {
+                    Ok(number) => tag.type_num = number,
+                    Err(_) if value.is_empty() => {}
+                    Err(_) => {
+                        table.skip(&name);
+                        table.issues.push(DcsTagIssue {
+                            line,
+                            message: format!("{} 的值 {} 不是整数", field.label(), value),
+                        });
+                        continue 'rows;
+                    }
+                }
+            } else {
+                tag.set_text(*field, value);
+            }
+        }
+        if let Err(message) = validate_tag(&tag) {
+            table.skip(&name);
+            table.issues.push(DcsTagIssue { line, message });
+            continue;
+        }
+
+        // 同一位号出现多次时以最后一行为准
+        if let Some((index, previous_line)) = positions.get(&tag.tag_name).copied() {
+            table.issues.push(DcsTagIssue {
+                line,
+                message: format!(
+                    "位号 {} 与第 {} 行重复，使用本行数据",
+                    tag.tag_name, previous_line
+                ),
+            });
+            table.skipped += 1;
+            positions.insert(tag.tag_name.clone(), (index, line));
+            table.tags[index] = tag;
+            table.lines[index] = line;
+        } else {
+            positions.insert(tag.tag_name.clone(), (table.tags.len(), line));
+            table.tags.push(tag);
+            table.lines.push(line);
+        }
+    }
+    Ok(table)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_records_quotes() {
+        let text = "\u{feff}a,\"b,1\",\"say \"\"hi\"\"\"\r\nc,\"multi\nline\",d\n";
+        let records = split_records(text, ',');
+        assert_eq!(
+            records,
+            vec![
+                (1, vec!["a".into(), "b,1".into(), "say \"hi\"".into()]),
+                (2, vec!["c".into(), "multi\nline".into(), "d".into()]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_vendor_csv() {
+        // 标题行 + DeltaV 风格参数名
+        let text = "Exported tag list\n\
+                    # comment\n\
+                    Module,Description,EU0,EU100,Units,HI_HI_LIM,HI_LIM,LO_LIM,LO_LO_LIM\n\
+                    FIC101,Feed flow,0,100,m3/h,95,90,10,5\n\
+                    TI102,Reactor temp,0,400,degC,,380,,\n";
+        let table = parse_dcs_tag_table(text, None, &[]).unwrap();
+        assert_eq!(table.tags.len(), 2);
+        assert!(table.issues.is_empty());
+        let fic = &table.tags[0];
+        assert_eq!(fic.tag_name, "FIC101");
+        assert_eq!(fic.unit, "m3/h");
+        assert_eq!(fic.range_high, Some(100.0));
+        assert_eq!(fic.alarm_hh, Some(95.0));
+        assert_eq!(fic.alarm_ll, Some(5.0));
+        assert_eq!(table.tags[1].alarm_hh, None);
+        assert_eq!(table.tags[1].alarm_h, Some(380.0));
+        assert!(!table.columns.contains(&DcsTagField::GraphicId));
+    }
+
+    #[test]
+    fn test_parse_tab_separated_chinese_headers() {
+        let text = "位号\t描述\t量程下限\t量程上限\t高限\t低限\t图元\t变量路径\n\
+                    PI201\t塔顶压力\t0\t1,6\t1,2\t0,2\tcol-1\tcolumn.top.P\n";
+        let table = parse_dcs_tag_table(text, None, &[]).unwrap();
+        let tag = &table.tags[0];
+        assert_eq!(tag.description, "塔顶压力");
+        assert_eq!(tag.range_high, Some(1.6));
+        assert_eq!(tag.alarm_l, Some(0.2));
+        assert_eq!(tag.graphic_id, "col-1");
+        assert_eq!(tag.variable_path, "column.top.P");
+    }
+
+    #[test]
+    fn test_parse_issues_and_column_map() {
+        let text = "Point;Text;HH;H\n\
+                    LI301;Level;90;95\n\
+                    LI302;Level;abc;1\n\
+                    ;Empty;1;2\n\
+                    LI303;Old;2;1\n\
+                    LI303;New;;\n";
+        let column_map = vec![(DcsTagField::Description, "Text".to_string())];
+        let table = parse_dcs_tag_table(text, None, &column_map).unwrap();
+        let names: Vec<&str> = table.tags.iter().map(|t| t.tag_name.as_str()).collect();
+        assert_eq!(names, vec!["LI303"]);
+        assert_eq!(table.tags[0].description, "New");
+        // HH < H、非数字、空位号、重复位号各一条
+        assert_eq!(table.issues.len(), 4);
+        assert_eq!(table.issues[0].line, 2);
+        assert_eq!(table.skipped, 4);
+        assert_eq!(table.skipped_names, vec!["LI301", "LI302"]);
+    }
+
+    #[test]
+    fn test_parse_sheet() {
+        // 工作表从第 2 行开始，数值单元格与文本单元格混用
+        let mut range = Range::new((1, 0), (3, 2));
+        range.set_value((1, 0), Data::String("Tag".into()));
+        range.set_value((1, 1), Data::String("HI_LIM".into()));
+        range.set_value((1, 2), Data::String("LO_LIM".into()));
+        range.set_value((2, 0), Data::String("FI401".into()));
+        range.set_value((2, 1), Data::Float(95.5));
+        range.set_value((2, 2), Data::Int(5));
+        range.set_value((3, 0), Data::String("FI402".into()));
+        range.set_value((3, 1), Data::String("1,5".into()));
+        let table = parse_dcs_tag_records(sheet_records(&range), '\t', &[]).unwrap();
+        assert_eq!(table.lines, vec![3, 4]);
+        assert_eq!(table.tags[0].alarm_h, Some(95.5));
+        assert_eq!(table.tags[0].alarm_l, Some(5.0));
+        assert_eq!(table.tags[1].alarm_h, Some(1.5));
+        assert_eq!(table.tags[1].alarm_l, None);
+    }
+
+    #[test]
+    fn test_missing_tag_column() {
+        assert!(parse_dcs_tag_table("a,b\n1,2\n", None, &[]).is_err());
+    }
+}
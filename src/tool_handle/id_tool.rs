@@ -0,0 +1,76 @@
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::collections::HashMap;
+
+// 主键生成与 ID 重映射功能（模型复制、导入导出共用）
+
+/// 生成一个新的字符串主键（UUID v4）
+pub fn new_id() -> String {
+    uuid::Uuid::new_v4().to_string()
+}
+
+// 列表型字段（如 "id1,id2" 或 "[\"id1\",\"id2\"]"）中的 ID 片段
+static ID_TOKEN: Lazy<Regex> = Lazy::new(|| Regex::new(r"[A-Za-z0-9_\-]+").unwrap());
+
+/// 旧 ID → 新 ID 的映射表
+#[derive(Debug, Default, Clone)]
+pub struct IdRemap {
+    map: HashMap<String, String>,
+}
+
+impl IdRemap {
+    /// 为旧 ID 分配新 ID（同一个旧 ID 多次调用返回相同结果），空字符串保持不变
+    pub fn fresh(&mut self, old: &str) -> String {
+        if old.is_empty() {
+            return String::new();
+        }
+        self.map
+            .entry(old.to_string())
+            .or_insert_with(new_id)
+            .clone()
+    }
+
+    /// 查询映射后的 ID，不在映射表中的引用（如物性库 ID）原样返回
+    pub fn get(&self, old: &str) -> String {
+        self.map
+            .get(old)
+            .cloned()
+            .unwrap_or_else(|| old.to_string())
+    }
+
+    /// 改写列表型引用字段，仅替换完整匹配的 ID 片段，分隔符与格式保持不变
+    pub fn rewrite_list(&self, text: &str) -> String {
+        ID_TOKEN
+            .replace_all(text, |caps: &regex::Captures| self.get(&caps[0]))
+            .into_owned()
+    }
+
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rewrite_list_keeps_format() {
+        let mut remap = IdRemap::default();
+        let a = remap.fresh("pkg-a");
+        let b = remap.fresh("pkg-b");
+        assert_eq!(remap.fresh("pkg-a"), a);
+
+        assert_eq!(remap.rewrite_list("pkg-a,pkg-b"), format!("{},{}", a, b));
+        assert_eq!(
+            remap.rewrite_list("[\"pkg-a\",\"other\"]"),
+            format!("[\"{}\",\"other\"]", a)
+        );
+        // 部分匹配不应被替换
+        assert_eq!(remap.rewrite_list("pkg-ab"), "pkg-ab");
+    }
+}
@@ -0,0 +1,134 @@
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+// ======================================
+// 快照 JSON 比较工具
+// - objects：图元数组（以 id / graphicId 为键）或 { 图元ID: 图元 } 对象
+// - 嵌套对象按 "a.b.c" 展开为叶子路径，数组整体作为一个值比较
+// ======================================
+
+/// 单个变量的变化，before / after 为 None 表示该变量不存在
+#[derive(Clone, Debug, PartialEq)]
+pub struct ValueChange {
+    pub path: String,
+    pub before: Option<Value>,
+    pub after: Option<Value>,
+}
+
+/// 图元的唯一标识：优先 id，其次 graphicId
+pub fn unit_id(unit: &Value) -> Option<String> {
+    ["id", "graphicId"].iter().find_map(|k| match unit.get(k)? {
+        Value::String(s) if !s.is_empty() => Some(s.clone()),
+        Value::Number(n) => Some(n.to_string()),
+        _ => None,
+    })
+}
+
+/// 将 objects 整理为 图元ID → 图元，保持 ID 有序便于输出稳定
+pub fn index_units(objects: &Value) -> Result<BTreeMap<String, Value>, String> {
+    match objects {
+        Value::Null => Ok(BTreeMap::new()),
+        Value::Array(items) => {
+            let mut units = BTreeMap::new();
+            for (i, item) in items.iter().enumerate() {
+                let id = unit_id(item).ok_or_else(|| format!("第 {} 个图元缺少 id", i + 1))?;
+                units.insert(id, item.clone());
+            }
+            Ok(units)
+        }
+        Value::Object(map) => Ok(map.iter().map(|(k, v)| (k.clone(), v.clone())).collect()),
+        _ => Err("objects 须为图元数组或对象".to_string()),
+    }
+}
+
+fn flatten_into(prefix: &str, value: &Value, out: &mut BTreeMap<String, Value>) {
+    match value {
+        Value::Object(map) if !map.is_empty() => {
+            for (k, v) in map {
+                let path = if prefix.is_empty() {
+                    k.clone()
+                } else {
+                    format!("{}.{}", prefix, k)
+                };
+                flatten_into(&path, v, out);
+            }
+        }
+        _ => {
+            out.insert(prefix.to_string(), value.clone());
+        }
+    }
+}
+
+/// 展开为 路径 → 叶子值
+pub fn flatten(value: &Value) -> BTreeMap<String, Value> {
+    let mut out = BTreeMap::new();
+    flatten_into("", value, &mut out);
+    out
+}
+
+/// 逐路径比较两个 JSON 值，只返回有差异的路径
+pub fn diff_values(before: &Value, after: &Value) -> Vec<ValueChange> {
+    let (a, b) = (flatten(before), flatten(after));
+    let mut changes: Vec<ValueChange> = a
+        .iter()
+        .filter(|(path, v)| b.get(*path) != Some(v))
+        .map(|(path, v)| ValueChange {
+            path: path.clone(),
+            before: Some(v.clone()),
+            after: b.get(path).cloned(),
+        })
+        .collect();
+    changes.extend(
+        b.iter()
+            .filter(|(path, _)| !a.contains_key(*path))
+            .map(|(path, v)| ValueChange {
+                path: path.clone(),
+                before: None,
+                after: Some(v.clone()),
+            }),
+    );
+    changes.sort_by(|x, y| x.path.cmp(&y.path));
+    changes
+}
+
+/// 取图元的某个字符串字段（name、type 等）
+pub fn unit_str(unit: &Value, key: &str) -> Option<String> {
+    unit.get(key).and_then(Value::as_str).map(str::to_string)
+}
+
+/// 解析快照中的 JSON 字符串，空串视为 null
+pub fn parse_blob(text: &str) -> Result<Value, String> {
+    if text.trim().is_empty() {
+        return Ok(Value::Null);
+    }
+    serde_json::from_str(text).map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_diff() {
+        let a = json!([
+            { "id": "V1", "name": "V1", "params": { "p": 1.0, "t": 300.0 }, "holdups": [1, 2] },
+            { "id": "P1", "params": { "on": true } }
+        ]);
+        let b = json!([
+            { "id": "V1", "name": "V1", "params": { "p": 2.0, "t": 300.0, "l": 0.5 }, "holdups": [1, 3] },
+            { "id": "F1", "params": {} }
+        ]);
+        let (ua, ub) = (index_units(&a).unwrap(), index_units(&b).unwrap());
+        assert_eq!(ua.keys().collect::<Vec<_>>(), vec!["P1", "V1"]);
+
+        let changes = diff_values(&ua["V1"], &ub["V1"]);
+        let paths: Vec<&str> = changes.iter().map(|c| c.path.as_str()).collect();
+        assert_eq!(paths, vec!["holdups", "params.l", "params.p"]);
+        assert_eq!(changes[1].before, None);
+        assert_eq!(changes[2].after, Some(json!(2.0)));
+
+        assert!(index_units(&json!([{ "name": "x" }])).is_err());
+        assert_eq!(parse_blob(" ").unwrap(), Value::Null);
+    }
+}
@@ -1,4 +1,11 @@
+pub mod cache_limit_tool;
+pub mod dcs_tag_import_tool;
+pub mod id_tool;
+pub mod json_diff_tool;
 pub mod json_status_handle;
 pub mod model_type;
 pub mod result_entity;
+pub mod retention_tool;
 pub mod time_tool;
+pub mod trend_export_tool;
+pub mod trend_tool;
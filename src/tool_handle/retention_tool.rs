@@ -0,0 +1,137 @@
+use std::collections::HashSet;
+
+// ======================================
+// 快照保留策略（祖父-父-子）
+// - keep_all_ms 内的快照全部保留
+// - 之后按分层稀疏：每层覆盖到 max_age_ms，按 interval_ms 切成时间桶，每桶保留最早的一条
+//   （保留最早的一条，新快照老化进入同一桶时不会挤掉已保留的快照，多次执行结果稳定）
+// - 超出最后一层的快照删除；没有任何时间层（keep_all_ms 为 0 且 tiers 为空）时不按时间删除
+// - max_count 限制未固定快照的总数，超出时删除最旧的
+// - 固定（pinned）的快照始终保留，也不计入桶和 max_count
+// ======================================
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct RetentionTier {
+    /// 该层覆盖的最大快照年龄（毫秒）
+    pub max_age_ms: i64,
+    /// 该层每个时间桶的长度（毫秒）
+    pub interval_ms: i64,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RetentionPolicy {
+    pub keep_all_ms: i64,
+    /// 按 max_age_ms 升序
+    pub tiers: Vec<RetentionTier>,
+    pub max_count: Option<usize>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct RetentionItem {
+    pub id: i32,
+    /// 保存时间（毫秒）
+    pub timestamp_ms: i64,
+    pub pinned: bool,
+}
+
+/// 按策略挑出应删除的快照 ID
+pub fn expired_items(now_ms: i64, items: &[RetentionItem], policy: &RetentionPolicy) -> Vec<i32> {
+    let mut tiers = policy.tiers.clone();
+    tiers.sort_by_key(|t| t.max_age_ms);
+    let age_limited = policy.keep_all_ms > 0 || !tiers.is_empty();
+
+    let mut sorted: Vec<&RetentionItem> = items.iter().filter(|i| !i.pinned).collect();
+    sorted.sort_by_key(|i| (i.timestamp_ms, i.id));
+
+    let mut expired = Vec::new();
+    let mut kept = Vec::new();
+    let mut buckets = HashSet::new();
+    for item in sorted {
+        let age = now_ms - item.timestamp_ms;
+        if !age_limited || age <= policy.keep_all_ms {
+            kept.push(item.id);
+            continue;
+        }
+        match tiers.iter().position(|t| age <= t.max_age_ms) {
+            Some(index) => {
+                let bucket = item
+                    .timestamp_ms
+                    .div_euclid(tiers[index].interval_ms.max(1));
+                if buckets.insert((index, bucket)) {
+                    kept.push(item.id);
+                } else {
+                    expired.push(item.id);
+                }
+            }
+            None => expired.push(item.id),
+        }
+    }
+
+    // kept 按时间升序，超出数量时从最旧的开始删除
+    if let Some(max) = policy.max_count
+        && kept.len() > max
+    {
+        expired.extend(kept.drain(..kept.len() - max));
+    }
+    expired
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MIN: i64 = 60_000;
+
+    fn item(id: i32, minutes_ago: i64, pinned: bool) -> RetentionItem {
+        RetentionItem {
+            id,
+            timestamp_ms: 1000 * 60 * MIN - minutes_ago * MIN,
+            pinned,
+        }
+    }
+
+    #[test]
+    fn test_expired_items() {
+        let now = 1000 * 60 * MIN;
+        let policy = RetentionPolicy {
+            keep_all_ms: 10 * MIN,
+            tiers: vec![
+                RetentionTier {
+                    max_age_ms: 60 * MIN,
+                    interval_ms: 30 * MIN,
+                },
+                RetentionTier {
+                    max_age_ms: 24 * 60 * MIN,
+                    interval_ms: 60 * MIN,
+                },
+            ],
+            max_count: None,
+        };
+        // 0-10 分钟全保留；11-60 分钟每 30 分钟一条；更早的每小时一条；超过一天删除
+        let items: Vec<RetentionItem> = (0..=25)
+            .map(|i| item(i, i as i64 * 5, false))
+            .chain([item(100, 5000, false), item(101, 5000, true)])
+            .collect();
+        let mut expired = expired_items(now, &items, &policy);
+        expired.sort();
+        assert_eq!(
+            expired,
+            vec![
+                3, 4, 5, 7, 8, 9, 10, 11, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 100
+            ]
+        );
+
+        // 没有时间层时只按数量保留最新的
+        let count_only = RetentionPolicy {
+            max_count: Some(2),
+            ..Default::default()
+        };
+        let items = vec![
+            item(1, 30, false),
+            item(2, 20, true),
+            item(3, 10, false),
+            item(4, 0, false),
+        ];
+        assert_eq!(expired_items(now, &items, &count_only), vec![1]);
+    }
+}
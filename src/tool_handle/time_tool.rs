@@ -40,6 +40,19 @@ pub fn integer_to_string(timestamp_ms: i64) -> String {
     dt_east8.format("%Y-%m-%d %H:%M:%S").to_string()
 }
 
+/// integer_to_string 的逆转换：东八区时间字符串或毫秒数字符串 → 毫秒时间戳
+pub fn string_to_integer(text: &str) -> Option<i64> {
+    let text = text.trim();
+    if let Ok(ms) = text.parse::<i64>() {
+        return Some(ms);
+    }
+    let naive = NaiveDateTime::parse_from_str(text, "%Y-%m-%d %H:%M:%S").ok()?;
+    FixedOffset::east_opt(8 * 3600)?
+        .from_local_datetime(&naive)
+        .single()
+        .map(|dt| dt.timestamp_millis())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -49,5 +62,8 @@ mod tests {
         let ts = 1766896693918; // 毫秒时间戳
         let formatted = integer_to_string(ts);
         println!("东八区时间: {}", formatted);
+        assert_eq!(string_to_integer(&formatted), Some(ts / 1000 * 1000));
+        assert_eq!(string_to_integer("1766896693918"), Some(ts));
+        assert_eq!(string_to_integer("abc"), None);
     }
 }
@@ -0,0 +1,249 @@
+use crate::tool_handle::trend_tool::TrendPoint;
+use parquet::basic::{Compression, Repetition, Type as PhysicalType, ZstdLevel};
+use parquet::data_type::DoubleType;
+use parquet::file::properties::WriterProperties;
+use parquet::file::writer::SerializedFileWriter;
+use parquet::schema::types::Type;
+use std::io::Write;
+use std::sync::Arc;
+
+// ======================================
+// 趋势数据导出为宽表：第一列为仿真时间，其余每个变量一列。
+// 各变量的采样时刻按并集对齐，某变量在该时刻没有采样时留空
+// ======================================
+
+/// 时间列表头
+pub const TIME_COLUMN: &str = "sim_time";
+
+/// Parquet 每个行组的行数
+const ROW_GROUP_ROWS: usize = 65536;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct TrendTable {
+    pub headers: Vec<String>,
+    pub times: Vec<f64>,
+    /// 按列存放，与 headers 一一对应
+    pub columns: Vec<Vec<Option<f64>>>,
+}
+
+impl TrendTable {
+    /// 按时间并集对齐各变量；series 需按时间升序，同一时刻有多个采样时取最后一个
+    pub fn from_series(headers: Vec<String>, series: &[Vec<TrendPoint>]) -> TrendTable {
+        let mut times: Vec<f64> = series.iter().flatten().map(|p| p.0).collect();
+        times.sort_by(f64::total_cmp);
+        times.dedup();
+
+        let columns = series
+            .iter()
+            .map(|points| {
+                let mut column = Vec::with_capacity(times.len());
+                let mut next = 0;
+                for &time in &times {
+                    let mut value = None;
+                    while next < points.len() && points[next].0 <= time {
+                        if points[next].0 == time {
+                            value = Some(points[next].1);
+                        }
+                        next += 1;
+                    }
+                    column.push(value);
+                }
+                column
+            })
+            .collect();
+        TrendTable {
+            headers: unique_headers(headers),
+            times,
+            columns,
+        }
+    }
+
+    pub fn row_count(&self) -> usize {
+        self.times.len()
+    }
+}
+
+/// 重名的表头追加序号，Parquet 等格式要求列名唯一
+fn unique_headers(headers: Vec<String>) -> Vec<String> {
+    let mut seen: Vec<String> = vec![TIME_COLUMN.to_string()];
+    headers
+        .into_iter()
+        .map(|header| {
+            let mut name = header.clone();
+            let mut index = 2;
+            while seen.contains(&name) {
+                name = format!("{} #{}", header, index);
+                index += 1;
+            }
+            seen.push(name.clone());
+            name
+        })
+        .collect()
+}
+
+/// CSV 字段转义：含逗号、引号或换行时加引号，引号双写
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// 写出 CSV，带 UTF-8 BOM 以便 Excel 正确识别中文表头
+pub fn write_csv<W: Write>(table: &TrendTable, mut out: W) -> Result<(), String> {
+    let mut line = String::from("\u{FEFF}");
+    line.push_str(TIME_COLUMN);
+    for header in &table.headers {
+        line.push(',');
+        line.push_str(&csv_field(header));
+    }
+    line.push_str("\r\n");
+    out.write_all(line.as_bytes()).map_err(|e| e.to_string())?;
+
+    for (row, time) in table.times.iter().enumerate() {
+        line.clear();
+        line.push_str(&time.to_string());
+        for column in &table.columns {
+            line.push(',');
+            if let Some(value) = column[row] {
+                line.push_str(&value.to_string());
+            }
+        }
+        line.push_str("\r\n");
+        out.write_all(line.as_bytes()).map_err(|e| e.to_string())?;
+    }
+    out.flush().map_err(|e| e.to_string())
+}
+
+/// 写出 Parquet：时间列必填，变量列可空，均为 DOUBLE，zstd 压缩
+pub fn write_parquet<W: Write + Send>(table: &TrendTable, out: W) -> Result<(), String> {
+    let err = |e: parquet::errors::ParquetError| e.to_string();
+    let mut fields = vec![Arc::new(
+        Type::primitive_type_builder(TIME_COLUMN, PhysicalType::DOUBLE)
+            .with_repetition(Repetition::REQUIRED)
+            .build()
+            .map_err(err)?,
+    )];
+    for header in &table.headers {
+        fields.push(Arc::new(
+            Type::primitive_type_builder(header, PhysicalType::DOUBLE)
+                .with_repetition(Repetition::OPTIONAL)
+                .build()
+                .map_err(err)?,
+        ));
+    }
+    let schema = Arc::new(
+        Type::group_type_builder("trend")
+            .with_fields(fields)
+            .build()
+            .map_err(err)?,
+    );
+    let props = Arc::new(
+        WriterProperties::builder()
+            .set_compression(Compression::ZSTD(ZstdLevel::default()))
+            .build(),
+    );
+
+    let mut writer = SerializedFileWriter::new(out, schema, props).map_err(err)?;
+    let mut start = 0;
+    while start < table.row_count() {
+        let end = (start + ROW_GROUP_ROWS).min(table.row_count());
+        let mut row_group = writer.next_row_group().map_err(err)?;
+        if let Some(mut column) = row_group.next_column().map_err(err)? {
+            column
+                .typed::<DoubleType>()
+                .write_batch(&table.times[start..end], None, None)
+                .map_err(err)?;
+            column.close().map_err(err)?;
+        }
+        for values in &table.columns {
+            let Some(mut column) = row_group.next_column().map_err(err)? else {
+                break;
+            };
+            let rows = &values[start..end];
+            let present: Vec<f64> = rows.iter().flatten().copied().collect();
+            let levels: Vec<i16> = rows.iter().map(|v| v.is_some() as i16).collect();
+            column
+                .typed::<DoubleType>()
+                .write_batch(&present, Some(&levels), None)
+                .map_err(err)?;
+            column.close().map_err(err)?;
+        }
+        row_group.close().map_err(err)?;
+        start = end;
+    }
+    writer.close().map_err(err)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use parquet::file::reader::{FileReader, SerializedFileReader};
+    use parquet::record::Field;
+
+    fn table() -> TrendTable {
+        TrendTable::from_series(
+            vec![
+                "温度 (℃)".to_string(),
+                "a,\"b\"".to_string(),
+                "温度 (℃)".to_string(),
+            ],
+            &[
+                vec![(0.0, 1.0), (1.0, 2.0), (2.0, 3.0)],
+                vec![(1.0, 10.0), (1.0, 11.0), (3.0, 12.0)],
+                vec![],
+            ],
+        )
+    }
+
+    #[test]
+    fn test_align_and_csv() {
+        let table = table();
+        assert_eq!(table.times, vec![0.0, 1.0, 2.0, 3.0]);
+        assert_eq!(
+            table.columns[0],
+            vec![Some(1.0), Some(2.0), Some(3.0), None]
+        );
+        // 同一时刻取最后一个采样
+        assert_eq!(table.columns[1], vec![None, Some(11.0), None, Some(12.0)]);
+        assert_eq!(table.headers[2], "温度 (℃) #2");
+
+        let mut out = Vec::new();
+        write_csv(&table, &mut out).unwrap();
+        let text = String::from_utf8(out).unwrap();
+        let lines: Vec<&str> = text.split("\r\n").collect();
+        assert_eq!(
+            lines[0],
+            "\u{FEFF}sim_time,温度 (℃),\"a,\"\"b\"\"\",温度 (℃) #2"
+        );
+        assert_eq!(lines[1], "0,1,,");
+        assert_eq!(lines[2], "1,2,11,");
+        assert_eq!(lines[4], "3,,12,");
+    }
+
+    #[test]
+    fn test_parquet_round_trip() {
+        let table = table();
+        let path =
+            std::env::temp_dir().join(format!("trend_export_{}.parquet", std::process::id()));
+        write_parquet(&table, std::fs::File::create(&path).unwrap()).unwrap();
+
+        let reader = SerializedFileReader::new(std::fs::File::open(&path).unwrap()).unwrap();
+        let schema = reader.metadata().file_metadata().schema_descr();
+        assert_eq!(schema.num_columns(), 4);
+        assert_eq!(schema.column(1).name(), "温度 (℃)");
+        let rows: Vec<_> = reader
+            .get_row_iter(None)
+            .unwrap()
+            .map(|r| r.unwrap())
+            .collect();
+        assert_eq!(rows.len(), 4);
+        let last: Vec<&Field> = rows[3].get_column_iter().map(|(_, f)| f).collect();
+        assert_eq!(last[0], &Field::Double(3.0));
+        assert_eq!(last[1], &Field::Null);
+        assert_eq!(last[2], &Field::Double(12.0));
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,156 @@
+// ======================================
+// 趋势曲线数据块编码与降采样
+// 数据块按列存放：点数(u32) + 全部时间(f64) + 全部数值(f64)，小端序，整体 zstd 压缩。
+// 同一列的相邻值接近，按列排布比交错排布压缩率更高
+// ======================================
+
+const COMPRESSION_LEVEL: i32 = 3;
+
+/// 趋势点：(仿真时间, 数值)
+pub type TrendPoint = (f64, f64);
+
+/// 编码并压缩一组趋势点
+pub fn encode_points(points: &[TrendPoint]) -> Result<Vec<u8>, String> {
+    let mut raw = Vec::with_capacity(4 + points.len() * 16);
+    raw.extend_from_slice(&(points.len() as u32).to_le_bytes());
+    for (time, _) in points {
+        raw.extend_from_slice(&time.to_le_bytes());
+    }
+    for (_, value) in points {
+        raw.extend_from_slice(&value.to_le_bytes());
+    }
+    zstd::bulk::compress(&raw, COMPRESSION_LEVEL).map_err(|e| e.to_string())
+}
+
+/// 解压并解码数据块
+pub fn decode_points(data: &[u8]) -> Result<Vec<TrendPoint>, String> {
+    let raw = zstd::decode_all(data).map_err(|e| e.to_string())?;
+    let count = raw
+        .get(..4)
+        .map(|b| u32::from_le_bytes(b.try_into().unwrap()) as usize)
+        .ok_or("数据块长度不足")?;
+    if raw.len() != 4 + count * 16 {
+        return Err(format!("数据块长度 {} 与点数 {} 不符", raw.len(), count));
+    }
+    let read = |offset: usize| f64::from_le_bytes(raw[offset..offset + 8].try_into().unwrap());
+    let values_at = 4 + count * 8;
+    Ok((0..count)
+        .map(|i| (read(4 + i * 8), read(values_at + i * 8)))
+        .collect())
+}
+
+/// LTTB（Largest-Triangle-Three-Buckets）降采样到 threshold 个点，保留首尾点。
+/// points 需按时间升序
+pub fn lttb(points: &[TrendPoint], threshold: usize) -> Vec<TrendPoint> {
+    if threshold == 0 || threshold >= points.len() {
+        return points.to_vec();
+    }
+    if threshold < 3 {
+        // 目标少于 3 个点时只保留首尾
+        return [points[0], points[points.len() - 1]][..threshold].to_vec();
+    }
+
+    let mut sampled = Vec::with_capacity(threshold);
+    sampled.push(points[0]);
+    // 除首尾外分成 threshold - 2 个桶
+    let every = (points.len() - 2) as f64 / (threshold - 2) as f64;
+    let mut a = 0;
+    for i in 0..threshold - 2 {
+        // 下一个桶的平均点作为三角形的第三个顶点
+        let next_start = ((i + 1) as f64 * every) as usize + 1;
+        let next_end = (((i + 2) as f64 * every) as usize + 1).min(points.len());
+        let next = &points[next_start..next_end];
+        let (avg_x, avg_y) = next.iter().fold((0.0, 0.0), |(x, y), p| (x + p.0, y + p.1));
+        let (avg_x, avg_y) = (avg_x / next.len() as f64, avg_y / next.len() as f64);
+
+        let start = (i as f64 * every) as usize + 1;
+        let end = ((i + 1) as f64 * every) as usize + 1;
+        let (ax, ay) = points[a];
+        let mut max_area = -1.0;
+        let mut chosen = start;
+        for (j, &(x, y)) in points.iter().enumerate().take(end).skip(start) {
+            let area = ((ax - avg_x) * (y - ay) - (ax - x) * (avg_y - ay)).abs();
+            if area > max_area {
+                max_area = area;
+                chosen = j;
+            }
+        }
+        sampled.push(points[chosen]);
+        a = chosen;
+    }
+    sampled.push(points[points.len() - 1]);
+    sampled
+}
+
+/// 按点数均分成 threshold / 2 个桶，每桶保留最小值与最大值（按时间先后），
+/// 不会丢失尖峰。points 需按时间升序
+pub fn min_max(points: &[TrendPoint], threshold: usize) -> Vec<TrendPoint> {
+    let buckets = threshold / 2;
+    if threshold >= points.len() || buckets == 0 {
+        return points.to_vec();
+    }
+    let every = points.len() as f64 / buckets as f64;
+    let mut sampled = Vec::with_capacity(buckets * 2);
+    for i in 0..buckets {
+        let start = (i as f64 * every) as usize;
+        let end = (((i + 1) as f64 * every) as usize).min(points.len());
+        let bucket = &points[start..end];
+        let (mut min, mut max) = (0, 0);
+        for (j, p) in bucket.iter().enumerate() {
+            if p.1 < bucket[min].1 {
+                min = j;
+            }
+            if p.1 > bucket[max].1 {
+                max = j;
+            }
+        }
+        let (first, second) = if min <= max { (min, max) } else { (max, min) };
+        sampled.push(bucket[first]);
+        if second != first {
+            sampled.push(bucket[second]);
+        }
+    }
+    sampled
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_decode() {
+        let points: Vec<TrendPoint> = (0..500)
+            .map(|i| (i as f64 * 0.5, (i as f64).sin()))
+            .collect();
+        let data = encode_points(&points).unwrap();
+        assert!(data.len() < points.len() * 16);
+        assert_eq!(decode_points(&data).unwrap(), points);
+        assert_eq!(decode_points(&encode_points(&[]).unwrap()).unwrap(), vec![]);
+        assert!(decode_points(&zstd::bulk::compress(&[1, 0, 0, 0], 3).unwrap()).is_err());
+    }
+
+    #[test]
+    fn test_downsample() {
+        // 平缓曲线中间有一个尖峰
+        let points: Vec<TrendPoint> = (0..1000)
+            .map(|i| (i as f64, if i == 437 { 100.0 } else { (i % 7) as f64 }))
+            .collect();
+
+        let sampled = lttb(&points, 50);
+        assert_eq!(sampled.len(), 50);
+        assert_eq!(sampled[0], points[0]);
+        assert_eq!(sampled[49], points[999]);
+        assert!(sampled.windows(2).all(|w| w[0].0 < w[1].0));
+        assert!(sampled.contains(&(437.0, 100.0)));
+
+        let sampled = min_max(&points, 50);
+        assert!(sampled.len() <= 50);
+        assert!(sampled.windows(2).all(|w| w[0].0 < w[1].0));
+        assert!(sampled.contains(&(437.0, 100.0)));
+
+        // 点数不超过目标时原样返回
+        assert_eq!(lttb(&points[..10], 20), points[..10].to_vec());
+        assert_eq!(min_max(&points[..10], 20), points[..10].to_vec());
+        assert_eq!(lttb(&points, 2), vec![points[0], points[999]]);
+    }
+}